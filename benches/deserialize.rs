@@ -0,0 +1,89 @@
+// Deserialization of large SMD and BSS payloads, straight from bytes and through an
+// intermediate `serde_json::Value` as some GET paths used to.
+//
+// Run with `cargo bench --bench deserialize`
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ochami_rs::{
+  bss::types::BootParameters, hsm::component::types::ComponentArray,
+};
+
+const NODE_COUNT: usize = 20_000;
+
+fn xname(i: usize) -> String {
+  format!("x1000c{}s{}b0n{}", i / 64, (i / 2) % 32, i % 2)
+}
+
+fn components_json(count: usize) -> Vec<u8> {
+  let component_vec: Vec<String> = (0..count)
+    .map(|i| {
+      format!(
+        r#"{{"ID":"{}","Type":"Node","State":"Ready","Flag":"OK","Enabled":true,"Role":"Compute","NID":{},"NetType":"Sling","Arch":"X86","Class":"Mountain"}}"#,
+        xname(i),
+        i + 1
+      )
+    })
+    .collect();
+
+  format!(r#"{{"Components":[{}]}}"#, component_vec.join(",")).into_bytes()
+}
+
+fn boot_parameters_json(count: usize) -> Vec<u8> {
+  let boot_parameters_vec: Vec<String> = (0..count)
+    .map(|i| {
+      format!(
+        r#"{{"hosts":["{}"],"params":"console=ttyS0,115200 root=live:s3://boot-images/compute/rootfs ip=dhcp","kernel":"s3://boot-images/compute/kernel","initrd":"s3://boot-images/compute/initrd"}}"#,
+        xname(i)
+      )
+    })
+    .collect();
+
+  format!("[{}]", boot_parameters_vec.join(",")).into_bytes()
+}
+
+fn bench_components(c: &mut Criterion) {
+  let body = components_json(NODE_COUNT);
+  let mut group = c.benchmark_group("components");
+
+  group.bench_function("from_slice", |b| {
+    b.iter(|| {
+      serde_json::from_slice::<ComponentArray>(black_box(&body)).unwrap()
+    })
+  });
+  group.bench_function("through_value", |b| {
+    b.iter(|| {
+      let value: serde_json::Value =
+        serde_json::from_slice(black_box(&body)).unwrap();
+      serde_json::from_value::<ComponentArray>(value).unwrap()
+    })
+  });
+
+  group.finish();
+}
+
+fn bench_boot_parameters(c: &mut Criterion) {
+  let body = boot_parameters_json(NODE_COUNT);
+  let mut group = c.benchmark_group("boot_parameters");
+
+  group.bench_function("from_slice", |b| {
+    b.iter(|| {
+      serde_json::from_slice::<Vec<BootParameters>>(black_box(&body)).unwrap()
+    })
+  });
+  group.bench_function("through_value", |b| {
+    b.iter(|| {
+      let value: serde_json::Value =
+        serde_json::from_slice(black_box(&body)).unwrap();
+      serde_json::from_value::<Vec<BootParameters>>(value).unwrap()
+    })
+  });
+
+  group.finish();
+}
+
+criterion_group! {
+  name = benches;
+  config = Criterion::default().sample_size(20);
+  targets = bench_components, bench_boot_parameters
+}
+criterion_main!(benches);
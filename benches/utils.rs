@@ -0,0 +1,117 @@
+// Utilities called for every node of a command: hostlist and NID expansion, kernel
+// parameter parsing and HSM group map filtering.
+//
+// Run with `cargo bench --bench utils`
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ochami_rs::{
+  bss::types::BootParameters,
+  hostlist,
+  hsm::group::{types::Group, utils as group_utils},
+  node::utils as node_utils,
+};
+
+const NODE_COUNT: usize = 10_000;
+const GROUP_COUNT: usize = 200;
+
+const KERNEL_PARAMS: &str = "console=ttyS0,115200 bad_page=panic crashkernel=360M hugepagelist=2m-2g intel_iommu=off intel_pstate=disable iommu.passthrough=on numa_interleave_omit=headless oops=panic pageblock_order=14 rd.neednet=1 rd.retry=10 rd.shell split_lock_detect=off systemd.unified_cgroup_hierarchy=1 ip=dhcp quiet root=live:s3://boot-images/compute/rootfs nmd_data=url=s3://boot-images/compute/rootfs,etag=1234";
+
+fn xname(i: usize) -> String {
+  format!("x1000c{}s{}b0n{}", i / 64, (i / 2) % 32, i % 2)
+}
+
+fn bench_hostlist(c: &mut Criterion) {
+  let nid_vec: Vec<String> = (1..=NODE_COUNT)
+    .map(|nid| format!("nid{:06}", nid))
+    .collect();
+  let expression = hostlist::compress(&nid_vec);
+
+  c.bench_function("hostlist_compress", |b| {
+    b.iter(|| hostlist::compress(black_box(&nid_vec)))
+  });
+  c.bench_function("hostlist_expand", |b| {
+    b.iter(|| hostlist_parser::parse(black_box(&expression)).unwrap())
+  });
+  c.bench_function("nid_range_from_regex", |b| {
+    b.iter(|| node_utils::nid_range_from_regex(black_box("^nid0012.*")))
+  });
+  c.bench_function("validate_xname_format", |b| {
+    b.iter(|| {
+      (0..NODE_COUNT)
+        .filter(|i| node_utils::validate_xname_format(&xname(*i)))
+        .count()
+    })
+  });
+}
+
+fn bench_kernel_params(c: &mut Criterion) {
+  let boot_parameters = BootParameters {
+    hosts: vec![xname(0)],
+    macs: None,
+    nids: None,
+    params: KERNEL_PARAMS.to_string(),
+    kernel: "s3://boot-images/compute/kernel".to_string(),
+    initrd: "s3://boot-images/compute/initrd".to_string(),
+    cloud_init: None,
+  };
+
+  c.bench_function("get_kernel_param_value", |b| {
+    b.iter(|| boot_parameters.get_kernel_param_value(black_box("root")))
+  });
+  c.bench_function("apply_kernel_params", |b| {
+    b.iter(|| {
+      let mut boot_parameters = boot_parameters.clone();
+      boot_parameters
+        .apply_kernel_params(black_box("quiet ip=dhcp spectre_v2=off"))
+    })
+  });
+}
+
+fn bench_group_map(c: &mut Criterion) {
+  let node_per_group = NODE_COUNT / GROUP_COUNT;
+  let group_vec: Vec<Group> = (0..GROUP_COUNT)
+    .map(|i| {
+      let member_vec: Vec<String> = (i * node_per_group
+        ..(i + 1) * node_per_group)
+        .map(xname)
+        .collect();
+      Group::with_members(&format!("group{}", i), &member_vec)
+    })
+    .collect();
+
+  let label_vec: Vec<String> = (0..GROUP_COUNT)
+    .step_by(4)
+    .map(|i| format!("group{}", i))
+    .collect();
+  let label_vec: Vec<&str> = label_vec.iter().map(String::as_str).collect();
+
+  let member_vec: Vec<String> =
+    (0..NODE_COUNT).step_by(97).map(xname).collect();
+  let member_vec: Vec<&str> = member_vec.iter().map(String::as_str).collect();
+
+  c.bench_function("filter_by_hsm_group_and_convert_to_map", |b| {
+    b.iter(|| {
+      group_utils::filter_by_hsm_group_and_convert_to_map(
+        black_box(&label_vec),
+        black_box(&group_vec),
+      )
+      .unwrap()
+    })
+  });
+  c.bench_function("filter_by_hsm_group_members_and_convert_to_map", |b| {
+    b.iter(|| {
+      group_utils::filter_by_hsm_group_members_and_convert_to_map(
+        black_box(&member_vec),
+        group_vec.clone(),
+      )
+    })
+  });
+}
+
+criterion_group!(
+  benches,
+  bench_hostlist,
+  bench_kernel_params,
+  bench_group_map
+);
+criterion_main!(benches);
@@ -0,0 +1,48 @@
+// Hostlist expansion and compression, and NID hostlists and regexes: malformed input
+// must be rejected with an error. Compressing names then expanding the expression must
+// give the same names back
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ochami_rs::{
+  hostlist,
+  node::utils::{nid_range_from_regex, parse_nid_hostlist},
+};
+
+/// Skip expressions which could expand into millions of names, eg: n[0-99999999]
+fn is_bounded(input: &str) -> bool {
+  input.matches('[').count() <= 2
+    && input
+      .split(|c: char| !c.is_ascii_digit())
+      .all(|number| number.len() <= 3)
+}
+
+fuzz_target!(|input: &str| {
+  let _ = nid_range_from_regex(input);
+
+  if !is_bounded(input) {
+    return;
+  }
+
+  let _ = parse_nid_hostlist(input);
+
+  let Ok(mut name_vec) = hostlist_parser::parse(input) else {
+    return;
+  };
+
+  // Names with hostlist syntax characters can't be expressed in a hostlist
+  if name_vec.iter().any(|name| {
+    name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric())
+  }) {
+    return;
+  }
+
+  let expression = hostlist::compress(&name_vec);
+  let mut expanded_vec = hostlist_parser::parse(&expression).unwrap();
+
+  name_vec.sort();
+  name_vec.dedup();
+  expanded_vec.sort();
+
+  assert_eq!(expanded_vec, name_vec, "expression: {}", expression);
+});
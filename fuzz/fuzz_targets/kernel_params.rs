@@ -0,0 +1,38 @@
+// Kernel parameter parsing and editing on BSS boot parameters: any operator input must
+// be handled without panicking
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ochami_rs::bss::{
+  types::BootParameters, utils::convert_kernel_params_to_map,
+};
+
+fuzz_target!(|input: (&str, &str)| {
+  let (params, new_params) = input;
+
+  let _ = convert_kernel_params_to_map(params);
+
+  let mut boot_parameters = BootParameters {
+    params: params.to_string(),
+    ..Default::default()
+  };
+
+  let _ = boot_parameters.get_num_kernel_params();
+  boot_parameters.apply_kernel_params(new_params);
+
+  if let Some((key, value)) = new_params
+    .split_whitespace()
+    .next()
+    .map(|param| param.split_once('=').unwrap_or((param, "")))
+  {
+    boot_parameters.add_kernel_param(key, value);
+    boot_parameters.update_kernel_param(key, value);
+    let _ = boot_parameters.get_kernel_param_value(key);
+    boot_parameters.delete_kernel_param(key);
+  }
+
+  for key in new_params.split_whitespace() {
+    boot_parameters.delete_kernel_param(key);
+    assert_eq!(boot_parameters.get_kernel_param_value(key), None);
+  }
+});
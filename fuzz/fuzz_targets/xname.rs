@@ -0,0 +1,24 @@
+// Xname parsing: malformed input must be rejected with an error, valid xnames must
+// survive a display/parse round trip
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ochami_rs::node::{types::Xname, utils::validate_xname_format};
+
+fuzz_target!(|input: &str| {
+  let _ = validate_xname_format(input);
+
+  let Ok(xname) = input.parse::<Xname>() else {
+    return;
+  };
+
+  assert_eq!(xname.to_string().parse::<Xname>().unwrap(), xname);
+
+  let _ = xname.r#type();
+  let _ = xname.cabinet();
+  let _ = xname.node_enclosure();
+
+  if let Some(parent) = xname.parent() {
+    assert!(parent.is_ancestor_of(&xname));
+  }
+});
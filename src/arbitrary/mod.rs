@@ -0,0 +1,251 @@
+#[cfg(test)]
+pub mod tests;
+
+use proptest::{
+  arbitrary::Arbitrary,
+  collection::vec,
+  option,
+  prelude::{any, BoxedStrategy, Just, Strategy},
+  prop_oneof,
+};
+use serde_json::{json, Value};
+
+use crate::{
+  bss::types::BootParameters,
+  hsm::{
+    component::types::Component,
+    group::types::{Group, Members},
+    inventory::{
+      ethernet_interfaces::types::{
+        ComponentEthernetInterface, IpAddressMapping,
+      },
+      types::MacAddress,
+    },
+  },
+};
+
+/// Node xname, eg: `x1000c0s0b0n0`
+pub fn xname() -> impl Strategy<Value = String> {
+  "x[1-9][0-9]{0,3}c[0-7]s[0-9]b[0-1]n[0-3]"
+}
+
+/// HSM group label, eg: `compute-rack1`
+pub fn group_label() -> impl Strategy<Value = String> {
+  "[a-z][a-z0-9_-]{0,15}"
+}
+
+/// MAC address in its canonical form, eg: `0a:1b:2c:3d:4e:5f`
+pub fn mac_address() -> impl Strategy<Value = MacAddress> {
+  "[0-9a-f]{2}(:[0-9a-f]{2}){5}".prop_map(|mac_address| {
+    mac_address
+      .parse()
+      .expect("generated MAC address should be valid")
+  })
+}
+
+pub fn ip_address() -> impl Strategy<Value = String> {
+  any::<[u8; 4]>().prop_map(|[a, b, c, d]| format!("{}.{}.{}.{}", a, b, c, d))
+}
+
+/// Kernel parameters, eg: `console=ttyS0 quiet`
+pub fn kernel_params() -> impl Strategy<Value = String> {
+  vec("[a-z_.]{1,12}(=[a-zA-Z0-9:/,._-]{1,24})?", 0..8)
+    .prop_map(|param_vec| param_vec.join(" "))
+}
+
+/// HSM state, eg: `Ready`
+pub fn hsm_state() -> impl Strategy<Value = String> {
+  prop_oneof![
+    Just("Unknown"),
+    Just("Empty"),
+    Just("Populated"),
+    Just("Off"),
+    Just("On"),
+    Just("Standby"),
+    Just("Halt"),
+    Just("Ready"),
+  ]
+  .prop_map(String::from)
+}
+
+fn text() -> impl Strategy<Value = String> {
+  "[a-zA-Z0-9 _-]{0,24}"
+}
+
+fn cloud_init() -> impl Strategy<Value = Value> {
+  (group_label(), text()).prop_map(|(key, value)| {
+    json!({
+      "meta-data": { key: value },
+      "user-data": {},
+    })
+  })
+}
+
+pub fn members() -> impl Strategy<Value = Members> {
+  option::of(vec(xname(), 0..8)).prop_map(|ids| Members { ids })
+}
+
+pub fn group() -> impl Strategy<Value = Group> {
+  (
+    group_label(),
+    option::of(text()),
+    option::of(vec(group_label(), 0..4)),
+    option::of(members()),
+    option::of(group_label()),
+  )
+    .prop_map(
+      |(label, description, tags, members, exclusive_group)| Group {
+        label,
+        description,
+        tags,
+        members,
+        exclusive_group,
+      },
+    )
+}
+
+pub fn component() -> impl Strategy<Value = Component> {
+  (
+    (
+      option::of(xname()),
+      option::of(Just("Node".to_string())),
+      option::of(hsm_state()),
+      option::of(prop_oneof![Just("OK"), Just("Warning"), Just("Alert")]),
+      option::of(any::<bool>()),
+      option::of(text()),
+      option::of(prop_oneof![Just("Compute"), Just("Management")]),
+      option::of(text()),
+    ),
+    (
+      option::of(any::<u32>()),
+      option::of(text()),
+      option::of(prop_oneof![Just("Sling"), Just("Ethernet")]),
+      option::of(prop_oneof![Just("X86"), Just("ARM")]),
+      option::of(prop_oneof![Just("River"), Just("Mountain")]),
+      option::of(any::<bool>()),
+      option::of(any::<bool>()),
+    ),
+  )
+    .prop_map(
+      |(
+        (id, r#type, state, flag, enabled, software_status, role, sub_role),
+        (nid, subtype, net_type, arch, class, reservation_disabled, locked),
+      )| Component {
+        id,
+        r#type,
+        state,
+        flag: flag.map(String::from),
+        enabled,
+        software_status,
+        role: role.map(String::from),
+        sub_role,
+        nid: nid.map(|nid| nid as usize),
+        subtype,
+        net_type: net_type.map(String::from),
+        arch: arch.map(String::from),
+        class: class.map(String::from),
+        reservation_disabled,
+        locked,
+      },
+    )
+}
+
+pub fn boot_parameters() -> impl Strategy<Value = BootParameters> {
+  (
+    vec(xname(), 0..8),
+    option::of(vec(mac_address(), 0..4)),
+    option::of(vec(any::<u32>(), 0..4)),
+    kernel_params(),
+    "s3://[a-z-]{1,12}/[a-z0-9/-]{1,24}/kernel",
+    "s3://[a-z-]{1,12}/[a-z0-9/-]{1,24}/initrd",
+    option::of(cloud_init()),
+  )
+    .prop_map(|(hosts, macs, nids, params, kernel, initrd, cloud_init)| {
+      BootParameters {
+        hosts,
+        macs: macs
+          .map(|mac_vec| mac_vec.into_iter().map(String::from).collect()),
+        nids,
+        params,
+        kernel,
+        initrd,
+        cloud_init,
+      }
+    })
+}
+
+pub fn ethernet_interface() -> impl Strategy<Value = ComponentEthernetInterface>
+{
+  (
+    option::of("[0-9a-f]{12}"),
+    option::of(text()),
+    option::of(mac_address()),
+    option::of(vec(
+      (ip_address(), option::of(text())).prop_map(|(ip_address, network)| {
+        IpAddressMapping {
+          ip_address,
+          network,
+        }
+      }),
+      0..3,
+    )),
+    option::of(Just("2024-01-01T00:00:00.000000Z".to_string())),
+    option::of(xname()),
+    option::of(Just("Node".to_string())),
+  )
+    .prop_map(
+      |(
+        id,
+        description,
+        mac_address,
+        ip_addresses,
+        last_update,
+        component_id,
+        parent_hms_type,
+      )| ComponentEthernetInterface {
+        id,
+        description,
+        mac_address,
+        ip_addresses,
+        last_update,
+        component_id,
+        parent_hms_type,
+      },
+    )
+}
+
+impl Arbitrary for Group {
+  type Parameters = ();
+  type Strategy = BoxedStrategy<Self>;
+
+  fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+    group().boxed()
+  }
+}
+
+impl Arbitrary for Component {
+  type Parameters = ();
+  type Strategy = BoxedStrategy<Self>;
+
+  fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+    component().boxed()
+  }
+}
+
+impl Arbitrary for BootParameters {
+  type Parameters = ();
+  type Strategy = BoxedStrategy<Self>;
+
+  fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+    boot_parameters().boxed()
+  }
+}
+
+impl Arbitrary for ComponentEthernetInterface {
+  type Parameters = ();
+  type Strategy = BoxedStrategy<Self>;
+
+  fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+    ethernet_interface().boxed()
+  }
+}
@@ -0,0 +1,50 @@
+use proptest::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+  bss::types::BootParameters,
+  hsm::{
+    component::types::Component, group::types::Group,
+    inventory::ethernet_interfaces::types::ComponentEthernetInterface,
+  },
+};
+
+/// Serialize, deserialize and serialize again. Not all types implement `PartialEq`, the
+/// json documents are compared instead
+fn assert_round_trip<T: Serialize + DeserializeOwned>(value: &T) {
+  let json = serde_json::to_value(value).unwrap();
+  let parsed: T = serde_json::from_value(json.clone()).unwrap();
+
+  assert_eq!(serde_json::to_value(&parsed).unwrap(), json);
+}
+
+proptest! {
+  #[test]
+  fn test_group_round_trip(group in any::<Group>()) {
+    assert_round_trip(&group);
+  }
+
+  #[test]
+  fn test_component_round_trip(component in any::<Component>()) {
+    assert_round_trip(&component);
+  }
+
+  #[test]
+  fn test_boot_parameters_round_trip(boot_parameters in any::<BootParameters>()) {
+    let json = serde_json::to_string(&boot_parameters).unwrap();
+
+    prop_assert_eq!(serde_json::from_str::<BootParameters>(&json).unwrap(), boot_parameters);
+  }
+
+  #[test]
+  fn test_ethernet_interface_round_trip(
+    eth_interface in any::<ComponentEthernetInterface>()
+  ) {
+    let json = serde_json::to_string(&eth_interface).unwrap();
+
+    prop_assert_eq!(
+      serde_json::from_str::<ComponentEthernetInterface>(&json).unwrap(),
+      eth_interface
+    );
+  }
+}
@@ -1,3 +1,8 @@
+#[cfg(test)]
+pub mod tests;
+pub mod types;
+pub mod utils;
+
 use std::env::VarError;
 
 pub fn get_api_token() -> Result<String, VarError> {
@@ -0,0 +1,95 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde_json::json;
+
+use crate::{
+  authentication::{
+    types::{Authorizer, Operation, Resource},
+    utils::{can_i, decode_claims},
+  },
+  error::Error,
+  http::ApiContext,
+};
+
+fn jwt(claims: serde_json::Value) -> String {
+  format!(
+    "{}.{}.signature",
+    URL_SAFE_NO_PAD.encode(r#"{"alg":"RS256","typ":"JWT"}"#),
+    URL_SAFE_NO_PAD.encode(claims.to_string())
+  )
+}
+
+fn ctx(auth_token: &str) -> ApiContext {
+  ApiContext::parse("https://api.example.com", auth_token, &[]).unwrap()
+}
+
+#[test]
+fn test_token_grants() {
+  let claims = decode_claims(&jwt(json!({
+    "sub": "alice",
+    "scope": "openid smd-write",
+    "realm_access": { "roles": ["bss-read"] }
+  })))
+  .unwrap();
+
+  assert!(claims.allows(Operation::Write, Resource::Hsm));
+  assert!(claims.allows(Operation::Read, Resource::Hsm));
+  assert!(claims.allows(Operation::Read, Resource::Bss));
+  assert!(!claims.allows(Operation::Write, Resource::Bss));
+  assert!(!claims.allows(Operation::Read, Resource::Pcs));
+}
+
+#[tokio::test]
+async fn test_can_i() {
+  let token = jwt(json!({ "scp": ["smd-read"], "exp": 4102444800i64 }));
+
+  assert!(can_i(&ctx(&token), Operation::Read, Resource::Hsm)
+    .await
+    .is_ok());
+
+  let error = can_i(&ctx(&token), Operation::Write, Resource::Hsm)
+    .await
+    .unwrap_err();
+  assert!(
+    matches!(error, Error::Forbidden(reason) if reason == "missing smd-write scope")
+  );
+
+  let expired_token = jwt(json!({ "scp": ["smd-read"], "exp": 1 }));
+  assert!(can_i(&ctx(&expired_token), Operation::Read, Resource::Hsm)
+    .await
+    .is_err());
+
+  // Opaque tokens can't be inspected
+  assert!(can_i(&ctx("opaque"), Operation::Write, Resource::Pcs)
+    .await
+    .is_ok());
+}
+
+#[tokio::test]
+async fn test_can_i_asks_context_authorizer() {
+  let mock = crate::test_util::MockOchami::start().await;
+  wiremock::Mock::given(wiremock::matchers::method("POST"))
+    .and(wiremock::matchers::path("/v1/data/ochami/allow"))
+    .respond_with(
+      wiremock::ResponseTemplate::new(200)
+        .set_body_json(json!({ "result": false })),
+    )
+    .expect(1)
+    .mount(mock.server())
+    .await;
+
+  let token = jwt(json!({ "scp": ["smd-write"] }));
+  let opa_ctx = ctx(&token).with_authorizer(Some(Authorizer::Opa {
+    url: format!("{}/v1/data/ochami/allow", mock.base_url()),
+    root_cert: crate::test_util::MOCK_ROOT_CERT.to_vec(),
+  }));
+
+  let error = can_i(&opa_ctx, Operation::Write, Resource::Hsm)
+    .await
+    .unwrap_err();
+  assert!(matches!(error, Error::Forbidden(_)));
+
+  // Contexts without an authorizer still inspect the token claims
+  assert!(can_i(&ctx(&token), Operation::Write, Resource::Hsm)
+    .await
+    .is_ok());
+}
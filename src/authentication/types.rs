@@ -0,0 +1,148 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Kind of access to a service checked by `can_i`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Operation {
+  Read,
+  /// Create, update or delete. Grants `Read` as well
+  Write,
+}
+
+impl fmt::Display for Operation {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Operation::Read => write!(f, "read"),
+      Operation::Write => write!(f, "write"),
+    }
+  }
+}
+
+/// OpenCHAMI service an operation targets
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Resource {
+  /// HSM, its scopes use the SMD service name, eg: `smd-write`
+  #[serde(rename = "smd")]
+  Hsm,
+  #[serde(rename = "bss")]
+  Bss,
+  #[serde(rename = "pcs")]
+  Pcs,
+  #[serde(rename = "cloud-init")]
+  CloudInit,
+}
+
+impl fmt::Display for Resource {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Resource::Hsm => write!(f, "smd"),
+      Resource::Bss => write!(f, "bss"),
+      Resource::Pcs => write!(f, "pcs"),
+      Resource::CloudInit => write!(f, "cloud-init"),
+    }
+  }
+}
+
+impl Resource {
+  /// Scope or role needed to run `operation` on this service, eg: `smd-write`
+  pub fn scope(&self, operation: Operation) -> String {
+    format!("{}-{}", self, operation)
+  }
+}
+
+/// `scope` claim, either a space separated string (RFC 8693) or a list
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum ScopeClaim {
+  Text(String),
+  List(Vec<String>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RealmAccess {
+  #[serde(default)]
+  pub roles: Vec<String>,
+}
+
+/// Claims of an access token relevant to authorization. Scopes and roles are read from
+/// the claims used by the common identity providers: `scope`, `scp`, `roles` and
+/// Keycloak `realm_access.roles`
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TokenClaims {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub sub: Option<String>,
+  /// Expiration time in seconds since epoch
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub exp: Option<i64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub scope: Option<ScopeClaim>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub scp: Option<Vec<String>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub roles: Option<Vec<String>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub realm_access: Option<RealmAccess>,
+}
+
+impl TokenClaims {
+  /// Scopes and roles granted by the token
+  pub fn grants(&self) -> Vec<&str> {
+    let scope_vec: Vec<&str> = match &self.scope {
+      Some(ScopeClaim::Text(scope)) => scope.split_whitespace().collect(),
+      Some(ScopeClaim::List(scope_vec)) => {
+        scope_vec.iter().map(String::as_str).collect()
+      }
+      None => Vec::new(),
+    };
+
+    scope_vec
+      .into_iter()
+      .chain(self.scp.iter().flatten().map(String::as_str))
+      .chain(self.roles.iter().flatten().map(String::as_str))
+      .chain(
+        self
+          .realm_access
+          .iter()
+          .flat_map(|realm_access| &realm_access.roles)
+          .map(String::as_str),
+      )
+      .collect()
+  }
+
+  /// Returns true if the token grants `operation` on `resource`, a write scope also
+  /// grants reads
+  pub fn allows(&self, operation: Operation, resource: Resource) -> bool {
+    let grant_vec = self.grants();
+    let allows =
+      |operation| grant_vec.contains(&resource.scope(operation).as_str());
+
+    match operation {
+      Operation::Read => allows(Operation::Read) || allows(Operation::Write),
+      Operation::Write => allows(Operation::Write),
+    }
+  }
+
+  /// Returns true if the token expired at `now` (seconds since epoch)
+  pub fn is_expired_at(&self, now: i64) -> bool {
+    self.exp.is_some_and(|exp| exp <= now)
+  }
+}
+
+/// Policy engine queried by `can_i` instead of inspecting the token claims
+#[derive(Debug, Clone)]
+pub enum Authorizer {
+  /// OPA decision endpoint returning a boolean, eg:
+  /// `https://opa.example.com/v1/data/ochami/allow`. The input document holds the
+  /// token, its subject and grants, the operation, the resource and the scope needed
+  Opa { url: String, root_cert: Vec<u8> },
+  /// OpenFGA store, checks relation `<operation>` between `user:<sub>` and
+  /// `service:<resource>`, eg: `write` between `user:alice` and `service:smd`
+  OpenFga {
+    url: String,
+    store_id: String,
+    authorization_model_id: Option<String>,
+    root_cert: Vec<u8>,
+  },
+}
@@ -0,0 +1,191 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::Utc;
+use serde_json::{json, Value};
+
+use crate::{error::Error, http::ApiContext};
+
+use super::types::{Authorizer, Operation, Resource, TokenClaims};
+
+/// Read the claims of a JWT access token. The signature is not verified, claims are
+/// only used to fail early, the services still enforce authorization
+pub fn decode_claims(auth_token: &str) -> Result<TokenClaims, Error> {
+  let payload = auth_token
+    .trim()
+    .split('.')
+    .nth(1)
+    .ok_or_else(|| Error::Message("Access token is not a JWT".to_string()))?;
+
+  let claims = URL_SAFE_NO_PAD
+    .decode(payload.trim_end_matches('='))
+    .map_err(|e| {
+      Error::Message(format!("Could not decode access token claims: {}", e))
+    })?;
+
+  serde_json::from_slice(&claims).map_err(Error::SerdeError)
+}
+
+/// Check the context access token allows `operation` on `resource` before sending
+/// requests, so tools fail fast (eg: "missing smd-write scope") instead of getting a 403
+/// halfway through a migration. Asks the context policy engine if one is set with
+/// `ApiContext::with_authorizer`, otherwise inspects the token scopes and roles. Tokens
+/// which are not JWTs can't be inspected and are let through. Fails with
+/// `Error::Forbidden` if access is denied
+pub async fn can_i(
+  ctx: &ApiContext,
+  operation: Operation,
+  resource: Resource,
+) -> Result<(), Error> {
+  let auth_token = ctx.auth_token.as_str();
+  let scope = resource.scope(operation);
+
+  let claims = match decode_claims(auth_token) {
+    Ok(claims) => Some(claims),
+    Err(e) => {
+      log::debug!("Could not read access token claims: {}", e);
+      None
+    }
+  };
+
+  if claims
+    .as_ref()
+    .is_some_and(|claims| claims.is_expired_at(Utc::now().timestamp()))
+  {
+    return Err(Error::Forbidden("access token expired".to_string()));
+  }
+
+  let allowed = match ctx.authorizer.as_deref() {
+    Some(Authorizer::Opa { url, root_cert }) => {
+      query_opa(
+        url,
+        root_cert,
+        auth_token,
+        claims.as_ref(),
+        operation,
+        resource,
+      )
+      .await?
+    }
+    Some(Authorizer::OpenFga {
+      url,
+      store_id,
+      authorization_model_id,
+      root_cert,
+    }) => {
+      let user = claims
+        .as_ref()
+        .and_then(|claims| claims.sub.as_deref())
+        .ok_or_else(|| {
+          Error::Message(
+            "Access token has no 'sub' claim to check with OpenFGA".to_string(),
+          )
+        })?;
+
+      query_openfga(
+        url,
+        root_cert,
+        store_id,
+        authorization_model_id.as_deref(),
+        user,
+        operation,
+        resource,
+      )
+      .await?
+    }
+    None => claims
+      .as_ref()
+      .is_none_or(|claims| claims.allows(operation, resource)),
+  };
+
+  if allowed {
+    Ok(())
+  } else {
+    Err(Error::Forbidden(format!("missing {} scope", scope)))
+  }
+}
+
+async fn query_opa(
+  url: &str,
+  root_cert: &[u8],
+  auth_token: &str,
+  claims: Option<&TokenClaims>,
+  operation: Operation,
+  resource: Resource,
+) -> Result<bool, Error> {
+  let client = crate::http::build_client(root_cert)?;
+
+  let input = json!({
+    "input": {
+      "token": auth_token,
+      "subject": claims.and_then(|claims| claims.sub.as_deref()),
+      "grants": claims.map(TokenClaims::grants).unwrap_or_default(),
+      "operation": operation,
+      "resource": resource,
+      "scope": resource.scope(operation),
+    }
+  });
+
+  let response = client.post(url).json(&input).send().await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    let error_payload = response.text().await?;
+    return Err(Error::RequestError {
+      response: e,
+      payload: error_payload,
+    });
+  }
+
+  // An undefined decision has no 'result' and means access is denied
+  let decision: Value = response.json().await?;
+
+  Ok(
+    decision
+      .get("result")
+      .and_then(Value::as_bool)
+      .unwrap_or(false),
+  )
+}
+
+async fn query_openfga(
+  url: &str,
+  root_cert: &[u8],
+  store_id: &str,
+  authorization_model_id: Option<&str>,
+  user: &str,
+  operation: Operation,
+  resource: Resource,
+) -> Result<bool, Error> {
+  let client = crate::http::build_client(root_cert)?;
+  let api_url =
+    format!("{}/stores/{}/check", url.trim_end_matches('/'), store_id);
+
+  let mut check = json!({
+    "tuple_key": {
+      "user": format!("user:{}", user),
+      "relation": operation.to_string(),
+      "object": format!("service:{}", resource),
+    }
+  });
+
+  if let Some(authorization_model_id) = authorization_model_id {
+    check["authorization_model_id"] = json!(authorization_model_id);
+  }
+
+  let response = client.post(api_url).json(&check).send().await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    let error_payload = response.text().await?;
+    return Err(Error::RequestError {
+      response: e,
+      payload: error_payload,
+    });
+  }
+
+  let decision: Value = response.json().await?;
+
+  Ok(
+    decision
+      .get("allowed")
+      .and_then(Value::as_bool)
+      .unwrap_or(false),
+  )
+}
@@ -3,12 +3,18 @@ use manta_backend_dispatcher::{
     authentication::AuthenticationTrait,
     delete_configurations_and_data_related::DeleteConfigurationsAndDataRelatedTrait,
   },
-  types::pcs::power_status::types::PowerStatusAll as FrontEndPowerStatusAll,
+  types::pcs::{
+    power_status::types::PowerStatusAll as FrontEndPowerStatusAll,
+    transitions::types::{
+      TransitionResponse as FrontEndTransitionResponse,
+      TransitionStartOutput as FrontEndTransitionStartOutput,
+    },
+  },
 };
-use std::{collections::HashMap, pin::Pin};
+use std::{collections::HashMap, pin::Pin, time::Duration};
 
+use chrono::{NaiveDateTime, Utc};
 use futures_io::AsyncBufRead;
-use hostlist_parser::parse;
 use manta_backend_dispatcher::{
   error::Error,
   interfaces::{
@@ -31,7 +37,12 @@ use manta_backend_dispatcher::{
     pcs::PCSTrait,
   },
   types::{
+    bos::{
+      session::{BosSession, Status, StatusLabel},
+      session_template::BosSessionTemplate,
+    },
     bss::BootParameters,
+    cfs::cfs_configuration_response::CfsConfigurationResponse,
     hsm::inventory::{
       ComponentEthernetInterface, RedfishEndpoint, RedfishEndpointArray,
     },
@@ -45,16 +56,33 @@ use regex::Regex;
 use serde_json::Value;
 use tokio::io::{AsyncRead, AsyncWrite};
 
-use crate::{authentication, bss};
+use crate::{authentication, bss, cloud_init, node, session, session_template};
 use crate::{
-  hsm::{self, component::types::ComponentArrayPostArray, group::types::Group},
-  pcs,
+  hsm::{
+    self,
+    component::types::ComponentArrayPostArray,
+    group::{types::Group, utils::MemberFetchSettings},
+  },
+  http::{ApiContext, ExecutionMode, HttpClient},
+  mutation::{
+    self,
+    types::{Mutation, MutationOutcome},
+  },
+  pcs::{
+    self,
+    transitions::types::{PowerOperation, TransitionStartOutput},
+  },
+  service_paths::ServicePaths,
 };
 
 #[derive(Debug, Clone)]
 pub struct Ochami {
   base_url: String,
-  root_cert: Vec<u8>,
+  http_client: HttpClient,
+  member_fetch_settings: MemberFetchSettings,
+  execution_mode: ExecutionMode,
+  service_paths: ServicePaths,
+  cache_ttl: Option<Duration>,
 }
 
 impl Ochami {
@@ -64,9 +92,86 @@ impl Ochami {
   ) -> Self {
     Self {
       base_url: base_url.to_string(),
-      root_cert: root_cert.to_vec(),
+      http_client: HttpClient::new(root_cert),
+      member_fetch_settings: MemberFetchSettings::default(),
+      execution_mode: ExecutionMode::default(),
+      service_paths: ServicePaths::default(),
+      cache_ttl: None,
     }
   }
+
+  /// Same as `new`, failing if `base_url` is not a valid http(s) url or `root_cert` is not
+  /// a valid PEM certificate
+  pub fn try_new(
+    base_url: &str,
+    root_cert: &[u8],
+  ) -> Result<Self, crate::error::Error> {
+    crate::service_paths::validate_base_url(base_url)?;
+    crate::http::root_certificate(root_cert)?;
+
+    Ok(Self::new(base_url, root_cert))
+  }
+
+  /// Set the error policy used when fetching the members of multiple HSM groups. Members
+  /// are fetched with a single request, `pipe_size` is not used
+  pub fn with_member_fetch_settings(
+    mut self,
+    member_fetch_settings: MemberFetchSettings,
+  ) -> Self {
+    self.member_fetch_settings = member_fetch_settings;
+    self
+  }
+
+  /// Describe mutating calls instead of sending them when set to
+  /// `ExecutionMode::DryRun`
+  pub fn with_execution_mode(mut self, execution_mode: ExecutionMode) -> Self {
+    self.execution_mode = execution_mode;
+    self
+  }
+
+  /// Set the path prefixes of the OpenCHAMI services, see `ServicePaths`
+  pub fn with_service_paths(mut self, service_paths: ServicePaths) -> Self {
+    self.service_paths = service_paths;
+    self
+  }
+
+  /// Set the root certificate, timeout and connection settings of the requests, see
+  /// `HttpClient`
+  pub fn with_http_client(mut self, http_client: HttpClient) -> Self {
+    self.http_client = http_client;
+    self
+  }
+
+  /// Read HSM groups and components through `hsm::cache`, entries are kept for
+  /// `cache_ttl`. The cache is not used if `None`, the default
+  pub fn with_cache_ttl(mut self, cache_ttl: Option<Duration>) -> Self {
+    self.cache_ttl = cache_ttl;
+    self
+  }
+
+  /// Apply a mutation following the client execution mode
+  pub async fn execute(
+    &self,
+    auth_token: &str,
+    mutation: &Mutation,
+  ) -> Result<MutationOutcome, crate::error::Error> {
+    mutation::utils::execute(&self.context(auth_token)?, mutation).await
+  }
+
+  /// Connection details for a request authenticated with `auth_token`
+  pub fn context(
+    &self,
+    auth_token: &str,
+  ) -> Result<ApiContext, crate::error::Error> {
+    ApiContext::parse(&self.base_url, auth_token, self.http_client.root_cert())
+      .map(|ctx| {
+        ctx
+          .with_service_paths(self.service_paths.clone())
+          .with_http_client(self.http_client.clone())
+          .with_execution_mode(self.execution_mode)
+          .with_cache_ttl(self.cache_ttl)
+      })
+  }
 }
 
 impl GroupTrait for Ochami {
@@ -78,15 +183,13 @@ impl GroupTrait for Ochami {
     token: &str,
   ) -> Result<Vec<FrontEndGroup>, Error> {
     // Get all groups
-    let hsm_group_backend_vec = hsm::group::http_client::get(
-      &self.base_url,
-      token,
-      &self.root_cert,
+    let hsm_group_backend_vec = hsm::group::client::get(
+      &self.context(token).map_err(Error::from)?,
       None,
       None,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))?;
+    .map_err(Error::from)?;
 
     // Convert from HsmGroup (backend) to Group (infra)
     let hsm_group_vec =
@@ -102,15 +205,13 @@ impl GroupTrait for Ochami {
     &self,
     token: &str,
   ) -> Result<Vec<String>, Error> {
-    let hsm_group_vec = hsm::group::http_client::get(
-      &self.base_url,
-      token,
-      &self.root_cert,
+    let hsm_group_vec = hsm::group::client::get(
+      &self.context(token).map_err(Error::from)?,
       None,
       None,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))?;
+    .map_err(Error::from)?;
 
     Ok(
       hsm_group_vec
@@ -125,14 +226,12 @@ impl GroupTrait for Ochami {
     auth_token: &str,
     hsm_group: FrontEndGroup,
   ) -> Result<FrontEndGroup, Error> {
-    let hsm_group_backend = hsm::group::http_client::post(
-      &self.base_url,
-      auth_token,
-      &self.root_cert,
+    let hsm_group_backend = hsm::group::client::post(
+      &self.context(auth_token).map_err(Error::from)?,
       hsm_group.clone().into(),
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))?;
+    .map_err(Error::from)?;
 
     log::info!("Group created: {}", hsm_group_backend);
 
@@ -145,14 +244,19 @@ impl GroupTrait for Ochami {
     auth_token: &str,
     hsm_group_name_vec: &[String],
   ) -> Result<Vec<String>, Error> {
-    hsm::group::utils::get_member_vec_from_hsm_name_vec_2(
-      auth_token,
-      &self.base_url,
-      &self.root_cert,
+    hsm::group::utils::get_member_vec_from_hsm_name_vec_with_settings(
+      &self.context(auth_token).map_err(Error::from)?,
       hsm_group_name_vec,
+      &self.member_fetch_settings,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map(|fetch| {
+      for (hsm_name, e) in &fetch.failed_vec {
+        log::warn!("Could not get members of HSM group '{}': {}", hsm_name, e);
+      }
+      fetch.member_vec
+    })
+    .map_err(Error::from)
   }
 
   async fn get_group_map_and_filter_by_group_vec(
@@ -160,14 +264,12 @@ impl GroupTrait for Ochami {
     auth_token: &str,
     hsm_name_vec: &[&str],
   ) -> Result<HashMap<String, Vec<String>>, Error> {
-    hsm::group::utils::get_hsm_map_and_filter_by_hsm_name_vec(
-      auth_token,
-      &self.base_url,
-      &self.root_cert,
+    hsm::group::utils::get_group_map_filtered_by_label_vec(
+      &self.context(auth_token).map_err(Error::from)?,
       hsm_name_vec,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn get_group_map_and_filter_by_member_vec(
@@ -175,14 +277,12 @@ impl GroupTrait for Ochami {
     auth_token: &str,
     hsm_name_vec: &[&str],
   ) -> Result<HashMap<String, Vec<String>>, Error> {
-    hsm::group::utils::get_hsm_group_map_and_filter_by_hsm_group_member_vec(
-      auth_token,
-      &self.base_url,
-      &self.root_cert,
+    hsm::group::utils::get_group_map_filtered_by_member_vec(
+      &self.context(auth_token).map_err(Error::from)?,
       hsm_name_vec,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn get_group(
@@ -191,14 +291,12 @@ impl GroupTrait for Ochami {
     hsm_name: &str,
   ) -> Result<FrontEndGroup, Error> {
     // Get all HSM groups
-    let hsm_group_backend = hsm::group::http_client::get_one(
-      &self.base_url,
-      auth_token,
-      &self.root_cert,
+    let hsm_group_backend = hsm::group::client::get_one(
+      &self.context(auth_token).map_err(Error::from)?,
       hsm_name,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))?;
+    .map_err(Error::from)?;
 
     let hsm_group: FrontEndGroup = hsm_group_backend.into();
 
@@ -211,15 +309,13 @@ impl GroupTrait for Ochami {
     hsm_name_vec: Option<&[String]>,
   ) -> Result<Vec<FrontEndGroup>, Error> {
     // Get all HSM groups
-    let hsm_group_backend_vec = hsm::group::http_client::get(
-      &self.base_url,
-      auth_token,
-      &self.root_cert,
+    let hsm_group_backend_vec = hsm::group::client::get(
+      &self.context(auth_token).map_err(Error::from)?,
       hsm_name_vec,
       None,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))?;
+    .map_err(Error::from)?;
 
     // Convert from HsmGroup (silla) to HsmGroup (infra)
     let mut hsm_group_vec = Vec::new();
@@ -236,15 +332,13 @@ impl GroupTrait for Ochami {
     auth_token: &str,
     hsm_group_name: &str,
   ) -> Result<HsmActionResponse, Error> {
-    let value = hsm::group::http_client::delete_one(
-      &self.base_url,
-      auth_token,
-      &self.root_cert,
+    hsm::group::client::delete_one(
+      &self.context(auth_token).map_err(Error::from)?,
       hsm_group_name,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))?;
-    serde_json::from_value(value).map_err(|e| Error::Message(e.to_string()))
+    .map(|response| response.into())
+    .map_err(Error::from)
   }
 
   async fn get_group_map_and_filter_by_group_name_vec(
@@ -252,14 +346,12 @@ impl GroupTrait for Ochami {
     shasta_token: &str,
     hsm_name_vec: &[&str],
   ) -> Result<HashMap<String, Vec<String>>, Error> {
-    hsm::group::utils::get_hsm_map_and_filter_by_hsm_name_vec(
-      shasta_token,
-      &self.base_url,
-      &self.root_cert,
+    hsm::group::utils::get_group_map_filtered_by_label_vec(
+      &self.context(shasta_token).map_err(Error::from)?,
       hsm_name_vec,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn post_member(
@@ -272,16 +364,21 @@ impl GroupTrait for Ochami {
       id: Some(xname.to_string()),
     };
 
-    let value = hsm::group::http_client::post_member(
-      auth_token,
-      &self.base_url,
-      &self.root_cert,
+    hsm::group::client::post_member(
+      &self.context(auth_token).map_err(Error::from)?,
       group_label,
       member,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))?;
-    serde_json::from_value(value).map_err(|e| Error::Message(e.to_string()))
+    .map(|resource_uri_vec| HsmActionResponse {
+      code: reqwest::StatusCode::CREATED.as_u16().to_string(),
+      message: resource_uri_vec
+        .into_iter()
+        .map(|resource_uri| resource_uri.uri)
+        .collect::<Vec<String>>()
+        .join(", "),
+    })
+    .map_err(Error::from)
   }
 
   async fn add_members_to_group(
@@ -290,21 +387,13 @@ impl GroupTrait for Ochami {
     group_label: &str,
     new_members: &[&str],
   ) -> Result<Vec<String>, Error> {
-    let mut sol: Vec<String> = Vec::new();
-
-    for new_member in new_members {
-      sol = hsm::group::utils::add_member(
-        auth_token,
-        &self.base_url,
-        &self.root_cert,
-        group_label,
-        new_member,
-      )
-      .await
-      .map_err(|e| Error::Message(e.to_string()))?;
-    }
-
-    Ok(sol)
+    hsm::group::utils::add_members(
+      &self.context(auth_token).map_err(Error::from)?,
+      group_label,
+      new_members,
+    )
+    .await
+    .map_err(Error::from)
   }
 
   async fn delete_member_from_group(
@@ -313,15 +402,13 @@ impl GroupTrait for Ochami {
     group_label: &str,
     xname: &str,
   ) -> Result<(), Error> {
-    hsm::group::http_client::delete_member(
-      &self.base_url,
-      auth_token,
-      &self.root_cert,
+    hsm::group::client::delete_member(
+      &self.context(auth_token).map_err(Error::from)?,
       group_label,
       xname,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn update_group_members(
@@ -331,16 +418,14 @@ impl GroupTrait for Ochami {
     members_to_remove: &[&str],
     members_to_add: &[&str],
   ) -> Result<(), Error> {
-    hsm::group::utils::update_hsm_group_members(
-      auth_token,
-      &self.base_url,
-      &self.root_cert,
+    hsm::group::utils::update_group_members(
+      &self.context(auth_token).map_err(Error::from)?,
       group_name,
       members_to_remove,
       members_to_add,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn migrate_group_members(
@@ -351,17 +436,19 @@ impl GroupTrait for Ochami {
     new_target_hsm_members: &[&str],
     dryrun: bool,
   ) -> Result<(Vec<String>, Vec<String>), Error> {
-    hsm::group::utils::migrate_hsm_members(
-      shasta_token,
-      &self.base_url,
-      &self.root_cert,
+    let mut ctx = self.context(shasta_token).map_err(Error::from)?;
+    if dryrun {
+      ctx = ctx.with_execution_mode(ExecutionMode::DryRun);
+    }
+
+    hsm::group::utils::migrate_group_members(
+      &ctx,
       target_hsm_group_name,
       parent_hsm_group_name,
       new_target_hsm_members,
-      dryrun,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 }
 
@@ -378,10 +465,8 @@ impl HardwareInventory for Ochami {
     // then into the dispatcher's `NodeSummary`. Returns `Default` if the
     // response is empty — matches the original "always return something"
     // shape of the pre-typed implementation.
-    hsm::inventory::hardware::http_client::get(
-      &auth_token,
-      &self.base_url,
-      &self.root_cert,
+    hsm::inventory::hardware::client::get(
+      &self.context(auth_token).map_err(Error::from)?,
       Some(xname),
       None,
       None,
@@ -403,7 +488,7 @@ impl HardwareInventory for Ochami {
         .map(Into::into)
         .unwrap_or_default()
     })
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn get_inventory_hardware_query(
@@ -416,10 +501,8 @@ impl HardwareInventory for Ochami {
     partition: Option<&str>,
     format: Option<&str>,
   ) -> Result<FrontEndHWInventory, Error> {
-    let value = hsm::inventory::hardware::http_client::get_query(
-      &auth_token,
-      &self.base_url,
-      &self.root_cert,
+    hsm::inventory::hardware::client::get_query_typed(
+      &self.context(auth_token).map_err(Error::from)?,
       xname,
       r#type,
       children,
@@ -428,8 +511,7 @@ impl HardwareInventory for Ochami {
       format,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))?;
-    serde_json::from_value(value).map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn post_inventory_hardware(
@@ -437,15 +519,13 @@ impl HardwareInventory for Ochami {
     auth_token: &str,
     hardware: FrontEndHWInventoryByLocationList,
   ) -> Result<HsmActionResponse, Error> {
-    let value = hsm::inventory::hardware::http_client::post(
-      auth_token,
-      &self.base_url,
-      &self.root_cert,
+    let value = hsm::inventory::hardware::client::post(
+      &self.context(auth_token).map_err(Error::from)?,
       hardware.into(),
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))?;
-    serde_json::from_value(value).map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)?;
+    serde_json::from_value(value).map_err(Error::from)
   }
 }
 
@@ -455,10 +535,8 @@ impl ComponentTrait for Ochami {
     auth_token: &str,
     nid_only: Option<&str>,
   ) -> Result<NodeMetadataArray, Error> {
-    hsm::component::http_client::get(
-      &self.base_url,
-      &self.root_cert,
-      auth_token,
+    hsm::component::client::get(
+      &self.context(auth_token).map_err(Error::from)?,
       None,
       Some("Node"),
       None,
@@ -482,7 +560,7 @@ impl ComponentTrait for Ochami {
     )
     .await
     .map(|c| c.into())
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn get_node_metadata_available(
@@ -519,10 +597,8 @@ impl ComponentTrait for Ochami {
     role_only: Option<&str>,
     nid_only: Option<&str>,
   ) -> Result<NodeMetadataArray, Error> {
-    hsm::component::http_client::get(
-      &self.base_url,
-      &self.root_cert,
-      auth_token,
+    hsm::component::client::get(
+      &self.context(auth_token).map_err(Error::from)?,
       id,
       r#type,
       state,
@@ -546,7 +622,7 @@ impl ComponentTrait for Ochami {
     )
     .await
     .map(|c| c.into())
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn post_nodes(
@@ -556,14 +632,12 @@ impl ComponentTrait for Ochami {
   ) -> Result<(), Error> {
     let component_backend: ComponentArrayPostArray = component.into();
 
-    hsm::component::http_client::post(
-      auth_token,
-      &self.base_url,
-      &self.root_cert,
+    hsm::component::client::post(
+      &self.context(auth_token).map_err(Error::from)?,
       component_backend,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn delete_node(
@@ -571,15 +645,13 @@ impl ComponentTrait for Ochami {
     auth_token: &str,
     id: &str,
   ) -> Result<HsmActionResponse, Error> {
-    let value = hsm::component::http_client::delete_one(
-      auth_token,
-      &self.base_url,
-      &self.root_cert,
+    hsm::component::client::delete_one(
+      &self.context(auth_token).map_err(Error::from)?,
       id,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))?;
-    serde_json::from_value(value).map_err(|e| Error::Message(e.to_string()))
+    .map(|response| response.into())
+    .map_err(Error::from)
   }
 
   /// Get list of xnames from NIDs
@@ -602,76 +674,34 @@ impl ComponentTrait for Ochami {
         .collect::<Result<Vec<Regex>, regex::Error>>()
         .map_err(|e| Error::Message(e.to_string()))?;
 
-      // Get all HSM components (list of xnames + nids)
-      let hsm_component_vec = hsm::component::http_client::get_all_nodes(
-        &self.base_url,
-        shasta_token,
-        &self.root_cert,
-        Some("true"),
+      let xname_vec = node::utils::get_xnames_from_nid_regex(
+        &self.context(shasta_token).map_err(Error::from)?,
+        &regex_vec,
       )
       .await
-      .map_err(|e| Error::Message(e.to_string()))?
-      .components
-      .unwrap_or_default();
-
-      let mut xname_vec: Vec<String> = vec![];
-
-      // Get list of xnames the user is asking for
-      for hsm_component in hsm_component_vec {
-        let nid_long =
-          format!("nid{:06}", &hsm_component.nid.expect("No NID found"));
-        for regex in &regex_vec {
-          if regex.is_match(&nid_long) {
-            log::debug!(
-              "Nid '{}' IS included in regex '{}'",
-              nid_long,
-              regex.as_str()
-            );
-            xname_vec.push(hsm_component.id.clone().expect("No XName found"));
-          }
-        }
-      }
+      .map_err(Error::from)?;
 
       return Ok(xname_vec);
     } else {
       log::debug!(
         "No regex found, getting xnames from list of NIDs or NIDs hostlist"
       );
-      let nid_hostlist_expanded_vec_rslt = parse(user_input_nid);
-
-      let nid_hostlist_expanded_vec = match nid_hostlist_expanded_vec_rslt {
-        Ok(xname_requested_vec) => xname_requested_vec,
-        Err(e) => {
-          return Err(Error::Message(format!(
-            "Could not parse list of nodes as a hostlist. Reason:\n{}Exit",
-            e
-          )));
-        }
-      };
+      let nid_vec =
+        node::utils::parse_nid_hostlist(user_input_nid).map_err(Error::from)?;
 
       log::debug!("hostlist: {}", user_input_nid);
-      log::debug!("hostlist expanded: {:?}", nid_hostlist_expanded_vec);
+      log::debug!("hostlist expanded: {:?}", nid_vec);
 
-      let nid_short = nid_hostlist_expanded_vec
+      let nid_short = nid_vec
         .iter()
-        .map(|nid_long| {
-          nid_long
-            .strip_prefix("nid")
-            .expect(
-              format!("Nid '{}' not valid, 'nid' prefix missing", nid_long)
-                .as_str(),
-            )
-            .trim_start_matches("0")
-        })
-        .collect::<Vec<&str>>()
+        .map(|nid| nid.to_string())
+        .collect::<Vec<String>>()
         .join(",");
 
       log::debug!("short NID list: {}", nid_short);
 
-      let hsm_components = hsm::component::http_client::get(
-        &self.base_url,
-        &self.root_cert,
-        shasta_token,
+      let hsm_components = hsm::component::client::get(
+        &self.context(shasta_token).map_err(Error::from)?,
         None,
         None,
         None,
@@ -694,7 +724,7 @@ impl ComponentTrait for Ochami {
         Some("true"),
       )
       .await
-      .map_err(|e| Error::Message(e.to_string()))?;
+      .map_err(Error::from)?;
 
       // Get list of xnames from HSM components
       let xname_vec: Vec<String> = hsm_components
@@ -724,20 +754,56 @@ impl PCSTrait for Ochami {
     let nodes_str: Vec<&str> = nodes.iter().map(|s| s.as_str()).collect();
     let nodes_opt = Some(nodes_str.as_slice());
 
-    pcs::power_status::http_client::post(
-      &self.base_url,
-      auth_token,
-      &self.root_cert,
+    pcs::power_status::client::post(
+      &self.context(auth_token).map_err(Error::from)?,
       nodes_opt,
       power_state_filter,
       management_state_filter,
     )
     .await
     .map(|status| {
-      println!("return value from async fn power_status : {:?}", status);
+      log::debug!("return value from async fn power_status : {:?}", status);
       status.into()
     })
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
+  }
+
+  async fn pcs_transitions_post(
+    &self,
+    auth_token: &str,
+    operation: &str,
+    nodes: &[String],
+  ) -> Result<FrontEndTransitionStartOutput, Error> {
+    let operation = operation.parse::<PowerOperation>().map_err(Error::from)?;
+
+    pcs::transitions::client::post(
+      &self.context(auth_token).map_err(Error::from)?,
+      operation,
+      &nodes.to_vec(),
+    )
+    .await
+    .map(|transition_handle| {
+      TransitionStartOutput {
+        transition_id: transition_handle.id,
+        operation,
+      }
+      .into()
+    })
+    .map_err(Error::from)
+  }
+
+  async fn pcs_transitions_get(
+    &self,
+    auth_token: &str,
+    transition_id: &str,
+  ) -> Result<FrontEndTransitionResponse, Error> {
+    pcs::transitions::client::get(
+      &self.context(auth_token).map_err(Error::from)?,
+      transition_id,
+    )
+    .await
+    .map(|transition| transition.into())
+    .map_err(Error::from)
   }
 }
 
@@ -746,14 +812,10 @@ impl BootParametersTrait for Ochami {
     &self,
     auth_token: &str,
   ) -> Result<Vec<BootParameters>, Error> {
-    let boot_parameter_vec = bss::http_client::get(
-      &self.base_url,
-      auth_token,
-      &self.root_cert,
-      &None,
-    )
-    .await
-    .map_err(|e| Error::Message(e.to_string()))?;
+    let boot_parameter_vec =
+      bss::client::get(&self.context(auth_token).map_err(Error::from)?, &None)
+        .await
+        .map_err(Error::from)?;
 
     let boot_parameter_infra_vec = boot_parameter_vec
       .into_iter()
@@ -774,14 +836,10 @@ impl BootParametersTrait for Ochami {
       Some(hosts.to_vec())
     };
 
-    let boot_parameter_vec = bss::http_client::get(
-      &self.base_url,
-      auth_token,
-      &self.root_cert,
-      &hosts,
-    )
-    .await
-    .map_err(|e| Error::Message(e.to_string()))?;
+    let boot_parameter_vec =
+      bss::client::get(&self.context(auth_token).map_err(Error::from)?, &hosts)
+        .await
+        .map_err(Error::from)?;
 
     let boot_parameter_infra_vec = boot_parameter_vec
       .into_iter()
@@ -796,14 +854,12 @@ impl BootParametersTrait for Ochami {
     auth_token: &str,
     boot_parameters: &BootParameters,
   ) -> Result<(), Error> {
-    bss::http_client::post(
-      &self.base_url,
-      auth_token,
-      &self.root_cert,
+    bss::client::post(
+      &self.context(auth_token).map_err(Error::from)?,
       boot_parameters.clone().into(),
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
     .map(|boot_parameter| boot_parameter.into())
   }
 
@@ -812,14 +868,12 @@ impl BootParametersTrait for Ochami {
     auth_token: &str,
     boot_parameter: &BootParameters,
   ) -> Result<(), Error> {
-    bss::http_client::patch(
-      &self.base_url,
-      auth_token,
-      &self.root_cert,
+    bss::client::patch(
+      &self.context(auth_token).map_err(Error::from)?,
       &boot_parameter.clone().into(),
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn delete_bootparameters(
@@ -827,14 +881,12 @@ impl BootParametersTrait for Ochami {
     auth_token: &str,
     boot_parameter: &BootParameters,
   ) -> Result<String, Error> {
-    bss::http_client::delete(
-      &self.base_url,
-      auth_token,
-      &self.root_cert,
+    bss::client::delete(
+      &self.context(auth_token).map_err(Error::from)?,
       &boot_parameter.clone().into(),
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 }
 
@@ -843,14 +895,12 @@ impl RedfishEndpointTrait for Ochami {
     &self,
     auth_token: &str,
   ) -> Result<RedfishEndpointArray, Error> {
-    hsm::inventory::redfish_endpoint::http_client::get_all(
-      auth_token,
-      &self.base_url,
-      &self.root_cert,
-    )
-    .await
-    .map(|re| re.into())
-    .map_err(|e| Error::Message(e.to_string()))
+    let ctx = self.context(auth_token).map_err(Error::from)?;
+
+    hsm::inventory::redfish_endpoint::client::get_all(&ctx)
+      .await
+      .map(|re| re.into())
+      .map_err(Error::from)
   }
 
   async fn get_redfish_endpoints(
@@ -864,10 +914,10 @@ impl RedfishEndpointTrait for Ochami {
     ip_address: Option<&str>,
     last_status: Option<&str>,
   ) -> Result<RedfishEndpointArray, Error> {
-    hsm::inventory::redfish_endpoint::http_client::get(
-      auth_token,
-      &self.base_url,
-      &self.root_cert,
+    let ctx = self.context(auth_token).map_err(Error::from)?;
+
+    hsm::inventory::redfish_endpoint::client::get(
+      &ctx,
       id,
       fqdn,
       r#type,
@@ -878,7 +928,7 @@ impl RedfishEndpointTrait for Ochami {
     )
     .await
     .map(|re| re.into())
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn add_redfish_endpoint(
@@ -886,14 +936,14 @@ impl RedfishEndpointTrait for Ochami {
     auth_token: &str,
     redfish_endpoint: &RedfishEndpointArray,
   ) -> Result<(), Error> {
-    hsm::inventory::redfish_endpoint::http_client::post(
-      auth_token,
-      &self.base_url,
-      &self.root_cert,
+    let ctx = self.context(auth_token).map_err(Error::from)?;
+
+    hsm::inventory::redfish_endpoint::client::post(
+      &ctx,
       redfish_endpoint.clone().into(),
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))?;
+    .map_err(Error::from)?;
 
     Ok(())
   }
@@ -903,15 +953,15 @@ impl RedfishEndpointTrait for Ochami {
     auth_token: &str,
     redfish_endpoint: &RedfishEndpoint,
   ) -> Result<(), Error> {
-    hsm::inventory::redfish_endpoint::http_client::put(
-      auth_token,
-      &self.base_url,
-      &self.root_cert,
+    let ctx = self.context(auth_token).map_err(Error::from)?;
+
+    hsm::inventory::redfish_endpoint::client::put(
+      &ctx,
       redfish_endpoint.id.as_str(),
       redfish_endpoint.clone().into(),
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))?;
+    .map_err(Error::from)?;
 
     Ok(())
   }
@@ -921,14 +971,12 @@ impl RedfishEndpointTrait for Ochami {
     auth_token: &str,
     id: &str,
   ) -> Result<Value, Error> {
-    hsm::inventory::redfish_endpoint::http_client::delete_one(
-      &self.base_url,
-      auth_token,
-      &self.root_cert,
-      id,
-    )
-    .await
-    .map_err(|e| Error::Message(e.to_string()))
+    let ctx = self.context(auth_token).map_err(Error::from)?;
+
+    hsm::inventory::redfish_endpoint::client::delete_one(&ctx, id)
+      .await
+      .map_err(Error::from)
+      .and_then(|response| serde_json::to_value(response).map_err(Error::from))
   }
 }
 
@@ -937,10 +985,8 @@ impl ComponentEthernetInterfaceTrait for Ochami {
     &self,
     auth_token: &str,
   ) -> Result<Vec<ComponentEthernetInterface>, Error> {
-    hsm::inventory::ethernet_interfaces::http_client::get(
-      auth_token,
-      &self.base_url,
-      &self.root_cert,
+    hsm::inventory::ethernet_interfaces::client::get(
+      &self.context(auth_token).map_err(Error::from)?,
       None,
       None,
       None,
@@ -951,7 +997,7 @@ impl ComponentEthernetInterfaceTrait for Ochami {
     )
     .await
     .map(|re| re.into_iter().map(|e| e.into()).collect())
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn get_component_ethernet_interface(
@@ -959,15 +1005,13 @@ impl ComponentEthernetInterfaceTrait for Ochami {
     auth_token: &str,
     eth_interface_id: &str,
   ) -> Result<ComponentEthernetInterface, Error> {
-    hsm::inventory::ethernet_interfaces::http_client::get_one(
-      auth_token,
-      &self.base_url,
-      &self.root_cert,
+    hsm::inventory::ethernet_interfaces::client::get_one(
+      &self.context(auth_token).map_err(Error::from)?,
       eth_interface_id,
     )
     .await
     .map(|re| re.into())
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn add_component_ethernet_interface(
@@ -975,14 +1019,12 @@ impl ComponentEthernetInterfaceTrait for Ochami {
     auth_token: &str,
     ethernet_interface: &ComponentEthernetInterface,
   ) -> Result<(), Error> {
-    hsm::inventory::ethernet_interfaces::http_client::post(
-      auth_token,
-      &self.base_url,
-      &self.root_cert,
+    hsm::inventory::ethernet_interfaces::client::post(
+      &self.context(auth_token).map_err(Error::from)?,
       ethernet_interface.clone().into(),
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn update_component_ethernet_interface(
@@ -997,10 +1039,8 @@ impl ComponentEthernetInterfaceTrait for Ochami {
     description: Option<&str>,
     ip_address_mapping: (&str, &str),
   ) -> Result<Value, Error> {
-    hsm::inventory::ethernet_interfaces::http_client::patch(
-      auth_token,
-      &self.base_url,
-      &self.root_cert,
+    hsm::inventory::ethernet_interfaces::client::patch(
+      &self.context(auth_token).map_err(Error::from)?,
       //shasta_token,
       //shasta_base_url,
       //shasta_root_cert,
@@ -1009,20 +1049,19 @@ impl ComponentEthernetInterfaceTrait for Ochami {
       ip_address_mapping,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn delete_all_component_ethernet_interfaces(
     &self,
     auth_token: &str,
   ) -> Result<Value, Error> {
-    hsm::inventory::ethernet_interfaces::http_client::delete_all(
-      auth_token,
-      &self.base_url,
-      &self.root_cert,
+    hsm::inventory::ethernet_interfaces::client::delete_all(
+      &self.context(auth_token).map_err(Error::from)?,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
+    .and_then(|response| serde_json::to_value(response).map_err(Error::from))
   }
 
   async fn delete_component_ethernet_interface(
@@ -1032,14 +1071,13 @@ impl ComponentEthernetInterfaceTrait for Ochami {
     //root_cert: &[u8],
     eth_interface_id: &str,
   ) -> Result<Value, Error> {
-    hsm::inventory::ethernet_interfaces::http_client::delete_one(
-      auth_token,
-      &self.base_url,
-      &self.root_cert,
+    hsm::inventory::ethernet_interfaces::client::delete_one(
+      &self.context(auth_token).map_err(Error::from)?,
       eth_interface_id,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
+    .and_then(|response| serde_json::to_value(response).map_err(Error::from))
   }
 
   /* async fn get_ip_addresses(
@@ -1047,15 +1085,13 @@ impl ComponentEthernetInterfaceTrait for Ochami {
     auth_token: &str,
     eth_interface_id: &str,
   ) -> Result<Vec<IpAddressMapping>, Error> {
-    hsm::inventory::ethernet_interfaces::http_client::get_ip_addresses(
-      auth_token,
-      &self.base_url,
-      &self.root_cert,
+    hsm::inventory::ethernet_interfaces::client::get_ip_addresses(
+      &self.context(auth_token).map_err(Error::from)?,
       eth_interface_id,
     )
     .await
     .map(|re| re.into_iter().map(|item| item.into()).collect())
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn delete_ip_address(
@@ -1067,16 +1103,13 @@ impl ComponentEthernetInterfaceTrait for Ochami {
     eth_interface_id: &str,
     ip_address: &str,
   ) -> Result<Value, Error> {
-    hsm::inventory::ethernet_interfaces::http_client::delete_ip_address(
-      auth_token,
-      &self.base_url,
-      &self.root_cert,
-      _group_label,
+    hsm::inventory::ethernet_interfaces::client::delete_ip_address(
+      &self.context(auth_token).map_err(Error::from)?,
       eth_interface_id,
       ip_address,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   } */
 }
 
@@ -1085,14 +1118,14 @@ impl ComponentEthernetInterfaceTrait for Ochami {
 //    auth_token: &str,
 //    component_ethernet_interface: &ComponentEthernetInterfaceArray,
 //  ) -> Result<(), Error> {
-//    hsm::inventory::ethernet_interfaces::http_client::post(
+//    hsm::inventory::ethernet_interfaces::client::post(
 //      auth_token,
 //      &self.base_url,
 //      &self.root_cert,
 //      component_ethernet_interface.clone().into(),
 //    )
 //    .await
-//    .map_err(|e| Error::Message(e.to_string()))?;
+//    .map_err(Error::from)?;
 //
 //    Ok(())
 //  }
@@ -1102,7 +1135,7 @@ impl ComponentEthernetInterfaceTrait for Ochami {
 //    auth_token: &str,
 //    component_ethernet_interface: &ComponentEthernetInterface,
 //  ) -> Result<(), Error> {
-//    hsm::inventory::ethernet_interfaces::http_client::put(
+//    hsm::inventory::ethernet_interfaces::client::put(
 //      auth_token,
 //      &self.base_url,
 //      &self.root_cert,
@@ -1110,7 +1143,7 @@ impl ComponentEthernetInterfaceTrait for Ochami {
 //      component_ethernet_interface.clone().into(),
 //    )
 //    .await
-//    .map_err(|e| Error::Message(e.to_string()))?;
+//    .map_err(Error::from)?;
 //
 //    Ok(())
 //  }
@@ -1120,14 +1153,14 @@ impl ComponentEthernetInterfaceTrait for Ochami {
 //    auth_token: &str,
 //    id: &str,
 //  ) -> Result<Value, Error> {
-//    hsm::inventory::ethernet_interfaces::http_client::delete_one(
+//    hsm::inventory::ethernet_interfaces::client::delete_one(
 //      &self.base_url,
 //      auth_token,
 //      &self.root_cert,
 //      id,
 //    )
 //    .await
-//    .map_err(|e| Error::Message(e.to_string()))
+//    .map_err(Error::from)
 //  }
 //}
 // --------- end
@@ -1154,6 +1187,47 @@ impl AuthenticationTrait for Ochami {
 
 impl CfsTrait for Ochami {
   type T = Pin<Box<dyn AsyncBufRead + Send>>;
+
+  // NOTE: configurations are backed by cloud-init groups
+  async fn get_configuration(
+    &self,
+    auth_token: &str,
+    configuration_name_opt: Option<&String>,
+  ) -> Result<Vec<CfsConfigurationResponse>, Error> {
+    cloud_init::utils::get_and_filter_groups(
+      &self.context(auth_token).map_err(Error::from)?,
+      configuration_name_opt.map(String::as_str),
+      None,
+      &[],
+      None,
+    )
+    .await
+    .map(|group_vec| group_vec.into_iter().map(|group| group.into()).collect())
+    .map_err(Error::from)
+  }
+
+  async fn get_and_filter_configuration(
+    &self,
+    auth_token: &str,
+    configuration_name: Option<&str>,
+    configuration_name_pattern: Option<&str>,
+    hsm_group_name_vec: &[String],
+    _since_opt: Option<NaiveDateTime>,
+    _until_opt: Option<NaiveDateTime>,
+    limit_number_opt: Option<&u8>,
+  ) -> Result<Vec<CfsConfigurationResponse>, Error> {
+    // NOTE: cloud-init groups have no timestamp, so 'since' and 'until' are ignored
+    cloud_init::utils::get_and_filter_groups(
+      &self.context(auth_token).map_err(Error::from)?,
+      configuration_name,
+      configuration_name_pattern,
+      hsm_group_name_vec,
+      limit_number_opt.map(|limit| *limit as usize),
+    )
+    .await
+    .map(|group_vec| group_vec.into_iter().map(|group| group.into()).collect())
+    .map_err(Error::from)
+  }
 }
 
 impl SatTrait for Ochami {}
@@ -1170,9 +1244,157 @@ impl MigrateBackupTrait for Ochami {}
 
 impl GetImagesAndDetailsTrait for Ochami {}
 
-impl ClusterSessionTrait for Ochami {}
+// NOTE: OpenCHAMI has no BOS, sessions set BSS boot parameters and power cycle the
+// nodes through PCS, see `session::utils::run`. The call blocks until PCS completes
+impl ClusterSessionTrait for Ochami {
+  async fn post_template_session(
+    &self,
+    auth_token: &str,
+    mut bos_session: BosSession,
+  ) -> Result<BosSession, Error> {
+    let start_time = Utc::now();
+
+    let operation: session::types::SessionOperation = bos_session
+      .operation
+      .take()
+      .map(|operation| operation.into())
+      .unwrap_or(session::types::SessionOperation::Boot);
+
+    let limit: Vec<String> = bos_session
+      .limit
+      .as_deref()
+      .map(|limit| {
+        limit
+          .split(',')
+          .map(str::trim)
+          .filter(|target| !target.is_empty())
+          .map(str::to_string)
+          .collect()
+      })
+      .unwrap_or_default();
+
+    let report = session::utils::run(
+      &self.context(auth_token).map_err(Error::from)?,
+      &bos_session.template_name,
+      operation,
+      &session::types::SessionSettings {
+        limit,
+        stage: bos_session.stage.unwrap_or(false),
+        ..Default::default()
+      },
+      |status| {
+        log::info!(
+          "Session on template '{}': transition {} {} ({}/{} tasks succeeded)",
+          bos_session.template_name,
+          status.transition_id,
+          status.transition_status,
+          status.task_counts.succeeded,
+          status.task_counts.total
+        )
+      },
+    )
+    .await
+    .map_err(Error::from)?;
+
+    let error = report
+      .outcome
+      .as_ref()
+      .filter(|outcome| !outcome.is_success())
+      .map(|outcome| {
+        format!(
+          "{} nodes failed, {} nodes pending",
+          outcome.failed.len(),
+          outcome.pending.len()
+        )
+      });
+
+    bos_session.name.get_or_insert_with(|| {
+      format!(
+        "{}-{}",
+        bos_session.template_name,
+        start_time.format("%Y%m%d%H%M%S")
+      )
+    });
+    bos_session.operation = Some(report.operation.into());
+    bos_session.components = Some(report.xnames.join(","));
+    bos_session.status = Some(Status {
+      start_time: start_time.to_rfc3339(),
+      end_time: Some(Utc::now().to_rfc3339()),
+      status: StatusLabel::Complete,
+      error,
+    });
+
+    Ok(bos_session)
+  }
+}
+
+// NOTE: templates are derived from HSM groups, BSS boot parameters and cloud-init
+// groups, see `session_template::utils::get_all`
+impl ClusterTemplateTrait for Ochami {
+  async fn get_template(
+    &self,
+    auth_token: &str,
+    bos_session_template_id_opt: Option<&str>,
+  ) -> Result<Vec<BosSessionTemplate>, Error> {
+    session_template::utils::get_and_filter(
+      &self.context(auth_token).map_err(Error::from)?,
+      bos_session_template_id_opt,
+      &[],
+      &[],
+      None,
+    )
+    .await
+    .map(|template_vec| {
+      template_vec
+        .into_iter()
+        .map(|template| template.into())
+        .collect()
+    })
+    .map_err(Error::from)
+  }
+
+  async fn get_and_filter_templates(
+    &self,
+    auth_token: &str,
+    hsm_group_name_vec: &[String],
+    hsm_member_vec: &[String],
+    bos_sessiontemplate_name_opt: Option<&str>,
+    limit_number_opt: Option<&u8>,
+  ) -> Result<Vec<BosSessionTemplate>, Error> {
+    session_template::utils::get_and_filter(
+      &self.context(auth_token).map_err(Error::from)?,
+      bos_sessiontemplate_name_opt,
+      hsm_group_name_vec,
+      hsm_member_vec,
+      limit_number_opt.map(|limit| *limit as usize),
+    )
+    .await
+    .map(|template_vec| {
+      template_vec
+        .into_iter()
+        .map(|template| template.into())
+        .collect()
+    })
+    .map_err(Error::from)
+  }
 
-impl ClusterTemplateTrait for Ochami {}
+  async fn get_all_templates(
+    &self,
+    auth_token: &str,
+  ) -> Result<Vec<BosSessionTemplate>, Error> {
+    session_template::utils::get_all(
+      &self.context(auth_token).map_err(Error::from)?,
+    )
+    .await
+    .map(|template_vec| {
+      template_vec
+        .into_iter()
+        .map(|template| template.into())
+        .collect()
+    })
+    .map_err(Error::from)
+  }
+}
 
 impl DeleteConfigurationsAndDataRelatedTrait for Ochami {}
 
@@ -0,0 +1,119 @@
+use std::{future::Future, sync::OnceLock};
+
+use tokio::runtime::{Builder, Runtime};
+
+use crate::error::Error;
+
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+/// Run a future on the private runtime used by the synchronous wrappers. Panics if called
+/// from inside an async context, as any tokio `block_on`
+fn block_on<F, T>(future: F) -> Result<T, Error>
+where
+  F: Future<Output = Result<T, Error>>,
+{
+  let runtime = match RUNTIME.get() {
+    Some(runtime) => runtime,
+    None => {
+      let runtime = Builder::new_current_thread().enable_all().build()?;
+      RUNTIME.get_or_init(|| runtime)
+    }
+  };
+
+  runtime.block_on(future)
+}
+
+#[cfg(feature = "hsm")]
+pub mod hsm {
+  use crate::{
+    error::Error,
+    hsm::{
+      component::{self, types::ComponentArray},
+      state::components::types::Component,
+    },
+    http::ApiContext,
+  };
+
+  use super::block_on;
+
+  /// Get all HSM components
+  pub fn get_components(ctx: &ApiContext) -> Result<ComponentArray, Error> {
+    block_on(component::client::get_all(ctx))
+  }
+
+  /// Get a HSM component
+  pub fn get_component(
+    ctx: &ApiContext,
+    xname: &str,
+  ) -> Result<Component, Error> {
+    block_on(component::client::get_one(ctx, xname))
+  }
+}
+
+#[cfg(feature = "bss")]
+pub mod bss {
+  use crate::{
+    bss::{self, types::BootParameters},
+    error::Error,
+    http::ApiContext,
+  };
+
+  use super::block_on;
+
+  /// Get the boot parameters of a list of nodes, all boot parameters if the list is
+  /// empty
+  pub fn get_bootparameters(
+    ctx: &ApiContext,
+    xname_vec: &[String],
+  ) -> Result<Vec<BootParameters>, Error> {
+    let xnames_opt = if xname_vec.is_empty() {
+      None
+    } else {
+      Some(xname_vec.to_vec())
+    };
+
+    block_on(bss::client::get(ctx, &xnames_opt))
+  }
+
+  pub fn patch_bootparameters(
+    ctx: &ApiContext,
+    boot_parameters: &BootParameters,
+  ) -> Result<(), Error> {
+    block_on(bss::client::patch(ctx, boot_parameters))
+  }
+}
+
+#[cfg(feature = "pcs")]
+pub mod pcs {
+  use crate::{
+    error::Error,
+    http::ApiContext,
+    pcs::{self, transitions::types::TransitionOutcome},
+  };
+
+  use super::block_on;
+
+  /// Power on nodes and wait for the transition to complete
+  pub fn power_on(
+    ctx: &ApiContext,
+    xname_vec: &[String],
+  ) -> Result<TransitionOutcome, Error> {
+    block_on(pcs::utils::power_on(ctx, xname_vec))
+  }
+
+  /// Power off nodes and wait for the transition to complete
+  pub fn power_off(
+    ctx: &ApiContext,
+    xname_vec: &[String],
+  ) -> Result<TransitionOutcome, Error> {
+    block_on(pcs::utils::power_off(ctx, xname_vec))
+  }
+
+  /// Hard restart nodes and wait for the transition to complete
+  pub fn power_reset(
+    ctx: &ApiContext,
+    xname_vec: &[String],
+  ) -> Result<TransitionOutcome, Error> {
+    block_on(pcs::utils::power_hard_restart(ctx, xname_vec))
+  }
+}
@@ -0,0 +1,301 @@
+use std::path::Path;
+
+use crate::{
+  error::Error,
+  http::{ApiContext, MutationRequest, RawResponse, SpilledResponse},
+  progress::ProgressSink,
+};
+use serde_json::Value;
+
+use core::result::Result;
+
+use super::types::{BootParameters, DumpState};
+
+pub async fn get_all(ctx: &ApiContext) -> Result<Vec<BootParameters>, Error> {
+  get(ctx, &None).await
+}
+
+pub async fn get(
+  ctx: &ApiContext,
+  xnames_opt: &Option<Vec<String>>,
+) -> Result<Vec<BootParameters>, Error> {
+  let client = ctx.client()?;
+
+  let url_api = ctx.bss_url("/bootparameters")?;
+
+  let payload = xnames_opt.as_ref().map(|xname_vec| BootParameters {
+    hosts: xname_vec.clone(),
+    macs: None,
+    nids: None,
+    params: String::new(),
+    kernel: String::new(),
+    initrd: String::new(),
+    cloud_init: None,
+  });
+
+  let response = client
+    .get(url_api)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(&payload)
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: serde_json::to_string_pretty(&error_payload)?,
+        });
+      }
+    }
+  }
+
+  // BSS returns `null` instead of an empty list if no boot parameters match
+  let boot_parameters_opt: Option<Vec<BootParameters>> =
+    crate::http::json_from_bytes(response).await?;
+
+  Ok(boot_parameters_opt.unwrap_or_default())
+}
+
+/// Same as `get` but returns the raw response, whatever its status
+pub async fn get_raw(
+  ctx: &ApiContext,
+  xnames_opt: &Option<Vec<String>>,
+) -> Result<RawResponse<Vec<BootParameters>>, Error> {
+  let client = ctx.client()?;
+
+  let url_api = ctx.bss_url("/bootparameters")?;
+
+  let payload = xnames_opt.as_ref().map(|xname_vec| BootParameters {
+    hosts: xname_vec.clone(),
+    macs: None,
+    nids: None,
+    params: String::new(),
+    kernel: String::new(),
+    initrd: String::new(),
+    cloud_init: None,
+  });
+
+  let response = client
+    .get(url_api)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(&payload)
+    .send()
+    .await?;
+
+  RawResponse::from_response(response).await
+}
+
+pub async fn post(
+  ctx: &ApiContext,
+  boot_parameters: BootParameters,
+) -> Result<(), Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.bss_url("/bootparameters")?;
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(&boot_parameters)
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: serde_json::to_string_pretty(&error_payload)?,
+        });
+      }
+    }
+  }
+
+  Ok(())
+}
+
+pub async fn put(
+  ctx: &ApiContext,
+  boot_parameters: &BootParameters,
+) -> Result<BootParameters, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.bss_url("/bootparameters")?;
+
+  let response = client
+    .put(api_url)
+    .json(&boot_parameters)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: serde_json::to_string_pretty(&error_payload)?,
+        });
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn patch(
+  ctx: &ApiContext,
+  boot_parameters: &BootParameters,
+) -> Result<(), Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.bss_url("/bootparameters")?;
+
+  let response = client
+    .patch(api_url)
+    .json(&boot_parameters)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: serde_json::to_string_pretty(&error_payload)?,
+        });
+      }
+    }
+  }
+
+  Ok(())
+}
+
+pub async fn delete(
+  ctx: &ApiContext,
+  boot_parameters: &BootParameters,
+) -> Result<String, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.bss_url("/bootparameters")?;
+
+  let response = client
+    .delete(api_url)
+    .json(&boot_parameters)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: serde_json::to_string_pretty(&error_payload)?,
+        });
+      }
+    }
+  }
+
+  response
+    .text()
+    .await
+    .map_err(|e| Error::Message(e.to_string()))
+}
+
+/// Get the iPXE boot script BSS serves to `host`
+pub async fn get_bootscript(
+  ctx: &ApiContext,
+  host: &str,
+) -> Result<String, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.bss_url("/bootscript")?;
+
+  let response = client
+    .get(api_url)
+    .query(&[("host", host)])
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    let error_payload = response.text().await?;
+    return Err(Error::RequestError {
+      response: e,
+      payload: error_payload,
+    });
+  }
+
+  response.text().await.map_err(Error::NetError)
+}
+
+/// Write the BSS dump to `path` as it is received, decompressed on the fly if BSS sends
+/// it gzipped. Process the file with `bss::utils::for_each_dumpstate_entry` or parse it
+/// whole with `SpilledResponse::parse`
+pub async fn get_dumpstate_to_file(
+  ctx: &ApiContext,
+  path: &Path,
+  progress: &dyn ProgressSink,
+) -> Result<SpilledResponse<DumpState>, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.bss_url("/dumpstate")?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    let error_payload = response.text().await?;
+    return Err(Error::RequestError {
+      response: e,
+      payload: error_payload,
+    });
+  }
+
+  crate::http::spill_to_file(response, path, progress).await
+}
@@ -0,0 +1,145 @@
+use crate::bss::types::{BootParameters, DumpStateEntry};
+
+#[test]
+fn test_boot_parameters_new() {
+  let boot_parameters = BootParameters::new(
+    vec!["x1000c0s0b0n0"],
+    None,
+    Some(vec!["1"]),
+    "console=ttyS0",
+    "s3://boot-images/kernel",
+    "s3://boot-images/initrd",
+    None,
+  )
+  .unwrap();
+
+  assert_eq!(boot_parameters.nids, Some(vec![1]));
+}
+
+#[test]
+fn test_boot_parameters_new_invalid_nid() {
+  let boot_parameters = BootParameters::new(
+    vec!["x1000c0s0b0n0"],
+    None,
+    Some(vec!["nid000001"]),
+    "console=ttyS0",
+    "s3://boot-images/kernel",
+    "s3://boot-images/initrd",
+    None,
+  );
+
+  assert!(boot_parameters.is_err())
+}
+
+/// Synthetic BSS dump generated on the fly, so large dumps can be read without holding
+/// them in memory
+struct DumpStateReader {
+  entry_count: usize,
+  next_entry: usize,
+  buffer: Vec<u8>,
+  position: usize,
+}
+
+impl DumpStateReader {
+  fn new(entry_count: usize) -> Self {
+    DumpStateReader {
+      entry_count,
+      next_entry: 0,
+      buffer:
+        br#"{"Components":[{"ID":"x1000c0s0b0n0","Type":"Node"}],"Params":["#
+          .to_vec(),
+      position: 0,
+    }
+  }
+
+  fn refill(&mut self) {
+    self.buffer.clear();
+    self.position = 0;
+
+    if self.next_entry > self.entry_count {
+      return;
+    }
+
+    if self.next_entry == self.entry_count {
+      self.buffer.extend_from_slice(b"]}");
+    } else {
+      if self.next_entry > 0 {
+        self.buffer.push(b',');
+      }
+      self.buffer.extend_from_slice(
+        format!(
+          r#"{{"hosts":["x1000c{}s{}b0n{}"],"params":"console=ttyS0,115200 root=live:s3://boot-images/compute/rootfs ip=dhcp","kernel":"s3://boot-images/compute/kernel","initrd":"s3://boot-images/compute/initrd"}}"#,
+          self.next_entry / 64,
+          (self.next_entry / 2) % 32,
+          self.next_entry % 2
+        )
+        .as_bytes(),
+      );
+    }
+
+    self.next_entry += 1;
+  }
+}
+
+impl std::io::Read for DumpStateReader {
+  fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+    if self.position == self.buffer.len() {
+      self.refill();
+    }
+
+    let len = buf.len().min(self.buffer.len() - self.position);
+    buf[..len]
+      .copy_from_slice(&self.buffer[self.position..self.position + len]);
+    self.position += len;
+
+    Ok(len)
+  }
+}
+
+#[test]
+fn test_for_each_dumpstate_entry() {
+  let mut component_count = 0;
+  let mut params_count = 0;
+
+  let entry_count = crate::bss::utils::for_each_dumpstate_entry(
+    DumpStateReader::new(1_000),
+    |entry| {
+      match entry {
+        DumpStateEntry::Component(_) => component_count += 1,
+        DumpStateEntry::Params(boot_parameters) => {
+          assert_eq!(boot_parameters.hosts.len(), 1);
+          params_count += 1;
+        }
+      }
+      Ok(())
+    },
+  )
+  .unwrap();
+
+  assert_eq!(entry_count, 1_001);
+  assert_eq!(component_count, 1);
+  assert_eq!(params_count, 1_000);
+}
+
+#[test]
+fn test_for_each_dumpstate_entry_stops_on_error() {
+  let rslt = crate::bss::utils::for_each_dumpstate_entry(
+    DumpStateReader::new(10),
+    |_| Err(crate::error::Error::Message("stop".to_string())),
+  );
+
+  assert!(rslt.is_err());
+}
+
+/// ~350MB dump, run with `cargo test --release -- --ignored`
+#[test]
+#[ignore]
+fn test_for_each_dumpstate_entry_large() {
+  let entry_count = crate::bss::utils::for_each_dumpstate_entry(
+    DumpStateReader::new(1_500_000),
+    |_| Ok(()),
+  )
+  .unwrap();
+
+  assert_eq!(entry_count, 1_500_001);
+}
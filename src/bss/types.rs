@@ -1,13 +1,16 @@
+#[cfg(feature = "backend-connector")]
 use manta_backend_dispatcher::types::bss::BootParameters as FrontEndBootParameters;
 
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::error::Error;
 
-#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[derive(
+  Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq, Hash,
+)]
 pub struct BootParameters {
   #[serde(default)]
   pub hosts: Vec<String>,
@@ -28,6 +31,7 @@ pub struct BootParameters {
   pub cloud_init: Option<Value>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndBootParameters> for BootParameters {
   fn from(value: FrontEndBootParameters) -> Self {
     BootParameters {
@@ -42,6 +46,7 @@ impl From<FrontEndBootParameters> for BootParameters {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndBootParameters> for BootParameters {
   fn into(self) -> FrontEndBootParameters {
     FrontEndBootParameters {
@@ -57,6 +62,7 @@ impl Into<FrontEndBootParameters> for BootParameters {
 }
 
 impl BootParameters {
+  /// Fails if a NID is not a number
   pub fn new(
     hosts: Vec<&str>,
     macs: Option<Vec<&str>>,
@@ -65,23 +71,31 @@ impl BootParameters {
     kernel: &str,
     initrd: &str,
     cloud_init_opt: Option<&str>,
-  ) -> Self {
-    BootParameters {
+  ) -> Result<Self, Error> {
+    let nids = nids
+      .map(|nid_vec| {
+        nid_vec
+          .iter()
+          .map(|value| {
+            value.parse::<u32>().map_err(|_| {
+              Error::Message(format!("'{}' is not a valid NID", value))
+            })
+          })
+          .collect::<Result<Vec<u32>, Error>>()
+      })
+      .transpose()?;
+
+    Ok(BootParameters {
       hosts: hosts.iter().map(|value| value.to_string()).collect(),
       macs: macs
         .map(|mac_vec| mac_vec.iter().map(|value| value.to_string()).collect()),
-      nids: nids.map(|nid_vec| {
-        nid_vec
-          .iter()
-          .map(|value| value.parse::<u32>().unwrap())
-          .collect()
-      }),
+      nids,
       params: params.to_string(),
       kernel: kernel.to_string(),
       initrd: initrd.to_string(),
       cloud_init: cloud_init_opt
-        .map(|cloud_init| serde_json::to_value(cloud_init).unwrap()),
-    }
+        .map(|cloud_init| Value::String(cloud_init.to_string())),
+    })
   }
 
   /// Returns the image id. This function may fail since it assumes kernel path has the following
@@ -601,3 +615,34 @@ impl BootParameters {
     changed
   }
 }
+
+impl fmt::Display for BootParameters {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{}: kernel '{}', initrd '{}', params '{}'",
+      crate::hostlist::compress(&self.hosts),
+      self.kernel,
+      self.initrd,
+      self.params
+    )
+  }
+}
+
+/// Response of `GET /dumpstate`, the HSM components known by BSS and every boot
+/// parameters entry. Use `bss::utils::for_each_dumpstate_entry` to process large dumps
+/// without loading them in memory
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct DumpState {
+  #[serde(rename = "Components", default)]
+  pub components: Vec<Value>,
+  #[serde(rename = "Params", default)]
+  pub params: Vec<BootParameters>,
+}
+
+/// An entry of a BSS dump
+#[derive(Debug, Clone)]
+pub enum DumpStateEntry {
+  Component(Value),
+  Params(BootParameters),
+}
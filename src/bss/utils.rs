@@ -1,8 +1,24 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt, io::Read};
 
+use futures_util::{stream, Stream, StreamExt};
+use serde::de::{
+  self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess,
+  Visitor,
+};
 use serde_json::Value;
 
-use super::types::BootParameters;
+use crate::{
+  error::Error,
+  hostlist,
+  http::ApiContext,
+  progress::{ProgressEvent, ProgressSink},
+  utils::for_each_item,
+};
+
+use super::{
+  client,
+  types::{BootParameters, DumpStateEntry},
+};
 
 pub fn convert_kernel_params_to_map(
   kernel_params: &str,
@@ -43,3 +59,217 @@ pub fn get_image_id(node_boot_params: &Value) -> String {
     .unwrap()
     .get_boot_image()
 }
+
+/// Patch a list of boot parameters one after the other, reporting each one to
+/// `progress`. All boot parameters are attempted, the error returned lists the ones
+/// which failed
+pub async fn patch_many(
+  ctx: &ApiContext,
+  boot_parameters_vec: &[BootParameters],
+  progress: &dyn ProgressSink,
+) -> Result<(), Error> {
+  let mut failure_vec: Vec<String> = Vec::new();
+
+  progress.report(ProgressEvent::Started {
+    operation: "patch boot parameters",
+    total: boot_parameters_vec.len(),
+  });
+
+  for boot_parameters in boot_parameters_vec {
+    let hosts = hostlist::compress(&boot_parameters.hosts);
+
+    match client::patch(ctx, boot_parameters).await {
+      Ok(()) => progress.report(ProgressEvent::ItemCompleted { item: &hosts }),
+      Err(e) => {
+        let reason = e.to_string();
+        progress.report(ProgressEvent::ItemFailed {
+          item: &hosts,
+          reason: &reason,
+        });
+        failure_vec.push(format!("{}: {}", hosts, reason));
+      }
+    }
+  }
+
+  progress.report(ProgressEvent::Finished {
+    completed: boot_parameters_vec.len() - failure_vec.len(),
+    failed: failure_vec.len(),
+  });
+
+  if failure_vec.is_empty() {
+    Ok(())
+  } else {
+    Err(Error::Message(format!(
+      "Could not patch boot parameters.\n{}",
+      failure_vec.join("\n")
+    )))
+  }
+}
+
+/// Delete the boot parameters of the hosts of a hostlist expression (eg:
+/// `x1000c0s[0-7]b0n[0-1]`), with at most `concurrency` requests in flight. Every host is
+/// attempted, the result of each one is returned
+pub async fn delete_many(
+  ctx: &ApiContext,
+  hosts: &str,
+  concurrency: usize,
+) -> Result<HashMap<String, Result<(), Error>>, Error> {
+  let host_vec = hostlist_parser::parse(hosts).map_err(|e| {
+    Error::Message(format!(
+      "Could not parse '{}' as a hostlist. Reason:\n{}",
+      hosts, e
+    ))
+  })?;
+
+  log::info!("Delete boot parameters of {} hosts", host_vec.len());
+
+  let result_map = for_each_item(&host_vec, concurrency, |host| async move {
+    let boot_parameters = BootParameters {
+      hosts: vec![host],
+      ..Default::default()
+    };
+
+    client::delete(ctx, &boot_parameters).await.map(|_| ())
+  })
+  .await;
+
+  Ok(result_map)
+}
+
+/// Stream the boot parameters of a list of nodes. Boot parameters are requested in chunks
+/// of `chunk_size` xnames, the next chunk is only requested once the consumer has
+/// processed the previous one
+pub fn stream_bootparameters<'a>(
+  ctx: &'a ApiContext,
+  xname_vec: &[String],
+  chunk_size: usize,
+) -> impl Stream<Item = Result<BootParameters, Error>> + 'a {
+  let xname_chunk_vec: Vec<Vec<String>> = xname_vec
+    .chunks(chunk_size.max(1))
+    .map(|xname_chunk| xname_chunk.to_vec())
+    .collect();
+
+  stream::iter(xname_chunk_vec)
+    .then(move |xname_chunk| async move {
+      client::get(ctx, &Some(xname_chunk)).await
+    })
+    .flat_map(|boot_parameters_rslt| {
+      let boot_parameters_rslt_vec: Vec<Result<BootParameters, Error>> =
+        match boot_parameters_rslt {
+          Ok(boot_parameters_vec) => {
+            boot_parameters_vec.into_iter().map(Ok).collect()
+          }
+          Err(e) => vec![Err(e)],
+        };
+
+      stream::iter(boot_parameters_rslt_vec)
+    })
+}
+
+/// Parse a BSS dump (see `client::get_dumpstate_to_file`) entry by entry, calling `f`
+/// on each HSM component and boot parameters entry. Only one entry is held in memory at a
+/// time. Stops at the first error returned by `f`. Returns the number of entries
+pub fn for_each_dumpstate_entry<R, F>(reader: R, f: F) -> Result<usize, Error>
+where
+  R: Read,
+  F: FnMut(DumpStateEntry) -> Result<(), Error>,
+{
+  let mut visitor = DumpStateVisitor { f, count: 0 };
+  let mut deserializer =
+    serde_json::Deserializer::from_reader(std::io::BufReader::new(reader));
+
+  deserializer.deserialize_map(&mut visitor)?;
+  deserializer.end()?;
+
+  Ok(visitor.count)
+}
+
+struct DumpStateVisitor<F> {
+  f: F,
+  count: usize,
+}
+
+impl<'de, F> Visitor<'de> for &mut DumpStateVisitor<F>
+where
+  F: FnMut(DumpStateEntry) -> Result<(), Error>,
+{
+  type Value = ();
+
+  fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    formatter.write_str("a BSS dump")
+  }
+
+  fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+    while let Some(key) = map.next_key::<String>()? {
+      match key.as_str() {
+        "Components" => map.next_value_seed(DumpStateSection {
+          visitor: &mut *self,
+          is_component: true,
+        })?,
+        "Params" => map.next_value_seed(DumpStateSection {
+          visitor: &mut *self,
+          is_component: false,
+        })?,
+        _ => {
+          map.next_value::<IgnoredAny>()?;
+        }
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// The `Components` or `Params` list of a BSS dump, `null` is read as an empty list
+struct DumpStateSection<'v, F> {
+  visitor: &'v mut DumpStateVisitor<F>,
+  is_component: bool,
+}
+
+impl<'de, F> DeserializeSeed<'de> for DumpStateSection<'_, F>
+where
+  F: FnMut(DumpStateEntry) -> Result<(), Error>,
+{
+  type Value = ();
+
+  fn deserialize<D: Deserializer<'de>>(
+    self,
+    deserializer: D,
+  ) -> Result<(), D::Error> {
+    deserializer.deserialize_any(self)
+  }
+}
+
+impl<'de, F> Visitor<'de> for DumpStateSection<'_, F>
+where
+  F: FnMut(DumpStateEntry) -> Result<(), Error>,
+{
+  type Value = ();
+
+  fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    formatter.write_str("a list of BSS dump entries")
+  }
+
+  fn visit_unit<E: de::Error>(self) -> Result<(), E> {
+    Ok(())
+  }
+
+  fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+    loop {
+      let entry_opt = if self.is_component {
+        seq.next_element::<Value>()?.map(DumpStateEntry::Component)
+      } else {
+        seq
+          .next_element::<BootParameters>()?
+          .map(DumpStateEntry::Params)
+      };
+
+      let Some(entry) = entry_opt else {
+        return Ok(());
+      };
+
+      (self.visitor.f)(entry).map_err(de::Error::custom)?;
+      self.visitor.count += 1;
+    }
+  }
+}
@@ -0,0 +1,233 @@
+use crate::{
+  error::Error,
+  http::{ApiContext, MutationRequest},
+};
+
+use super::types::{CloudInitDocument, GroupData, InstanceInfo};
+
+pub async fn get_all_groups(ctx: &ApiContext) -> Result<Vec<GroupData>, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.cloud_init_url("/admin/groups")?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    return Err(request_error(e, response).await);
+  }
+
+  // NOTE: cloud-init returns groups as a map indexed by group name
+  response
+    .json::<std::collections::HashMap<String, GroupData>>()
+    .await
+    .map(|group_map| group_map.into_values().collect())
+    .map_err(Error::NetError)
+}
+
+pub async fn get_group(
+  ctx: &ApiContext,
+  group_name: &str,
+) -> Result<GroupData, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.cloud_init_url(&format!("/admin/groups/{}", group_name))?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    return Err(request_error(e, response).await);
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn post_group(
+  ctx: &ApiContext,
+  group: &GroupData,
+) -> Result<(), Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.cloud_init_url("/admin/groups")?;
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(group)
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    return Err(request_error(e, response).await);
+  }
+
+  Ok(())
+}
+
+pub async fn put_group(
+  ctx: &ApiContext,
+  group: &GroupData,
+) -> Result<(), Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.cloud_init_url(&format!("/admin/groups/{}", group.name))?;
+
+  let response = client
+    .put(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(group)
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    return Err(request_error(e, response).await);
+  }
+
+  Ok(())
+}
+
+pub async fn delete_group(
+  ctx: &ApiContext,
+  group_name: &str,
+) -> Result<(), Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.cloud_init_url(&format!("/admin/groups/{}", group_name))?;
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    return Err(request_error(e, response).await);
+  }
+
+  Ok(())
+}
+
+/// Get the cloud-init data specific to a node
+pub async fn get_instance_info(
+  ctx: &ApiContext,
+  node_id: &str,
+) -> Result<InstanceInfo, Error> {
+  let client = ctx.client()?;
+  let api_url =
+    ctx.cloud_init_url(&format!("/admin/instance-info/{}", node_id))?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    return Err(request_error(e, response).await);
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+/// Set the cloud-init data specific to a node
+pub async fn put_instance_info(
+  ctx: &ApiContext,
+  node_id: &str,
+  instance_info: &InstanceInfo,
+) -> Result<(), Error> {
+  let client = ctx.client()?;
+  let api_url =
+    ctx.cloud_init_url(&format!("/admin/instance-info/{}", node_id))?;
+
+  let response = client
+    .put(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(instance_info)
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    return Err(request_error(e, response).await);
+  }
+
+  Ok(())
+}
+
+/// Delete the cloud-init data specific to a node, the node keeps the data of its
+/// groups
+pub async fn delete_instance_info(
+  ctx: &ApiContext,
+  node_id: &str,
+) -> Result<(), Error> {
+  let client = ctx.client()?;
+  let api_url =
+    ctx.cloud_init_url(&format!("/admin/instance-info/{}", node_id))?;
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    return Err(request_error(e, response).await);
+  }
+
+  Ok(())
+}
+
+/// Get a cloud-init document (meta-data, user-data or vendor-data) as the node would
+/// get it.
+/// The server generates the vendor-data from the groups of the node, it has no CRUD
+/// of its own: it changes with the group data and the node group memberships
+pub async fn get_node_document(
+  ctx: &ApiContext,
+  node_id: &str,
+  document: CloudInitDocument,
+) -> Result<String, Error> {
+  let client = ctx.client()?;
+  let api_url = format!(
+    "{}/cloud-init/admin/impersonation/{}/{}",
+    ctx.base_url.as_str(),
+    node_id,
+    document.as_str()
+  );
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    return Err(request_error(e, response).await);
+  }
+
+  response.text().await.map_err(Error::NetError)
+}
+
+async fn request_error(
+  e: reqwest::Error,
+  response: reqwest::Response,
+) -> Error {
+  match response.status() {
+    reqwest::StatusCode::UNAUTHORIZED => match response.text().await {
+      Ok(error_payload) => Error::RequestError {
+        response: e,
+        payload: error_payload,
+      },
+      Err(e) => Error::NetError(e),
+    },
+    _ => match response.text().await {
+      Ok(error_payload) => Error::Message(error_payload),
+      Err(e) => Error::NetError(e),
+    },
+  }
+}
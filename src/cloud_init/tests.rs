@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+
+use crate::{
+  fixtures,
+  test_util::{MockOchami, MockService},
+};
+
+use super::{
+  http_client,
+  types::InstanceInfo,
+  utils::{render_node_user_data, render_template, validate_cloud_config},
+};
+
+fn var_map(var_vec: &[(&str, &str)]) -> HashMap<String, String> {
+  var_vec
+    .iter()
+    .map(|(name, value)| (name.to_string(), value.to_string()))
+    .collect()
+}
+
+#[tokio::test]
+async fn test_instance_info_crud() {
+  let mock = MockOchami::start().await;
+  mock
+    .mock(
+      MockService::CloudInit,
+      "GET",
+      "/admin/instance-info/x1000c0s0b0n0",
+      200,
+      Some(json!({
+        "id": "x1000c0s0b0n0",
+        "local-hostname": "nid000001",
+        "public-keys": ["ssh-ed25519 AAAA admin"]
+      })),
+    )
+    .await;
+  mock
+    .mock(
+      MockService::CloudInit,
+      "PUT",
+      "/admin/instance-info/x1000c0s0b0n0",
+      200,
+      None,
+    )
+    .await;
+  mock
+    .mock(
+      MockService::CloudInit,
+      "DELETE",
+      "/admin/instance-info/x1000c0s0b0n0",
+      200,
+      None,
+    )
+    .await;
+
+  let ctx = mock.context();
+
+  let instance_info = http_client::get_instance_info(&ctx, "x1000c0s0b0n0")
+    .await
+    .unwrap();
+  assert_eq!(instance_info.local_hostname.as_deref(), Some("nid000001"));
+  assert_eq!(
+    instance_info.public_keys,
+    Some(vec!["ssh-ed25519 AAAA admin".to_string()])
+  );
+
+  http_client::put_instance_info(
+    &ctx,
+    "x1000c0s0b0n0",
+    &InstanceInfo {
+      hostname: Some("nid000001".to_string()),
+      ..Default::default()
+    },
+  )
+  .await
+  .unwrap();
+
+  http_client::delete_instance_info(&ctx, "x1000c0s0b0n0")
+    .await
+    .unwrap();
+
+  let request_vec = mock.server().received_requests().await.unwrap();
+  let method_vec: Vec<&str> = request_vec
+    .iter()
+    .map(|request| request.method.as_str())
+    .collect();
+  assert_eq!(method_vec, vec!["GET", "PUT", "DELETE"]);
+  assert_eq!(
+    request_vec[1].body_json::<serde_json::Value>().unwrap(),
+    json!({ "hostname": "nid000001" })
+  );
+}
+
+#[tokio::test]
+async fn test_get_instance_info_not_found() {
+  let mock = MockOchami::start().await;
+  mock
+    .mock_error(
+      MockService::CloudInit,
+      "GET",
+      "/admin/instance-info/x1000c0s0b0n0",
+      404,
+      "instance-info not found",
+    )
+    .await;
+
+  let error = http_client::get_instance_info(&mock.context(), "x1000c0s0b0n0")
+    .await
+    .unwrap_err();
+
+  assert!(error.to_string().contains("instance-info not found"));
+}
+
+#[test]
+fn test_render_template() {
+  let var_map =
+    var_map(&[("xname", "x1000c0s0b0n0"), ("nid", "1"), ("boot-ip", "")]);
+
+  assert_eq!(
+    render_template(
+      "hostname: {{xname}}\nnid: {{ nid }}\nalias: nid{{  nid}}\nip: '{{boot-ip}}'",
+      &var_map
+    )
+    .unwrap(),
+    "hostname: x1000c0s0b0n0\nnid: 1\nalias: nid1\nip: ''"
+  );
+
+  // Nothing to replace, single braces are not placeholders
+  assert_eq!(
+    render_template("runcmd: [echo {xname}]", &var_map).unwrap(),
+    "runcmd: [echo {xname}]"
+  );
+}
+
+#[test]
+fn test_render_template_missing_variables() {
+  let var_map = var_map(&[("xname", "x1000c0s0b0n0")]);
+
+  let e = render_template("{{ xname }} {{ mac }} {{ ip }} {{mac}}", &var_map)
+    .unwrap_err()
+    .to_string();
+
+  // Listed once, in order of appearance
+  assert!(e.contains("Unknown variables in cloud-init template: mac, ip"));
+}
+
+#[test]
+fn test_validate_cloud_config() {
+  assert!(
+    validate_cloud_config("#cloud-config\npackages:\n  - vim\n").is_empty()
+  );
+
+  let issue_vec = validate_cloud_config("packages:\n  - vim\n");
+  assert_eq!(issue_vec.len(), 1);
+  assert_eq!(issue_vec[0].line, Some(1));
+
+  let issue_vec = validate_cloud_config("#cloud-config\n- vim\n");
+  assert_eq!(issue_vec.len(), 1);
+  assert_eq!(issue_vec[0].message, "document must be a YAML mapping");
+
+  let issue_vec = validate_cloud_config("#cloud-config\npackages: [vim\n");
+  assert_eq!(issue_vec.len(), 1);
+  assert!(issue_vec[0].line.is_some());
+}
+
+#[tokio::test]
+async fn test_render_node_user_data() {
+  let mock = MockOchami::start().await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "POST",
+      "/State/Components/Query",
+      200,
+      Some(json!({ "Components": [{ "ID": "x1000c0s0b0n0", "NID": 1 }] })),
+    )
+    .await;
+  mock
+    .mock_json(
+      MockService::Hsm,
+      "GET",
+      "/Inventory/EthernetInterfaces",
+      fixtures::ETHERNET_INTERFACES_JSON,
+    )
+    .await;
+
+  let ctx = mock.context();
+
+  let user_data = render_node_user_data(
+    &ctx,
+    "x1000c0s0b0n0",
+    "#cloud-config\nhostname: nid{{ nid }}\nwrite_files:\n  - path: /etc/mac\n    content: '{{ mac }} {{ ip }}'\n",
+  )
+  .await
+  .unwrap();
+
+  assert_eq!(
+    user_data,
+    "#cloud-config\nhostname: nid1\nwrite_files:\n  - path: /etc/mac\n    content: 'a4:bf:01:38:ee:01 10.252.1.10'\n"
+  );
+
+  let e = render_node_user_data(
+    &ctx,
+    "x1000c0s0b0n0",
+    "#cloud-config\nuser: {{ user }}\n",
+  )
+  .await
+  .unwrap_err()
+  .to_string();
+  assert!(e.contains("Unknown variables in cloud-init template: user"));
+
+  // Rendered but not a cloud-config
+  let e =
+    render_node_user_data(&ctx, "x1000c0s0b0n0", "hostname: {{ xname }}\n")
+      .await
+      .unwrap_err()
+      .to_string();
+  assert!(e.contains("user-data for 'x1000c0s0b0n0' is not valid"));
+}
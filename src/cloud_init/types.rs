@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "backend-connector")]
+use manta_backend_dispatcher::types::cfs::cfs_configuration_response::CfsConfigurationResponse as FrontEndCfsConfigurationResponse;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Cloud-config file attached to a cloud-init group, served to the group members as
+/// (part of) their user-data
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct CloudConfigFile {
+  pub content: String,
+  /// Either `plain` or `base64`
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub encoding: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub filename: Option<String>,
+}
+
+/// Cloud-init data shared by all members of a group. Group names match HSM group labels
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct GroupData {
+  pub name: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub description: Option<String>,
+  /// Variables exposed to the members in their meta-data
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "meta-data")]
+  pub meta_data: Option<HashMap<String, Value>>,
+  /// Cloud-config served to the members in their user-data
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub file: Option<CloudConfigFile>,
+}
+
+// NOTE: OpenCHAMI has no CFS, nodes are configured through cloud-init groups, therefore
+// each cloud-init group is exposed as a configuration without layers
+#[cfg(feature = "backend-connector")]
+impl From<GroupData> for FrontEndCfsConfigurationResponse {
+  fn from(group: GroupData) -> Self {
+    FrontEndCfsConfigurationResponse {
+      name: group.name,
+      last_updated: String::new(),
+      layers: Vec::new(),
+      additional_inventory: None,
+    }
+  }
+}
+
+/// Node specific cloud-init data
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct InstanceInfo {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub id: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "instance-id")]
+  pub instance_id: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "local-hostname")]
+  pub local_hostname: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub hostname: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "cluster-name")]
+  pub cluster_name: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub region: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "availability-zone")]
+  pub availability_zone: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "cloud-provider")]
+  pub cloud_provider: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "instance-type")]
+  pub instance_type: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "public-keys")]
+  pub public_keys: Option<Vec<String>>,
+}
+
+/// Cloud-init documents served to a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloudInitDocument {
+  MetaData,
+  UserData,
+  VendorData,
+}
+
+impl CloudInitDocument {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      CloudInitDocument::MetaData => "meta-data",
+      CloudInitDocument::UserData => "user-data",
+      CloudInitDocument::VendorData => "vendor-data",
+    }
+  }
+}
+
+/// Problem found while validating a cloud-config document
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CloudConfigIssue {
+  /// Line (starting at 1) where the problem was found, if known
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub line: Option<usize>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub column: Option<usize>,
+  pub message: String,
+}
+
+impl std::fmt::Display for CloudConfigIssue {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match (self.line, self.column) {
+      (Some(line), Some(column)) => {
+        write!(f, "line {} column {}: {}", line, column, self.message)
+      }
+      (Some(line), None) => write!(f, "line {}: {}", line, self.message),
+      _ => write!(f, "{}", self.message),
+    }
+  }
+}
+
+/// Cloud-init documents the server would serve to a node
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct NodeCloudInit {
+  pub node_id: String,
+  #[serde(rename = "meta-data")]
+  pub meta_data: String,
+  #[serde(rename = "user-data")]
+  pub user_data: String,
+  #[serde(rename = "vendor-data")]
+  pub vendor_data: String,
+}
+
+/// Export of the cloud-init server configuration
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct CloudInitBackup {
+  pub groups: Vec<GroupData>,
+}
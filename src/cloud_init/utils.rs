@@ -0,0 +1,350 @@
+use std::collections::HashMap;
+
+use regex::{Captures, Regex};
+use serde_json::Value;
+
+use crate::{
+  error::Error,
+  hsm::{
+    self, component::types::ComponentArrayPostQuery, group::utils::GroupPattern,
+  },
+  http::ApiContext,
+};
+
+use super::{
+  http_client,
+  types::{
+    CloudConfigIssue, CloudInitBackup, CloudInitDocument, GroupData,
+    NodeCloudInit,
+  },
+};
+
+/// Meta-data variable holding the xnames of the group members
+pub const XNAMES_VAR: &str = "xnames";
+/// Meta-data variable holding the NIDs of the group members
+pub const NIDS_VAR: &str = "nids";
+
+/// Create or update the cloud-init group matching a HSM group so its meta-data lists
+/// the xnames and NIDs of the HSM group members. Other meta-data variables and the
+/// cloud-config file of an existing cloud-init group are kept
+pub async fn sync_group_from_hsm(
+  ctx: &ApiContext,
+  group_label: &str,
+) -> Result<GroupData, Error> {
+  let hsm_group = hsm::group::client::get_one(ctx, group_label).await?;
+
+  let mut xname_vec = hsm_group.get_members();
+  xname_vec.sort();
+
+  let mut nid_vec: Vec<usize> = if xname_vec.is_empty() {
+    Vec::new()
+  } else {
+    hsm::component::client::post_query(
+      ctx,
+      ComponentArrayPostQuery {
+        component_ids: Some(xname_vec.clone()),
+        ..Default::default()
+      },
+    )
+    .await?
+    .components
+    .unwrap_or_default()
+    .into_iter()
+    .filter_map(|component| component.nid)
+    .collect()
+  };
+  nid_vec.sort();
+
+  let existing_group_opt = http_client::get_all_groups(ctx)
+    .await?
+    .into_iter()
+    .find(|group| group.name == group_label);
+
+  let is_new = existing_group_opt.is_none();
+
+  let mut group = existing_group_opt.unwrap_or_else(|| GroupData {
+    name: group_label.to_string(),
+    description: hsm_group.description.clone(),
+    ..Default::default()
+  });
+
+  let meta_data = group.meta_data.get_or_insert_with(Default::default);
+  meta_data.insert(XNAMES_VAR.to_string(), Value::from(xname_vec));
+  meta_data.insert(NIDS_VAR.to_string(), Value::from(nid_vec));
+
+  if is_new {
+    log::info!("Create cloud-init group '{}'", group_label);
+    http_client::post_group(ctx, &group).await?;
+  } else {
+    log::info!("Update cloud-init group '{}'", group_label);
+    http_client::put_group(ctx, &group).await?;
+  }
+
+  Ok(group)
+}
+
+/// Collect the variables available to user-data templates for a node: `xname`, `nid`,
+/// `mac` and `ip` (first interface in HSM), `macs` and `ips` (comma separated list of
+/// all interfaces)
+pub async fn get_node_template_vars(
+  ctx: &ApiContext,
+  xname: &str,
+) -> Result<HashMap<String, String>, Error> {
+  // NOTE: `get_one` returns a component whose renames only apply when serializing, so
+  // the NID HSM sends is not read. Query the node instead
+  let component = hsm::component::client::post_query(
+    ctx,
+    ComponentArrayPostQuery {
+      component_ids: Some(vec![xname.to_string()]),
+      nid_only: Some(true),
+      ..Default::default()
+    },
+  )
+  .await?
+  .components
+  .unwrap_or_default()
+  .into_iter()
+  .next()
+  .ok_or_else(|| {
+    Error::Message(format!("Node '{}' not found in HSM", xname))
+  })?;
+
+  let eth_interface_vec = hsm::inventory::ethernet_interfaces::client::get(
+    ctx,
+    None,
+    None,
+    None,
+    Some(xname),
+    None,
+    None,
+    None,
+  )
+  .await?;
+
+  let mac_vec: Vec<String> = eth_interface_vec
+    .iter()
+    .filter_map(|eth_interface| eth_interface.mac_address.clone())
+    .map(String::from)
+    .collect();
+
+  let ip_vec: Vec<String> = eth_interface_vec
+    .iter()
+    .flat_map(|eth_interface| {
+      eth_interface.ip_addresses.clone().unwrap_or_default()
+    })
+    .map(|ip_address| ip_address.ip_address)
+    .collect();
+
+  let mut var_map = HashMap::new();
+  var_map.insert("xname".to_string(), xname.to_string());
+  var_map.insert(
+    "nid".to_string(),
+    component.nid.map(|nid| nid.to_string()).unwrap_or_default(),
+  );
+  var_map.insert(
+    "mac".to_string(),
+    mac_vec.first().cloned().unwrap_or_default(),
+  );
+  var_map.insert("macs".to_string(), mac_vec.join(","));
+  var_map.insert(
+    "ip".to_string(),
+    ip_vec.first().cloned().unwrap_or_default(),
+  );
+  var_map.insert("ips".to_string(), ip_vec.join(","));
+
+  Ok(var_map)
+}
+
+/// Replace `{{ var }}` placeholders in a template. Returns an error listing the
+/// placeholders without a value
+pub fn render_template(
+  template: &str,
+  var_map: &HashMap<String, String>,
+) -> Result<String, Error> {
+  let placeholder_regex =
+    Regex::new(r"\{\{\s*([A-Za-z0-9_-]+)\s*\}\}").unwrap();
+
+  let mut unknown_var_vec: Vec<String> = Vec::new();
+
+  let rendered =
+    placeholder_regex.replace_all(template, |captures: &Captures| {
+      let var_name = &captures[1];
+      match var_map.get(var_name) {
+        Some(value) => value.clone(),
+        None => {
+          if !unknown_var_vec.iter().any(|name| name == var_name) {
+            unknown_var_vec.push(var_name.to_string());
+          }
+          captures[0].to_string()
+        }
+      }
+    });
+
+  if !unknown_var_vec.is_empty() {
+    return Err(Error::Message(format!(
+      "Unknown variables in cloud-init template: {}",
+      unknown_var_vec.join(", ")
+    )));
+  }
+
+  Ok(rendered.into_owned())
+}
+
+/// Check a user-data document is a valid cloud-config: it must start with the
+/// `#cloud-config` header and be a YAML mapping. Returns the list of problems found,
+/// empty if the document is valid
+pub fn validate_cloud_config(user_data: &str) -> Vec<CloudConfigIssue> {
+  let mut issue_vec = Vec::new();
+
+  if user_data.lines().next().map(str::trim_end) != Some("#cloud-config") {
+    issue_vec.push(CloudConfigIssue {
+      line: Some(1),
+      column: None,
+      message: "document must start with '#cloud-config'".to_string(),
+    });
+  }
+
+  match serde_yaml::from_str::<serde_yaml::Value>(user_data) {
+    Ok(serde_yaml::Value::Mapping(_)) => {}
+    Ok(_) => issue_vec.push(CloudConfigIssue {
+      line: None,
+      column: None,
+      message: "document must be a YAML mapping".to_string(),
+    }),
+    Err(e) => issue_vec.push(CloudConfigIssue {
+      line: e.location().map(|location| location.line()),
+      column: e.location().map(|location| location.column()),
+      message: e.to_string(),
+    }),
+  }
+
+  issue_vec
+}
+
+/// Render a user-data template for a node and validate the result
+pub async fn render_node_user_data(
+  ctx: &ApiContext,
+  xname: &str,
+  template: &str,
+) -> Result<String, Error> {
+  let var_map = get_node_template_vars(ctx, xname).await?;
+
+  let user_data = render_template(template, &var_map)?;
+
+  let issue_vec = validate_cloud_config(&user_data);
+
+  if !issue_vec.is_empty() {
+    return Err(Error::Message(format!(
+      "Cloud-init user-data for '{}' is not valid:\n{}",
+      xname,
+      issue_vec
+        .iter()
+        .map(|issue| issue.to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+    )));
+  }
+
+  Ok(user_data)
+}
+
+/// Get the meta-data, user-data and vendor-data the cloud-init server would give a
+/// node, as rendered by the server. Useful to debug nodes not getting the expected
+/// configuration without console access
+pub async fn get_node_cloud_init(
+  ctx: &ApiContext,
+  node_id: &str,
+) -> Result<NodeCloudInit, Error> {
+  let meta_data =
+    http_client::get_node_document(ctx, node_id, CloudInitDocument::MetaData)
+      .await?;
+
+  let user_data =
+    http_client::get_node_document(ctx, node_id, CloudInitDocument::UserData)
+      .await?;
+
+  let vendor_data =
+    http_client::get_node_document(ctx, node_id, CloudInitDocument::VendorData)
+      .await?;
+
+  Ok(NodeCloudInit {
+    node_id: node_id.to_string(),
+    meta_data,
+    user_data,
+    vendor_data,
+  })
+}
+
+/// Export all cloud-init groups, including their meta-data and cloud-config file
+pub async fn backup(ctx: &ApiContext) -> Result<CloudInitBackup, Error> {
+  let mut group_vec = http_client::get_all_groups(ctx).await?;
+
+  group_vec.sort_by(|a, b| a.name.cmp(&b.name));
+
+  Ok(CloudInitBackup { groups: group_vec })
+}
+
+/// Import cloud-init groups from a backup. Groups already in the server are only
+/// updated if `overwrite` is true, otherwise they are skipped
+pub async fn restore(
+  ctx: &ApiContext,
+  cloud_init_backup: &CloudInitBackup,
+  overwrite: bool,
+) -> Result<(), Error> {
+  let existing_group_name_vec: Vec<String> = http_client::get_all_groups(ctx)
+    .await?
+    .into_iter()
+    .map(|group| group.name)
+    .collect();
+
+  for group in &cloud_init_backup.groups {
+    if !existing_group_name_vec.contains(&group.name) {
+      log::info!("Create cloud-init group '{}'", group.name);
+      http_client::post_group(ctx, group).await?;
+    } else if overwrite {
+      log::info!("Overwrite cloud-init group '{}'", group.name);
+      http_client::put_group(ctx, group).await?;
+    } else {
+      log::warn!("Cloud-init group '{}' already exists, skipping", group.name);
+    }
+  }
+
+  Ok(())
+}
+
+/// Get cloud-init groups filtered by exact name, glob pattern (eg: `compute-*`) or list
+/// of HSM group labels, sorted by name. At most `limit_opt` groups are returned
+pub async fn get_and_filter_groups(
+  ctx: &ApiContext,
+  name_opt: Option<&str>,
+  pattern_opt: Option<&str>,
+  hsm_group_label_vec: &[String],
+  limit_opt: Option<usize>,
+) -> Result<Vec<GroupData>, Error> {
+  let pattern_regex_opt = pattern_opt
+    .map(|pattern| GroupPattern::Glob(pattern.to_string()).to_regex())
+    .transpose()?;
+
+  let mut group_vec: Vec<GroupData> = http_client::get_all_groups(ctx)
+    .await?
+    .into_iter()
+    .filter(|group| name_opt.is_none_or(|name| group.name == name))
+    .filter(|group| {
+      pattern_regex_opt
+        .as_ref()
+        .is_none_or(|regex| regex.is_match(&group.name))
+    })
+    .filter(|group| {
+      hsm_group_label_vec.is_empty()
+        || hsm_group_label_vec.contains(&group.name)
+    })
+    .collect();
+
+  group_vec.sort_by(|a, b| a.name.cmp(&b.name));
+
+  if let Some(limit) = limit_opt {
+    group_vec.truncate(limit);
+  }
+
+  Ok(group_vec)
+}
@@ -0,0 +1,195 @@
+use std::{env, fs, time::Duration};
+
+use crate::{
+  authentication::types::Authorizer,
+  error::Error,
+  http::{ApiContext, AuthToken, BaseUrl, ConnectionSettings, HttpClient},
+  service_paths::ServicePaths,
+};
+
+/// Base url of the OpenCHAMI API, eg: https://api.example.com
+pub const ENV_BASE_URL: &str = "OCHAMI_BASE_URL";
+/// Path to the PEM root certificate of the API
+pub const ENV_CACERT: &str = "OCHAMI_CACERT";
+/// Path to a file containing the access token
+pub const ENV_TOKEN_FILE: &str = "OCHAMI_TOKEN_FILE";
+/// Access token, used if `OCHAMI_TOKEN_FILE` is not set
+pub const ENV_ACCESS_TOKEN: &str = "OCHAMI_ACCESS_TOKEN";
+/// Request timeout in seconds
+pub const ENV_TIMEOUT: &str = "OCHAMI_TIMEOUT";
+/// Time to live in seconds of the HSM group and component cache, disabled if not set
+pub const ENV_CACHE_TTL: &str = "OCHAMI_CACHE_TTL";
+/// Service path prefixes, see `ServicePaths`
+pub const ENV_HSM_PATH: &str = "OCHAMI_HSM_PATH";
+pub const ENV_BSS_PATH: &str = "OCHAMI_BSS_PATH";
+pub const ENV_PCS_PATH: &str = "OCHAMI_PCS_PATH";
+pub const ENV_CLOUD_INIT_PATH: &str = "OCHAMI_CLOUD_INIT_PATH";
+/// OPA decision endpoint checked by `authentication::utils::can_i`
+pub const ENV_OPA_URL: &str = "OCHAMI_OPA_URL";
+/// OpenFGA API url and store checked by `authentication::utils::can_i`, used if
+/// `OCHAMI_OPA_URL` is not set
+pub const ENV_OPENFGA_URL: &str = "OCHAMI_OPENFGA_URL";
+pub const ENV_OPENFGA_STORE_ID: &str = "OCHAMI_OPENFGA_STORE_ID";
+pub const ENV_OPENFGA_MODEL_ID: &str = "OCHAMI_OPENFGA_MODEL_ID";
+
+/// Connection settings of an OpenCHAMI deployment
+#[derive(Debug, Clone)]
+pub struct OchamiConfig {
+  pub base_url: BaseUrl,
+  pub root_cert: Vec<u8>,
+  pub auth_token: Option<AuthToken>,
+  pub timeout: Option<Duration>,
+  pub cache_ttl: Option<Duration>,
+  pub service_paths: ServicePaths,
+  pub connection_settings: ConnectionSettings,
+  pub authorizer: Option<Authorizer>,
+}
+
+fn env_var(name: &str) -> Option<String> {
+  env::var(name).ok().filter(|value| !value.trim().is_empty())
+}
+
+fn required_env_var(name: &str) -> Result<String, Error> {
+  env_var(name).ok_or_else(|| {
+    Error::Message(format!("Environment variable '{}' is not set", name))
+  })
+}
+
+fn seconds_env_var(name: &str) -> Result<Option<Duration>, Error> {
+  env_var(name)
+    .map(|seconds| {
+      seconds
+        .parse::<u64>()
+        .map(Duration::from_secs)
+        .map_err(|_| {
+          Error::Message(format!(
+            "Invalid value '{}' for '{}', expected a number of seconds",
+            seconds, name
+          ))
+        })
+    })
+    .transpose()
+}
+
+impl OchamiConfig {
+  /// Read the configuration from the `OCHAMI_*` environment variables. Only
+  /// `OCHAMI_BASE_URL` and `OCHAMI_CACERT` are mandatory
+  pub fn from_env() -> Result<Self, Error> {
+    let base_url = BaseUrl::parse(&required_env_var(ENV_BASE_URL)?)?;
+
+    let cacert_path = required_env_var(ENV_CACERT)?;
+    let root_cert = fs::read(&cacert_path).map_err(|e| {
+      Error::Message(format!(
+        "Could not read root certificate '{}': {}",
+        cacert_path, e
+      ))
+    })?;
+
+    let auth_token = match env_var(ENV_TOKEN_FILE) {
+      Some(token_file) => Some(AuthToken::from(
+        fs::read_to_string(&token_file)
+          .map_err(|e| {
+            Error::Message(format!(
+              "Could not read token file '{}': {}",
+              token_file, e
+            ))
+          })?
+          .trim(),
+      )),
+      None => env_var(ENV_ACCESS_TOKEN).map(AuthToken::from),
+    };
+
+    let timeout = seconds_env_var(ENV_TIMEOUT)?;
+    let cache_ttl = seconds_env_var(ENV_CACHE_TTL)?;
+
+    let default_service_paths = ServicePaths::default();
+    let service_paths = ServicePaths {
+      hsm: env_var(ENV_HSM_PATH).unwrap_or(default_service_paths.hsm),
+      bss: env_var(ENV_BSS_PATH).unwrap_or(default_service_paths.bss),
+      pcs: env_var(ENV_PCS_PATH).unwrap_or(default_service_paths.pcs),
+      cloud_init: env_var(ENV_CLOUD_INIT_PATH)
+        .unwrap_or(default_service_paths.cloud_init),
+    };
+
+    let authorizer = match (env_var(ENV_OPA_URL), env_var(ENV_OPENFGA_URL)) {
+      (Some(url), _) => Some(Authorizer::Opa {
+        url,
+        root_cert: root_cert.clone(),
+      }),
+      (None, Some(url)) => Some(Authorizer::OpenFga {
+        url,
+        store_id: required_env_var(ENV_OPENFGA_STORE_ID)?,
+        authorization_model_id: env_var(ENV_OPENFGA_MODEL_ID),
+        root_cert: root_cert.clone(),
+      }),
+      (None, None) => None,
+    };
+
+    Ok(OchamiConfig {
+      base_url,
+      root_cert,
+      auth_token,
+      timeout,
+      cache_ttl,
+      service_paths,
+      connection_settings: ConnectionSettings::default(),
+      authorizer,
+    })
+  }
+
+  /// Tune the connections for burst workloads, see `ConnectionSettings`
+  pub fn with_connection_settings(
+    mut self,
+    connection_settings: ConnectionSettings,
+  ) -> Self {
+    self.connection_settings = connection_settings;
+    self
+  }
+
+  /// HTTP client configuration: root certificate, timeout and connection settings
+  pub fn http_client(&self) -> HttpClient {
+    HttpClient::new(&self.root_cert)
+      .with_timeout(self.timeout)
+      .with_connection_settings(self.connection_settings.clone())
+  }
+
+  /// Access token, fails if none was configured
+  pub fn auth_token(&self) -> Result<&AuthToken, Error> {
+    self.auth_token.as_ref().ok_or_else(|| {
+      Error::Message(format!(
+        "No access token configured, set '{}' or '{}'",
+        ENV_TOKEN_FILE, ENV_ACCESS_TOKEN
+      ))
+    })
+  }
+
+  /// Request context for the free functions of this crate
+  pub fn context(&self) -> Result<ApiContext, Error> {
+    Ok(
+      ApiContext::new(
+        self.base_url.clone(),
+        self.auth_token()?.clone(),
+        &self.root_cert,
+      )
+      .with_service_paths(self.service_paths.clone())
+      .with_http_client(self.http_client())
+      .with_authorizer(self.authorizer.clone())
+      .with_cache_ttl(self.cache_ttl),
+    )
+  }
+
+  /// Backend client built from this configuration
+  #[cfg(feature = "backend-connector")]
+  pub fn ochami(&self) -> Result<crate::backend_connector::Ochami, Error> {
+    crate::backend_connector::Ochami::try_new(
+      self.base_url.as_str(),
+      &self.root_cert,
+    )
+    .map(|ochami| {
+      ochami
+        .with_service_paths(self.service_paths.clone())
+        .with_http_client(self.http_client())
+        .with_cache_ttl(self.cache_ttl)
+    })
+  }
+}
@@ -0,0 +1,39 @@
+use crate::discovery::utils::expand_addresses;
+
+#[test]
+fn test_expand_addresses() {
+  let address_vec = expand_addresses(&[
+    "10.1.0.0/30".to_string(),
+    "10.1.1.10-10.1.1.12".to_string(),
+    "bmc1".to_string(),
+  ])
+  .unwrap();
+
+  assert_eq!(
+    address_vec,
+    vec![
+      "10.1.0.1",
+      "10.1.0.2",
+      "10.1.1.10",
+      "10.1.1.11",
+      "10.1.1.12",
+      "bmc1"
+    ]
+  );
+}
+
+#[test]
+fn test_expand_addresses_too_many() {
+  assert_eq!(
+    expand_addresses(&["10.0.0.0/16".to_string()])
+      .unwrap()
+      .len(),
+    65534
+  );
+  assert!(expand_addresses(&["10.0.0.0/8".to_string()]).is_err());
+  assert!(expand_addresses(&[
+    "10.0.0.0/16".to_string(),
+    "10.1.0.0/16".to_string()
+  ])
+  .is_err());
+}
@@ -0,0 +1,74 @@
+use std::{collections::HashMap, fmt, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::pcs::transitions::types::PollOptions;
+
+/// Credentials used to query the BMCs and stored in the redfish endpoints
+#[derive(Clone)]
+pub struct BmcCredentials {
+  pub username: String,
+  pub password: String,
+}
+
+impl fmt::Debug for BmcCredentials {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("BmcCredentials")
+      .field("username", &self.username)
+      .field("password", &"***")
+      .finish()
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct DiscoverySettings {
+  /// Max number of BMCs probed at the same time
+  pub concurrency: usize,
+  /// Timeout for each request to a BMC
+  pub timeout: Duration,
+  /// Do not verify the BMC certificates, eg: BMCs with self signed certificates. Off by
+  /// default, certificates are verified against the root certificate of the
+  /// `ApiContext`
+  pub accept_invalid_certs: bool,
+  /// Trigger HSM inventory discovery once the redfish endpoints are created
+  pub trigger_discover: bool,
+  /// Polling settings used while waiting for HSM inventory discovery to complete
+  pub poll_options: PollOptions,
+}
+
+impl Default for DiscoverySettings {
+  fn default() -> Self {
+    DiscoverySettings {
+      concurrency: 16,
+      timeout: Duration::from_secs(10),
+      accept_invalid_certs: false,
+      trigger_discover: true,
+      poll_options: PollOptions::default(),
+    }
+  }
+}
+
+/// Result of querying the redfish service root of a BMC
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BmcProbe {
+  pub address: String,
+  /// Xname assigned to the BMC, BMCs without xname are not registered in HSM
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub xname: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub uuid: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub redfish_version: Option<String>,
+  /// Error if the BMC could not be queried
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DiscoveryReport {
+  pub probes: Vec<BmcProbe>,
+  /// Xnames of the redfish endpoints created in HSM
+  pub registered: Vec<String>,
+  /// HSM discovery status (`LastDiscoveryStatus`) of each redfish endpoint registered
+  pub discovery_status: HashMap<String, String>,
+}
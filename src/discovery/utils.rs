@@ -0,0 +1,265 @@
+use std::{collections::HashMap, net::Ipv4Addr, time::Instant};
+
+use serde_json::Value;
+use tokio::task::{JoinError, JoinSet};
+
+use crate::{
+  error::Error,
+  hsm::inventory::redfish_endpoint::{
+    self,
+    types::{RedfishEndpoint, RedfishEndpointArray},
+  },
+  http::ApiContext,
+};
+
+use super::types::{
+  BmcCredentials, BmcProbe, DiscoveryReport, DiscoverySettings,
+};
+
+pub use crate::utils::expand_addresses;
+
+/// HSM discovery status while a redfish endpoint is still being discovered
+const DISCOVERY_PENDING_STATUS: [&str; 2] =
+  ["NotYetQueried", "DiscoveryStarted"];
+
+/// Query the redfish service root of a BMC. The BMC certificate is verified against the
+/// root certificate of `ctx`, unless `settings.accept_invalid_certs` is set
+pub async fn probe_bmc(
+  ctx: &ApiContext,
+  address: &str,
+  credentials: &BmcCredentials,
+  settings: &DiscoverySettings,
+) -> BmcProbe {
+  let mut probe = BmcProbe {
+    address: address.to_string(),
+    ..Default::default()
+  };
+
+  let service_root_rslt = async {
+    let mut builder = crate::http::with_tls_backend(reqwest::Client::builder())
+      .timeout(settings.timeout);
+
+    builder = if settings.accept_invalid_certs {
+      builder.danger_accept_invalid_certs(true)
+    } else {
+      builder.add_root_certificate(ctx.http_client.certificate()?)
+    };
+
+    let service_root = builder
+      .build()?
+      .get(format!("https://{}/redfish/v1/", address))
+      .basic_auth(&credentials.username, Some(&credentials.password))
+      .send()
+      .await?
+      .error_for_status()?
+      .json::<Value>()
+      .await?;
+
+    Ok::<Value, Error>(service_root)
+  }
+  .await;
+
+  match service_root_rslt {
+    Ok(service_root) => {
+      probe.uuid = service_root
+        .get("UUID")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+      probe.redfish_version = service_root
+        .get("RedfishVersion")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    }
+    Err(e) => probe.error = Some(e.to_string()),
+  }
+
+  probe
+}
+
+/// Probe a list of BMCs concurrently, at most `concurrency` at the same time. Probes are
+/// returned in the same order as the addresses
+pub async fn probe_bmcs(
+  ctx: &ApiContext,
+  address_vec: &[String],
+  credentials: &BmcCredentials,
+  settings: &DiscoverySettings,
+) -> Vec<BmcProbe> {
+  let mut probe_vec: Vec<Option<BmcProbe>> = vec![None; address_vec.len()];
+  let mut join_set = JoinSet::new();
+
+  for (i, address) in address_vec.iter().enumerate() {
+    if join_set.len() >= settings.concurrency.max(1) {
+      if let Some(join_rslt) = join_set.join_next().await {
+        store_probe(&mut probe_vec, join_rslt);
+      }
+    }
+
+    let ctx = ctx.clone();
+    let address = address.clone();
+    let credentials = credentials.clone();
+    let settings = settings.clone();
+
+    join_set.spawn(async move {
+      (i, probe_bmc(&ctx, &address, &credentials, &settings).await)
+    });
+  }
+
+  while let Some(join_rslt) = join_set.join_next().await {
+    store_probe(&mut probe_vec, join_rslt);
+  }
+
+  probe_vec
+    .into_iter()
+    .zip(address_vec)
+    .map(|(probe_opt, address)| {
+      probe_opt.unwrap_or_else(|| BmcProbe {
+        address: address.clone(),
+        error: Some("probe task failed".to_string()),
+        ..Default::default()
+      })
+    })
+    .collect()
+}
+
+/// Keep the probe returned by a task. A task which panicked or was cancelled leaves its
+/// probe empty, it is reported as failed
+fn store_probe(
+  probe_vec: &mut [Option<BmcProbe>],
+  join_rslt: Result<(usize, BmcProbe), JoinError>,
+) {
+  match join_rslt {
+    Ok((i, probe)) => probe_vec[i] = Some(probe),
+    Err(e) => log::error!("BMC probe task failed: {}", e),
+  }
+}
+
+/// Discover BMCs: probe their redfish service root, register the ones reachable as
+/// redfish endpoints in HSM and, if `settings.trigger_discover` is set, trigger HSM
+/// inventory discovery and wait for it to complete.
+/// `xname_resolver` returns the xname of a BMC given its address, BMCs without xname
+/// are probed but not registered
+pub async fn discover(
+  ctx: &ApiContext,
+  address_vec: &[String],
+  credentials: &BmcCredentials,
+  xname_resolver: impl Fn(&str) -> Option<String>,
+  settings: &DiscoverySettings,
+) -> Result<DiscoveryReport, Error> {
+  let address_vec = expand_addresses(address_vec)?;
+
+  log::info!("Probe {} BMCs", address_vec.len());
+
+  let mut probe_vec =
+    probe_bmcs(ctx, &address_vec, credentials, settings).await;
+
+  for probe in probe_vec.iter_mut() {
+    probe.xname = xname_resolver(&probe.address);
+  }
+
+  let redfish_endpoint_vec: Vec<RedfishEndpoint> = probe_vec
+    .iter()
+    .filter(|probe| probe.error.is_none())
+    .filter_map(|probe| {
+      probe.xname.as_ref().map(|xname| RedfishEndpoint {
+        id: xname.clone(),
+        r#type: None,
+        name: None,
+        hostname: Some(probe.address.clone()),
+        domain: None,
+        fqdn: Some(probe.address.clone()),
+        enabled: Some(true),
+        uuid: probe.uuid.clone(),
+        user: Some(credentials.username.clone()),
+        password: Some(credentials.password.clone()),
+        use_ssdp: None,
+        mac_required: None,
+        mac_addr: None,
+        ip_address: probe
+          .address
+          .parse::<Ipv4Addr>()
+          .ok()
+          .map(|ip| ip.to_string()),
+        rediscover_on_update: Some(true),
+        template_id: None,
+        discovery_info: None,
+      })
+    })
+    .collect();
+
+  let mut report = DiscoveryReport {
+    registered: redfish_endpoint_vec
+      .iter()
+      .map(|redfish_endpoint| redfish_endpoint.id.clone())
+      .collect(),
+    probes: probe_vec,
+    ..Default::default()
+  };
+
+  if redfish_endpoint_vec.is_empty() {
+    return Ok(report);
+  }
+
+  log::info!("Register {} redfish endpoints", redfish_endpoint_vec.len());
+
+  redfish_endpoint::client::post(
+    ctx,
+    RedfishEndpointArray {
+      redfish_endpoints: Some(redfish_endpoint_vec),
+    },
+  )
+  .await?;
+
+  if !settings.trigger_discover {
+    return Ok(report);
+  }
+
+  redfish_endpoint::client::post_discover(ctx, &report.registered, false)
+    .await?;
+
+  report.discovery_status =
+    wait_discovery(ctx, &report.registered, settings).await?;
+
+  Ok(report)
+}
+
+/// Poll HSM until none of the redfish endpoints is being discovered or
+/// `poll_options.max_wait` expires. Returns the last discovery status of each endpoint
+async fn wait_discovery(
+  ctx: &ApiContext,
+  xname_vec: &[String],
+  settings: &DiscoverySettings,
+) -> Result<HashMap<String, String>, Error> {
+  let start = Instant::now();
+
+  loop {
+    let mut discovery_status: HashMap<String, String> = xname_vec
+      .iter()
+      .map(|xname| (xname.clone(), "NotYetQueried".to_string()))
+      .collect();
+
+    let redfish_endpoint_vec =
+      redfish_endpoint::client::get_many(ctx, xname_vec)
+        .await?
+        .redfish_endpoints
+        .unwrap_or_default();
+
+    for redfish_endpoint in redfish_endpoint_vec {
+      if let Some(status) = redfish_endpoint
+        .discovery_info
+        .and_then(|discovery_info| discovery_info.last_status)
+      {
+        discovery_status.insert(redfish_endpoint.id, status);
+      }
+    }
+
+    let pending = discovery_status
+      .values()
+      .any(|status| DISCOVERY_PENDING_STATUS.contains(&status.as_str()));
+
+    if !pending || start.elapsed() >= settings.poll_options.max_wait {
+      return Ok(discovery_status);
+    }
+
+    tokio::time::sleep(settings.poll_options.interval).await;
+  }
+}
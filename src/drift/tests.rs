@@ -0,0 +1,80 @@
+use crate::{
+  bss::types::BootParameters,
+  hsm::group::types::{Group, Members},
+  mutation::types::Mutation,
+};
+
+use super::{
+  types::{DesiredState, Drift},
+  utils::compare,
+};
+
+#[test]
+fn test_compare() {
+  let mut desired_state = DesiredState::default();
+  desired_state.groups.insert(
+    "compute".to_string(),
+    vec!["x1000c0s0b0n0".to_string(), "x1000c0s0b0n1".to_string()],
+  );
+  desired_state.groups.insert("gpu".to_string(), Vec::new());
+  desired_state.boot_parameters.push(BootParameters {
+    hosts: vec!["x1000c0s0b0n0".to_string(), "x1000c0s0b0n1".to_string()],
+    kernel: "s3://boot-images/compute/kernel".to_string(),
+    ..Default::default()
+  });
+
+  let group_vec = vec![Group {
+    label: "compute".to_string(),
+    description: None,
+    tags: None,
+    members: Some(Members {
+      ids: Some(vec![
+        "x1000c0s0b0n1".to_string(),
+        "x1000c0s0b0n2".to_string(),
+      ]),
+    }),
+    exclusive_group: None,
+  }];
+
+  let boot_parameters_vec = vec![BootParameters {
+    hosts: vec!["x1000c0s0b0n1".to_string()],
+    kernel: "s3://boot-images/old/kernel".to_string(),
+    ..Default::default()
+  }];
+
+  let drift_vec =
+    compare(&desired_state, &group_vec, &boot_parameters_vec, &[]);
+
+  assert_eq!(
+    drift_vec,
+    vec![
+      Drift::MissingGroupMember {
+        group_label: "compute".to_string(),
+        xname: "x1000c0s0b0n0".to_string(),
+      },
+      Drift::UnexpectedGroupMember {
+        group_label: "compute".to_string(),
+        xname: "x1000c0s0b0n2".to_string(),
+      },
+      Drift::MissingGroup {
+        group_label: "gpu".to_string(),
+      },
+      Drift::MissingBootParameters {
+        host: "x1000c0s0b0n0".to_string(),
+      },
+      Drift::BootParametersMismatch {
+        host: "x1000c0s0b0n1".to_string(),
+        fields: vec!["kernel".to_string()],
+      },
+    ]
+  );
+
+  let Some(Mutation::SetBootParameters(boot_parameters)) =
+    drift_vec[4].remediation(&desired_state)
+  else {
+    panic!("Boot parameters drift should be fixed by setting boot parameters");
+  };
+
+  assert_eq!(boot_parameters.hosts, vec!["x1000c0s0b0n1".to_string()]);
+  assert_eq!(boot_parameters.kernel, "s3://boot-images/compute/kernel");
+}
@@ -0,0 +1,143 @@
+use std::{collections::BTreeMap, fmt};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  bss::types::BootParameters, cloud_init::types::GroupData,
+  mutation::types::Mutation,
+};
+
+/// Declared state of the system. Only what is declared is checked, eg: HSM groups not
+/// listed in `groups` are ignored
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct DesiredState {
+  /// HSM group label -> members
+  pub groups: BTreeMap<String, Vec<String>>,
+  pub boot_parameters: Vec<BootParameters>,
+  pub cloud_init_groups: Vec<GroupData>,
+}
+
+/// Difference between the declared and the live state
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum Drift {
+  MissingGroup {
+    group_label: String,
+  },
+  MissingGroupMember {
+    group_label: String,
+    xname: String,
+  },
+  UnexpectedGroupMember {
+    group_label: String,
+    xname: String,
+  },
+  MissingBootParameters {
+    host: String,
+  },
+  /// `fields` are the boot parameters fields which differ (`kernel`, `initrd` or
+  /// `params`)
+  BootParametersMismatch {
+    host: String,
+    fields: Vec<String>,
+  },
+  MissingCloudInitGroup {
+    name: String,
+  },
+  CloudInitGroupMismatch {
+    name: String,
+  },
+}
+
+impl Drift {
+  /// Mutation bringing the live state back to the declared one, `None` if the drift has
+  /// to be fixed by other means (eg: creating a HSM or cloud-init group)
+  pub fn remediation(&self, desired_state: &DesiredState) -> Option<Mutation> {
+    match self {
+      Drift::MissingGroupMember { group_label, xname } => {
+        Some(Mutation::AddGroupMember {
+          group_label: group_label.clone(),
+          xname: xname.clone(),
+        })
+      }
+      Drift::UnexpectedGroupMember { group_label, xname } => {
+        Some(Mutation::RemoveGroupMember {
+          group_label: group_label.clone(),
+          xname: xname.clone(),
+        })
+      }
+      Drift::MissingBootParameters { host }
+      | Drift::BootParametersMismatch { host, .. } => desired_state
+        .boot_parameters
+        .iter()
+        .find(|boot_parameters| boot_parameters.hosts.contains(host))
+        .map(|boot_parameters| {
+          Mutation::SetBootParameters(BootParameters {
+            hosts: vec![host.clone()],
+            ..boot_parameters.clone()
+          })
+        }),
+      Drift::MissingGroup { .. }
+      | Drift::MissingCloudInitGroup { .. }
+      | Drift::CloudInitGroupMismatch { .. } => None,
+    }
+  }
+}
+
+impl fmt::Display for Drift {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Drift::MissingGroup { group_label } => {
+        write!(f, "HSM group '{}' does not exist", group_label)
+      }
+      Drift::MissingGroupMember { group_label, xname } => {
+        write!(f, "'{}' missing from HSM group '{}'", xname, group_label)
+      }
+      Drift::UnexpectedGroupMember { group_label, xname } => {
+        write!(f, "'{}' unexpected in HSM group '{}'", xname, group_label)
+      }
+      Drift::MissingBootParameters { host } => {
+        write!(f, "'{}' has no boot parameters", host)
+      }
+      Drift::BootParametersMismatch { host, fields } => {
+        write!(
+          f,
+          "'{}' boot parameters differ: {}",
+          host,
+          fields.join(", ")
+        )
+      }
+      Drift::MissingCloudInitGroup { name } => {
+        write!(f, "cloud-init group '{}' does not exist", name)
+      }
+      Drift::CloudInitGroupMismatch { name } => {
+        write!(f, "cloud-init group '{}' differs", name)
+      }
+    }
+  }
+}
+
+/// Result of comparing the declared and the live state
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DriftReport {
+  pub checked_at: DateTime<Utc>,
+  pub drifts: Vec<Drift>,
+}
+
+impl DriftReport {
+  /// Returns true if the live state matches the declared one
+  pub fn is_empty(&self) -> bool {
+    self.drifts.is_empty()
+  }
+
+  /// Mutations fixing the drifts which can be fixed automatically, see
+  /// `Drift::remediation`
+  pub fn remediation(&self, desired_state: &DesiredState) -> Vec<Mutation> {
+    self
+      .drifts
+      .iter()
+      .filter_map(|drift| drift.remediation(desired_state))
+      .collect()
+  }
+}
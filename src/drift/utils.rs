@@ -0,0 +1,180 @@
+use std::{
+  collections::{BTreeSet, HashMap},
+  time::Duration,
+};
+
+use chrono::Utc;
+
+use crate::{
+  bss::{self, types::BootParameters},
+  cloud_init::{self, types::GroupData},
+  error::Error,
+  hsm::{self, group::types::Group},
+  http::ApiContext,
+};
+
+use super::types::{DesiredState, Drift, DriftReport};
+
+/// Compare the declared state with the live one
+pub fn compare(
+  desired_state: &DesiredState,
+  group_vec: &[Group],
+  boot_parameters_vec: &[BootParameters],
+  cloud_init_group_vec: &[GroupData],
+) -> Vec<Drift> {
+  let mut drift_vec = Vec::new();
+
+  for (group_label, xname_vec) in &desired_state.groups {
+    let Some(group) =
+      group_vec.iter().find(|group| &group.label == group_label)
+    else {
+      drift_vec.push(Drift::MissingGroup {
+        group_label: group_label.clone(),
+      });
+      continue;
+    };
+
+    let desired_set: BTreeSet<String> = xname_vec.iter().cloned().collect();
+    let live_set: BTreeSet<String> = group.get_members().into_iter().collect();
+
+    drift_vec.extend(desired_set.difference(&live_set).map(|xname| {
+      Drift::MissingGroupMember {
+        group_label: group_label.clone(),
+        xname: xname.clone(),
+      }
+    }));
+
+    drift_vec.extend(live_set.difference(&desired_set).map(|xname| {
+      Drift::UnexpectedGroupMember {
+        group_label: group_label.clone(),
+        xname: xname.clone(),
+      }
+    }));
+  }
+
+  let live_boot_parameters_map: HashMap<&String, &BootParameters> =
+    boot_parameters_vec
+      .iter()
+      .flat_map(|boot_parameters| {
+        boot_parameters
+          .hosts
+          .iter()
+          .map(move |host| (host, boot_parameters))
+      })
+      .collect();
+
+  for desired in &desired_state.boot_parameters {
+    for host in &desired.hosts {
+      let Some(live) = live_boot_parameters_map.get(host) else {
+        drift_vec.push(Drift::MissingBootParameters { host: host.clone() });
+        continue;
+      };
+
+      let field_vec: Vec<String> = [
+        ("kernel", desired.kernel == live.kernel),
+        ("initrd", desired.initrd == live.initrd),
+        ("params", desired.params == live.params),
+      ]
+      .into_iter()
+      .filter(|(_, same)| !same)
+      .map(|(field, _)| field.to_string())
+      .collect();
+
+      if !field_vec.is_empty() {
+        drift_vec.push(Drift::BootParametersMismatch {
+          host: host.clone(),
+          fields: field_vec,
+        });
+      }
+    }
+  }
+
+  for desired in &desired_state.cloud_init_groups {
+    match cloud_init_group_vec
+      .iter()
+      .find(|group| group.name == desired.name)
+    {
+      None => drift_vec.push(Drift::MissingCloudInitGroup {
+        name: desired.name.clone(),
+      }),
+      // GroupData has no PartialEq, meta-data values are compared as json
+      Some(live)
+        if serde_json::to_value(live).ok()
+          != serde_json::to_value(desired).ok() =>
+      {
+        drift_vec.push(Drift::CloudInitGroupMismatch {
+          name: desired.name.clone(),
+        })
+      }
+      Some(_) => {}
+    }
+  }
+
+  drift_vec
+}
+
+/// Fetch the live state declared in `desired_state` and compare them. Services with
+/// nothing declared are not queried
+pub async fn detect(
+  ctx: &ApiContext,
+  desired_state: &DesiredState,
+) -> Result<DriftReport, Error> {
+  let group_vec = if desired_state.groups.is_empty() {
+    Vec::new()
+  } else {
+    hsm::group::client::get_all(ctx).await?
+  };
+
+  let host_vec: Vec<String> = desired_state
+    .boot_parameters
+    .iter()
+    .flat_map(|boot_parameters| boot_parameters.hosts.iter().cloned())
+    .collect();
+
+  let boot_parameters_vec = if host_vec.is_empty() {
+    Vec::new()
+  } else {
+    bss::client::get(ctx, &Some(host_vec)).await?
+  };
+
+  let cloud_init_group_vec = if desired_state.cloud_init_groups.is_empty() {
+    Vec::new()
+  } else {
+    cloud_init::http_client::get_all_groups(ctx).await?
+  };
+
+  Ok(DriftReport {
+    checked_at: Utc::now(),
+    drifts: compare(
+      desired_state,
+      &group_vec,
+      &boot_parameters_vec,
+      &cloud_init_group_vec,
+    ),
+  })
+}
+
+/// Check for drift every `interval` until the future is dropped, `callback` is called
+/// with each report. Checks failing are logged and retried at the next interval
+pub async fn watch<F>(
+  ctx: &ApiContext,
+  desired_state: &DesiredState,
+  interval: Duration,
+  mut callback: F,
+) where
+  F: FnMut(&DriftReport),
+{
+  loop {
+    match detect(ctx, desired_state).await {
+      Ok(drift_report) => {
+        for drift in &drift_report.drifts {
+          log::warn!("Drift detected: {}", drift);
+        }
+        callback(&drift_report);
+      }
+      Err(e) => log::warn!("Could not check for drift. Reason:\n{}", e),
+    }
+
+    tokio::time::sleep(interval).await;
+  }
+}
@@ -0,0 +1,144 @@
+use std::io;
+
+use serde_json::Value;
+
+use crate::http::PlannedRequest;
+#[cfg(feature = "pcs")]
+use crate::pcs::types::PcsErrorKind;
+
+#[cfg(all(test, feature = "backend-connector"))]
+pub mod tests;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+  #[error("OCHAMI-RS: {0}")]
+  Message(String),
+  #[error("OCHAMI-RS > IO: {0}")]
+  IoError(#[from] io::Error),
+  #[error("OCHAMI-RS > Serde: {0}")]
+  SerdeError(#[from] serde_json::Error),
+  #[error("OCHAMI-RS > Net: {0}")]
+  NetError(#[from] reqwest::Error),
+  #[error(
+    "OCHAMI-RS: http request:\nresponse: {response}\npayload: {payload}"
+  )]
+  RequestError {
+    response: reqwest::Error,
+    payload: String, // NOTE: CSM/OCHAMI Apis either returns plain text or a json therefore, we
+                     // will just return a String
+  },
+  #[error("OCHAMI-RS: missing field '{0}'")]
+  MissingField(String),
+  #[error("OCHAMI-RS: invalid base url: {0}")]
+  InvalidBaseUrl(String),
+  #[error("OCHAMI-RS: forbidden: {0}")]
+  Forbidden(String),
+  /// Request not sent because the context is in dry-run, holds the request
+  #[error("OCHAMI-RS: dry-run, not sent: {0}")]
+  DryRun(PlannedRequest),
+  #[error("OCHAMI-RS > OCHAMI: {0}")]
+  OchamiError(Value),
+  #[cfg(feature = "pcs")]
+  #[error("OCHAMI-RS > PCS: {kind}: {detail}")]
+  PcsError { kind: PcsErrorKind, detail: String },
+}
+
+#[cfg(feature = "hsm")]
+impl Error {
+  /// Copy of this error for when a single failure is reported more than once, e.g. for
+  /// every HSM group of a batched request. The HTTP status and error kind are kept, errors
+  /// which can't be copied are turned into a message
+  pub(crate) fn duplicate(&self) -> Self {
+    match self {
+      Error::Message(message) => Error::Message(message.clone()),
+      Error::IoError(e) => {
+        Error::IoError(io::Error::new(e.kind(), e.to_string()))
+      }
+      Error::RequestError { response, payload } => match response.status() {
+        Some(status) => Error::OchamiError(serde_json::json!({
+          "title": status.canonical_reason(),
+          "detail": payload,
+          "status": status.as_u16()
+        })),
+        None => Error::Message(self.to_string()),
+      },
+      Error::SerdeError(_) | Error::NetError(_) => {
+        Error::Message(self.to_string())
+      }
+      Error::MissingField(field) => Error::MissingField(field.clone()),
+      Error::InvalidBaseUrl(url) => Error::InvalidBaseUrl(url.clone()),
+      Error::Forbidden(detail) => Error::Forbidden(detail.clone()),
+      Error::DryRun(request) => Error::DryRun(request.clone()),
+      Error::OchamiError(body) => Error::OchamiError(body.clone()),
+      #[cfg(feature = "pcs")]
+      Error::PcsError { kind, detail } => Error::PcsError {
+        kind: *kind,
+        detail: detail.clone(),
+      },
+    }
+  }
+}
+
+/// Keeps the HTTP status, payload and error kind when handing errors over to the backend
+/// dispatcher, instead of flattening them into a message
+#[cfg(feature = "backend-connector")]
+impl From<Error> for manta_backend_dispatcher::error::Error {
+  fn from(error: Error) -> Self {
+    use manta_backend_dispatcher::error::Error as FrontEndError;
+
+    match error {
+      Error::Message(message) => FrontEndError::Message(message),
+      Error::IoError(e) => FrontEndError::IoError(e),
+      Error::SerdeError(e) => FrontEndError::SerdeError(e),
+      Error::NetError(e) => FrontEndError::NetError(e),
+      Error::RequestError { response, payload } => match response.status() {
+        Some(reqwest::StatusCode::NOT_FOUND) => {
+          FrontEndError::NotFound(payload)
+        }
+        Some(reqwest::StatusCode::BAD_REQUEST) => {
+          FrontEndError::BadRequest(payload)
+        }
+        Some(reqwest::StatusCode::CONFLICT) => FrontEndError::Conflict(payload),
+        _ => FrontEndError::RequestError { response, payload },
+      },
+      Error::MissingField(field) => FrontEndError::MissingField(field),
+      Error::InvalidBaseUrl(_) => FrontEndError::Message(error.to_string()),
+      Error::Forbidden(detail) => FrontEndError::CsmError {
+        status: 403,
+        detail,
+        body: None,
+      },
+      Error::DryRun(_) => FrontEndError::Message(error.to_string()),
+      Error::OchamiError(body) => {
+        let status = body
+          .get("status")
+          .and_then(Value::as_u64)
+          .and_then(|status| u16::try_from(status).ok())
+          .unwrap_or_default();
+        let detail = body
+          .get("detail")
+          .or_else(|| body.get("title"))
+          .and_then(Value::as_str)
+          .map(str::to_string)
+          .unwrap_or_else(|| body.to_string());
+
+        FrontEndError::CsmError {
+          status,
+          detail,
+          body: Some(body),
+        }
+      }
+      #[cfg(feature = "pcs")]
+      Error::PcsError { kind, detail } => match kind {
+        PcsErrorKind::NotFound => FrontEndError::NotFound(detail),
+        PcsErrorKind::BadRequest => FrontEndError::BadRequest(detail),
+        PcsErrorKind::Conflict => FrontEndError::Conflict(detail),
+        _ => FrontEndError::CsmError {
+          status: kind.status(),
+          detail: format!("{}: {}", kind, detail),
+          body: None,
+        },
+      },
+    }
+  }
+}
@@ -0,0 +1,245 @@
+use std::io;
+
+use manta_backend_dispatcher::error::Error as FrontEndError;
+use serde_json::{json, Value};
+use wiremock::{
+  matchers::{method, path},
+  Mock, MockServer, ResponseTemplate,
+};
+
+use crate::{http::PlannedRequest, pcs::types::PcsErrorKind};
+
+use super::Error;
+
+/// `Error::RequestError` as returned for a response with the given HTTP status
+async fn request_error(status: u16, payload: &str) -> Error {
+  let server = MockServer::start().await;
+  Mock::given(method("GET"))
+    .and(path("/status"))
+    .respond_with(ResponseTemplate::new(status))
+    .mount(&server)
+    .await;
+
+  let response = reqwest::get(format!("{}/status", server.uri()))
+    .await
+    .unwrap()
+    .error_for_status()
+    .unwrap_err();
+
+  Error::RequestError {
+    response,
+    payload: payload.to_string(),
+  }
+}
+
+#[test]
+fn test_message_into_frontend_error() {
+  let error = FrontEndError::from(Error::Message("boom".to_string()));
+
+  assert!(
+    matches!(error, FrontEndError::Message(message) if message == "boom")
+  );
+}
+
+#[test]
+fn test_io_error_into_frontend_error() {
+  let error =
+    FrontEndError::from(Error::IoError(io::Error::other("disk full")));
+
+  assert!(
+    matches!(error, FrontEndError::IoError(e) if e.to_string() == "disk full")
+  );
+}
+
+#[test]
+fn test_serde_error_into_frontend_error() {
+  let serde_error = serde_json::from_str::<Value>("{").unwrap_err();
+
+  let error = FrontEndError::from(Error::SerdeError(serde_error));
+
+  assert!(matches!(error, FrontEndError::SerdeError(_)));
+}
+
+#[tokio::test]
+async fn test_net_error_into_frontend_error() {
+  let net_error = reqwest::get("not a url").await.unwrap_err();
+
+  let error = FrontEndError::from(Error::NetError(net_error));
+
+  assert!(matches!(error, FrontEndError::NetError(_)));
+}
+
+#[tokio::test]
+async fn test_request_error_into_frontend_error() {
+  let error = FrontEndError::from(request_error(404, "no such node").await);
+  assert!(
+    matches!(error, FrontEndError::NotFound(payload) if payload == "no such node")
+  );
+
+  let error = FrontEndError::from(request_error(400, "bad xname").await);
+  assert!(
+    matches!(error, FrontEndError::BadRequest(payload) if payload == "bad xname")
+  );
+
+  let error = FrontEndError::from(request_error(409, "already exists").await);
+  assert!(
+    matches!(error, FrontEndError::Conflict(payload) if payload == "already exists")
+  );
+
+  // Other statuses keep the response
+  let error = FrontEndError::from(request_error(401, "expired token").await);
+  match error {
+    FrontEndError::RequestError { response, payload } => {
+      assert_eq!(response.status(), Some(reqwest::StatusCode::UNAUTHORIZED));
+      assert_eq!(payload, "expired token");
+    }
+    other => panic!("Expected RequestError, got {:?}", other),
+  }
+}
+
+#[test]
+fn test_missing_field_into_frontend_error() {
+  let error = FrontEndError::from(Error::MissingField("ID".to_string()));
+
+  assert!(matches!(error, FrontEndError::MissingField(field) if field == "ID"));
+}
+
+#[test]
+fn test_message_only_variants_into_frontend_error() {
+  let error_vec = vec![
+    Error::InvalidBaseUrl("ftp://hsm".to_string()),
+    Error::DryRun(PlannedRequest {
+      method: "DELETE".to_string(),
+      url: "https://ochami/hsm/v2/groups/compute".to_string(),
+      body: None,
+    }),
+  ];
+
+  for error in error_vec {
+    let expected = error.to_string();
+
+    match FrontEndError::from(error) {
+      FrontEndError::Message(message) => assert_eq!(message, expected),
+      other => panic!("Expected Message, got {:?}", other),
+    }
+  }
+}
+
+#[test]
+fn test_forbidden_into_frontend_error() {
+  match FrontEndError::from(Error::Forbidden(
+    "token has no admin role".to_string(),
+  )) {
+    FrontEndError::CsmError {
+      status,
+      detail,
+      body,
+    } => {
+      assert_eq!(status, 403);
+      assert_eq!(detail, "token has no admin role");
+      assert!(body.is_none());
+    }
+    other => panic!("Expected CsmError, got {:?}", other),
+  }
+}
+
+#[test]
+fn test_pcs_error_into_frontend_error() {
+  let pcs_error = |kind| Error::PcsError {
+    kind,
+    detail: "x1000c0s0b0n0".to_string(),
+  };
+
+  assert!(matches!(
+    FrontEndError::from(pcs_error(PcsErrorKind::NotFound)),
+    FrontEndError::NotFound(detail) if detail == "x1000c0s0b0n0"
+  ));
+  assert!(matches!(
+    FrontEndError::from(pcs_error(PcsErrorKind::BadRequest)),
+    FrontEndError::BadRequest(detail) if detail == "x1000c0s0b0n0"
+  ));
+  assert!(matches!(
+    FrontEndError::from(pcs_error(PcsErrorKind::Conflict)),
+    FrontEndError::Conflict(detail) if detail == "x1000c0s0b0n0"
+  ));
+
+  // Other kinds keep a status
+  for (kind, expected_status) in [
+    (PcsErrorKind::NodeUnreachable, 504),
+    (PcsErrorKind::BmcCredentials, 502),
+    (PcsErrorKind::UnsupportedOperation, 422),
+    (PcsErrorKind::ServiceUnavailable, 503),
+    (PcsErrorKind::Other, 500),
+  ] {
+    match FrontEndError::from(pcs_error(kind)) {
+      FrontEndError::CsmError { status, detail, .. } => {
+        assert_eq!(status, expected_status);
+        assert_eq!(detail, format!("{}: x1000c0s0b0n0", kind));
+      }
+      other => panic!("Expected CsmError, got {:?}", other),
+    }
+  }
+}
+
+#[tokio::test]
+async fn test_duplicate_keeps_status() {
+  let error = request_error(404, "no such group").await.duplicate();
+
+  match FrontEndError::from(error) {
+    FrontEndError::CsmError { status, detail, .. } => {
+      assert_eq!(status, 404);
+      assert_eq!(detail, "no such group");
+    }
+    other => panic!("Expected CsmError, got {:?}", other),
+  }
+
+  let error = Error::PcsError {
+    kind: PcsErrorKind::Conflict,
+    detail: "transition in progress".to_string(),
+  };
+
+  assert!(matches!(
+    error.duplicate(),
+    Error::PcsError { kind: PcsErrorKind::Conflict, detail }
+      if detail == "transition in progress"
+  ));
+}
+
+#[test]
+fn test_ochami_error_into_frontend_error() {
+  let body = json!({
+    "type": "about:blank",
+    "title": "Not Found",
+    "detail": "no such group: compute",
+    "status": 404
+  });
+
+  match FrontEndError::from(Error::OchamiError(body.clone())) {
+    FrontEndError::CsmError {
+      status,
+      detail,
+      body: body_opt,
+    } => {
+      assert_eq!(status, 404);
+      assert_eq!(detail, "no such group: compute");
+      assert_eq!(body_opt, Some(body));
+    }
+    other => panic!("Expected CsmError, got {:?}", other),
+  }
+
+  // Falls back to the title, then to the whole body
+  match FrontEndError::from(Error::OchamiError(json!({"title": "Conflict"}))) {
+    FrontEndError::CsmError { status, detail, .. } => {
+      assert_eq!(status, 0);
+      assert_eq!(detail, "Conflict");
+    }
+    other => panic!("Expected CsmError, got {:?}", other),
+  }
+
+  match FrontEndError::from(Error::OchamiError(json!("plain text"))) {
+    FrontEndError::CsmError { detail, .. } => {
+      assert_eq!(detail, "\"plain text\"")
+    }
+    other => panic!("Expected CsmError, got {:?}", other),
+  }
+}
@@ -0,0 +1,85 @@
+#[cfg(all(test, feature = "hsm", feature = "bss", feature = "pcs"))]
+pub mod tests;
+
+#[cfg(feature = "bss")]
+use crate::bss::types::BootParameters;
+#[cfg(feature = "hsm")]
+use crate::hsm::{
+  component::types::ComponentArray, group::types::Group,
+  inventory::ethernet_interfaces::types::ComponentEthernetInterface,
+  memberships::types::Membership,
+};
+#[cfg(feature = "pcs")]
+use crate::pcs::{
+  power_status::types::PowerStatusAll, transitions::types::TransitionResponse,
+};
+
+/// Response of SMD `GET /State/Components` for a small cluster: two compute nodes, one
+/// of them disabled, and a management node
+pub const COMPONENTS_JSON: &str = include_str!("components.json");
+
+/// Response of SMD `GET /groups`
+pub const GROUPS_JSON: &str = include_str!("groups.json");
+
+/// Response of SMD `GET /Inventory/EthernetInterfaces`
+pub const ETHERNET_INTERFACES_JSON: &str =
+  include_str!("ethernet_interfaces.json");
+
+/// Response of SMD `GET /memberships`
+pub const MEMBERSHIPS_JSON: &str = include_str!("memberships.json");
+
+/// Response of BSS `GET /bootparameters`
+pub const BOOT_PARAMETERS_JSON: &str = include_str!("boot_parameters.json");
+
+/// Response of PCS `GET /transitions/{id}` for a completed power on with one failed node
+pub const TRANSITION_JSON: &str = include_str!("transition.json");
+
+/// Response of PCS `POST /power-status`
+pub const POWER_STATUS_JSON: &str = include_str!("power_status.json");
+
+#[cfg(any(feature = "hsm", feature = "bss", feature = "pcs"))]
+fn parse<T: serde::de::DeserializeOwned>(json: &str) -> T {
+  serde_json::from_str(json).expect("fixture must match its type")
+}
+
+/// `COMPONENTS_JSON` as a typed value
+#[cfg(feature = "hsm")]
+pub fn components() -> ComponentArray {
+  parse(COMPONENTS_JSON)
+}
+
+/// `GROUPS_JSON` as a typed value
+#[cfg(feature = "hsm")]
+pub fn groups() -> Vec<Group> {
+  parse(GROUPS_JSON)
+}
+
+/// `ETHERNET_INTERFACES_JSON` as a typed value
+#[cfg(feature = "hsm")]
+pub fn ethernet_interfaces() -> Vec<ComponentEthernetInterface> {
+  parse(ETHERNET_INTERFACES_JSON)
+}
+
+/// `MEMBERSHIPS_JSON` as a typed value
+#[cfg(feature = "hsm")]
+pub fn memberships() -> Vec<Membership> {
+  parse(MEMBERSHIPS_JSON)
+}
+
+/// `BOOT_PARAMETERS_JSON` as a typed value
+#[cfg(feature = "bss")]
+pub fn boot_parameters() -> Vec<BootParameters> {
+  parse(BOOT_PARAMETERS_JSON)
+}
+
+/// `TRANSITION_JSON` as a typed value
+#[cfg(feature = "pcs")]
+pub fn transition() -> TransitionResponse {
+  parse(TRANSITION_JSON)
+}
+
+/// `POWER_STATUS_JSON` as a typed value
+#[cfg(feature = "pcs")]
+pub fn power_status() -> PowerStatusAll {
+  parse(POWER_STATUS_JSON)
+}
@@ -0,0 +1,19 @@
+use crate::fixtures;
+
+#[test]
+fn test_fixtures_parse() {
+  assert_eq!(fixtures::components().components.map(|c| c.len()), Some(3));
+  assert_eq!(fixtures::groups()[0].get_members().len(), 2);
+  assert_eq!(fixtures::ethernet_interfaces().len(), 2);
+  assert_eq!(fixtures::memberships().len(), 2);
+  assert_eq!(fixtures::boot_parameters()[1].nids, Some(vec![100001]));
+  assert_eq!(fixtures::power_status().status.len(), 2);
+}
+
+#[test]
+fn test_fixture_transition_outcome() {
+  let outcome = fixtures::transition().outcome();
+
+  assert_eq!(outcome.succeeded, vec!["x1000c0s0b0n0".to_string()]);
+  assert_eq!(outcome.failed.len(), 1);
+}
@@ -0,0 +1,152 @@
+use std::{
+  future::Future,
+  time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{error::Error, http::ApiContext, pcs};
+
+/// Health of a single OpenCHAMI service
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ServiceHealth {
+  pub service: String,
+  pub healthy: bool,
+  /// Error or details reported when the service is not healthy
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub detail: Option<String>,
+  /// Time taken by the service to answer the check
+  #[serde(default)]
+  pub latency: Duration,
+}
+
+impl ServiceHealth {
+  fn from_result(
+    service: &str,
+    result: Result<(), Error>,
+    latency: Duration,
+  ) -> Self {
+    ServiceHealth {
+      service: service.to_string(),
+      healthy: result.is_ok(),
+      detail: result.err().map(|e| e.to_string()),
+      latency,
+    }
+  }
+}
+
+/// Health of all the OpenCHAMI services, checked concurrently
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HealthReport {
+  pub services: Vec<ServiceHealth>,
+}
+
+impl HealthReport {
+  /// Health of a service by name, eg: "hsm", "bss", "pcs" or "cloud-init"
+  pub fn get(&self, service: &str) -> Option<&ServiceHealth> {
+    self
+      .services
+      .iter()
+      .find(|health| health.service == service)
+  }
+
+  /// Returns true if every service checked is healthy, so it is safe to run operations
+  /// involving several services
+  pub fn is_operational(&self) -> bool {
+    is_healthy(&self.services)
+  }
+
+  /// Services not healthy
+  pub fn unhealthy(&self) -> Vec<&ServiceHealth> {
+    self
+      .services
+      .iter()
+      .filter(|health| !health.healthy)
+      .collect()
+  }
+}
+
+/// Check SMD, BSS, PCS and, if the `cloud-init` feature is enabled, the cloud-init
+/// service concurrently
+pub async fn get_report(ctx: &ApiContext) -> HealthReport {
+  let hsm = timed(async {
+    let api_url = ctx.hsm_url("/service/ready")?;
+    get_status(ctx, api_url).await
+  });
+  let bss = timed(async {
+    let api_url = ctx.bss_url("/service/status")?;
+    get_status(ctx, api_url).await
+  });
+  let pcs = timed(pcs::health::http_client::get_readiness(ctx));
+
+  #[cfg(feature = "cloud-init")]
+  let (hsm, bss, pcs, cloud_init) = tokio::join!(
+    hsm,
+    bss,
+    pcs,
+    timed(async {
+      // cloud-init has no status endpoint, listing groups checks the service and its
+      // storage are up
+      let api_url = ctx.cloud_init_url("/admin/groups")?;
+      get_status(ctx, api_url).await
+    })
+  );
+  #[cfg(not(feature = "cloud-init"))]
+  let (hsm, bss, pcs) = tokio::join!(hsm, bss, pcs);
+
+  #[allow(unused_mut)]
+  let mut services = vec![
+    ServiceHealth::from_result("hsm", hsm.0, hsm.1),
+    ServiceHealth::from_result("bss", bss.0, bss.1),
+    ServiceHealth::from_result("pcs", pcs.0, pcs.1),
+  ];
+
+  #[cfg(feature = "cloud-init")]
+  services.push(ServiceHealth::from_result(
+    "cloud-init",
+    cloud_init.0,
+    cloud_init.1,
+  ));
+
+  HealthReport { services }
+}
+
+/// Check the readiness of HSM, BSS, PCS and cloud-init (see `get_report`). Services are
+/// reported individually so callers can decide which ones are required (eg: refuse a large
+/// power operation if PCS is not ready)
+pub async fn get_all(ctx: &ApiContext) -> Vec<ServiceHealth> {
+  get_report(ctx).await.services
+}
+
+/// Returns true if all services checked are healthy
+pub fn is_healthy(service_health_vec: &[ServiceHealth]) -> bool {
+  service_health_vec.iter().all(|service| service.healthy)
+}
+
+async fn timed<F>(check: F) -> (Result<(), Error>, Duration)
+where
+  F: Future<Output = Result<(), Error>>,
+{
+  let start = Instant::now();
+  let result = check.await;
+  (result, start.elapsed())
+}
+
+async fn get_status(ctx: &ApiContext, api_url: Url) -> Result<(), Error> {
+  let client = ctx.client()?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if response.status().is_success() {
+    Ok(())
+  } else {
+    let status = response.status();
+    let payload = response.text().await.map_err(Error::NetError)?;
+    Err(Error::Message(format!("{}: {}", status, payload)))
+  }
+}
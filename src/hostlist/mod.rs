@@ -0,0 +1,108 @@
+#[cfg(test)]
+pub mod tests;
+
+use std::collections::BTreeMap;
+
+/// Collapse a list of host names into a hostlist expression, the inverse of
+/// `hostlist_parser::parse`. Eg: ["nid000001", "nid000002", "nid000003"] becomes
+/// "nid00000[1-3]". Names are grouped by the text around their last number,
+/// duplicates are removed
+pub fn compress(name_vec: &[String]) -> String {
+  // (prefix, suffix, number width) -> numbers
+  let mut group_map: BTreeMap<(&str, &str, usize), Vec<u64>> = BTreeMap::new();
+  let mut other_vec: Vec<&str> = Vec::new();
+
+  for name in name_vec {
+    match split_last_number(name) {
+      Some((prefix, number, suffix)) => match number.parse::<u64>() {
+        Ok(value) => group_map
+          .entry((prefix, suffix, number.len()))
+          .or_default()
+          .push(value),
+        Err(_) => other_vec.push(name),
+      },
+      None => other_vec.push(name),
+    }
+  }
+
+  let mut expression_vec: Vec<String> = group_map
+    .into_iter()
+    .map(|((prefix, suffix, width), mut number_vec)| {
+      number_vec.sort_unstable();
+      number_vec.dedup();
+
+      let padded_vec: Vec<String> = number_vec
+        .iter()
+        .map(|number| format!("{:0width$}", number, width = width))
+        .collect();
+
+      if padded_vec.len() == 1 {
+        return format!("{}{}{}", prefix, padded_vec[0], suffix);
+      }
+
+      // Move leading digits shared by all numbers out of the brackets
+      let common_len = padded_vec[0]
+        .char_indices()
+        .take_while(|(i, c)| {
+          *i < width - 1
+            && padded_vec
+              .iter()
+              .all(|padded| padded.as_bytes()[*i] == *c as u8)
+        })
+        .count();
+
+      let range_vec: Vec<String> = to_ranges(&number_vec)
+        .into_iter()
+        .map(|(start, end)| {
+          let start = format!("{:0width$}", start, width = width);
+          let end = format!("{:0width$}", end, width = width);
+          if start == end {
+            start[common_len..].to_string()
+          } else {
+            format!("{}-{}", &start[common_len..], &end[common_len..])
+          }
+        })
+        .collect();
+
+      format!(
+        "{}{}[{}]{}",
+        prefix,
+        &padded_vec[0][..common_len],
+        range_vec.join(","),
+        suffix
+      )
+    })
+    .collect();
+
+  other_vec.sort_unstable();
+  other_vec.dedup();
+  expression_vec.extend(other_vec.into_iter().map(str::to_string));
+
+  expression_vec.join(",")
+}
+
+/// Split a name around its last run of digits, eg: "x1000c0s1b0n1" becomes
+/// ("x1000c0s1b0n", "1", "")
+fn split_last_number(name: &str) -> Option<(&str, &str, &str)> {
+  let end = name.rfind(|c: char| c.is_ascii_digit())? + 1;
+  let start = name[..end]
+    .rfind(|c: char| !c.is_ascii_digit())
+    .map(|i| i + 1)
+    .unwrap_or(0);
+
+  Some((&name[..start], &name[start..end], &name[end..]))
+}
+
+/// Group sorted numbers into consecutive ranges
+fn to_ranges(number_vec: &[u64]) -> Vec<(u64, u64)> {
+  let mut range_vec: Vec<(u64, u64)> = Vec::new();
+
+  for &number in number_vec {
+    match range_vec.last_mut() {
+      Some((_, end)) if *end + 1 == number => *end = number,
+      _ => range_vec.push((number, number)),
+    }
+  }
+
+  range_vec
+}
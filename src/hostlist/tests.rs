@@ -0,0 +1,89 @@
+use super::compress;
+
+fn names(name_vec: &[&str]) -> Vec<String> {
+  name_vec.iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn test_compress_empty() {
+  assert_eq!(compress(&[]), "");
+}
+
+#[test]
+fn test_compress_single_host() {
+  assert_eq!(compress(&names(&["nid000001"])), "nid000001");
+  assert_eq!(compress(&names(&["login"])), "login");
+}
+
+#[test]
+fn test_compress_consecutive_and_duplicates() {
+  assert_eq!(
+    compress(&names(&[
+      "nid000003",
+      "nid000001",
+      "nid000002",
+      "nid000001"
+    ])),
+    "nid00000[1-3]"
+  );
+}
+
+#[test]
+fn test_compress_gaps() {
+  assert_eq!(
+    compress(&names(&[
+      "nid000001",
+      "nid000002",
+      "nid000005",
+      "nid000007"
+    ])),
+    "nid00000[1-2,5,7]"
+  );
+  // Leading digits are only moved out of the brackets if all numbers share them
+  assert_eq!(
+    compress(&names(&["nid000009", "nid000010", "nid000012"])),
+    "nid0000[09-10,12]"
+  );
+}
+
+#[test]
+fn test_compress_zero_padding() {
+  // Numbers of different widths are kept apart to preserve their padding
+  assert_eq!(
+    compress(&names(&["n1", "n01", "n2", "n02"])),
+    "n[1-2],n0[1-2]"
+  );
+  assert_eq!(
+    compress(&names(&["node1", "node2", "node3", "node10"])),
+    "node[1-3],node10"
+  );
+}
+
+#[test]
+fn test_compress_mixed_prefixes_and_suffixes() {
+  assert_eq!(
+    compress(&names(&[
+      "x1000c0s0b0n1",
+      "login",
+      "nid000001",
+      "x1000c0s0b0n0",
+      "node1-ib",
+      "node2-ib",
+    ])),
+    "nid000001,node[1-2]-ib,x1000c0s0b0n[0-1],login"
+  );
+}
+
+#[test]
+fn test_compress_round_trip() {
+  let name_vec =
+    names(&["nid000001", "nid000002", "nid000005", "nid000010", "login"]);
+
+  let mut expanded_vec = hostlist_parser::parse(&compress(&name_vec)).unwrap();
+  expanded_vec.sort();
+
+  let mut expected_vec = name_vec.clone();
+  expected_vec.sort();
+
+  assert_eq!(expanded_vec, expected_vec);
+}
@@ -0,0 +1,280 @@
+use std::{
+  collections::HashMap,
+  hash::Hash,
+  sync::Mutex,
+  time::{Duration, Instant},
+};
+
+use crate::{
+  error::Error,
+  hsm::{
+    component::{self, types::ComponentArray},
+    group::{
+      self,
+      types::{Group, Members},
+    },
+  },
+  http::{ApiContext, AuthToken, Validators},
+};
+
+static CACHE: Mutex<Option<Cache>> = Mutex::new(None);
+
+/// Values cached per base url and access token, so several OpenCHAMI instances can be
+/// used in the same process and a caller is never served data fetched with another
+/// caller's token
+#[derive(Default)]
+struct Cache {
+  groups: TtlMap<ContextKey, Vec<Group>>,
+  members: TtlMap<(ContextKey, String), Members>,
+  components: HashMap<ContextKey, ConditionalEntry<ComponentArray>>,
+}
+
+impl Cache {
+  /// Drop the entries fetched for the deployment of `key` with another token. Tokens
+  /// are rotated through `ApiContext::with_auth_token`, entries of the previous token
+  /// would otherwise stay in the cache for the life of the process
+  fn evict_rotated(&mut self, key: &ContextKey) {
+    self
+      .groups
+      .entries
+      .retain(|other, _| !other.is_rotated(key));
+    self
+      .members
+      .entries
+      .retain(|(other, _), _| !other.is_rotated(key));
+    self.components.retain(|other, _| !other.is_rotated(key));
+  }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct ContextKey {
+  base_url: String,
+  auth_token: AuthToken,
+}
+
+impl ContextKey {
+  fn new(ctx: &ApiContext) -> Self {
+    ContextKey {
+      base_url: ctx.base_url.as_str().to_string(),
+      auth_token: ctx.auth_token.clone(),
+    }
+  }
+
+  /// Whether `self` is the same deployment as `other` with another token
+  fn is_rotated(&self, other: &ContextKey) -> bool {
+    self.base_url == other.base_url && self.auth_token != other.auth_token
+  }
+}
+
+/// Entry kept past its TTL, revalidated with a conditional request once expired
+struct ConditionalEntry<V> {
+  checked: Instant,
+  validators: Validators,
+  value: V,
+}
+
+struct TtlMap<K, V> {
+  entries: HashMap<K, (Instant, V)>,
+}
+
+impl<K, V> Default for TtlMap<K, V> {
+  fn default() -> Self {
+    TtlMap {
+      entries: HashMap::new(),
+    }
+  }
+}
+
+impl<K: Eq + Hash, V: Clone> TtlMap<K, V> {
+  fn get(&mut self, key: &K, ttl: Duration) -> Option<V> {
+    match self.entries.get(key) {
+      Some((inserted, value)) if inserted.elapsed() < ttl => {
+        Some(value.clone())
+      }
+      Some(_) => {
+        self.entries.remove(key);
+        None
+      }
+      None => None,
+    }
+  }
+
+  /// Insert `value`, dropping the entries older than `ttl` on the way
+  fn insert(&mut self, key: K, value: V, ttl: Duration) {
+    self
+      .entries
+      .retain(|_, (inserted, _)| inserted.elapsed() < ttl);
+    self.entries.insert(key, (Instant::now(), value));
+  }
+}
+
+fn with_cache<R>(f: impl FnOnce(&mut Cache) -> R) -> R {
+  let mut cache = CACHE
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner());
+  f(cache.get_or_insert_with(Cache::default))
+}
+
+/// Drop every cached entry
+pub fn invalidate() {
+  let mut cache = CACHE
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner());
+  *cache = None;
+}
+
+/// Drop the cached members of a group and the cached group lists of the deployment of
+/// `ctx`, whatever the token they were fetched with. Called by the group mutations in
+/// `hsm::group::client`
+pub fn invalidate_group(ctx: &ApiContext, group_label: &str) {
+  let base_url = ctx.base_url.as_str();
+
+  with_cache(|cache| {
+    cache
+      .groups
+      .entries
+      .retain(|key, _| key.base_url != base_url);
+    cache.members.entries.retain(|(key, label), _| {
+      key.base_url != base_url || label != group_label
+    });
+  });
+}
+
+/// Drop the cached components of the deployment of `ctx`, whatever the token they were
+/// fetched with. Called by the component mutations in `hsm::component::client` and
+/// `hsm::state::components::client`
+pub fn invalidate_components(ctx: &ApiContext) {
+  let base_url = ctx.base_url.as_str();
+
+  with_cache(|cache| {
+    cache.components.retain(|key, _| key.base_url != base_url);
+  });
+}
+
+/// Same as `hsm::group::client::get_all`, served from the cache if enabled on `ctx`
+pub async fn get_all_groups(ctx: &ApiContext) -> Result<Vec<Group>, Error> {
+  let ttl = match ctx.cache_ttl {
+    Some(ttl) => ttl,
+    None => return group::client::get_all(ctx).await,
+  };
+
+  let key = ContextKey::new(ctx);
+
+  if let Some(group_vec) = with_cache(|cache| cache.groups.get(&key, ttl)) {
+    return Ok(group_vec);
+  }
+
+  let group_vec = group::client::get_all(ctx).await?;
+
+  with_cache(|cache| {
+    cache.evict_rotated(&key);
+    cache.groups.insert(key, group_vec.clone(), ttl)
+  });
+
+  Ok(group_vec)
+}
+
+/// Same as `hsm::group::client::get_members`, served from the cache if enabled on
+/// `ctx`
+pub async fn get_members(
+  ctx: &ApiContext,
+  group_label: &str,
+) -> Result<Members, Error> {
+  let ttl = match ctx.cache_ttl {
+    Some(ttl) => ttl,
+    None => return group::client::get_members(ctx, group_label).await,
+  };
+
+  let key = (ContextKey::new(ctx), group_label.to_string());
+
+  if let Some(members) = with_cache(|cache| cache.members.get(&key, ttl)) {
+    return Ok(members);
+  }
+
+  let members = group::client::get_members(ctx, group_label).await?;
+
+  with_cache(|cache| {
+    cache.evict_rotated(&key.0);
+    cache.members.insert(key, members.clone(), ttl)
+  });
+
+  Ok(members)
+}
+
+/// Same as `hsm::component::client::get_all`, served from the cache if enabled on
+/// `ctx`. Once the entry expires, it is revalidated with a conditional request so SMD
+/// only sends the components again if they changed
+pub async fn get_all_components(
+  ctx: &ApiContext,
+) -> Result<ComponentArray, Error> {
+  match ctx.cache_ttl {
+    Some(ttl) => get_all_components_max_age(ctx, ttl).await,
+    None => component::client::get_all(ctx).await,
+  }
+}
+
+/// Same as `get_all_components` but the cached entry is always revalidated, so the
+/// components are never older than the call. SMD only sends them again if they changed
+pub async fn get_all_components_revalidated(
+  ctx: &ApiContext,
+) -> Result<ComponentArray, Error> {
+  match ctx.cache_ttl {
+    Some(_) => get_all_components_max_age(ctx, Duration::ZERO).await,
+    None => component::client::get_all(ctx).await,
+  }
+}
+
+async fn get_all_components_max_age(
+  ctx: &ApiContext,
+  max_age: Duration,
+) -> Result<ComponentArray, Error> {
+  let key = ContextKey::new(ctx);
+
+  let (fresh_opt, validators) =
+    with_cache(|cache| match cache.components.get(&key) {
+      Some(entry) if entry.checked.elapsed() < max_age => {
+        (Some(entry.value.clone()), entry.validators.clone())
+      }
+      Some(entry) => (None, entry.validators.clone()),
+      None => (None, Validators::default()),
+    });
+
+  if let Some(component_array) = fresh_opt {
+    return Ok(component_array);
+  }
+
+  let modified_opt =
+    component::client::get_all_if_modified(ctx, &validators).await?;
+
+  match modified_opt {
+    Some((component_array, validators)) => {
+      with_cache(|cache| {
+        cache.evict_rotated(&key);
+        cache.components.insert(
+          key,
+          ConditionalEntry {
+            checked: Instant::now(),
+            validators,
+            value: component_array.clone(),
+          },
+        )
+      });
+
+      Ok(component_array)
+    }
+    None => {
+      let cached_opt = with_cache(|cache| {
+        cache.components.get_mut(&key).map(|entry| {
+          entry.checked = Instant::now();
+          entry.value.clone()
+        })
+      });
+
+      // The entry was invalidated while the request was in flight
+      match cached_opt {
+        Some(component_array) => Ok(component_array),
+        None => component::client::get_all(ctx).await,
+      }
+    }
+  }
+}
@@ -0,0 +1,437 @@
+use serde_json::Value;
+
+use crate::{
+  error::Error,
+  hsm::{state::components::types::Component, types::ActionResponse},
+  http::{ApiContext, MutationRequest, RawResponse, Validators},
+};
+
+use super::types::{
+  ComponentArray, ComponentArrayPostArray, ComponentArrayPostByNidQuery,
+  ComponentArrayPostQuery, ComponentPut,
+};
+
+pub async fn get_all(ctx: &ApiContext) -> Result<ComponentArray, Error> {
+  get(
+    ctx, None, None, None, None, None, None, None, None, None, None, None,
+    None, None, None, None, None, None, None, None, None,
+  )
+  .await
+}
+
+pub async fn get_all_nodes(
+  ctx: &ApiContext,
+  nid_only: Option<&str>,
+) -> Result<ComponentArray, Error> {
+  get(
+    ctx,
+    None,
+    Some("Node"),
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    nid_only,
+  )
+  .await
+}
+
+/// Get all components.
+/// NOTE: nid is a comma separated list of NIDs like "1,2,3".
+pub async fn get(
+  ctx: &ApiContext,
+  id: Option<&str>,
+  r#type: Option<&str>,
+  state: Option<&str>,
+  flag: Option<&str>,
+  role: Option<&str>,
+  subrole: Option<&str>,
+  enabled: Option<&str>,
+  software_status: Option<&str>,
+  subtype: Option<&str>,
+  arch: Option<&str>,
+  class: Option<&str>,
+  nid: Option<&str>,
+  nid_start: Option<&str>,
+  nid_end: Option<&str>,
+  partition: Option<&str>,
+  group: Option<&str>,
+  state_only: Option<&str>,
+  flag_only: Option<&str>,
+  role_only: Option<&str>,
+  nid_only: Option<&str>,
+) -> Result<ComponentArray, Error> {
+  let client = ctx.client()?;
+
+  let mut nid_vec_query = nid.map(|nids| {
+    nids
+      .split(",")
+      .map(|nid| ("nid", Some(nid)))
+      .collect::<Vec<(&str, Option<&str>)>>()
+  });
+
+  let mut query_params = vec![
+    ("id", id),
+    ("type", r#type),
+    ("state", state),
+    ("flag", flag),
+    ("role", role),
+    ("subrole", subrole),
+    ("enabled", enabled),
+    ("softwarestatus", software_status),
+    ("subtype", subtype),
+    ("arch", arch),
+    ("class", class),
+    ("nidstart", nid_start),
+    ("nidend", nid_end),
+    ("partition", partition),
+    ("group", group),
+    ("stateonly", state_only),
+    ("flagonly", flag_only),
+    ("roleonly", role_only),
+    ("nidonly", nid_only),
+  ];
+
+  if let Some(mut nid_vec_query) = nid_vec_query.take() {
+    query_params.append(&mut nid_vec_query);
+  }
+
+  let api_url = ctx.hsm_url("/State/Components")?;
+
+  let response = client
+    .get(api_url)
+    .query(&query_params)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if !response.status().is_success() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  response
+    .json::<ComponentArray>()
+    .await
+    .map_err(Error::NetError)
+}
+
+/// Conditional variant of `get_all`. Returns `None` if the components did not change since
+/// the response `validators` come from, otherwise the components and their new validators
+pub async fn get_all_if_modified(
+  ctx: &ApiContext,
+  validators: &Validators,
+) -> Result<Option<(ComponentArray, Validators)>, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/State/Components")?;
+
+  let response = validators
+    .apply(client.get(api_url).bearer_auth(ctx.auth_token.as_str()))
+    .send()
+    .await?;
+
+  if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+    return Ok(None);
+  }
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  let validators = Validators::from_headers(response.headers());
+  let component_array = response
+    .json::<ComponentArray>()
+    .await
+    .map_err(Error::NetError)?;
+
+  Ok(Some((component_array, validators)))
+}
+
+pub async fn get_one(
+  ctx: &ApiContext,
+  xname: &str,
+) -> Result<Component, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!("/State/Components/{}", xname))?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+/// Same as `get_one` but returns the raw response, whatever its status
+pub async fn get_one_raw(
+  ctx: &ApiContext,
+  xname: &str,
+) -> Result<RawResponse<Component>, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!("/State/Components/{}", xname))?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  RawResponse::from_response(response).await
+}
+
+pub async fn post(
+  ctx: &ApiContext,
+  component: ComponentArrayPostArray,
+) -> Result<(), Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/State/Components")?;
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(&component)
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  crate::hsm::cache::invalidate_components(ctx);
+
+  Ok(())
+}
+
+pub async fn post_query(
+  ctx: &ApiContext,
+  component: ComponentArrayPostQuery,
+) -> Result<ComponentArray, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/State/Components/Query")?;
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(&component)
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn post_bynid_query(
+  ctx: &ApiContext,
+  component: ComponentArrayPostByNidQuery,
+) -> Result<ComponentArray, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/State/Components/ByNID/Query")?;
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(&component)
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn put(
+  ctx: &ApiContext,
+  xname: &str,
+  component: ComponentPut,
+) -> Result<(), Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!("/State/Components/{}", xname))?;
+
+  let response = client
+    .put(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(&component)
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  crate::hsm::cache::invalidate_components(ctx);
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn delete_one(
+  ctx: &ApiContext,
+  xname: &str,
+) -> Result<ActionResponse, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!("/State/Components/{}", xname))?;
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  crate::hsm::cache::invalidate_components(ctx);
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn delete(ctx: &ApiContext) -> Result<ActionResponse, Error> {
+  let client = ctx.client()?;
+  // NOTE: pre-existing typo in the URL ("Componnets")
+  let api_url = ctx.hsm_url("/State/Componnets")?;
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  crate::hsm::cache::invalidate_components(ctx);
+
+  response.json().await.map_err(Error::NetError)
+}
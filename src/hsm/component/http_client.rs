@@ -1,61 +1,44 @@
 use serde_json::Value;
 
-use crate::{error::Error, hsm::state::components::types::Component};
+use crate::{
+  error::Error, hsm::state::components::types::Component, http::ApiContext,
+};
 
-use super::types::{
-  ComponentArray, ComponentArrayPostArray, ComponentArrayPostByNidQuery,
-  ComponentArrayPostQuery, ComponentPut,
+use super::{
+  client,
+  types::{
+    ComponentArray, ComponentArrayPostArray, ComponentArrayPostByNidQuery,
+    ComponentArrayPostQuery, ComponentPut,
+  },
 };
 
+#[deprecated(note = "use `component::client::get_all` with an `ApiContext`")]
 pub async fn get_all(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
 ) -> Result<ComponentArray, Error> {
-  get(
-    base_url, root_cert, auth_token, None, None, None, None,
-    None, None, None, None, None, None, None, None, None, None, None, None,
-    None, None, None, None,
-  )
-  .await
+  client::get_all(&ApiContext::parse(base_url, auth_token, root_cert)?).await
 }
 
+#[deprecated(
+  note = "use `component::client::get_all_nodes` with an `ApiContext`"
+)]
 pub async fn get_all_nodes(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   nid_only: Option<&str>,
 ) -> Result<ComponentArray, Error> {
-  get(
-    base_url,
-    root_cert,
-    auth_token,
-    None,
-    Some("Node"),
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
+  client::get_all_nodes(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
     nid_only,
   )
   .await
 }
 
-/// Get all components.
-/// NOTE: nid is a comma separated list of NIDs like "1,2,3".
+#[deprecated(note = "use `component::client::get` with an `ApiContext`")]
+#[allow(clippy::too_many_arguments)]
 pub async fn get(
   base_url: &str,
   root_cert: &[u8],
@@ -81,204 +64,88 @@ pub async fn get(
   role_only: Option<&str>,
   nid_only: Option<&str>,
 ) -> Result<ComponentArray, Error> {
-  let client = crate::http::build_client(root_cert)?;
-
-  let mut nid_vec_query = nid.map(|nids| {
-    nids
-      .split(",")
-      .map(|nid| ("nid", Some(nid)))
-      .collect::<Vec<(&str, Option<&str>)>>()
-  });
-
-  let mut query_params = vec![
-    ("id", id),
-    ("type", r#type),
-    ("state", state),
-    ("flag", flag),
-    ("role", role),
-    ("subrole", subrole),
-    ("enabled", enabled),
-    ("softwarestatus", software_status),
-    ("subtype", subtype),
-    ("arch", arch),
-    ("class", class),
-    ("nidstart", nid_start),
-    ("nidend", nid_end),
-    ("partition", partition),
-    ("group", group),
-    ("stateonly", state_only),
-    ("flagonly", flag_only),
-    ("roleonly", role_only),
-    ("nidonly", nid_only),
-  ];
-
-  if let Some(mut nid_vec_query) = nid_vec_query.take() {
-    query_params.append(&mut nid_vec_query);
-  }
-
-  let api_url = format!("{}/hsm/v2/State/Components", base_url);
-
-  let response = client
-    .get(api_url)
-    .query(&query_params)
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if !response.status().is_success() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::Message(error_payload));
-      }
-      _ => {
-        let error_payload = response.text().await?;
-        return Err(Error::Message(error_payload));
-      }
-    }
-  }
-
-  response
-    .json::<ComponentArray>()
-    .await
-    .map_err(Error::NetError)
+  client::get(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    id,
+    r#type,
+    state,
+    flag,
+    role,
+    subrole,
+    enabled,
+    software_status,
+    subtype,
+    arch,
+    class,
+    nid,
+    nid_start,
+    nid_end,
+    partition,
+    group,
+    state_only,
+    flag_only,
+    role_only,
+    nid_only,
+  )
+  .await
 }
 
+#[deprecated(note = "use `component::client::get_one` with an `ApiContext`")]
 pub async fn get_one(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   xname: &str,
 ) -> Result<Component, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!("{}/hsm/v2/State/Components/{}", base_url, xname);
-
-  let response = client.get(api_url).bearer_auth(auth_token).send().await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get_one(&ApiContext::parse(base_url, auth_token, root_cert)?, xname)
+    .await
 }
 
+#[deprecated(note = "use `component::client::post` with an `ApiContext`")]
 pub async fn post(
   auth_token: &str,
   base_url: &str,
   root_cert: &[u8],
   component: ComponentArrayPostArray,
 ) -> Result<(), Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = base_url.to_owned() + "/hsm/v2/State/Components";
-
-  let response = client
-    .post(api_url)
-    .bearer_auth(auth_token)
-    .json(&component)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  Ok(())
+  client::post(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    component,
+  )
+  .await
 }
 
+#[deprecated(note = "use `component::client::post_query` with an `ApiContext`")]
 pub async fn post_query(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   component: ComponentArrayPostQuery,
 ) -> Result<ComponentArray, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = base_url.to_owned() + "/hsm/v2/State/Components";
-
-  let response = client
-    .post(api_url)
-    .bearer_auth(auth_token)
-    .json(&component)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::post_query(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    component,
+  )
+  .await
 }
 
+#[deprecated(
+  note = "use `component::client::post_bynid_query` with an `ApiContext`"
+)]
 pub async fn post_bynid_query(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   component: ComponentArrayPostByNidQuery,
 ) -> Result<ComponentArray, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = base_url.to_owned() + "/hsm/v2/State/Components/ByNID/Query";
-
-  let response = client
-    .post(api_url)
-    .bearer_auth(auth_token)
-    .json(&component)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::post_bynid_query(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    component,
+  )
+  .await
 }
 
+#[deprecated(note = "use `component::client::put` with an `ApiContext`")]
 pub async fn put(
   base_url: &str,
   auth_token: &str,
@@ -286,99 +153,39 @@ pub async fn put(
   xname: &str,
   component: ComponentPut,
 ) -> Result<(), Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!("{}/hsm/v2/State/Components/{}", base_url, xname);
-
-  let response = client
-    .put(api_url)
-    .bearer_auth(auth_token)
-    .json(&component)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::put(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    xname,
+    component,
+  )
+  .await
 }
 
+#[deprecated(note = "use `component::client::delete_one` with an `ApiContext`")]
 pub async fn delete_one(
   auth_token: &str,
   base_url: &str,
   root_cert: &[u8],
   xname: &str,
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!("{}/hsm/v2/State/Components/{}", base_url, xname);
-
-  let response = client
-    .delete(api_url)
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
+  let response = client::delete_one(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    xname,
+  )
+  .await?;
 
-  response.json().await.map_err(Error::NetError)
+  Ok(serde_json::to_value(response)?)
 }
 
+#[deprecated(note = "use `component::client::delete` with an `ApiContext`")]
 pub async fn delete(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  // NOTE: pre-existing typo in the URL ("Componnets")
-  let api_url = format!("{}/hsm/v2/State/Componnets", base_url);
-
-  let response = client
-    .delete(api_url)
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
+  let response =
+    client::delete(&ApiContext::parse(base_url, auth_token, root_cert)?)
+      .await?;
 
-  response.json().await.map_err(Error::NetError)
+  Ok(serde_json::to_value(response)?)
 }
@@ -1,2 +1,6 @@
+pub mod client;
 pub mod http_client;
+#[cfg(test)]
+pub mod tests;
 pub mod types;
+pub mod utils;
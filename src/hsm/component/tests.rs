@@ -0,0 +1,276 @@
+use crate::hsm::component::utils::component_array_from_csv;
+
+#[test]
+fn test_component_array_from_csv() {
+  let input = "xname,nid,role,arch,class
+x1000c0s0b0n0,1,Compute,X86,River
+
+# management nodes
+x1000c0s1b0n0,,Management
+";
+
+  let component_array = component_array_from_csv(input).unwrap();
+
+  assert_eq!(component_array.components.len(), 2);
+  assert_eq!(component_array.components[0].nid, Some(1));
+  assert_eq!(
+    component_array.components[0].class.as_deref(),
+    Some("River")
+  );
+  assert_eq!(component_array.components[1].nid, None);
+  assert_eq!(
+    component_array.components[1].role.as_deref(),
+    Some("Management")
+  );
+}
+
+#[test]
+fn test_component_array_from_tsv_reports_rows() {
+  let input = "x1000c0s0b0n0\t1\tCompute
+x1000c0s0b0n1\tone\tCompute
+not-an-xname\t3\tCompute
+x1000c0s0b0n2\t1\tCompute";
+
+  let error = component_array_from_csv(input).unwrap_err().to_string();
+
+  assert!(error.contains("row 2: invalid nid 'one'"));
+  assert!(error.contains("row 3: invalid xname 'not-an-xname'"));
+  assert!(error.contains("row 4: duplicated nid '1'"));
+}
+
+#[test]
+fn test_component_array_from_csv_header_and_quoting() {
+  let input = "# node list
+Role, XNAME,NID
+\"Compute, GPU\",x1000c0s0b0n0,1
+,\"x1000c0s0b0n1\",2
+";
+
+  let component_array = component_array_from_csv(input).unwrap();
+
+  assert_eq!(component_array.components.len(), 2);
+  assert_eq!(component_array.components[0].id, "x1000c0s0b0n0");
+  assert_eq!(
+    component_array.components[0].role.as_deref(),
+    Some("Compute, GPU")
+  );
+  assert_eq!(component_array.components[1].id, "x1000c0s0b0n1");
+  assert_eq!(component_array.components[1].nid, Some(2));
+  assert_eq!(component_array.components[1].role, None);
+}
+
+#[test]
+fn test_component_array_from_csv_malformed() {
+  let error = component_array_from_csv("xname,rack\nx1000c0s0b0n0,1\n")
+    .unwrap_err()
+    .to_string();
+  assert!(error.contains("row 1: unknown column 'rack'"));
+
+  let error = component_array_from_csv(
+    "x1000c0s0b0n0,1,\"Compute\nx1000c0s0b0n1,2,Compute,X86,River,extra\n",
+  )
+  .unwrap_err()
+  .to_string();
+  assert!(error.contains("row 1: quoted field is not terminated"));
+  assert!(error.contains("row 2: expected at most 5 columns"));
+}
+
+#[tokio::test]
+async fn test_component_mutation_invalidates_cache() {
+  let mock = crate::test_util::MockOchami::start().await;
+  mock
+    .mock_json(
+      crate::test_util::MockService::Hsm,
+      "GET",
+      "/State/Components",
+      crate::fixtures::COMPONENTS_JSON,
+    )
+    .await;
+  mock
+    .mock(
+      crate::test_util::MockService::Hsm,
+      "DELETE",
+      "/State/Components/x1000c0s0b0n1",
+      200,
+      Some(serde_json::json!({"code": 0, "message": "deleted 1 entry"})),
+    )
+    .await;
+
+  // wiremock reuses its servers across tests, the token keeps the cache entries apart
+  let ctx = mock
+    .context()
+    .with_auth_token("test_component_mutation_invalidates_cache")
+    .with_cache_ttl(Some(std::time::Duration::from_secs(60)));
+
+  crate::hsm::cache::get_all_components(&ctx).await.unwrap();
+  crate::hsm::cache::get_all_components(&ctx).await.unwrap();
+  crate::hsm::component::client::delete_one(&ctx, "x1000c0s0b0n1")
+    .await
+    .unwrap();
+  crate::hsm::cache::get_all_components(&ctx).await.unwrap();
+
+  let get_count = mock
+    .server()
+    .received_requests()
+    .await
+    .unwrap()
+    .iter()
+    .filter(|request| request.method.as_str() == "GET")
+    .count();
+  assert_eq!(get_count, 2);
+}
+
+#[tokio::test]
+async fn test_component_cache_evicts_rotated_token() {
+  let mock = crate::test_util::MockOchami::start().await;
+  mock
+    .mock_json(
+      crate::test_util::MockService::Hsm,
+      "GET",
+      "/State/Components",
+      crate::fixtures::COMPONENTS_JSON,
+    )
+    .await;
+
+  let ctx = mock
+    .context()
+    .with_auth_token("test_component_cache_evicts_rotated_token")
+    .with_cache_ttl(Some(std::time::Duration::from_secs(60)));
+  let rotated_ctx = ctx.with_auth_token("rotated-token");
+
+  crate::hsm::cache::get_all_components(&ctx).await.unwrap();
+  crate::hsm::cache::get_all_components(&rotated_ctx)
+    .await
+    .unwrap();
+  crate::hsm::cache::get_all_components(&rotated_ctx)
+    .await
+    .unwrap();
+
+  // The snapshot of the previous token was dropped when the rotated one was cached
+  crate::hsm::cache::get_all_components(&ctx).await.unwrap();
+
+  let get_count = mock
+    .server()
+    .received_requests()
+    .await
+    .unwrap()
+    .iter()
+    .filter(|request| request.method.as_str() == "GET")
+    .count();
+  assert_eq!(get_count, 3);
+}
+
+#[test]
+fn test_validators_from_headers() {
+  let mut headers = reqwest::header::HeaderMap::new();
+  headers.insert(reqwest::header::ETAG, "\"v1\"".parse().unwrap());
+  headers.insert(
+    reqwest::header::LAST_MODIFIED,
+    "Wed, 14 Oct 2026 08:00:00 GMT".parse().unwrap(),
+  );
+
+  let validators = crate::http::Validators::from_headers(&headers);
+  assert_eq!(validators.etag.as_deref(), Some("\"v1\""));
+  assert_eq!(
+    validators.last_modified.as_deref(),
+    Some("Wed, 14 Oct 2026 08:00:00 GMT")
+  );
+
+  let request = validators
+    .apply(reqwest::Client::new().get("http://localhost/"))
+    .build()
+    .unwrap();
+  assert_eq!(request.headers()["if-none-match"], "\"v1\"");
+  assert_eq!(
+    request.headers()["if-modified-since"],
+    "Wed, 14 Oct 2026 08:00:00 GMT"
+  );
+
+  let request = crate::http::Validators::default()
+    .apply(reqwest::Client::new().get("http://localhost/"))
+    .build()
+    .unwrap();
+  assert!(request.headers().is_empty());
+}
+
+/// `/State/Components` answering 304 to requests revalidating the `"v1"` ETag
+async fn mock_conditional_components(mock: &crate::test_util::MockOchami) {
+  let api_url = mock.context().hsm_url("/State/Components").unwrap();
+
+  wiremock::Mock::given(wiremock::matchers::method("GET"))
+    .and(wiremock::matchers::path(api_url.path()))
+    .and(wiremock::matchers::header("if-none-match", "\"v1\""))
+    .respond_with(wiremock::ResponseTemplate::new(304))
+    .with_priority(1)
+    .mount(mock.server())
+    .await;
+  wiremock::Mock::given(wiremock::matchers::method("GET"))
+    .and(wiremock::matchers::path(api_url.path()))
+    .respond_with(
+      wiremock::ResponseTemplate::new(200)
+        .insert_header("etag", "\"v1\"")
+        .set_body_raw(crate::fixtures::COMPONENTS_JSON, "application/json"),
+    )
+    .mount(mock.server())
+    .await;
+}
+
+#[tokio::test]
+async fn test_get_all_if_modified() {
+  let mock = crate::test_util::MockOchami::start().await;
+  mock_conditional_components(&mock).await;
+
+  let ctx = mock.context();
+
+  let (component_array, validators) =
+    crate::hsm::component::client::get_all_if_modified(
+      &ctx,
+      &crate::http::Validators::default(),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+  assert_eq!(
+    component_array
+      .components
+      .map(|component_vec| component_vec.len()),
+    Some(3)
+  );
+  assert_eq!(validators.etag.as_deref(), Some("\"v1\""));
+
+  let not_modified =
+    crate::hsm::component::client::get_all_if_modified(&ctx, &validators)
+      .await
+      .unwrap();
+  assert!(not_modified.is_none());
+}
+
+#[tokio::test]
+async fn test_revalidated_components_ignore_the_ttl() {
+  let mock = crate::test_util::MockOchami::start().await;
+  mock_conditional_components(&mock).await;
+
+  let ctx = mock
+    .context()
+    .with_auth_token("test_revalidated_components_ignore_the_ttl")
+    .with_cache_ttl(Some(std::time::Duration::from_secs(60)));
+
+  for _ in 0..2 {
+    let component_array =
+      crate::hsm::cache::get_all_components_revalidated(&ctx)
+        .await
+        .unwrap();
+    assert_eq!(
+      component_array
+        .components
+        .map(|component_vec| component_vec.len()),
+      Some(3)
+    );
+  }
+
+  // The second call is sent within the TTL, as a conditional request
+  let request_vec = mock.server().received_requests().await.unwrap();
+  assert_eq!(request_vec.len(), 2);
+  assert!(!request_vec[0].headers.contains_key("if-none-match"));
+  assert_eq!(request_vec[1].headers["if-none-match"], "\"v1\"");
+}
@@ -1,9 +1,12 @@
+#[cfg(feature = "backend-connector")]
 use manta_backend_dispatcher::types::{
   Component as FrontEndComponent,
   ComponentArrayPostArray as FrontEndComponentArrayPostArray,
   ComponentCreate as FrontEndComponentCreate, NodeMetadataArray,
 };
 
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -13,6 +16,7 @@ pub struct ComponentArray {
   pub components: Option<Vec<Component>>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<NodeMetadataArray> for ComponentArray {
   fn from(value: NodeMetadataArray) -> Self {
     let component_vec_opt: Option<Vec<Component>> =
@@ -37,6 +41,7 @@ impl From<NodeMetadataArray> for ComponentArray {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<NodeMetadataArray> for ComponentArray {
   fn into(self) -> NodeMetadataArray {
     let component_vec_opt: Option<Vec<FrontEndComponent>> =
@@ -108,6 +113,7 @@ pub struct Component {
   pub locked: Option<bool>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndComponent> for Component {
   fn from(value: FrontEndComponent) -> Self {
     Component {
@@ -130,6 +136,7 @@ impl From<FrontEndComponent> for Component {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndComponent> for Component {
   fn into(self) -> FrontEndComponent {
     FrontEndComponent {
@@ -152,7 +159,7 @@ impl Into<FrontEndComponent> for Component {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct ComponentArrayPostQuery {
   #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename(serialize = "ComponentIDs"))]
@@ -231,6 +238,7 @@ pub struct ComponentArrayPostArray {
   pub force: Option<bool>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndComponentArrayPostArray> for ComponentArrayPostArray {
   fn from(value: FrontEndComponentArrayPostArray) -> Self {
     let mut component_vec: Vec<ComponentCreate> =
@@ -248,6 +256,7 @@ impl From<FrontEndComponentArrayPostArray> for ComponentArrayPostArray {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndComponentArrayPostArray> for ComponentArrayPostArray {
   fn into(self) -> FrontEndComponentArrayPostArray {
     let mut component_vec: Vec<FrontEndComponentCreate> =
@@ -268,41 +277,42 @@ impl Into<FrontEndComponentArrayPostArray> for ComponentArrayPostArray {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ComponentCreate {
   #[serde(rename(serialize = "ID"))]
-  id: String,
+  pub id: String,
   #[serde(rename(serialize = "State"))]
-  state: String,
+  pub state: String,
   #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename(serialize = "Flag"))]
-  flag: Option<String>,
+  pub flag: Option<String>,
   #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename(serialize = "Enabled"))]
-  enabled: Option<bool>,
+  pub enabled: Option<bool>,
   #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename(serialize = "SoftwareStatus"))]
-  software_status: Option<String>,
+  pub software_status: Option<String>,
   #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename(serialize = "Role"))]
-  role: Option<String>,
+  pub role: Option<String>,
   #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename(serialize = "SubRole"))]
-  sub_role: Option<String>,
+  pub sub_role: Option<String>,
   #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename(serialize = "NID"))]
-  nid: Option<usize>,
+  pub nid: Option<usize>,
   #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename(serialize = "Subtype"))]
-  subtype: Option<String>,
+  pub subtype: Option<String>,
   #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename(serialize = "NetType"))]
-  net_type: Option<String>,
+  pub net_type: Option<String>,
   #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename(serialize = "Arch"))]
-  arch: Option<String>,
+  pub arch: Option<String>,
   #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename(serialize = "Class"))]
-  class: Option<String>,
+  pub class: Option<String>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndComponentCreate> for ComponentCreate {
   fn from(value: FrontEndComponentCreate) -> Self {
     ComponentCreate {
@@ -322,6 +332,7 @@ impl From<FrontEndComponentCreate> for ComponentCreate {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndComponentCreate> for ComponentCreate {
   fn into(self) -> FrontEndComponentCreate {
     FrontEndComponentCreate {
@@ -348,3 +359,39 @@ pub struct ComponentPut {
   #[serde(rename(serialize = "Force"))]
   force: Option<bool>,
 }
+
+impl fmt::Display for Component {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let detail_vec: Vec<String> = [
+      self.r#type.clone(),
+      self.state.clone(),
+      self.role.clone(),
+      self.nid.map(|nid| format!("nid {}", nid)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    write!(
+      f,
+      "{} ({})",
+      self.id.as_deref().unwrap_or("unknown"),
+      detail_vec.join(", ")
+    )
+  }
+}
+
+impl fmt::Display for ComponentCreate {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let detail_vec: Vec<String> = [
+      Some(self.state.clone()),
+      self.role.clone(),
+      self.nid.map(|nid| format!("nid {}", nid)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    write!(f, "{} ({})", self.id, detail_vec.join(", "))
+  }
+}
@@ -0,0 +1,278 @@
+use std::{
+  collections::HashSet,
+  time::{Duration, Instant},
+};
+
+use futures_util::{stream, Stream, StreamExt};
+
+use crate::{
+  error::Error, http::ApiContext, node::utils::validate_xname_format,
+  utils::split_csv_line,
+};
+
+use super::{
+  client,
+  types::{
+    Component, ComponentArrayPostArray, ComponentArrayPostQuery,
+    ComponentCreate,
+  },
+};
+
+/// Columns expected in node list files, in order. Only the xname is mandatory
+const NODE_LIST_COLUMNS: [&str; 5] = ["xname", "nid", "role", "arch", "class"];
+
+/// Poll HSM until all nodes reach `target_state` (eg: Ready) or `timeout` expires.
+/// Returns the nodes which did not reach the state in time (stragglers), empty if all
+/// did. Nodes not found in HSM are considered stragglers
+pub async fn wait_for_state(
+  ctx: &ApiContext,
+  xname_vec: &[String],
+  target_state: &str,
+  timeout: Duration,
+  interval: Duration,
+) -> Result<Vec<String>, Error> {
+  let start = Instant::now();
+  let mut pending_vec: Vec<String> = xname_vec.to_vec();
+
+  while !pending_vec.is_empty() {
+    let reached_set: HashSet<String> = client::post_query(
+      ctx,
+      ComponentArrayPostQuery {
+        component_ids: Some(pending_vec.clone()),
+        state_only: Some(true),
+        ..Default::default()
+      },
+    )
+    .await?
+    .components
+    .unwrap_or_default()
+    .into_iter()
+    .filter(|component| {
+      component
+        .state
+        .as_deref()
+        .is_some_and(|state| state.eq_ignore_ascii_case(target_state))
+    })
+    .filter_map(|component| component.id)
+    .collect();
+
+    pending_vec.retain(|xname| !reached_set.contains(xname));
+
+    log::debug!(
+      "{} of {} nodes reached state '{}'",
+      xname_vec.len() - pending_vec.len(),
+      xname_vec.len(),
+      target_state
+    );
+
+    if pending_vec.is_empty() || start.elapsed() >= timeout {
+      break;
+    }
+
+    tokio::time::sleep(interval).await;
+  }
+
+  Ok(pending_vec)
+}
+
+/// Build the components to create from a node list in CSV or TSV format with columns
+/// (xname, nid, role, arch, class). The delimiter is a tab if the first line has one,
+/// a comma otherwise. Fields are split with `utils::split_csv_line`, like manifests.
+/// The first line can be a header naming the columns in any order, otherwise the
+/// columns are in the order above. Empty lines and lines starting with '#' are
+/// skipped. Components are created as Populated and enabled.
+/// All invalid rows are reported in the error, with their line number
+pub fn component_array_from_csv(
+  input: &str,
+) -> Result<ComponentArrayPostArray, Error> {
+  let mut line_iter = input
+    .lines()
+    .enumerate()
+    .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+    .peekable();
+
+  let delimiter = match line_iter.peek() {
+    Some((_, line)) if line.contains('\t') => '\t',
+    _ => ',',
+  };
+
+  let mut column_vec: Vec<String> = NODE_LIST_COLUMNS
+    .iter()
+    .map(|column| column.to_string())
+    .collect();
+
+  let mut component_vec = Vec::new();
+  let mut error_vec = Vec::new();
+  let mut xname_seen = HashSet::new();
+  let mut nid_seen = HashSet::new();
+  let mut first_line = true;
+
+  for (index, line) in line_iter {
+    let row = index + 1;
+
+    let field_vec = match split_csv_line(line, delimiter) {
+      Some(field_vec) => field_vec,
+      None => {
+        error_vec.push(format!("row {}: quoted field is not terminated", row));
+        continue;
+      }
+    };
+
+    let is_header = first_line
+      && field_vec
+        .iter()
+        .any(|field| field.eq_ignore_ascii_case(NODE_LIST_COLUMNS[0]));
+    first_line = false;
+
+    if is_header {
+      column_vec = field_vec.iter().map(|field| field.to_lowercase()).collect();
+
+      if let Some(column) = column_vec
+        .iter()
+        .find(|column| !NODE_LIST_COLUMNS.contains(&column.as_str()))
+      {
+        return Err(Error::Message(format!(
+          "Invalid node list:\nrow {}: unknown column '{}', valid columns: {}",
+          row,
+          column,
+          NODE_LIST_COLUMNS.join(", ")
+        )));
+      }
+
+      continue;
+    }
+
+    if field_vec.len() > column_vec.len() {
+      error_vec.push(format!(
+        "row {}: expected at most {} columns ({}), found {}",
+        row,
+        column_vec.len(),
+        column_vec.join(", "),
+        field_vec.len()
+      ));
+      continue;
+    }
+
+    let field = |column: &str| -> Option<String> {
+      column_vec
+        .iter()
+        .position(|name| name == column)
+        .and_then(|position| field_vec.get(position))
+        .filter(|value| !value.is_empty())
+        .cloned()
+    };
+
+    let xname = field("xname").unwrap_or_default();
+
+    if !validate_xname_format(&xname) {
+      error_vec.push(format!("row {}: invalid xname '{}'", row, xname));
+      continue;
+    }
+
+    if !xname_seen.insert(xname.clone()) {
+      error_vec.push(format!("row {}: duplicated xname '{}'", row, xname));
+      continue;
+    }
+
+    let nid = match field("nid").map(|nid| (nid.parse::<usize>(), nid)) {
+      Some((Ok(nid), _)) => Some(nid),
+      Some((Err(_), nid)) => {
+        error_vec.push(format!("row {}: invalid nid '{}'", row, nid));
+        continue;
+      }
+      None => None,
+    };
+
+    if let Some(nid) = nid {
+      if !nid_seen.insert(nid) {
+        error_vec.push(format!("row {}: duplicated nid '{}'", row, nid));
+        continue;
+      }
+    }
+
+    component_vec.push(ComponentCreate {
+      id: xname,
+      state: "Populated".to_string(),
+      flag: None,
+      enabled: Some(true),
+      software_status: None,
+      role: field("role"),
+      sub_role: None,
+      nid,
+      subtype: None,
+      net_type: None,
+      arch: field("arch"),
+      class: field("class"),
+    });
+  }
+
+  if !error_vec.is_empty() {
+    return Err(Error::Message(format!(
+      "Invalid node list:\n{}",
+      error_vec.join("\n")
+    )));
+  }
+
+  Ok(ComponentArrayPostArray {
+    components: component_vec,
+    force: None,
+  })
+}
+
+/// Stream the HSM components of a list of xnames. Components are requested in chunks of
+/// `chunk_size` xnames, the next chunk is only requested once the consumer has processed
+/// the previous one
+pub fn stream_components<'a>(
+  ctx: &'a ApiContext,
+  xname_vec: &[String],
+  chunk_size: usize,
+) -> impl Stream<Item = Result<Component, Error>> + 'a {
+  let xname_chunk_vec: Vec<Vec<String>> = xname_vec
+    .chunks(chunk_size.max(1))
+    .map(|xname_chunk| xname_chunk.to_vec())
+    .collect();
+
+  stream::iter(xname_chunk_vec)
+    .then(move |xname_chunk| async move {
+      client::post_query(
+        ctx,
+        ComponentArrayPostQuery {
+          component_ids: Some(xname_chunk),
+          ..Default::default()
+        },
+      )
+      .await
+    })
+    .flat_map(|component_array_rslt| {
+      let component_rslt_vec: Vec<Result<Component, Error>> =
+        match component_array_rslt {
+          Ok(component_array) => component_array
+            .components
+            .unwrap_or_default()
+            .into_iter()
+            .map(Ok)
+            .collect(),
+          Err(e) => vec![Err(e)],
+        };
+
+      stream::iter(component_rslt_vec)
+    })
+}
+
+/// Body to create `component` again, `None` if it has no id or state
+pub fn to_component_create(component: Component) -> Option<ComponentCreate> {
+  Some(ComponentCreate {
+    id: component.id?,
+    state: component.state?,
+    flag: component.flag,
+    enabled: component.enabled,
+    software_status: component.software_status,
+    role: component.role,
+    sub_role: component.sub_role,
+    nid: component.nid,
+    subtype: component.subtype,
+    net_type: component.net_type,
+    arch: component.arch,
+    class: component.class,
+  })
+}
@@ -0,0 +1,320 @@
+use serde_json::Value;
+
+use crate::{
+  error::Error,
+  hsm::{
+    group::types::Member,
+    types::{ActionResponse, ResourceUri},
+  },
+  http::{ApiContext, MutationRequest, RawResponse},
+};
+
+use super::types::{Group, Members};
+
+pub async fn get_all(ctx: &ApiContext) -> Result<Vec<Group>, Error> {
+  get(ctx, None, None).await
+}
+
+pub async fn get(
+  ctx: &ApiContext,
+  label_vec_opt: Option<&[String]>,
+  tag_vec_opt: Option<&[String]>,
+) -> Result<Vec<Group>, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/groups")?;
+
+  let mut query = Vec::new();
+  if let Some(label_vec) = label_vec_opt {
+    for label in label_vec {
+      query.push(("group", label));
+    }
+  }
+  if let Some(tag_vec) = tag_vec_opt {
+    for tag in tag_vec {
+      query.push(("tag", tag));
+    }
+  }
+
+  let response = client
+    .get(api_url)
+    .query(query.as_slice())
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn get_one(
+  ctx: &ApiContext,
+  group_label: &str,
+) -> Result<Group, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!("/groups/{}", group_label))?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+/// Same as `get_one` but returns the raw response, whatever its status
+pub async fn get_one_raw(
+  ctx: &ApiContext,
+  group_label: &str,
+) -> Result<RawResponse<Group>, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!("/groups/{}", group_label))?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  RawResponse::from_response(response).await
+}
+
+pub async fn get_labels(ctx: &ApiContext) -> Result<Vec<String>, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/groups/labels")?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn get_members(
+  ctx: &ApiContext,
+  group_label: &str,
+) -> Result<Members, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!("/groups/{}/members", group_label))?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn post(ctx: &ApiContext, group: Group) -> Result<String, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/groups")?;
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(&group)
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  crate::hsm::cache::invalidate_group(ctx, &group.label);
+
+  response
+    .text()
+    .await
+    .map_err(|e| Error::Message(e.to_string()))
+}
+
+pub async fn post_member(
+  ctx: &ApiContext,
+  group_label: &str,
+  member: Member,
+) -> Result<Vec<ResourceUri>, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!("/groups/{}/members", group_label))?;
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(&member)
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  crate::hsm::cache::invalidate_group(ctx, group_label);
+
+  response
+    .json()
+    .await
+    .map_err(|e| Error::Message(e.to_string()))
+}
+
+pub async fn delete_one(
+  ctx: &ApiContext,
+  group_label: &str,
+) -> Result<ActionResponse, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!("/groups/{}", group_label))?;
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  crate::hsm::cache::invalidate_group(ctx, group_label);
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn delete_member(
+  ctx: &ApiContext,
+  group_label: &str,
+  xname: &str,
+) -> Result<(), Error> {
+  let client = ctx.client()?;
+  let api_url =
+    ctx.hsm_url(&format!("/groups/{}/members/{}", group_label, xname))?;
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  crate::hsm::cache::invalidate_group(ctx, group_label);
+
+  Ok(())
+}
@@ -1,17 +1,22 @@
 use serde_json::Value;
 
-use crate::{error::Error, hsm::group::types::Member};
+use crate::{error::Error, http::ApiContext};
 
-use super::types::{Group, Members};
+use super::{
+  client,
+  types::{Group, Member, Members},
+};
 
+#[deprecated(note = "use `group::client::get_all` with an `ApiContext`")]
 pub async fn get_all(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
 ) -> Result<Vec<Group>, Error> {
-  get(base_url, auth_token, root_cert, None, None).await
+  client::get_all(&ApiContext::parse(base_url, auth_token, root_cert)?).await
 }
 
+#[deprecated(note = "use `group::client::get` with an `ApiContext`")]
 pub async fn get(
   base_url: &str,
   auth_token: &str,
@@ -19,175 +24,63 @@ pub async fn get(
   label_vec_opt: Option<&[String]>,
   tag_vec_opt: Option<&[String]>,
 ) -> Result<Vec<Group>, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!("{}/hsm/v2/groups", base_url);
-
-  let mut query = Vec::new();
-  if let Some(label_vec) = label_vec_opt {
-    for label in label_vec {
-      query.push(("group", label));
-    }
-  }
-  if let Some(tag_vec) = tag_vec_opt {
-    for tag in tag_vec {
-      query.push(("tag", tag));
-    }
-  }
-
-  let response = client
-    .get(api_url)
-    .query(query.as_slice())
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.text().await?;
-        return Err(Error::Message(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    label_vec_opt,
+    tag_vec_opt,
+  )
+  .await
 }
 
+#[deprecated(note = "use `group::client::get_one` with an `ApiContext`")]
 pub async fn get_one(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   group_label: &str,
 ) -> Result<Group, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!("{}/hsm/v2/groups/{}", base_url, group_label);
-
-  let response = client.get(api_url).bearer_auth(auth_token).send().await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get_one(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    group_label,
+  )
+  .await
 }
 
+#[deprecated(note = "use `group::client::get_labels` with an `ApiContext`")]
 pub async fn get_labels(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
 ) -> Result<Vec<String>, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!("{}/hsm/v2/groups/labels", base_url);
-
-  let response = client.get(api_url).bearer_auth(auth_token).send().await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get_labels(&ApiContext::parse(base_url, auth_token, root_cert)?).await
 }
 
+#[deprecated(note = "use `group::client::get_members` with an `ApiContext`")]
 pub async fn get_members(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   group_label: &str,
 ) -> Result<Members, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url =
-    format!("{}/hsm/v2/groups/{}/members", base_url, group_label);
-
-  let response = client.get(api_url).bearer_auth(auth_token).send().await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get_members(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    group_label,
+  )
+  .await
 }
 
+#[deprecated(note = "use `group::client::post` with an `ApiContext`")]
 pub async fn post(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   group: Group,
 ) -> Result<String, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!("{}/hsm/v2/groups", base_url);
-
-  let response = client
-    .post(api_url)
-    .bearer_auth(auth_token)
-    .json(&group)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response
-    .text()
+  client::post(&ApiContext::parse(base_url, auth_token, root_cert)?, group)
     .await
-    .map_err(|e| Error::Message(e.to_string()))
 }
 
+#[deprecated(note = "use `group::client::post_member` with an `ApiContext`")]
 pub async fn post_member(
   auth_token: &str,
   base_url: &str,
@@ -195,70 +88,33 @@ pub async fn post_member(
   group_label: &str,
   member: Member,
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url =
-    format!("{}/hsm/v2/groups/{}/members", base_url, group_label);
-
-  let response = client
-    .post(api_url)
-    .bearer_auth(auth_token)
-    .json(&member)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.text().await?;
-        return Err(Error::Message(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(|e| Error::Message(e.to_string()))
+  let uri_vec = client::post_member(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    group_label,
+    member,
+  )
+  .await?;
+
+  Ok(serde_json::to_value(uri_vec)?)
 }
 
+#[deprecated(note = "use `group::client::delete_one` with an `ApiContext`")]
 pub async fn delete_one(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   group_label: &str,
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!("{}/hsm/v2/groups/{}", base_url, group_label);
-
-  let response = client
-    .delete(api_url)
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
+  let response = client::delete_one(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    group_label,
+  )
+  .await?;
 
-  response.json().await.map_err(Error::NetError)
+  Ok(serde_json::to_value(response)?)
 }
 
+#[deprecated(note = "use `group::client::delete_member` with an `ApiContext`")]
 pub async fn delete_member(
   base_url: &str,
   auth_token: &str,
@@ -266,33 +122,10 @@ pub async fn delete_member(
   group_label: &str,
   xname: &str,
 ) -> Result<(), Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!(
-    "{}/hsm/v2/groups/{}/members/{}",
-    base_url, group_label, xname
-  );
-
-  let response = client
-    .delete(api_url)
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  Ok(())
+  client::delete_member(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    group_label,
+    xname,
+  )
+  .await
 }
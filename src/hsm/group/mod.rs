@@ -1,3 +1,4 @@
+pub mod client;
 pub mod hacks;
 pub mod http_client;
 #[cfg(test)]
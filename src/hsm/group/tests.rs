@@ -1,4 +1,14 @@
-use crate::hsm::group::types::Group;
+use crate::{
+  error::Error,
+  hsm::group::{
+    types::Group,
+    utils::{
+      expand_group_members, filter_by_hsm_group_and_convert_to_map,
+      filter_by_hsm_group_members_and_convert_to_map, filter_groups_by_pattern,
+      GroupPattern,
+    },
+  },
+};
 
 #[test]
 fn test_add_xnames() {
@@ -18,3 +28,566 @@ fn test_add_xnames() {
     ]
   )
 }
+
+#[test]
+fn test_group_builder() {
+  let xnames = vec!["x1000c0s0b0n0".to_string(), "x1000c0s0b0n1".to_string()];
+
+  let group = Group::builder()
+    .label("blue")
+    .description("blue partition")
+    .members(&xnames)
+    .tags(["prod"])
+    .build();
+
+  assert_eq!(group.label, "blue");
+  assert_eq!(group.description.as_deref(), Some("blue partition"));
+  assert_eq!(group.tags, Some(vec!["prod".to_string()]));
+  assert_eq!(group.get_members(), xnames);
+  assert_eq!(
+    Group::with_members("blue", &xnames).get_members(),
+    group.get_members()
+  );
+}
+
+#[test]
+fn test_expand_group_members_nested() {
+  let mut rack = Group::new("compute-rack1", Some(vec!["x1000c0s0b0n0"]));
+  rack.tags = Some(vec!["include:compute-rack2".to_string()]);
+  let rack2 = Group::new("compute-rack2", Some(vec!["x1000c0s1b0n0"]));
+  let mut compute = Group::new("compute", Some(vec!["x1000c0s0b0n0"]));
+  compute.tags = Some(vec![
+    "include:compute-rack1".to_string(),
+    "include:compute-rack2".to_string(),
+    "other-tag".to_string(),
+  ]);
+
+  let members = expand_group_members(&[compute, rack, rack2], "compute");
+
+  assert_eq!(
+    members.unwrap(),
+    vec!["x1000c0s0b0n0".to_string(), "x1000c0s1b0n0".to_string()]
+  )
+}
+
+#[test]
+fn test_expand_group_members_cycle() {
+  let mut a = Group::new("a", Some(vec!["x1000c0s0b0n0"]));
+  a.tags = Some(vec!["include:b".to_string()]);
+  let mut b = Group::new("b", Some(vec!["x1000c0s1b0n0"]));
+  b.tags = Some(vec!["include:a".to_string()]);
+
+  assert!(expand_group_members(&[a, b], "a").is_err())
+}
+
+#[test]
+fn test_filter_groups_by_pattern() {
+  let group_vec = vec![
+    Group::new("gpu-a100", None),
+    Group::new("gpu-h100", None),
+    Group::new("compute-rack1", None),
+    Group::new("compute-rack2", None),
+  ];
+
+  let labels = |pattern: GroupPattern| -> Vec<String> {
+    filter_groups_by_pattern(group_vec.clone(), &pattern)
+      .unwrap()
+      .into_iter()
+      .map(|group| group.label)
+      .collect()
+  };
+
+  assert_eq!(
+    labels(GroupPattern::Regex("gpu-.*".to_string())),
+    vec!["gpu-a100", "gpu-h100"]
+  );
+  assert_eq!(
+    labels(GroupPattern::Glob("compute-*".to_string())),
+    vec!["compute-rack1", "compute-rack2"]
+  );
+  assert_eq!(
+    labels(GroupPattern::Hostlist("compute-rack[2-3]".to_string())),
+    vec!["compute-rack2"]
+  );
+}
+
+#[test]
+fn test_filter_by_hsm_group_empty_group() {
+  let group_vec = vec![Group::new("compute", Some(Vec::new()))];
+
+  let hsm_group_map =
+    filter_by_hsm_group_and_convert_to_map(&["compute"], &group_vec).unwrap();
+
+  assert_eq!(hsm_group_map.get("compute"), Some(&Vec::new()));
+}
+
+#[test]
+fn test_filter_by_hsm_group_missing_members() {
+  let group_vec = vec![Group::new("compute", None)];
+
+  assert!(matches!(
+    filter_by_hsm_group_and_convert_to_map(&["compute"], &group_vec),
+    Err(Error::MissingField(_))
+  ));
+}
+
+#[test]
+fn test_filter_by_hsm_group_members_no_members() {
+  let group_vec = vec![
+    Group::new("compute", None),
+    Group::new("gpu", Some(vec!["x1000c0s0b0n0"])),
+  ];
+
+  let hsm_group_map = filter_by_hsm_group_members_and_convert_to_map(
+    &["x1000c0s0b0n0"],
+    group_vec,
+  );
+
+  assert_eq!(hsm_group_map.len(), 1);
+  assert_eq!(
+    hsm_group_map.get("gpu"),
+    Some(&vec!["x1000c0s0b0n0".to_string()])
+  );
+}
+
+#[tokio::test]
+async fn test_get_all_from_mock() {
+  let mock = crate::test_util::MockOchami::with_fixtures().await;
+
+  let group_vec = crate::hsm::group::client::get_all(&mock.context())
+    .await
+    .unwrap();
+
+  assert_eq!(group_vec.len(), crate::fixtures::groups().len());
+}
+
+#[tokio::test]
+async fn test_get_all_unauthorized_keeps_payload() {
+  let mock = crate::test_util::MockOchami::start().await;
+  mock
+    .mock_error(
+      crate::test_util::MockService::Hsm,
+      "GET",
+      "/groups",
+      401,
+      "token expired",
+    )
+    .await;
+
+  let rslt = crate::hsm::group::client::get_all(&mock.context()).await;
+
+  match rslt {
+    Err(Error::RequestError { response, payload }) => {
+      assert_eq!(response.status(), Some(reqwest::StatusCode::UNAUTHORIZED));
+      assert_eq!(payload, "token expired");
+    }
+    other => panic!("Expected a request error, got {:?}", other),
+  }
+}
+
+#[tokio::test]
+async fn test_get_all_uses_context_service_paths() {
+  let service_paths = crate::service_paths::ServicePaths {
+    hsm: "/smd/hsm/v2".to_string(),
+    ..Default::default()
+  };
+  let mock = crate::test_util::MockOchami::start()
+    .await
+    .with_service_paths(service_paths);
+  mock
+    .mock_json(
+      crate::test_util::MockService::Hsm,
+      "GET",
+      "/groups",
+      crate::fixtures::GROUPS_JSON,
+    )
+    .await;
+
+  let group_vec = crate::hsm::group::client::get_all(&mock.context())
+    .await
+    .unwrap();
+  assert_eq!(group_vec.len(), crate::fixtures::groups().len());
+
+  // A context with the default paths is not affected by the other context
+  let default_ctx = crate::http::ApiContext::parse(
+    &mock.base_url(),
+    crate::test_util::MOCK_AUTH_TOKEN,
+    crate::test_util::MOCK_ROOT_CERT,
+  )
+  .unwrap();
+  assert!(crate::hsm::group::client::get_all(&default_ctx)
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn test_timeout_is_per_context() {
+  let mock = crate::test_util::MockOchami::start().await;
+  wiremock::Mock::given(wiremock::matchers::method("GET"))
+    .respond_with(
+      wiremock::ResponseTemplate::new(200)
+        .set_body_string(crate::fixtures::GROUPS_JSON)
+        .insert_header("content-type", "application/json")
+        .set_delay(std::time::Duration::from_millis(500)),
+    )
+    .mount(mock.server())
+    .await;
+
+  let ctx = mock.context();
+  let short_timeout_ctx = ctx.clone().with_http_client(
+    ctx
+      .http_client
+      .clone()
+      .with_timeout(Some(std::time::Duration::from_millis(50))),
+  );
+
+  let rslt = crate::hsm::group::client::get_all(&short_timeout_ctx).await;
+  assert!(matches!(rslt, Err(Error::NetError(e)) if e.is_timeout()));
+
+  // The timeout of the other context does not apply to this one
+  assert!(crate::hsm::group::client::get_all(&ctx).await.is_ok());
+}
+
+#[tokio::test]
+#[cfg(feature = "backend-connector")]
+async fn test_dry_run_sends_no_write() {
+  use manta_backend_dispatcher::interfaces::hsm::group::GroupTrait;
+
+  let mock = crate::test_util::MockOchami::start().await;
+  wiremock::Mock::given(wiremock::matchers::any())
+    .respond_with(wiremock::ResponseTemplate::new(200))
+    .expect(0)
+    .mount(mock.server())
+    .await;
+
+  let ctx = mock
+    .context()
+    .with_execution_mode(crate::http::ExecutionMode::DryRun);
+  let result = crate::hsm::group::client::delete_one(&ctx, "blue").await;
+  assert!(matches!(result, Err(Error::DryRun(_))));
+
+  let ochami = crate::backend_connector::Ochami::new(
+    &mock.base_url(),
+    crate::test_util::MOCK_ROOT_CERT,
+  )
+  .with_execution_mode(crate::http::ExecutionMode::DryRun);
+  assert!(ochami
+    .delete_group(crate::test_util::MOCK_AUTH_TOKEN, "blue")
+    .await
+    .is_err());
+
+  assert!(mock.server().received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_add_members_posts_one_member_per_request() {
+  let mock = crate::test_util::MockOchami::start().await;
+  mock
+    .mock(
+      crate::test_util::MockService::Hsm,
+      "GET",
+      "/groups/compute",
+      200,
+      Some(serde_json::json!({
+        "label": "compute",
+        "members": {"ids": ["x1000c0s0b0n0"]}
+      })),
+    )
+    .await;
+  mock
+    .mock(
+      crate::test_util::MockService::Hsm,
+      "POST",
+      "/groups/compute/members",
+      201,
+      Some(serde_json::json!([{"URI": "/hsm/v2/groups/compute/members/x"}])),
+    )
+    .await;
+
+  let member_vec = crate::hsm::group::utils::add_members(
+    &mock.context(),
+    "compute",
+    &[
+      "x1000c0s0b0n0",
+      "x1000c0s0b0n1",
+      "x1000c0s0b0n2",
+      "x1000c0s0b0n1",
+    ],
+  )
+  .await
+  .unwrap();
+  assert_eq!(
+    member_vec,
+    vec!["x1000c0s0b0n0", "x1000c0s0b0n1", "x1000c0s0b0n2"]
+  );
+
+  let mut body_vec: Vec<serde_json::Value> = mock
+    .server()
+    .received_requests()
+    .await
+    .unwrap()
+    .into_iter()
+    .filter(|request| request.method.as_str() == "POST")
+    .map(|request| request.body_json().unwrap())
+    .collect();
+  body_vec.sort_by_key(|body| body.to_string());
+  assert_eq!(
+    body_vec,
+    vec![
+      serde_json::json!({"id": "x1000c0s0b0n1"}),
+      serde_json::json!({"id": "x1000c0s0b0n2"})
+    ]
+  );
+}
+
+#[tokio::test]
+async fn test_get_member_vec_from_hsm_name_vec_single_query() {
+  let mock = crate::test_util::MockOchami::start().await;
+  mock
+    .mock_json(
+      crate::test_util::MockService::Hsm,
+      "GET",
+      "/groups",
+      crate::fixtures::GROUPS_JSON,
+    )
+    .await;
+
+  let member_vec = crate::hsm::group::utils::get_member_vec_from_hsm_name_vec(
+    &mock.context(),
+    &[
+      "management".to_string(),
+      "compute".to_string(),
+      "management".to_string(),
+    ],
+  )
+  .await
+  .unwrap();
+  assert_eq!(
+    member_vec,
+    vec!["x3000c0s1b0n0", "x1000c0s0b0n0", "x1000c0s0b0n1"]
+  );
+
+  let request_vec = mock.server().received_requests().await.unwrap();
+  assert_eq!(request_vec.len(), 1);
+  assert_eq!(
+    request_vec[0].url.query(),
+    Some("group=management&group=compute")
+  );
+}
+
+#[tokio::test]
+async fn test_get_member_vec_from_hsm_name_vec_missing_group() {
+  let mock = crate::test_util::MockOchami::start().await;
+  mock
+    .mock_json(
+      crate::test_util::MockService::Hsm,
+      "GET",
+      "/groups",
+      crate::fixtures::GROUPS_JSON,
+    )
+    .await;
+  let hsm_name_vec = ["compute".to_string(), "comptue".to_string()];
+
+  let fetch =
+    crate::hsm::group::utils::get_member_vec_from_hsm_name_vec_with_settings(
+      &mock.context(),
+      &hsm_name_vec,
+      &crate::hsm::group::utils::MemberFetchSettings {
+        fail_fast: false,
+        ..Default::default()
+      },
+    )
+    .await
+    .unwrap();
+  assert_eq!(fetch.member_vec, vec!["x1000c0s0b0n0", "x1000c0s0b0n1"]);
+  assert_eq!(fetch.failed_vec.len(), 1);
+  assert_eq!(fetch.failed_vec[0].0, "comptue");
+  assert!(matches!(
+    &fetch.failed_vec[0].1,
+    Error::OchamiError(body) if body["status"] == 404
+  ));
+
+  let error =
+    crate::hsm::group::utils::get_member_vec_from_hsm_name_vec_with_settings(
+      &mock.context(),
+      &hsm_name_vec,
+      &crate::hsm::group::utils::MemberFetchSettings::default(),
+    )
+    .await
+    .unwrap_err();
+  assert!(error
+    .to_string()
+    .contains("HSM group(s) not found: comptue"));
+}
+
+#[tokio::test]
+#[allow(deprecated)]
+async fn test_positional_wrapper_forwards_to_client() {
+  let mock = crate::test_util::MockOchami::with_fixtures().await;
+  let ctx = mock.context();
+
+  let group_vec = crate::hsm::group::http_client::get_all(
+    ctx.base_url.as_str(),
+    "positional-token",
+    &[],
+  )
+  .await
+  .unwrap();
+  assert_eq!(group_vec.len(), 2);
+
+  let request_vec = mock.server().received_requests().await.unwrap();
+  assert_eq!(
+    request_vec[0].headers["authorization"].to_str().unwrap(),
+    "Bearer positional-token"
+  );
+}
+
+fn get_request_count(request_vec: &[wiremock::Request]) -> usize {
+  request_vec
+    .iter()
+    .filter(|request| request.method.as_str() == "GET")
+    .count()
+}
+
+#[tokio::test]
+async fn test_cache_serves_members_until_expiry() {
+  let mock = crate::test_util::MockOchami::start().await;
+  mock
+    .mock(
+      crate::test_util::MockService::Hsm,
+      "GET",
+      "/groups/compute/members",
+      200,
+      Some(serde_json::json!({"ids": ["x1000c0s0b0n0"]})),
+    )
+    .await;
+
+  // wiremock reuses its servers across tests, the token keeps the cache entries apart
+  let ctx = mock
+    .context()
+    .with_auth_token("test_cache_serves_members_until_expiry")
+    .with_cache_ttl(Some(std::time::Duration::from_millis(200)));
+
+  for _ in 0..2 {
+    let members = crate::hsm::cache::get_members(&ctx, "compute")
+      .await
+      .unwrap();
+    assert_eq!(members.ids, Some(vec!["x1000c0s0b0n0".to_string()]));
+  }
+
+  let request_vec = mock.server().received_requests().await.unwrap();
+  assert_eq!(get_request_count(&request_vec), 1);
+
+  tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+
+  crate::hsm::cache::get_members(&ctx, "compute")
+    .await
+    .unwrap();
+
+  let request_vec = mock.server().received_requests().await.unwrap();
+  assert_eq!(get_request_count(&request_vec), 2);
+}
+
+#[tokio::test]
+async fn test_cache_is_not_shared_between_tokens() {
+  let mock = crate::test_util::MockOchami::start().await;
+  mock
+    .mock_json(
+      crate::test_util::MockService::Hsm,
+      "GET",
+      "/groups",
+      crate::fixtures::GROUPS_JSON,
+    )
+    .await;
+
+  let ctx = mock
+    .context()
+    .with_auth_token("test_cache_is_not_shared_between_tokens")
+    .with_cache_ttl(Some(std::time::Duration::from_secs(60)));
+
+  crate::hsm::cache::get_all_groups(&ctx).await.unwrap();
+  crate::hsm::cache::get_all_groups(&ctx.with_auth_token("other-token"))
+    .await
+    .unwrap();
+
+  let request_vec = mock.server().received_requests().await.unwrap();
+  assert_eq!(get_request_count(&request_vec), 2);
+  assert_eq!(
+    request_vec[1].headers["authorization"].to_str().unwrap(),
+    "Bearer other-token"
+  );
+}
+
+#[tokio::test]
+async fn test_cache_evicts_rotated_token() {
+  let mock = crate::test_util::MockOchami::start().await;
+  mock
+    .mock_json(
+      crate::test_util::MockService::Hsm,
+      "GET",
+      "/groups",
+      crate::fixtures::GROUPS_JSON,
+    )
+    .await;
+
+  let ctx = mock
+    .context()
+    .with_auth_token("test_cache_evicts_rotated_token")
+    .with_cache_ttl(Some(std::time::Duration::from_secs(60)));
+  let rotated_ctx = ctx.with_auth_token("rotated-token");
+
+  crate::hsm::cache::get_all_groups(&ctx).await.unwrap();
+  crate::hsm::cache::get_all_groups(&rotated_ctx)
+    .await
+    .unwrap();
+  crate::hsm::cache::get_all_groups(&rotated_ctx)
+    .await
+    .unwrap();
+
+  // The entry of the previous token was dropped when the rotated one was cached
+  crate::hsm::cache::get_all_groups(&ctx).await.unwrap();
+
+  let request_vec = mock.server().received_requests().await.unwrap();
+  assert_eq!(get_request_count(&request_vec), 3);
+}
+
+#[tokio::test]
+async fn test_group_mutation_invalidates_cache() {
+  let mock = crate::test_util::MockOchami::start().await;
+  mock
+    .mock(
+      crate::test_util::MockService::Hsm,
+      "GET",
+      "/groups/compute/members",
+      200,
+      Some(serde_json::json!({"ids": ["x1000c0s0b0n0", "x1000c0s0b0n1"]})),
+    )
+    .await;
+  mock
+    .mock(
+      crate::test_util::MockService::Hsm,
+      "DELETE",
+      "/groups/compute/members/x1000c0s0b0n1",
+      200,
+      None,
+    )
+    .await;
+
+  let ctx = mock
+    .context()
+    .with_auth_token("test_group_mutation_invalidates_cache")
+    .with_cache_ttl(Some(std::time::Duration::from_secs(60)));
+
+  crate::hsm::cache::get_members(&ctx, "compute")
+    .await
+    .unwrap();
+  crate::hsm::group::client::delete_member(&ctx, "compute", "x1000c0s0b0n1")
+    .await
+    .unwrap();
+  crate::hsm::cache::get_members(&ctx, "compute")
+    .await
+    .unwrap();
+
+  let request_vec = mock.server().received_requests().await.unwrap();
+  assert_eq!(get_request_count(&request_vec), 2);
+}
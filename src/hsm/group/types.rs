@@ -1,15 +1,24 @@
+use std::{collections::HashMap, fmt};
+
+#[cfg(feature = "backend-connector")]
 use manta_backend_dispatcher::types::{
   Group as FrontEndGroup, Member as FrontEndMember,
 };
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+/// Tag prefix used to reference another HSM group from a group's tags, eg:
+/// `include:compute-rack1`
+pub const INCLUDE_TAG_PREFIX: &str = "include:";
+
+#[derive(
+  Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq, Hash,
+)]
 pub struct Member {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub id: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Group {
   pub label: String,
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -19,16 +28,29 @@ pub struct Group {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub members: Option<Members>,
   #[serde(skip_serializing_if = "Option::is_none")]
-  #[serde(rename(serialize = "exclusiveGroup"))]
+  #[serde(rename(serialize = "exclusiveGroup"), alias = "exclusiveGroup")]
   pub exclusive_group: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[derive(
+  Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq, Hash,
+)]
 pub struct Members {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub ids: Option<Vec<String>>,
 }
 
+/// Health overview of a HSM group. Members are counted by the State, Role and Arch of their
+/// SMD component, members without a component in SMD are counted as `Unknown`
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+pub struct GroupSummary {
+  pub label: String,
+  pub member_count: usize,
+  pub by_state: HashMap<String, usize>,
+  pub by_role: HashMap<String, usize>,
+  pub by_arch: HashMap<String, usize>,
+}
+
 impl Group {
   pub fn new(label: &str, member_vec_opt: Option<Vec<&str>>) -> Self {
     let members_opt = if let Some(member_vec) = member_vec_opt {
@@ -50,6 +72,15 @@ impl Group {
     group
   }
 
+  /// HSM group with the given members and no description nor tags
+  pub fn with_members(label: &str, xname_vec: &[String]) -> Self {
+    Self::builder().label(label).members(xname_vec).build()
+  }
+
+  pub fn builder() -> GroupBuilder {
+    GroupBuilder::default()
+  }
+
   /// Get HSM group members
   pub fn get_members(&self) -> Vec<String> {
     // FIXME: try to improve this logic by introducing "smart pointers" or "lifetimes"
@@ -69,6 +100,23 @@ impl Group {
       .and_then(|members| members.ids.clone())
   }
 
+  /// Get labels of the HSM groups referenced by this group through
+  /// `include:<label>` tags
+  pub fn get_included_group_labels(&self) -> Vec<String> {
+    self
+      .tags
+      .as_ref()
+      .map(|tags| {
+        tags
+          .iter()
+          .filter_map(|tag| tag.strip_prefix(INCLUDE_TAG_PREFIX))
+          .map(|label| label.trim().to_string())
+          .filter(|label| !label.is_empty())
+          .collect()
+      })
+      .unwrap_or_default()
+  }
+
   /// Add list of xnames to HSM group members
   pub fn add_xnames(&mut self, xnames: &[String]) -> Vec<String> {
     self.members.as_mut().and_then(|members| {
@@ -82,6 +130,73 @@ impl Group {
   }
 }
 
+/// Builder for [`Group`], eg:
+/// `Group::builder().label("blue").members(&xnames).tags(["prod"]).build()`
+#[derive(Debug, Default, Clone)]
+pub struct GroupBuilder {
+  label: String,
+  description: Option<String>,
+  tags: Option<Vec<String>>,
+  members: Option<Vec<String>>,
+  exclusive_group: Option<String>,
+}
+
+impl GroupBuilder {
+  pub fn label(mut self, label: &str) -> Self {
+    self.label = label.to_string();
+    self
+  }
+
+  pub fn description(mut self, description: &str) -> Self {
+    self.description = Some(description.to_string());
+    self
+  }
+
+  pub fn members<I, S>(mut self, xnames: I) -> Self
+  where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+  {
+    self.members = Some(
+      xnames
+        .into_iter()
+        .map(|xname| xname.as_ref().to_string())
+        .collect(),
+    );
+    self
+  }
+
+  pub fn tags<I, S>(mut self, tags: I) -> Self
+  where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+  {
+    self.tags = Some(
+      tags
+        .into_iter()
+        .map(|tag| tag.as_ref().to_string())
+        .collect(),
+    );
+    self
+  }
+
+  pub fn exclusive_group(mut self, exclusive_group: &str) -> Self {
+    self.exclusive_group = Some(exclusive_group.to_string());
+    self
+  }
+
+  pub fn build(self) -> Group {
+    Group {
+      label: self.label,
+      description: self.description,
+      tags: self.tags,
+      members: self.members.map(|ids| Members { ids: Some(ids) }),
+      exclusive_group: self.exclusive_group,
+    }
+  }
+}
+
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndGroup> for Group {
   fn from(value: FrontEndGroup) -> Self {
     let mut member_vec = Vec::new();
@@ -105,6 +220,7 @@ impl From<FrontEndGroup> for Group {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndGroup> for Group {
   fn into(self) -> FrontEndGroup {
     let mut member_vec = Vec::new();
@@ -127,3 +243,9 @@ impl Into<FrontEndGroup> for Group {
     }
   }
 }
+
+impl fmt::Display for Group {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} ({} members)", self.label, self.get_members().len())
+  }
+}
@@ -1,32 +1,33 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+use futures_util::{stream, Stream, StreamExt};
+use regex::Regex;
 
 use crate::{
   error::Error,
-  hsm::{self, group::types::Member},
-  node::utils::validate_xnames_format_and_membership_agaisnt_single_hsm,
+  hsm::{self, cache, group::types::Member},
+  http::{ApiContext, ExecutionMode},
+  node::{types::Xname, utils::validate_xnames_format_and_membership},
+  progress::{NoProgress, ProgressEvent, ProgressSink},
+  utils::{for_each_item, for_each_node, summarize_failures},
 };
 
-use crate::hsm::group::{http_client, types::Group};
+use crate::hsm::group::types::{Group, GroupSummary};
+
+use super::client::{delete_member, post_member};
 
-use super::http_client::{delete_member, post_member};
+/// Max number of concurrent requests sent to SMD when updating group members
+const MEMBER_UPDATE_CONCURRENCY: usize = 10;
 
 /// Add a list of xnames to target HSM group
 /// Returns the new list of nodes in target HSM group
-pub async fn add_member(
-  auth_token: &str,
-  base_url: &str,
-  root_cert: &[u8],
+pub async fn add_member_to_group(
+  ctx: &ApiContext,
   group_label: &str,
   new_member: &str,
 ) -> Result<Vec<String>, Error> {
   // Get HSM group from CSM
-  let group = crate::hsm::group::http_client::get_one(
-    base_url,
-    auth_token,
-    root_cert,
-    group_label,
-  )
-  .await?;
+  let group = crate::hsm::group::client::get_one(ctx, group_label).await?;
 
   // Update HSM group with new memebers
   // Create Member struct
@@ -36,14 +37,7 @@ pub async fn add_member(
   };
 
   // Update HSM group in CSM
-  let _ = crate::hsm::group::http_client::post_member(
-    auth_token,
-    base_url,
-    root_cert,
-    group_label,
-    member,
-  )
-  .await?;
+  crate::hsm::group::client::post_member(ctx, group_label, member).await?;
 
   // Generate list of updated group members
   group.get_members().push(new_member);
@@ -51,63 +45,595 @@ pub async fn add_member(
   Ok(group.get_members())
 }
 
+#[deprecated(note = "use `add_member_to_group` with an `ApiContext`")]
+pub async fn add_member(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  group_label: &str,
+  new_member: &str,
+) -> Result<Vec<String>, Error> {
+  add_member_to_group(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    group_label,
+    new_member,
+  )
+  .await
+}
+
+/// Add a list of xnames to target HSM group. Xnames already in the group are skipped
+/// Returns the new list of nodes in target HSM group
+pub async fn add_members(
+  ctx: &ApiContext,
+  group_label: &str,
+  new_member_vec: &[&str],
+) -> Result<Vec<String>, Error> {
+  let group = crate::hsm::group::client::get_one(ctx, group_label).await?;
+
+  let mut member_vec = group.get_members();
+
+  let mut seen_set: HashSet<&str> =
+    member_vec.iter().map(String::as_str).collect();
+  let xname_to_add_vec: Vec<String> = new_member_vec
+    .iter()
+    .filter(|xname| seen_set.insert(xname))
+    .map(|xname| xname.to_string())
+    .collect();
+
+  if xname_to_add_vec.is_empty() {
+    return Ok(member_vec);
+  }
+
+  post_member_vec(ctx, group_label, &xname_to_add_vec).await?;
+
+  member_vec.extend(xname_to_add_vec);
+
+  Ok(member_vec)
+}
+
+/// Add a list of xnames to target HSM group without reading the group first, for callers
+/// which already know the delta. Xnames are expected not to be members yet. If `verify`
+/// is set, the members of the group are read back once to check the xnames were added
+pub async fn add_members_delta(
+  ctx: &ApiContext,
+  group_label: &str,
+  new_member_vec: &[&str],
+  verify: bool,
+) -> Result<(), Error> {
+  if new_member_vec.is_empty() {
+    return Ok(());
+  }
+
+  let xname_vec: Vec<String> = new_member_vec
+    .iter()
+    .map(|xname| xname.to_string())
+    .collect();
+
+  post_member_vec(ctx, group_label, &xname_vec).await?;
+
+  if verify {
+    verify_members(ctx, group_label, new_member_vec, &[]).await?;
+  }
+
+  Ok(())
+}
+
+/// Add xnames to a HSM group. SMD takes a single member per request, requests run
+/// concurrently and are all sent even if some fail
+async fn post_member_vec(
+  ctx: &ApiContext,
+  group_label: &str,
+  xname_vec: &[String],
+) -> Result<(), Error> {
+  let mut failure_vec: Vec<(String, Error)> =
+    for_each_item(xname_vec, MEMBER_UPDATE_CONCURRENCY, |xname| async move {
+      post_member(ctx, group_label, Member { id: Some(xname) }).await
+    })
+    .await
+    .into_iter()
+    .filter_map(|(xname, result)| result.err().map(|e| (xname, e)))
+    .collect();
+
+  if failure_vec.is_empty() {
+    return Ok(());
+  }
+
+  failure_vec.sort_by(|a, b| a.0.cmp(&b.0));
+
+  Err(Error::Message(format!(
+    "Could not add members to HSM group '{}':\n{}",
+    group_label,
+    failure_vec
+      .iter()
+      .map(|(xname, e)| format!("{}: {}", xname, e))
+      .collect::<Vec<String>>()
+      .join("\n")
+  )))
+}
+
+/// Check with a single read of the group members that `present_vec` are members of the
+/// HSM group and `absent_vec` are not
+pub async fn verify_members(
+  ctx: &ApiContext,
+  group_label: &str,
+  present_vec: &[&str],
+  absent_vec: &[&str],
+) -> Result<(), Error> {
+  let member_vec = crate::hsm::group::client::get_members(ctx, group_label)
+    .await?
+    .ids
+    .unwrap_or_default();
+  let member_set: HashSet<&str> =
+    member_vec.iter().map(String::as_str).collect();
+
+  let missing_vec: Vec<&str> = present_vec
+    .iter()
+    .filter(|xname| !member_set.contains(**xname))
+    .copied()
+    .collect();
+  let unexpected_vec: Vec<&str> = absent_vec
+    .iter()
+    .filter(|xname| member_set.contains(**xname))
+    .copied()
+    .collect();
+
+  if missing_vec.is_empty() && unexpected_vec.is_empty() {
+    return Ok(());
+  }
+
+  Err(Error::Message(format!(
+    "Members of HSM group '{}' do not match the update. Missing: [{}]. Still present: [{}]",
+    group_label,
+    missing_vec.join(", "),
+    unexpected_vec.join(", ")
+  )))
+}
+
+/// Settings to control how the members of multiple HSM groups are fetched from SMD
+#[derive(Debug, Clone)]
+pub struct MemberFetchSettings {
+  /// Max number of concurrent requests sent to SMD by `stream_member_vec_from_hsm_name_vec`.
+  /// Not used by `get_member_vec_from_hsm_name_vec_with_settings`, which sends a single
+  /// request
+  pub pipe_size: usize,
+  /// If true, any HSM group failing fails the whole operation. Otherwise, the members of the
+  /// remaining HSM groups are returned along with the HSM groups which failed
+  pub fail_fast: bool,
+}
+
+impl Default for MemberFetchSettings {
+  fn default() -> Self {
+    Self {
+      pipe_size: 10,
+      fail_fast: true,
+    }
+  }
+}
+
+/// Members of a list of HSM groups, along with the HSM groups which could not be fetched
+#[derive(Debug, Default)]
+pub struct GroupMemberFetch {
+  /// Members of the HSM groups fetched, following the order of the requested HSM groups
+  pub member_vec: Vec<String>,
+  /// HSM groups which could not be fetched, with the reason
+  pub failed_vec: Vec<(String, Error)>,
+}
+
+impl GroupMemberFetch {
+  /// The members, or an error listing every HSM group which could not be fetched. A
+  /// single failed HSM group returns its original error
+  pub fn into_result(mut self) -> Result<Vec<String>, Error> {
+    match self.failed_vec.len() {
+      0 => Ok(self.member_vec),
+      1 => Err(self.failed_vec.remove(0).1),
+      _ => Err(group_fetch_error(&self.failed_vec)),
+    }
+  }
+}
+
+fn group_fetch_error(failed_vec: &[(String, Error)]) -> Error {
+  Error::Message(
+    failed_vec
+      .iter()
+      .map(|(hsm_name, e)| {
+        format!(
+          "Could not get members of HSM group '{}'. Reason:\n{}",
+          hsm_name, e
+        )
+      })
+      .collect::<Vec<String>>()
+      .join("\n"),
+  )
+}
+
+pub async fn get_member_vec_from_hsm_name_vec(
+  ctx: &ApiContext,
+  hsm_name_vec: &[String],
+) -> Result<Vec<String>, Error> {
+  get_member_vec_from_hsm_name_vec_with_settings(
+    ctx,
+    hsm_name_vec,
+    &MemberFetchSettings::default(),
+  )
+  .await?
+  .into_result()
+}
+
+#[deprecated(
+  note = "use `get_member_vec_from_hsm_name_vec` with an `ApiContext`"
+)]
 pub async fn get_member_vec_from_hsm_name_vec_2(
   auth_token: &str,
   base_url: &str,
   root_cert: &[u8],
   hsm_name_vec: &[String],
 ) -> Result<Vec<String>, Error> {
+  get_member_vec_from_hsm_name_vec(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    hsm_name_vec,
+  )
+  .await
+}
+
+/// Get the members of a list of HSM groups with a single request to SMD, HSM groups listed
+/// more than once are only requested once. Members are returned following the order of
+/// the HSM groups in `hsm_name_vec`. HSM groups missing from the response fail with a
+/// 404 `Error::OchamiError`. If the request or any HSM group
+/// fails and `settings.fail_fast` is false, the HSM groups are returned in
+/// `GroupMemberFetch::failed_vec` instead of an error. `settings.pipe_size` is not used
+pub async fn get_member_vec_from_hsm_name_vec_with_settings(
+  ctx: &ApiContext,
+  hsm_name_vec: &[String],
+  settings: &MemberFetchSettings,
+) -> Result<GroupMemberFetch, Error> {
   log::info!("Get xnames for HSM groups: {:?}", hsm_name_vec);
 
-  let hsm_group_name_vec: Vec<String> = hsm_name_vec.to_vec();
+  let mut seen = HashSet::new();
+  let hsm_name_vec: Vec<String> = hsm_name_vec
+    .iter()
+    .filter(|hsm_name| seen.insert(hsm_name.as_str()))
+    .cloned()
+    .collect();
 
-  let group_vec = http_client::get(
-    base_url,
-    auth_token,
-    root_cert,
-    Some(&hsm_group_name_vec),
-    None,
-  )
-  .await
-  .map_err(|e| Error::Message(e.to_string()))?;
+  let mut fetch = GroupMemberFetch::default();
 
-  let hsm_group_member_vec: Vec<String> = group_vec
+  let group_vec = match super::client::get(ctx, Some(&hsm_name_vec), None).await
+  {
+    Ok(group_vec) => group_vec,
+    Err(e) if settings.fail_fast => return Err(e),
+    Err(e) => {
+      fetch.failed_vec = hsm_name_vec
+        .iter()
+        .map(|hsm_name| (hsm_name.clone(), e.duplicate()))
+        .collect();
+      return Ok(fetch);
+    }
+  };
+
+  let mut missing_vec: Vec<&str> = Vec::new();
+
+  for hsm_name in &hsm_name_vec {
+    match group_vec.iter().find(|group| &group.label == hsm_name) {
+      Some(group) => fetch.member_vec.extend(group.get_members()),
+      None => missing_vec.push(hsm_name),
+    }
+  }
+
+  if !missing_vec.is_empty() && settings.fail_fast {
+    return Err(group_not_found_error(&missing_vec));
+  }
+
+  fetch.failed_vec = missing_vec
     .into_iter()
-    .flat_map(|group| group.get_members())
+    .map(|hsm_name| (hsm_name.to_string(), group_not_found_error(&[hsm_name])))
     .collect();
 
-  Ok(hsm_group_member_vec)
+  Ok(fetch)
+}
+
+/// Error for HSM groups SMD did not return, shaped like the problem details SMD sends
+/// with a 404 so it maps to a not found error downstream
+fn group_not_found_error(hsm_name_vec: &[&str]) -> Error {
+  Error::OchamiError(serde_json::json!({
+    "type": "about:blank",
+    "title": "Not Found",
+    "detail": format!("HSM group(s) not found: {}", hsm_name_vec.join(", ")),
+    "status": 404,
+  }))
+}
+
+/// Stream the members of a list of HSM groups, as (group label, members), following the
+/// order of `hsm_name_vec`. Up to `settings.pipe_size` requests run ahead of the consumer.
+/// `settings.fail_fast` is ignored, each group yields its own result
+pub fn stream_member_vec_from_hsm_name_vec<'a>(
+  ctx: &'a ApiContext,
+  hsm_name_vec: &'a [String],
+  settings: &MemberFetchSettings,
+) -> impl Stream<Item = Result<(String, Vec<String>), Error>> + 'a {
+  stream::iter(hsm_name_vec)
+    .map(move |hsm_name| async move {
+      cache::get_members(ctx, hsm_name)
+        .await
+        .map(|members| (hsm_name.clone(), members.ids.unwrap_or_default()))
+        .map_err(|e| {
+          Error::Message(format!(
+            "Could not get members of HSM group '{}'. Reason:\n{}",
+            hsm_name, e
+          ))
+        })
+    })
+    .buffered(settings.pipe_size.max(1))
+}
+
+/// Get the flat list of members of a HSM group, resolving nested groups referenced through
+/// `include:<label>` tags
+pub async fn get_expanded_member_vec(
+  ctx: &ApiContext,
+  group_label: &str,
+) -> Result<Vec<String>, Error> {
+  let group_vec = cache::get_all_groups(ctx).await?;
+
+  expand_group_members(&group_vec, group_label)
+}
+
+/// Given a list of HSM groups, returns the members of `group_label` plus the members of all
+/// the groups it includes (recursively) through `include:<label>` tags. Members are sorted
+/// and deduplicated.
+/// Returns an error if a referenced group does not exist or if the includes form a cycle
+pub fn expand_group_members(
+  group_vec: &[Group],
+  group_label: &str,
+) -> Result<Vec<String>, Error> {
+  let group_map: HashMap<&str, &Group> = group_vec
+    .iter()
+    .map(|group| (group.label.as_str(), group))
+    .collect();
+
+  let mut member_set: HashSet<String> = HashSet::new();
+  let mut visited: HashSet<String> = HashSet::new();
+  let mut path: Vec<String> = Vec::new();
+
+  expand_group_members_rec(
+    &group_map,
+    group_label,
+    &mut path,
+    &mut visited,
+    &mut member_set,
+  )?;
+
+  let mut member_vec: Vec<String> = member_set.into_iter().collect();
+  member_vec.sort();
+
+  Ok(member_vec)
+}
+
+fn expand_group_members_rec(
+  group_map: &HashMap<&str, &Group>,
+  group_label: &str,
+  path: &mut Vec<String>,
+  visited: &mut HashSet<String>,
+  member_set: &mut HashSet<String>,
+) -> Result<(), Error> {
+  if path.iter().any(|label| label == group_label) {
+    path.push(group_label.to_string());
+    return Err(Error::Message(format!(
+      "Cycle detected while expanding HSM group includes: {}",
+      path.join(" -> ")
+    )));
+  }
+
+  // Group already expanded through another include path
+  if visited.contains(group_label) {
+    return Ok(());
+  }
+
+  let group = group_map.get(group_label).ok_or_else(|| {
+    Error::Message(format!("HSM group '{}' not found", group_label))
+  })?;
+
+  path.push(group_label.to_string());
+
+  member_set.extend(group.get_members());
+
+  for included_label in group.get_included_group_labels() {
+    expand_group_members_rec(
+      group_map,
+      &included_label,
+      path,
+      visited,
+      member_set,
+    )?;
+  }
+
+  path.pop();
+  visited.insert(group_label.to_string());
+
+  Ok(())
+}
+
+/// Pattern used to select HSM groups by label
+#[derive(Debug, Clone)]
+pub enum GroupPattern {
+  /// Regular expression matched against the whole label, eg: `gpu-.*`
+  Regex(String),
+  /// Shell like glob supporting `*` and `?`, eg: `gpu-*`
+  Glob(String),
+  /// Hostlist expression, eg: `compute-rack[1-4]`
+  Hostlist(String),
+}
+
+impl GroupPattern {
+  /// Convert the pattern into a regex matching the whole HSM group label
+  pub fn to_regex(&self) -> Result<Regex, Error> {
+    let regex_str = match self {
+      GroupPattern::Regex(pattern) => format!("^(?:{})$", pattern),
+      GroupPattern::Glob(pattern) => {
+        let regex_body: String = pattern
+          .chars()
+          .map(|c| match c {
+            '*' => ".*".to_string(),
+            '?' => ".".to_string(),
+            _ => regex::escape(&c.to_string()),
+          })
+          .collect();
+
+        format!("^{}$", regex_body)
+      }
+      GroupPattern::Hostlist(pattern) => {
+        let label_vec = hostlist_parser::parse(pattern).map_err(|e| {
+          Error::Message(format!(
+            "Could not parse '{}' as a hostlist. Reason:\n{}",
+            pattern, e
+          ))
+        })?;
+
+        format!(
+          "^(?:{})$",
+          label_vec
+            .iter()
+            .map(|label| regex::escape(label))
+            .collect::<Vec<String>>()
+            .join("|")
+        )
+      }
+    };
+
+    Regex::new(&regex_str).map_err(|e| Error::Message(e.to_string()))
+  }
+}
+
+/// Get the HSM groups which label matches `pattern`
+pub async fn select_groups(
+  ctx: &ApiContext,
+  pattern: &GroupPattern,
+) -> Result<Vec<Group>, Error> {
+  let group_vec = cache::get_all_groups(ctx).await?;
+
+  filter_groups_by_pattern(group_vec, pattern)
+}
+
+/// Given a list of HSM groups, returns those which label matches `pattern`
+pub fn filter_groups_by_pattern(
+  group_vec: Vec<Group>,
+  pattern: &GroupPattern,
+) -> Result<Vec<Group>, Error> {
+  let regex = pattern.to_regex()?;
+
+  Ok(
+    group_vec
+      .into_iter()
+      .filter(|group| regex.is_match(&group.label))
+      .collect(),
+  )
+}
+
+/// Get a summary of a HSM group with the number of members grouped by component State, Role
+/// and Arch
+pub async fn summarize(
+  ctx: &ApiContext,
+  group_label: &str,
+) -> Result<GroupSummary, Error> {
+  let member_vec = cache::get_members(ctx, group_label)
+    .await?
+    .ids
+    .unwrap_or_default();
+
+  let component_vec = hsm::component::client::get(
+    ctx,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    Some(group_label),
+    None,
+    None,
+    None,
+    None,
+  )
+  .await?
+  .components
+  .unwrap_or_default();
+
+  let component_map: HashMap<&str, &hsm::component::types::Component> =
+    component_vec
+      .iter()
+      .filter_map(|component| component.id.as_deref().map(|id| (id, component)))
+      .collect();
+
+  let mut group_summary = GroupSummary {
+    label: group_label.to_string(),
+    member_count: member_vec.len(),
+    ..Default::default()
+  };
+
+  let unknown = "Unknown".to_string();
+
+  for member in &member_vec {
+    let component_opt = component_map.get(member.as_str());
+
+    let state = component_opt
+      .and_then(|component| component.state.clone())
+      .unwrap_or(unknown.clone());
+    let role = component_opt
+      .and_then(|component| component.role.clone())
+      .unwrap_or(unknown.clone());
+    let arch = component_opt
+      .and_then(|component| component.arch.clone())
+      .unwrap_or(unknown.clone());
+
+    *group_summary.by_state.entry(state).or_default() += 1;
+    *group_summary.by_role.entry(role).or_default() += 1;
+    *group_summary.by_arch.entry(arch).or_default() += 1;
+  }
+
+  Ok(group_summary)
 }
 
 // Returns a HashMap with keys being the hsm names/labels the user has access a curated list of xnames
 // for each hsm name as values
+pub async fn get_group_map_filtered_by_label_vec(
+  ctx: &ApiContext,
+  hsm_name_vec: &[&str],
+) -> Result<HashMap<String, Vec<String>>, Error> {
+  let hsm_group_vec = cache::get_all_groups(ctx).await?;
+
+  filter_by_hsm_group_and_convert_to_map(hsm_name_vec, &hsm_group_vec)
+}
+
+#[deprecated(
+  note = "use `get_group_map_filtered_by_label_vec` with an `ApiContext`"
+)]
 pub async fn get_hsm_map_and_filter_by_hsm_name_vec(
   auth_token: &str,
   base_url: &str,
   root_cert: &[u8],
   hsm_name_vec: &[&str],
 ) -> Result<HashMap<String, Vec<String>>, Error> {
-  let hsm_group_vec = http_client::get_all(base_url, auth_token, root_cert)
-    .await
-    .map_err(|e| Error::Message(e.to_string()))?;
-
-  Ok(filter_by_hsm_group_and_convert_to_map(
+  get_group_map_filtered_by_label_vec(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
     hsm_name_vec,
-    &hsm_group_vec,
-  ))
+  )
+  .await
 }
 
 // Returns a HashMap with keys being the hsm names/labels the user has access a curated list of xnames
 // for each hsm name as values
-pub async fn get_hsm_group_map_and_filter_by_hsm_group_member_vec(
-  shasta_token: &str,
-  shasta_base_url: &str,
-  shasta_root_cert: &[u8],
+pub async fn get_group_map_filtered_by_member_vec(
+  ctx: &ApiContext,
   hsm_name_vec: &[&str],
 ) -> Result<HashMap<String, Vec<String>>, Error> {
-  let hsm_group_vec =
-    http_client::get_all(shasta_base_url, shasta_token, shasta_root_cert)
-      .await?;
+  let hsm_group_vec = cache::get_all_groups(ctx).await?;
 
   Ok(filter_by_hsm_group_members_and_convert_to_map(
     hsm_name_vec,
@@ -115,27 +641,48 @@ pub async fn get_hsm_group_map_and_filter_by_hsm_group_member_vec(
   ))
 }
 
+#[deprecated(
+  note = "use `get_group_map_filtered_by_member_vec` with an `ApiContext`"
+)]
+pub async fn get_hsm_group_map_and_filter_by_hsm_group_member_vec(
+  shasta_token: &str,
+  shasta_base_url: &str,
+  shasta_root_cert: &[u8],
+  hsm_name_vec: &[&str],
+) -> Result<HashMap<String, Vec<String>>, Error> {
+  get_group_map_filtered_by_member_vec(
+    &ApiContext::parse(shasta_base_url, shasta_token, shasta_root_cert)?,
+    hsm_name_vec,
+  )
+  .await
+}
+
 /// Given a list of HsmGroup struct and a list of Hsm group names, it will filter out those
-/// not in the Hsm group names and convert from HsmGroup struct to HashMap
+/// not in the Hsm group names and convert from HsmGroup struct to HashMap.
+/// Fails if a selected group has no `members` field
 pub fn filter_by_hsm_group_and_convert_to_map(
   hsm_name_vec: &[&str],
   hsm_group_vec: &[Group],
-) -> HashMap<String, Vec<String>> {
+) -> Result<HashMap<String, Vec<String>>, Error> {
   let mut hsm_group_map: HashMap<String, Vec<String>> = HashMap::new();
 
   for hsm_group in hsm_group_vec {
     if hsm_name_vec.contains(&hsm_group.label.as_str()) {
-      hsm_group_map.entry(hsm_group.label.clone()).or_insert(
-        hsm_group
-          .members
-          .clone()
-          .and_then(|members| Some(members.ids.unwrap_or_default()))
-          .unwrap(),
-      );
+      let member_vec = hsm_group
+        .members
+        .as_ref()
+        .map(|members| members.ids.clone().unwrap_or_default())
+        .ok_or_else(|| {
+          Error::MissingField(format!("{}.members", hsm_group.label))
+        })?;
+
+      hsm_group_map
+        .entry(hsm_group.label.clone())
+        .or_insert(member_vec);
     }
   }
 
-  hsm_group_map
+  Ok(hsm_group_map)
 }
 
 /// Given a list of HsmGroup struct and a list of Hsm group members, it will filter out those
@@ -152,9 +699,8 @@ pub fn filter_by_hsm_group_members_and_convert_to_map(
       .iter()
       .any(|member| hsm_group_member_vec.contains(&member.as_str()))
     {
-      hsm_group_map
-        .entry(hsm_group.label)
-        .or_insert(hsm_group.members.and_then(|members| members.ids).unwrap());
+      let member_vec = hsm_group.get_members();
+      hsm_group_map.entry(hsm_group.label).or_insert(member_vec);
     }
   }
 
@@ -162,6 +708,61 @@ pub fn filter_by_hsm_group_members_and_convert_to_map(
 }
 
 /// Receives 2 lists of xnames old xnames to remove from parent HSM group and new xhanges to add to target HSM group, and does just that
+/// Every xname is processed, the error returned lists all the xnames which failed
+pub async fn update_group_members(
+  ctx: &ApiContext,
+  group_label: &str,
+  group_members_to_delete: &[&str],
+  group_members_to_add: &[&str],
+) -> Result<(), Error> {
+  let xname_to_delete_vec = group_members_to_delete
+    .iter()
+    .map(|xname| xname.parse::<Xname>())
+    .collect::<Result<Vec<Xname>, Error>>()?;
+
+  let xname_to_add_vec = group_members_to_add
+    .iter()
+    .map(|xname| xname.parse::<Xname>())
+    .collect::<Result<Vec<Xname>, Error>>()?;
+
+  let delete_result_map =
+    for_each_node(&xname_to_delete_vec, MEMBER_UPDATE_CONCURRENCY, |xname| {
+      let xname = xname.to_string();
+      async move { delete_member(ctx, group_label, &xname).await }
+    })
+    .await;
+
+  let add_result_map =
+    for_each_node(&xname_to_add_vec, MEMBER_UPDATE_CONCURRENCY, |xname| {
+      let member = Member {
+        id: Some(xname.to_string()),
+      };
+      async move { post_member(ctx, group_label, member).await }
+    })
+    .await;
+
+  let failure_vec: Vec<String> = [
+    summarize_failures(&delete_result_map)
+      .map(|failures| format!("Could not remove members:\n{}", failures)),
+    summarize_failures(&add_result_map)
+      .map(|failures| format!("Could not add members:\n{}", failures)),
+  ]
+  .into_iter()
+  .flatten()
+  .collect();
+
+  if failure_vec.is_empty() {
+    Ok(())
+  } else {
+    Err(Error::Message(format!(
+      "Could not update members of HSM group '{}'.\n{}",
+      group_label,
+      failure_vec.join("\n")
+    )))
+  }
+}
+
+#[deprecated(note = "use `update_group_members` with an `ApiContext`")]
 pub async fn update_hsm_group_members(
   auth_token: &str,
   base_url: &str,
@@ -170,27 +771,59 @@ pub async fn update_hsm_group_members(
   group_members_to_delete: &[&str],
   group_members_to_add: &[&str],
 ) -> Result<(), Error> {
-  let group = hsm::group::http_client::get_one(
-    base_url,
-    auth_token,
-    root_cert,
+  update_group_members(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
     group_label,
+    group_members_to_delete,
+    group_members_to_add,
   )
-  .await?;
-
-  let mut group_members = group.members.unwrap().ids.unwrap();
+  .await
+}
 
-  group_members
-    .retain(|xname| group_members_to_delete.contains(&xname.as_str()));
+/// Same as `update_group_members`, then reads the group members back once to check
+/// the update was applied
+pub async fn update_hsm_group_members_and_verify(
+  ctx: &ApiContext,
+  group_label: &str,
+  group_members_to_delete: &[&str],
+  group_members_to_add: &[&str],
+) -> Result<(), Error> {
+  update_group_members(
+    ctx,
+    group_label,
+    group_members_to_delete,
+    group_members_to_add,
+  )
+  .await?;
 
-  for xname in group_members_to_add {
-    group_members.push(xname.to_string());
-  }
+  verify_members(
+    ctx,
+    group_label,
+    group_members_to_add,
+    group_members_to_delete,
+  )
+  .await
+}
 
-  Ok(())
+/// Moves list of xnames from parent to target HSM group. If `ctx` is in dry-run the
+/// resulting HSM groups are only logged
+pub async fn migrate_group_members(
+  ctx: &ApiContext,
+  target_hsm_group_name: &str,
+  parent_hsm_group_name: &str,
+  new_target_hsm_members: &[&str],
+) -> Result<(Vec<String>, Vec<String>), Error> {
+  migrate_hsm_members_with_progress(
+    ctx,
+    target_hsm_group_name,
+    parent_hsm_group_name,
+    new_target_hsm_members,
+    &NoProgress,
+  )
+  .await
 }
 
-/// Moves list of xnames from parent to target HSM group
+#[deprecated(note = "use `migrate_group_members` with an `ApiContext`")]
 pub async fn migrate_hsm_members(
   shasta_token: &str,
   shasta_base_url: &str,
@@ -200,30 +833,48 @@ pub async fn migrate_hsm_members(
   new_target_hsm_members: &[&str],
   nodryrun: bool,
 ) -> Result<(Vec<String>, Vec<String>), Error> {
-  // Check nodes are valid xnames and they belong to parent HSM group
-  if !validate_xnames_format_and_membership_agaisnt_single_hsm(
-    shasta_token,
-    shasta_base_url,
-    shasta_root_cert,
+  migrate_group_members(
+    &ApiContext::parse(shasta_base_url, shasta_token, shasta_root_cert)?
+      .with_execution_mode(ExecutionMode::from(!nodryrun)),
+    target_hsm_group_name,
+    parent_hsm_group_name,
     new_target_hsm_members,
-    Some(&parent_hsm_group_name.to_string()),
   )
   .await
-  {
-    let error_msg =
-      format!("Nodes '{}' not valid", new_target_hsm_members.join(", "));
+}
+
+/// Same as `migrate_group_members`, reporting each node moved to `progress`
+pub async fn migrate_hsm_members_with_progress(
+  ctx: &ApiContext,
+  target_hsm_group_name: &str,
+  parent_hsm_group_name: &str,
+  new_target_hsm_members: &[&str],
+  progress: &dyn ProgressSink,
+) -> Result<(Vec<String>, Vec<String>), Error> {
+  // Check nodes are valid xnames and they belong to parent HSM group
+  let failed_vec = validate_xnames_format_and_membership(
+    ctx,
+    new_target_hsm_members,
+    &[parent_hsm_group_name.to_string()],
+  )
+  .await?;
+
+  if !failed_vec.is_empty() {
+    let error_msg = format!(
+      "Nodes not valid: {}",
+      failed_vec
+        .iter()
+        .map(|(xname, reason)| format!("{} ({})", xname, reason))
+        .collect::<Vec<String>>()
+        .join(", ")
+    );
     return Err(Error::Message(error_msg));
   }
 
   // get list of target HSM group members
   let mut target_hsm_group_member_vec: Vec<String> =
-    get_member_vec_from_hsm_name_vec_2(
-      shasta_token,
-      shasta_base_url,
-      shasta_root_cert,
-      &[target_hsm_group_name.to_string()],
-    )
-    .await?;
+    get_member_vec_from_hsm_name_vec(ctx, &[target_hsm_group_name.to_string()])
+      .await?;
 
   // merge HSM group list with the list of xnames provided by the user
   target_hsm_group_member_vec
@@ -234,13 +885,8 @@ pub async fn migrate_hsm_members(
 
   // get list of parent HSM group members
   let mut parent_hsm_group_member_vec: Vec<String> =
-    get_member_vec_from_hsm_name_vec_2(
-      shasta_token,
-      shasta_base_url,
-      shasta_root_cert,
-      &[parent_hsm_group_name.to_string()],
-    )
-    .await?;
+    get_member_vec_from_hsm_name_vec(ctx, &[parent_hsm_group_name.to_string()])
+      .await?;
 
   parent_hsm_group_member_vec.retain(|parent_member| {
     !target_hsm_group_member_vec.contains(parent_member)
@@ -251,57 +897,153 @@ pub async fn migrate_hsm_members(
 
   // *********************************************************************************************************
   // UPDATE HSM GROUP MEMBERS IN CSM
-  if !nodryrun {
-    let target_hsm_group = serde_json::json!({
-        "label": target_hsm_group_name,
-        "decription": "",
-        "members": target_hsm_group_member_vec,
-        "tags": []
-    });
-
-    println!(
+  if ctx.execution_mode.is_dry_run() {
+    let target_hsm_group =
+      Group::with_members(target_hsm_group_name, &target_hsm_group_member_vec);
+
+    log::info!(
       "Target HSM group:\n{}",
-      serde_json::to_string_pretty(&target_hsm_group).unwrap()
+      serde_json::to_string_pretty(&target_hsm_group)?
     );
 
-    let parent_hsm_group = serde_json::json!({
-        "label": parent_hsm_group_name,
-        "decription": "",
-        "members": parent_hsm_group_member_vec,
-        "tags": []
-    });
+    let parent_hsm_group =
+      Group::with_members(parent_hsm_group_name, &parent_hsm_group_member_vec);
 
-    println!(
+    log::info!(
       "Parent HSM group:\n{}",
-      serde_json::to_string_pretty(&parent_hsm_group).unwrap()
+      serde_json::to_string_pretty(&parent_hsm_group)?
     );
 
-    println!("dry-run enabled, changes not persisted.");
+    log::info!("dry-run enabled, changes not persisted.");
   } else {
-    for xname in new_target_hsm_members {
-      let member = Member {
-        id: Some(xname.to_string()),
-      };
+    move_members_between_groups(
+      ctx,
+      parent_hsm_group_name,
+      target_hsm_group_name,
+      new_target_hsm_members,
+      progress,
+    )
+    .await?;
+  }
+
+  Ok((target_hsm_group_member_vec, parent_hsm_group_member_vec))
+}
+
+/// Moves xnames one by one from parent to target HSM group. If any move fails, the moves
+/// already done are rolled back so nodes don't end up in both or neither HSM group.
+/// The error returned describes the xname that failed and the outcome of the rollback
+async fn move_members_between_groups(
+  ctx: &ApiContext,
+  parent_hsm_group_name: &str,
+  target_hsm_group_name: &str,
+  xname_vec: &[&str],
+  progress: &dyn ProgressSink,
+) -> Result<(), Error> {
+  let mut moved_xname_vec: Vec<&str> = Vec::new();
+
+  progress.report(ProgressEvent::Started {
+    operation: "move HSM group members",
+    total: xname_vec.len(),
+  });
 
-      let _ = post_member(
-        shasta_token,
-        shasta_base_url,
-        shasta_root_cert,
+  for xname in xname_vec {
+    let move_rslt =
+      move_member(ctx, parent_hsm_group_name, target_hsm_group_name, xname)
+        .await;
+
+    if let Err(e) = move_rslt {
+      progress.report(ProgressEvent::ItemFailed {
+        item: xname,
+        reason: &e.to_string(),
+      });
+      progress.report(ProgressEvent::Finished {
+        completed: moved_xname_vec.len(),
+        failed: 1,
+      });
+
+      log::error!(
+        "Could not move node '{}' from HSM group '{}' to '{}'. Rolling back {} node(s) already moved",
+        xname,
+        parent_hsm_group_name,
         target_hsm_group_name,
-        member,
-      )
-      .await;
-
-      let _ = delete_member(
-        shasta_token,
-        shasta_base_url,
-        shasta_root_cert,
+        moved_xname_vec.len()
+      );
+
+      let mut rollback_failed_vec: Vec<String> = Vec::new();
+
+      for moved_xname in moved_xname_vec.iter().rev() {
+        if let Err(rollback_error) = move_member(
+          ctx,
+          target_hsm_group_name,
+          parent_hsm_group_name,
+          moved_xname,
+        )
+        .await
+        {
+          rollback_failed_vec
+            .push(format!("{}: {}", moved_xname, rollback_error));
+        }
+      }
+
+      let rollback_summary = if rollback_failed_vec.is_empty() {
+        format!("{} node(s) rolled back", moved_xname_vec.len())
+      } else {
+        format!(
+          "rollback failed for node(s):\n{}",
+          rollback_failed_vec.join("\n")
+        )
+      };
+
+      return Err(Error::Message(format!(
+        "Could not move node '{}' from HSM group '{}' to '{}'. Reason:\n{}\n{}",
+        xname,
         parent_hsm_group_name,
+        target_hsm_group_name,
+        e,
+        rollback_summary
+      )));
+    }
+
+    progress.report(ProgressEvent::ItemCompleted { item: xname });
+
+    moved_xname_vec.push(xname);
+  }
+
+  progress.report(ProgressEvent::Finished {
+    completed: moved_xname_vec.len(),
+    failed: 0,
+  });
+
+  Ok(())
+}
+
+/// Moves a single xname from one HSM group to another. If the xname can't be removed from
+/// the origin HSM group, it is also removed from the destination HSM group
+async fn move_member(
+  ctx: &ApiContext,
+  from_hsm_group_name: &str,
+  to_hsm_group_name: &str,
+  xname: &str,
+) -> Result<(), Error> {
+  let member = Member {
+    id: Some(xname.to_string()),
+  };
+
+  post_member(ctx, to_hsm_group_name, member).await?;
+
+  if let Err(e) = delete_member(ctx, from_hsm_group_name, xname).await {
+    if let Err(undo_error) = delete_member(ctx, to_hsm_group_name, xname).await
+    {
+      log::error!(
+        "Could not remove node '{}' from HSM group '{}'. Reason:\n{}",
         xname,
-      )
-      .await;
+        to_hsm_group_name,
+        undo_error
+      );
     }
+
+    return Err(e);
   }
 
-  Ok((target_hsm_group_member_vec, parent_hsm_group_member_vec))
+  Ok(())
 }
@@ -0,0 +1,34 @@
+use crate::{error::Error, http::ApiContext};
+
+use super::types::ComponentEndpointArray;
+
+pub async fn get_all(
+  ctx: &ApiContext,
+) -> Result<ComponentEndpointArray, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/Inventory/ComponentEndpoints")?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+use crate::hsm::inventory::types::MacAddress;
+
+/// Component discovered through a redfish endpoint
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct ComponentEndpoint {
+  #[serde(rename = "ID")]
+  pub id: String,
+  #[serde(rename = "Type")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub r#type: Option<String>,
+  #[serde(rename = "RedfishType")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub redfish_type: Option<String>,
+  #[serde(rename = "RedfishSubtype")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub redfish_subtype: Option<String>,
+  #[serde(rename = "MACAddr")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(default, deserialize_with = "MacAddress::deserialize_lenient")]
+  pub mac_addr: Option<MacAddress>,
+  #[serde(rename = "UUID")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub uuid: Option<String>,
+  #[serde(rename = "RedfishEndpointID")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub redfish_endpoint_id: Option<String>,
+  #[serde(rename = "RedfishEndpointFQDN")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub redfish_endpoint_fqdn: Option<String>,
+  #[serde(rename = "RedfishURL")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub redfish_url: Option<String>,
+  #[serde(rename = "ComponentEndpointType")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub component_endpoint_type: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct ComponentEndpointArray {
+  #[serde(rename = "ComponentEndpoints")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub component_endpoints: Option<Vec<ComponentEndpoint>>,
+}
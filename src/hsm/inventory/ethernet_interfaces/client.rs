@@ -0,0 +1,411 @@
+use serde_json::Value;
+
+use crate::{
+  error::Error,
+  hsm::types::ActionResponse,
+  http::{ApiContext, MutationRequest},
+};
+
+use super::types::{
+  ComponentEthernetInterface, EthernetInterfaceQuery, IpAddressMapping,
+};
+
+pub async fn post(
+  ctx: &ApiContext,
+  eht_interface: ComponentEthernetInterface,
+) -> Result<(), Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/Inventory/EthernetInterfaces")?;
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(&eht_interface)
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        log::debug!("{}", error_payload);
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+/// Add an IP address to an ethernet interface
+pub async fn post_ip_addresses(
+  ctx: &ApiContext,
+  eth_interface_id: &str,
+  ip_address_mapping: &IpAddressMapping,
+) -> Result<Value, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!(
+    "/Inventory/EthernetInterfaces/{}/IPAddresses",
+    eth_interface_id
+  ))?;
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(ip_address_mapping)
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn get(
+  ctx: &ApiContext,
+  mac_address: Option<&str>,
+  ip_address: Option<&str>,
+  network: Option<&str>,
+  component_id: Option<&str>,
+  r#type: Option<&str>,
+  older_than: Option<&str>,
+  newer_than: Option<&str>,
+) -> Result<Vec<ComponentEthernetInterface>, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/Inventory/EthernetInterfaces")?;
+
+  let response = client
+    .get(api_url)
+    .query(&[
+      ("MACAddress", mac_address),
+      ("IPAddress", ip_address),
+      ("Network", network),
+      ("ComponentID", component_id),
+      ("Type", r#type),
+      ("OlderThan", older_than),
+      ("NewerThan", newer_than),
+    ])
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+/// Get a page of at most `limit` ethernet interfaces starting at `offset`. SMD versions
+/// without paging support ignore `limit` and `offset` and return every interface
+pub async fn get_page(
+  ctx: &ApiContext,
+  query: &EthernetInterfaceQuery,
+  limit: usize,
+  offset: usize,
+) -> Result<Vec<ComponentEthernetInterface>, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/Inventory/EthernetInterfaces")?;
+
+  let limit = limit.to_string();
+  let offset = offset.to_string();
+
+  let response = client
+    .get(api_url)
+    .query(&[
+      ("MACAddress", query.mac_address.as_deref()),
+      ("IPAddress", query.ip_address.as_deref()),
+      ("Network", query.network.as_deref()),
+      ("ComponentID", query.component_id.as_deref()),
+      ("Type", query.r#type.as_deref()),
+      ("OlderThan", query.older_than.as_deref()),
+      ("NewerThan", query.newer_than.as_deref()),
+      ("limit", Some(limit.as_str())),
+      ("offset", Some(offset.as_str())),
+    ])
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn get_one(
+  ctx: &ApiContext,
+  eth_interface_id: &str,
+) -> Result<ComponentEthernetInterface, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!(
+    "/Inventory/EthernetInterfaces/{}",
+    eth_interface_id
+  ))?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn patch(
+  ctx: &ApiContext,
+  eth_interface_id: &str,
+  description: Option<&str>,
+  ip_address_mapping: (&str, &str),
+) -> Result<Value, Error> {
+  let ip_address = ip_address_mapping.0;
+  let network = ip_address_mapping.1;
+  let cei = ComponentEthernetInterface {
+    id: None,
+    description: description.map(|value| value.to_string()),
+    mac_address: None,
+    ip_addresses: Some(vec![IpAddressMapping {
+      ip_address: ip_address.to_string(),
+      network: Some(network.to_string()),
+    }]),
+    last_update: None,
+    component_id: Some(eth_interface_id.to_string()),
+    parent_hms_type: None,
+  };
+
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!(
+    "/Inventory/EthernetInterfaces/{}",
+    eth_interface_id
+  ))?;
+
+  let response = client
+    .patch(api_url)
+    .query(&[("ethInterfaceID", ip_address), ("ipAddress", ip_address)])
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(&cei)
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn delete_all(ctx: &ApiContext) -> Result<ActionResponse, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/Inventory/EthernetInterfaces")?;
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn delete_one(
+  ctx: &ApiContext,
+  eth_interface_id: &str,
+) -> Result<ActionResponse, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!(
+    "/Inventory/EthernetInterfaces/{}",
+    eth_interface_id
+  ))?;
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn get_ip_addresses(
+  ctx: &ApiContext,
+  eth_interface_id: &str,
+) -> Result<Vec<IpAddressMapping>, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!(
+    "/Inventory/EthernetInterfaces/{}/IPAddresses",
+    eth_interface_id
+  ))?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+/// Remove an IP address from an ethernet interface
+pub async fn delete_ip_address(
+  ctx: &ApiContext,
+  eth_interface_id: &str,
+  ip_address: &str,
+) -> Result<Value, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!(
+    "/Inventory/EthernetInterfaces/{}/IPAddresses/{}",
+    eth_interface_id, ip_address
+  ))?;
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
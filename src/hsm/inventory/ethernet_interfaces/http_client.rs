@@ -1,84 +1,58 @@
 use serde_json::Value;
 
-use crate::error::Error;
+use crate::{error::Error, http::ApiContext};
 
-use super::types::{ComponentEthernetInterface, IpAddressMapping};
+use super::{
+  client,
+  types::{ComponentEthernetInterface, IpAddressMapping},
+};
 
+#[deprecated(
+  note = "use `ethernet_interfaces::client::post` with an `ApiContext`"
+)]
 pub async fn post(
   auth_token: &str,
   base_url: &str,
   root_cert: &[u8],
   eht_interface: ComponentEthernetInterface,
 ) -> Result<(), Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!("{}/hsm/v2/Inventory/EthernetInterfaces", base_url);
-
-  let response = client
-    .post(api_url)
-    .bearer_auth(auth_token)
-    .json(&eht_interface)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.text().await?;
-        dbg!(&error_payload);
-        return Err(Error::Message(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::post(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    eht_interface,
+  )
+  .await
 }
 
+#[deprecated(
+  note = "use `ethernet_interfaces::client::post_ip_addresses` with an `ApiContext`"
+)]
 pub async fn post_ip_addresses(
   auth_token: &str,
   base_url: &str,
   root_cert: &[u8],
   eht_interface: ComponentEthernetInterface,
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!(
-    "{}/hsm/v2/Inventory/EthernetInterfaces/{}/IPAddresses",
-    base_url,
-    eht_interface.component_id.as_ref().unwrap()
-  );
-
-  let response = client
-    .post(api_url)
-    .bearer_auth(auth_token)
-    .json(&eht_interface)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.text().await?;
-        return Err(Error::Message(error_payload));
-      }
-    }
+  let ctx = ApiContext::parse(base_url, auth_token, root_cert)?;
+  let eth_interface_id = eht_interface
+    .id
+    .as_deref()
+    .ok_or_else(|| Error::MissingField("ID".to_string()))?;
+
+  let mut response_vec = Vec::new();
+  for ip_address_mapping in eht_interface.ip_addresses.iter().flatten() {
+    response_vec.push(
+      client::post_ip_addresses(&ctx, eth_interface_id, ip_address_mapping)
+        .await?,
+    );
   }
 
-  response.json().await.map_err(Error::NetError)
+  Ok(Value::Array(response_vec))
 }
 
+#[deprecated(
+  note = "use `ethernet_interfaces::client::get` with an `ApiContext`"
+)]
+#[allow(clippy::too_many_arguments)]
 pub async fn get(
   auth_token: &str,
   base_url: &str,
@@ -91,77 +65,38 @@ pub async fn get(
   older_than: Option<&str>,
   newer_than: Option<&str>,
 ) -> Result<Vec<ComponentEthernetInterface>, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url =
-    base_url.to_owned() + "/hsm/v2/Inventory/EthernetInterfaces";
-
-  let response = client
-    .get(api_url)
-    .query(&[
-      ("MACAddress", mac_address),
-      ("IPAddress", ip_address),
-      ("Network", network),
-      ("ComponentID", component_id),
-      ("Type", r#type),
-      ("OlderThan", older_than),
-      ("NewerThan", newer_than),
-    ])
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.text().await?;
-        return Err(Error::Message(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    mac_address,
+    ip_address,
+    network,
+    component_id,
+    r#type,
+    older_than,
+    newer_than,
+  )
+  .await
 }
 
+#[deprecated(
+  note = "use `ethernet_interfaces::client::get_one` with an `ApiContext`"
+)]
 pub async fn get_one(
   auth_token: &str,
   base_url: &str,
   root_cert: &[u8],
   eth_interface_id: &str,
 ) -> Result<ComponentEthernetInterface, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!(
-    "{}/hsm/v2/Inventory/EthernetInterfaces/{}",
-    base_url, eth_interface_id
-  );
-
-  let response = client.get(api_url).bearer_auth(auth_token).send().await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.text().await?;
-        return Err(Error::Message(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get_one(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    eth_interface_id,
+  )
+  .await
 }
 
+#[deprecated(
+  note = "use `ethernet_interfaces::client::patch` with an `ApiContext`"
+)]
 pub async fn patch(
   auth_token: &str,
   base_url: &str,
@@ -170,158 +105,67 @@ pub async fn patch(
   description: Option<&str>,
   ip_address_mapping: (&str, &str),
 ) -> Result<Value, Error> {
-  let ip_address = ip_address_mapping.0;
-  let network = ip_address_mapping.1;
-  let cei = ComponentEthernetInterface {
-    id: None,
-    description: description.map(|value| value.to_string()),
-    mac_address: None,
-    ip_addresses: Some(vec![IpAddressMapping {
-      ip_address: ip_address.to_string(),
-      network: Some(network.to_string()),
-    }]),
-    last_update: None,
-    component_id: Some(eth_interface_id.to_string()),
-    parent_hms_type: None,
-  };
-
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!(
-    "{}/hsm/v2/Inventory/EthernetInterfaces/{}",
-    base_url, eth_interface_id
-  );
-
-  let response = client
-    .patch(api_url)
-    .query(&[("ethInterfaceID", ip_address), ("ipAddress", ip_address)])
-    .bearer_auth(auth_token)
-    .json(&cei)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::patch(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    eth_interface_id,
+    description,
+    ip_address_mapping,
+  )
+  .await
 }
 
+#[deprecated(
+  note = "use `ethernet_interfaces::client::delete_all` with an `ApiContext`"
+)]
 pub async fn delete_all(
   auth_token: &str,
   base_url: &str,
   root_cert: &[u8],
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url =
-    format!("{}/hsm/v2/Inventory/EthernetInterfaces", base_url);
-
-  let response = client
-    .delete(api_url)
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
+  let response =
+    client::delete_all(&ApiContext::parse(base_url, auth_token, root_cert)?)
+      .await?;
 
-  response.json().await.map_err(Error::NetError)
+  Ok(serde_json::to_value(response)?)
 }
 
+#[deprecated(
+  note = "use `ethernet_interfaces::client::delete_one` with an `ApiContext`"
+)]
 pub async fn delete_one(
   auth_token: &str,
   base_url: &str,
   root_cert: &[u8],
   eth_interface_id: &str,
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!(
-    "{}/hsm/v2/Inventory/EthernetInterfaces/{}",
-    base_url, eth_interface_id
-  );
-
-  let response = client
-    .delete(api_url)
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
+  let response = client::delete_one(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    eth_interface_id,
+  )
+  .await?;
 
-  response.json().await.map_err(Error::NetError)
+  Ok(serde_json::to_value(response)?)
 }
 
+#[deprecated(
+  note = "use `ethernet_interfaces::client::get_ip_addresses` with an `ApiContext`"
+)]
 pub async fn get_ip_addresses(
   auth_token: &str,
   base_url: &str,
   root_cert: &[u8],
   eth_interface_id: &str,
 ) -> Result<Vec<IpAddressMapping>, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!(
-    "{}/hsm/v2/Inventory/EthernetInterfaces/{}/IPAddresses",
-    base_url, eth_interface_id
-  );
-
-  let response = client.get(api_url).bearer_auth(auth_token).send().await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get_ip_addresses(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    eth_interface_id,
+  )
+  .await
 }
 
+#[deprecated(
+  note = "use `ethernet_interfaces::client::delete_ip_address` with an `ApiContext`"
+)]
 pub async fn delete_ip_address(
   auth_token: &str,
   base_url: &str,
@@ -330,33 +174,10 @@ pub async fn delete_ip_address(
   eth_interface_id: &str,
   ip_address: &str,
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!(
-    "{}/hsm/v2/Inventory/EthernetInterfaces/{}/IpAddress/{}",
-    base_url, eth_interface_id, ip_address
-  );
-
-  let response = client
-    .delete(api_url)
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::delete_ip_address(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    eth_interface_id,
+    ip_address,
+  )
+  .await
 }
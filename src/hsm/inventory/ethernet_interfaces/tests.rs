@@ -0,0 +1,256 @@
+use futures_util::TryStreamExt;
+use serde_json::json;
+use wiremock::{
+  matchers::{method, path, query_param},
+  Mock, ResponseTemplate,
+};
+
+use crate::{
+  hsm::inventory::ethernet_interfaces::{
+    types::EthernetInterfaceQuery,
+    utils::{assign_free_ip, propose_free_ips, stream_ethernet_interfaces},
+  },
+  test_util::{MockOchami, MockService},
+};
+
+/// HSM ethernet interfaces of network HMN, `ip_vec` are the addresses used and the
+/// interfaces using them
+fn eth_interfaces_json(ip_vec: &[(&str, &str)]) -> serde_json::Value {
+  ip_vec
+    .iter()
+    .map(|(eth_interface_id, ip_address)| {
+      json!({
+        "ID": eth_interface_id,
+        "IPAddresses": [{"IPAddress": ip_address, "Network": "HMN"}]
+      })
+    })
+    .collect()
+}
+
+#[tokio::test]
+async fn test_propose_free_ips_skips_used_and_gateway() {
+  let mock = MockOchami::start().await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "GET",
+      "/Inventory/EthernetInterfaces",
+      200,
+      Some(eth_interfaces_json(&[("a4bf01000001", "10.1.0.3")])),
+    )
+    .await;
+
+  let ip_vec = propose_free_ips(
+    &mock.context(),
+    "HMN",
+    "10.1.0.0/29",
+    Some("10.1.0.1"),
+    3,
+  )
+  .await
+  .unwrap();
+
+  assert_eq!(ip_vec, vec!["10.1.0.2", "10.1.0.4", "10.1.0.5"]);
+}
+
+#[tokio::test]
+async fn test_assign_free_ip() {
+  let mock = MockOchami::start().await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "GET",
+      "/Inventory/EthernetInterfaces",
+      200,
+      Some(eth_interfaces_json(&[("a4bf01000001", "10.1.0.2")])),
+    )
+    .await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "POST",
+      "/Inventory/EthernetInterfaces/[^/]+/IPAddresses",
+      201,
+      Some(json!([{"URI": "/hsm/v2/Inventory/EthernetInterfaces/a4bf01000002/IPAddresses/10.1.0.3"}])),
+    )
+    .await;
+
+  let ip_address = assign_free_ip(
+    &mock.context(),
+    "HMN",
+    "10.1.0.0/29",
+    Some("10.1.0.1"),
+    "a4bf01000002",
+  )
+  .await
+  .unwrap();
+  assert_eq!(ip_address, "10.1.0.3");
+
+  let request = mock
+    .server()
+    .received_requests()
+    .await
+    .unwrap()
+    .into_iter()
+    .find(|request| request.method.as_str() == "POST")
+    .unwrap();
+  assert!(request
+    .url
+    .path()
+    .ends_with("/Inventory/EthernetInterfaces/a4bf01000002/IPAddresses"));
+  assert_eq!(
+    request.body_json::<serde_json::Value>().unwrap(),
+    json!({"IPAddress": "10.1.0.3", "Network": "HMN"})
+  );
+}
+
+#[tokio::test]
+async fn test_assign_free_ip_rolls_back_on_conflict() {
+  let mock = MockOchami::start().await;
+  let ctx = mock.context();
+  let eth_interfaces_path = ctx
+    .hsm_url("/Inventory/EthernetInterfaces")
+    .unwrap()
+    .path()
+    .to_string();
+
+  // Another interface takes the address between the proposal and the check
+  Mock::given(method("GET"))
+    .and(path(eth_interfaces_path.as_str()))
+    .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+    .up_to_n_times(1)
+    .mount(mock.server())
+    .await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "GET",
+      "/Inventory/EthernetInterfaces",
+      200,
+      Some(eth_interfaces_json(&[
+        ("a4bf01000001", "10.1.0.2"),
+        ("a4bf01000002", "10.1.0.2"),
+      ])),
+    )
+    .await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "POST",
+      "/Inventory/EthernetInterfaces/[^/]+/IPAddresses",
+      201,
+      Some(json!([])),
+    )
+    .await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "DELETE",
+      "/Inventory/EthernetInterfaces/[^/]+/IPAddresses/[^/]+",
+      200,
+      Some(json!({"code": 0, "message": "deleted 1 entry"})),
+    )
+    .await;
+
+  let result = assign_free_ip(
+    &ctx,
+    "HMN",
+    "10.1.0.0/29",
+    Some("10.1.0.1"),
+    "a4bf01000002",
+  )
+  .await;
+  assert!(result.unwrap_err().to_string().contains("a4bf01000001"));
+
+  let delete_path_vec: Vec<String> = mock
+    .server()
+    .received_requests()
+    .await
+    .unwrap()
+    .into_iter()
+    .filter(|request| request.method.as_str() == "DELETE")
+    .map(|request| request.url.path().to_string())
+    .collect();
+  assert_eq!(delete_path_vec.len(), 1);
+  assert!(delete_path_vec[0].ends_with(
+    "/Inventory/EthernetInterfaces/a4bf01000002/IPAddresses/10.1.0.2"
+  ));
+}
+
+async fn get_count(mock: &MockOchami) -> usize {
+  mock
+    .server()
+    .received_requests()
+    .await
+    .unwrap()
+    .iter()
+    .filter(|request| request.method.as_str() == "GET")
+    .count()
+}
+
+#[tokio::test]
+async fn test_stream_ethernet_interfaces_pages() {
+  let mock = MockOchami::start().await;
+  let ctx = mock.context();
+  let eth_interfaces_path = ctx
+    .hsm_url("/Inventory/EthernetInterfaces")
+    .unwrap()
+    .path()
+    .to_string();
+
+  for (offset, ip_vec) in [
+    (
+      "0",
+      vec![("a4bf01000001", "10.1.0.1"), ("a4bf01000002", "10.1.0.2")],
+    ),
+    ("2", vec![("a4bf01000003", "10.1.0.3")]),
+  ] {
+    Mock::given(method("GET"))
+      .and(path(eth_interfaces_path.as_str()))
+      .and(query_param("offset", offset))
+      .respond_with(
+        ResponseTemplate::new(200).set_body_json(eth_interfaces_json(&ip_vec)),
+      )
+      .mount(mock.server())
+      .await;
+  }
+
+  let eth_interface_vec: Vec<_> =
+    stream_ethernet_interfaces(&ctx, EthernetInterfaceQuery::default(), 2)
+      .try_collect()
+      .await
+      .unwrap();
+
+  assert_eq!(eth_interface_vec.len(), 3);
+  assert_eq!(get_count(&mock).await, 2);
+}
+
+#[tokio::test]
+async fn test_stream_ethernet_interfaces_stops_on_repeated_page() {
+  // SMD ignoring limit and offset returns every interface for every page
+  let mock = MockOchami::start().await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "GET",
+      "/Inventory/EthernetInterfaces",
+      200,
+      Some(eth_interfaces_json(&[
+        ("a4bf01000001", "10.1.0.1"),
+        ("a4bf01000002", "10.1.0.2"),
+      ])),
+    )
+    .await;
+
+  let eth_interface_vec: Vec<_> = stream_ethernet_interfaces(
+    &mock.context(),
+    EthernetInterfaceQuery::default(),
+    2,
+  )
+  .try_collect()
+  .await
+  .unwrap();
+
+  assert_eq!(eth_interface_vec.len(), 2);
+  assert_eq!(get_count(&mock).await, 2);
+}
@@ -1,12 +1,17 @@
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
+
+use crate::hsm::inventory::types::MacAddress;
 //use crate::hsm::inventory::types::ComponentType;
 //use manta_backend_dispatcher::types::hsm::inventory::{
+#[cfg(feature = "backend-connector")]
 use manta_backend_dispatcher::types::hsm::inventory::{
   ComponentEthernetInterface as FrontendComponentEthernetInterface,
   ComponentEthernetInterfaceArray as FrontendComponentEthernetInterfaceArray,
   IpAddressMapping as FrontendIpAddressMapping,
 };
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct ComponentEthernetInterface {
   #[serde(rename = "ID")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -16,7 +21,8 @@ pub struct ComponentEthernetInterface {
   pub description: Option<String>,
   #[serde(rename = "MACAddress")]
   #[serde(skip_serializing_if = "Option::is_none")]
-  pub mac_address: Option<String>,
+  #[serde(default, deserialize_with = "MacAddress::deserialize_lenient")]
+  pub mac_address: Option<MacAddress>,
   #[serde(rename = "IPAddresses")]
   #[serde(skip_serializing_if = "Option::is_none")]
   pub ip_addresses: Option<Vec<IpAddressMapping>>,
@@ -30,25 +36,31 @@ pub struct ComponentEthernetInterface {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub parent_hms_type: Option<String>,
 }
+#[cfg(feature = "backend-connector")]
 impl From<FrontendComponentEthernetInterface> for ComponentEthernetInterface {
   fn from(interface: FrontendComponentEthernetInterface) -> Self {
     ComponentEthernetInterface {
       id: interface.id,
       description: interface.description,
-      mac_address: interface.mac_address,
-      ip_addresses: interface.ip_addresses.map(|ips| ips.into_iter().map(IpAddressMapping::from).collect()),
+      mac_address: interface
+        .mac_address
+        .and_then(|mac| MacAddress::parse_lenient(&mac)),
+      ip_addresses: interface
+        .ip_addresses
+        .map(|ips| ips.into_iter().map(IpAddressMapping::from).collect()),
       last_update: interface.last_update,
       component_id: interface.component_id,
       parent_hms_type: interface.parent_hms_type,
     }
   }
 }
+#[cfg(feature = "backend-connector")]
 impl Into<FrontendComponentEthernetInterface> for ComponentEthernetInterface {
   fn into(self) -> FrontendComponentEthernetInterface {
     FrontendComponentEthernetInterface {
       id: self.id,
       description: self.description,
-      mac_address: self.mac_address,
+      mac_address: self.mac_address.map(String::from),
       ip_addresses: self.ip_addresses.map(|ips| ips.into_iter().map(|ip| ip.into()).collect()),
       last_update: self.last_update,
       component_id: self.component_id,
@@ -56,12 +68,13 @@ impl Into<FrontendComponentEthernetInterface> for ComponentEthernetInterface {
     }
   }
 }
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct ComponentEthernetInterfaceArray {
   #[serde(rename = "EthernetInterfaces")]
   #[serde(skip_serializing_if = "Option::is_none")]
   pub ethernet_interfaces: Option<Vec<ComponentEthernetInterface>>,
 }
+#[cfg(feature = "backend-connector")]
 impl From<FrontendComponentEthernetInterfaceArray> for ComponentEthernetInterfaceArray {
   fn from(array: FrontendComponentEthernetInterfaceArray) -> Self {
     ComponentEthernetInterfaceArray {
@@ -71,6 +84,7 @@ impl From<FrontendComponentEthernetInterfaceArray> for ComponentEthernetInterfac
     }
   }
 }
+#[cfg(feature = "backend-connector")]
 impl Into<FrontendComponentEthernetInterfaceArray> for ComponentEthernetInterfaceArray {
   fn into(self) -> FrontendComponentEthernetInterfaceArray {
     FrontendComponentEthernetInterfaceArray {
@@ -83,7 +97,9 @@ impl Into<FrontendComponentEthernetInterfaceArray> for ComponentEthernetInterfac
     }
   }
 }
-#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[derive(
+  Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq, Hash,
+)]
 pub struct IpAddressMapping {
   #[serde(rename = "IPAddress")]
   pub ip_address: String,
@@ -91,6 +107,7 @@ pub struct IpAddressMapping {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub network: Option<String>,
 }
+#[cfg(feature = "backend-connector")]
 impl From<FrontendIpAddressMapping> for IpAddressMapping {
   fn from(address: FrontendIpAddressMapping) -> Self {
     IpAddressMapping {
@@ -99,6 +116,7 @@ impl From<FrontendIpAddressMapping> for IpAddressMapping {
     }
   }
 }
+#[cfg(feature = "backend-connector")]
 impl Into<FrontendIpAddressMapping> for IpAddressMapping {
   fn into(self) -> FrontendIpAddressMapping {
     FrontendIpAddressMapping {
@@ -107,3 +125,49 @@ impl Into<FrontendIpAddressMapping> for IpAddressMapping {
     }
   }
 }
+
+/// Filters of `GET /Inventory/EthernetInterfaces`, `None` fields are not sent
+#[derive(Debug, Clone, Default)]
+pub struct EthernetInterfaceQuery {
+  pub mac_address: Option<String>,
+  pub ip_address: Option<String>,
+  pub network: Option<String>,
+  pub component_id: Option<String>,
+  pub r#type: Option<String>,
+  pub older_than: Option<String>,
+  pub newer_than: Option<String>,
+}
+
+/// IP address assigned to more than one ethernet interface of the same network
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct IpConflict {
+  pub ip_address: String,
+  pub eth_interface_ids: Vec<String>,
+}
+
+impl fmt::Display for ComponentEthernetInterface {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{} -> {}",
+      self
+        .mac_address
+        .as_ref()
+        .map_or("unknown", |mac_address| mac_address.as_str()),
+      self.component_id.as_deref().unwrap_or("unknown")
+    )?;
+
+    let ip_address_vec: Vec<&str> = self
+      .ip_addresses
+      .iter()
+      .flatten()
+      .map(|ip_address| ip_address.ip_address.as_str())
+      .collect();
+
+    if !ip_address_vec.is_empty() {
+      write!(f, " ({})", ip_address_vec.join(", "))?;
+    }
+
+    Ok(())
+  }
+}
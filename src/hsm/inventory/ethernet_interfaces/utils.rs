@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use futures_util::{stream, Stream, StreamExt};
+
+use crate::{error::Error, http::ApiContext, utils::expand_addresses};
+
+use super::{
+  client,
+  types::{
+    ComponentEthernetInterface, EthernetInterfaceQuery, IpAddressMapping,
+    IpConflict,
+  },
+};
+
+/// IP addresses of a network in use by ethernet interfaces, with the IDs of the
+/// interfaces using each of them
+pub async fn get_used_ips(
+  ctx: &ApiContext,
+  network: &str,
+) -> Result<HashMap<String, Vec<String>>, Error> {
+  let eth_interface_vec =
+    client::get(ctx, None, None, Some(network), None, None, None, None).await?;
+
+  let mut used_ip_map: HashMap<String, Vec<String>> = HashMap::new();
+
+  for eth_interface in eth_interface_vec {
+    let eth_interface_id = eth_interface.id.unwrap_or_default();
+
+    for ip_address_mapping in eth_interface.ip_addresses.unwrap_or_default() {
+      // HSM filters interfaces, not addresses, interfaces may have addresses in
+      // other networks
+      if ip_address_mapping
+        .network
+        .as_deref()
+        .is_some_and(|ip_network| ip_network != network)
+      {
+        continue;
+      }
+
+      used_ip_map
+        .entry(ip_address_mapping.ip_address)
+        .or_default()
+        .push(eth_interface_id.clone());
+    }
+  }
+
+  Ok(used_ip_map)
+}
+
+/// IP addresses of a network used by more than one ethernet interface
+pub async fn find_conflicts(
+  ctx: &ApiContext,
+  network: &str,
+) -> Result<Vec<IpConflict>, Error> {
+  let mut conflict_vec: Vec<IpConflict> = get_used_ips(ctx, network)
+    .await?
+    .into_iter()
+    .filter(|(_, eth_interface_id_vec)| eth_interface_id_vec.len() > 1)
+    .map(|(ip_address, eth_interface_ids)| IpConflict {
+      ip_address,
+      eth_interface_ids,
+    })
+    .collect();
+
+  conflict_vec.sort_by(|a, b| a.ip_address.cmp(&b.ip_address));
+
+  Ok(conflict_vec)
+}
+
+/// Propose up to `count` IP addresses of `cidr` (eg: 10.1.0.0/24, or a range like
+/// 10.1.0.10-10.1.0.20) not used by any ethernet interface of `network`. The gateway
+/// of the subnet, if given, is never proposed
+pub async fn propose_free_ips(
+  ctx: &ApiContext,
+  network: &str,
+  cidr: &str,
+  gateway_opt: Option<&str>,
+  count: usize,
+) -> Result<Vec<String>, Error> {
+  let used_ip_map = get_used_ips(ctx, network).await?;
+
+  Ok(
+    expand_addresses(&[cidr.to_string()])?
+      .into_iter()
+      .filter(|ip_address| Some(ip_address.as_str()) != gateway_opt)
+      .filter(|ip_address| !used_ip_map.contains_key(ip_address))
+      .take(count)
+      .collect(),
+  )
+}
+
+/// Assign a free IP address of `cidr`, other than `gateway_opt`, to an ethernet
+/// interface. The address is checked again after the assignment and removed if another
+/// interface took it meanwhile
+pub async fn assign_free_ip(
+  ctx: &ApiContext,
+  network: &str,
+  cidr: &str,
+  gateway_opt: Option<&str>,
+  eth_interface_id: &str,
+) -> Result<String, Error> {
+  let ip_address = propose_free_ips(ctx, network, cidr, gateway_opt, 1)
+    .await?
+    .pop()
+    .ok_or_else(|| {
+      Error::Message(format!(
+        "No free IP address left in '{}' for network '{}'",
+        cidr, network
+      ))
+    })?;
+
+  client::post_ip_addresses(
+    ctx,
+    eth_interface_id,
+    &IpAddressMapping {
+      ip_address: ip_address.clone(),
+      network: Some(network.to_string()),
+    },
+  )
+  .await?;
+
+  let user_vec = get_used_ips(ctx, network)
+    .await?
+    .remove(&ip_address)
+    .unwrap_or_default();
+
+  if user_vec.iter().any(|id| id != eth_interface_id) {
+    let conflict_msg = format!(
+      "IP address '{}' was assigned concurrently to {}",
+      ip_address,
+      user_vec.join(", ")
+    );
+
+    return Err(
+      match client::delete_ip_address(ctx, eth_interface_id, &ip_address).await
+      {
+        Ok(_) => Error::Message(conflict_msg),
+        Err(e) => Error::Message(format!(
+          "{}. Could not remove it from '{}': {}",
+          conflict_msg, eth_interface_id, e
+        )),
+      },
+    );
+  }
+
+  Ok(ip_address)
+}
+
+/// Stream the ethernet interfaces matching `query`, requested in pages of `page_size`
+/// interfaces. The next page is only requested once the consumer has processed the
+/// previous one. Stops after the first error, after a page shorter than `page_size`, or
+/// when a page repeats the previous one (SMD ignoring `limit` and `offset`)
+pub fn stream_ethernet_interfaces<'a>(
+  ctx: &'a ApiContext,
+  query: EthernetInterfaceQuery,
+  page_size: usize,
+) -> impl Stream<Item = Result<ComponentEthernetInterface, Error>> + 'a {
+  let page_size = page_size.max(1);
+
+  stream::unfold(
+    Some((0, Vec::new())),
+    move |state_opt: Option<(usize, Vec<Option<String>>)>| {
+      let query = query.clone();
+
+      async move {
+        let (offset, previous_id_vec) = state_opt?;
+
+        match client::get_page(ctx, &query, page_size, offset).await {
+          Ok(eth_interface_vec) => {
+            let id_vec: Vec<Option<String>> = eth_interface_vec
+              .iter()
+              .map(|eth_interface| eth_interface.id.clone())
+              .collect();
+
+            if !id_vec.is_empty() && id_vec == previous_id_vec {
+              return None;
+            }
+
+            // A short page is the last one. A page bigger than requested means SMD
+            // does not support paging and returned every interface
+            let next_state_opt = (eth_interface_vec.len() == page_size)
+              .then_some((offset + page_size, id_vec));
+
+            Some((
+              eth_interface_vec.into_iter().map(Ok).collect::<Vec<_>>(),
+              next_state_opt,
+            ))
+          }
+          Err(e) => Some((vec![Err(e)], None)),
+        }
+      }
+    },
+  )
+  .flat_map(stream::iter)
+}
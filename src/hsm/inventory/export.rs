@@ -0,0 +1,285 @@
+use futures_util::StreamExt;
+use serde::Serialize;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::{
+  error::Error,
+  hsm::{
+    component,
+    component::types::{Component, ComponentArrayPostQuery},
+    inventory::{
+      ethernet_interfaces::{
+        self,
+        types::{ComponentEthernetInterface, EthernetInterfaceQuery},
+      },
+      redfish_endpoint::{self, types::RedfishEndpoint},
+    },
+  },
+  http::ApiContext,
+};
+
+/// Records fetched per query to HSM while exporting them
+const EXPORT_PAGE_SIZE: usize = 500;
+
+/// File formats inventory can be exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+  Csv,
+  /// Newline delimited JSON, one record per line
+  Ndjson,
+}
+
+const COMPONENT_COLUMNS: [&str; 10] = [
+  "ID", "Type", "State", "Flag", "Enabled", "Role", "SubRole", "NID", "Arch",
+  "Class",
+];
+
+const ETHERNET_INTERFACE_COLUMNS: [&str; 6] = [
+  "ID",
+  "MACAddress",
+  "ComponentID",
+  "Type",
+  "IPAddresses",
+  "LastUpdate",
+];
+
+const REDFISH_ENDPOINT_COLUMNS: [&str; 8] = [
+  "ID",
+  "Type",
+  "Hostname",
+  "Domain",
+  "FQDN",
+  "IPAddress",
+  "Enabled",
+  "UUID",
+];
+
+/// Quote a CSV field if it contains a comma, a quote or a line break
+pub(crate) fn csv_field(value: &str) -> String {
+  if value.contains([',', '"', '\n', '\r']) {
+    format!("\"{}\"", value.replace('"', "\"\""))
+  } else {
+    value.to_string()
+  }
+}
+
+fn optional_field<T: ToString>(value: &Option<T>) -> String {
+  value.as_ref().map(ToString::to_string).unwrap_or_default()
+}
+
+/// Write records one at a time, so the output is never held in memory as a whole
+struct RecordWriter<'a, W> {
+  writer: &'a mut W,
+  format: ExportFormat,
+  count: usize,
+}
+
+impl<'a, W: AsyncWrite + Unpin> RecordWriter<'a, W> {
+  /// Starts with the CSV header
+  async fn new(
+    writer: &'a mut W,
+    format: ExportFormat,
+    column_vec: &[&str],
+  ) -> Result<Self, Error> {
+    if format == ExportFormat::Csv {
+      writer
+        .write_all(format!("{}\n", column_vec.join(",")).as_bytes())
+        .await?;
+    }
+
+    Ok(RecordWriter {
+      writer,
+      format,
+      count: 0,
+    })
+  }
+
+  async fn write<T: Serialize>(
+    &mut self,
+    record: &T,
+    csv_row: fn(&T) -> Vec<String>,
+  ) -> Result<(), Error> {
+    let mut line = match self.format {
+      ExportFormat::Csv => csv_row(record)
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<String>>()
+        .join(","),
+      ExportFormat::Ndjson => serde_json::to_string(record)?,
+    };
+    line.push('\n');
+
+    self.writer.write_all(line.as_bytes()).await?;
+    self.count += 1;
+
+    Ok(())
+  }
+
+  /// Returns the number of records written
+  async fn finish(self) -> Result<usize, Error> {
+    self.writer.flush().await?;
+
+    Ok(self.count)
+  }
+}
+
+fn component_csv_row(c: &Component) -> Vec<String> {
+  vec![
+    optional_field(&c.id),
+    optional_field(&c.r#type),
+    optional_field(&c.state),
+    optional_field(&c.flag),
+    optional_field(&c.enabled),
+    optional_field(&c.role),
+    optional_field(&c.sub_role),
+    optional_field(&c.nid),
+    optional_field(&c.arch),
+    optional_field(&c.class),
+  ]
+}
+
+fn ethernet_interface_csv_row(
+  eth_interface: &ComponentEthernetInterface,
+) -> Vec<String> {
+  vec![
+    optional_field(&eth_interface.id),
+    optional_field(&eth_interface.mac_address),
+    optional_field(&eth_interface.component_id),
+    optional_field(&eth_interface.parent_hms_type),
+    eth_interface
+      .ip_addresses
+      .iter()
+      .flatten()
+      .map(|ip_address| ip_address.ip_address.as_str())
+      .collect::<Vec<&str>>()
+      .join(";"),
+    optional_field(&eth_interface.last_update),
+  ]
+}
+
+fn redfish_endpoint_csv_row(redfish_endpoint: &RedfishEndpoint) -> Vec<String> {
+  vec![
+    redfish_endpoint.id.clone(),
+    optional_field(&redfish_endpoint.r#type),
+    optional_field(&redfish_endpoint.hostname),
+    optional_field(&redfish_endpoint.domain),
+    optional_field(&redfish_endpoint.fqdn),
+    optional_field(&redfish_endpoint.ip_address),
+    optional_field(&redfish_endpoint.enabled),
+    optional_field(&redfish_endpoint.uuid),
+  ]
+}
+
+/// Export all HSM components. Only their ids are listed at once, the components
+/// are fetched and written `EXPORT_PAGE_SIZE` at a time
+pub async fn export_components<W: AsyncWrite + Unpin>(
+  ctx: &ApiContext,
+  format: ExportFormat,
+  writer: &mut W,
+) -> Result<usize, Error> {
+  let component_id_vec: Vec<String> = component::client::get(
+    ctx,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    Some("true"),
+    None,
+    None,
+    None,
+  )
+  .await?
+  .components
+  .unwrap_or_default()
+  .into_iter()
+  .filter_map(|component| component.id)
+  .collect();
+
+  let mut record_writer =
+    RecordWriter::new(writer, format, &COMPONENT_COLUMNS).await?;
+
+  for component_id_page in component_id_vec.chunks(EXPORT_PAGE_SIZE) {
+    let component_vec = component::client::post_query(
+      ctx,
+      ComponentArrayPostQuery {
+        component_ids: Some(component_id_page.to_vec()),
+        ..Default::default()
+      },
+    )
+    .await?
+    .components
+    .unwrap_or_default();
+
+    for component in &component_vec {
+      record_writer.write(component, component_csv_row).await?;
+    }
+  }
+
+  record_writer.finish().await
+}
+
+/// Export all ethernet interfaces. IP addresses are separated by ';' in CSV. The
+/// interfaces are fetched and written `EXPORT_PAGE_SIZE` at a time
+pub async fn export_ethernet_interfaces<W: AsyncWrite + Unpin>(
+  ctx: &ApiContext,
+  format: ExportFormat,
+  writer: &mut W,
+) -> Result<usize, Error> {
+  let mut record_writer =
+    RecordWriter::new(writer, format, &ETHERNET_INTERFACE_COLUMNS).await?;
+
+  let eth_interface_stream =
+    ethernet_interfaces::utils::stream_ethernet_interfaces(
+      ctx,
+      EthernetInterfaceQuery::default(),
+      EXPORT_PAGE_SIZE,
+    );
+  tokio::pin!(eth_interface_stream);
+
+  while let Some(eth_interface) = eth_interface_stream.next().await {
+    record_writer
+      .write(&eth_interface?, ethernet_interface_csv_row)
+      .await?;
+  }
+
+  record_writer.finish().await
+}
+
+/// Export all redfish endpoints. BMC credentials are never exported. HSM returns
+/// them in a single response, written one record at a time
+pub async fn export_redfish_endpoints<W: AsyncWrite + Unpin>(
+  ctx: &ApiContext,
+  format: ExportFormat,
+  writer: &mut W,
+) -> Result<usize, Error> {
+  let redfish_endpoint_vec = redfish_endpoint::client::get_all(ctx)
+    .await?
+    .redfish_endpoints
+    .unwrap_or_default();
+
+  let mut record_writer =
+    RecordWriter::new(writer, format, &REDFISH_ENDPOINT_COLUMNS).await?;
+
+  for mut redfish_endpoint in redfish_endpoint_vec {
+    redfish_endpoint.user = None;
+    redfish_endpoint.password = None;
+
+    record_writer
+      .write(&redfish_endpoint, redfish_endpoint_csv_row)
+      .await?;
+  }
+
+  record_writer.finish().await
+}
@@ -0,0 +1,348 @@
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{
+  error::Error,
+  hsm::inventory::types::{HWInventoryByLocation, HWInventoryByLocationList},
+  http::{ApiContext, MutationRequest, SpilledResponse},
+  progress::{ProgressEvent, ProgressSink},
+};
+
+use super::types::HardwareHistoryArray;
+
+pub async fn get_query(
+  ctx: &ApiContext,
+  xname: &str,
+  r#type: Option<&str>,
+  children: Option<bool>,
+  parents: Option<bool>,
+  partition: Option<&str>,
+  format: Option<&str>,
+) -> Result<Value, Error> {
+  get_query_typed(ctx, xname, r#type, children, parents, partition, format)
+    .await
+}
+
+/// Same as `get_query`, deserializing the hardware inventory straight into `T`
+#[allow(clippy::too_many_arguments)]
+pub async fn get_query_typed<T: DeserializeOwned>(
+  ctx: &ApiContext,
+  xname: &str,
+  r#type: Option<&str>,
+  children: Option<bool>,
+  parents: Option<bool>,
+  partition: Option<&str>,
+  format: Option<&str>,
+) -> Result<T, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!("/Inventory/Hardware/Query/{}", xname))?;
+
+  let response = client
+    .get(api_url)
+    .query(&[
+      r#type,
+      children.map(|value| value.to_string()).as_deref(),
+      parents.map(|value| value.to_string()).as_deref(),
+      partition,
+      format,
+    ])
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  crate::http::json_from_bytes(response).await
+}
+
+pub async fn get(
+  ctx: &ApiContext,
+  id: Option<&str>,
+  r#type: Option<&str>,
+  manufacturer: Option<&str>,
+  partnumber: Option<&str>,
+  serialnumber: Option<&str>,
+  fruid: Option<&str>,
+) -> Result<Vec<HWInventoryByLocation>, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/Inventory/Hardware")?;
+
+  let response = client
+    .get(api_url)
+    .query(&[id, r#type, manufacturer, partnumber, serialnumber, fruid])
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+/// Same as `get`, writing the hardware inventory to `path` as it is received instead of
+/// deserializing it in memory. Meant for full system inventories, the returned handle
+/// parses the file on demand
+pub async fn get_to_file(
+  ctx: &ApiContext,
+  id: Option<&str>,
+  r#type: Option<&str>,
+  path: &Path,
+  progress: &dyn ProgressSink,
+) -> Result<SpilledResponse<Vec<HWInventoryByLocation>>, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/Inventory/Hardware")?;
+
+  let response = client
+    .get(api_url)
+    .query(&[("id", id), ("type", r#type)])
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  let operation = format!("Download hardware inventory to {}", path.display());
+  progress.report(ProgressEvent::Started {
+    operation: &operation,
+    total: 1,
+  });
+
+  match crate::http::spill_to_file(response, path, progress).await {
+    Ok(spilled_response) => {
+      progress.report(ProgressEvent::ItemCompleted {
+        item: &path.to_string_lossy(),
+      });
+      progress.report(ProgressEvent::Finished {
+        completed: 1,
+        failed: 0,
+      });
+
+      Ok(spilled_response)
+    }
+    Err(e) => {
+      progress.report(ProgressEvent::ItemFailed {
+        item: &path.to_string_lossy(),
+        reason: &e.to_string(),
+      });
+      progress.report(ProgressEvent::Finished {
+        completed: 0,
+        failed: 1,
+      });
+
+      Err(e)
+    }
+  }
+}
+
+pub async fn get_one(
+  ctx: &ApiContext,
+  xname: &str,
+) -> Result<HWInventoryByLocation, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!("/Inventory/Hardware/{}", xname))?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn post(
+  ctx: &ApiContext,
+  hardware: HWInventoryByLocationList,
+) -> Result<Value, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/Inventory/Hardware")?;
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(&hardware)
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn delete_all(ctx: &ApiContext) -> Result<Value, Error> {
+  let client = ctx.client()?;
+  // NOTE: pre-existing bug — missing leading '/' before "hsm"
+  let api_url = ctx.hsm_url("/Inventory/Hardware")?;
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn delete_one(ctx: &ApiContext, xname: &str) -> Result<Value, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!("/Inventory/Hardware/{}", xname))?;
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+/// Get the history of hardware events grouped by location. Times are RFC 3339
+pub async fn get_history(
+  ctx: &ApiContext,
+  fru_id: Option<&str>,
+  event_type: Option<&str>,
+  start_time: Option<&str>,
+  end_time: Option<&str>,
+) -> Result<HardwareHistoryArray, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/Inventory/Hardware/History")?;
+
+  let response = client
+    .get(api_url)
+    .query(&[
+      ("fruid", fru_id),
+      ("eventtype", event_type),
+      ("starttime", start_time),
+      ("endtime", end_time),
+    ])
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
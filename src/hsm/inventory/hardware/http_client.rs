@@ -1,10 +1,13 @@
 use serde_json::Value;
 
-use crate::{
-  error::Error,
-  hsm::inventory::types::{HWInventoryByLocation, HWInventoryByLocationList},
-};
+use crate::hsm::inventory::types::HWInventoryByLocation;
+use crate::hsm::inventory::types::HWInventoryByLocationList;
+use crate::{error::Error, http::ApiContext};
 
+use super::client;
+
+#[deprecated(note = "use `hardware::client::get_query` with an `ApiContext`")]
+#[allow(clippy::too_many_arguments)]
 pub async fn get_query(
   auth_token: &str,
   base_url: &str,
@@ -16,44 +19,20 @@ pub async fn get_query(
   partition: Option<&str>,
   format: Option<&str>,
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!(
-    "{}/hsm/v2/Inventory/Hardware/Query/{}",
-    base_url, xname
-  );
-
-  let response = client
-    .get(api_url)
-    .query(&[
-      r#type,
-      children.map(|value| value.to_string()).as_deref(),
-      parents.map(|value| value.to_string()).as_deref(),
-      partition,
-      format,
-    ])
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get_query(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    xname,
+    r#type,
+    children,
+    parents,
+    partition,
+    format,
+  )
+  .await
 }
 
+#[deprecated(note = "use `hardware::client::get` with an `ApiContext`")]
+#[allow(clippy::too_many_arguments)]
 pub async fn get(
   auth_token: &str,
   base_url: &str,
@@ -65,165 +44,62 @@ pub async fn get(
   serialnumber: Option<&str>,
   fruid: Option<&str>,
 ) -> Result<Vec<HWInventoryByLocation>, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!("{}/hsm/v2/Inventory/Hardware", base_url);
-
-  let response = client
-    .get(api_url)
-    .query(&[id, r#type, manufacturer, partnumber, serialnumber, fruid])
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.text().await?;
-        return Err(Error::Message(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    id,
+    r#type,
+    manufacturer,
+    partnumber,
+    serialnumber,
+    fruid,
+  )
+  .await
 }
 
+#[deprecated(note = "use `hardware::client::get_one` with an `ApiContext`")]
 pub async fn get_one(
   auth_token: &str,
   base_url: &str,
   root_cert: &[u8],
   xname: &str,
 ) -> Result<HWInventoryByLocation, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!("{}/hsm/v2/Inventory/Hardware/{}", base_url, xname);
-
-  let response = client.get(api_url).bearer_auth(auth_token).send().await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.text().await?;
-        return Err(Error::Message(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get_one(&ApiContext::parse(base_url, auth_token, root_cert)?, xname)
+    .await
 }
 
+#[deprecated(note = "use `hardware::client::post` with an `ApiContext`")]
 pub async fn post(
   auth_token: &str,
   base_url: &str,
   root_cert: &[u8],
   hardware: HWInventoryByLocationList,
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!("{}/hsm/v2/Inventory/Hardware", base_url);
-
-  let response = client
-    .post(api_url)
-    .bearer_auth(auth_token)
-    .json(&hardware)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.text().await?;
-        return Err(Error::Message(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::post(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    hardware,
+  )
+  .await
 }
 
+#[deprecated(note = "use `hardware::client::delete_all` with an `ApiContext`")]
 pub async fn delete_all(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  // NOTE: pre-existing bug — missing leading '/' before "hsm"
-  let api_url = base_url.to_owned() + "hsm/v2/Inventory/Hardware";
-
-  let response = client
-    .delete(api_url)
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.text().await?;
-        return Err(Error::Message(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::delete_all(&ApiContext::parse(base_url, auth_token, root_cert)?).await
 }
 
+#[deprecated(note = "use `hardware::client::delete_one` with an `ApiContext`")]
 pub async fn delete_one(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   xname: &str,
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url =
-    format!("{}/hsm/v2/Inventory/Hardware/{}", base_url, xname);
-
-  let response = client
-    .delete(api_url)
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.text().await?;
-        return Err(Error::Message(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::delete_one(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    xname,
+  )
+  .await
 }
@@ -0,0 +1,149 @@
+use chrono::{DateTime, Utc};
+use serde_json::json;
+
+use crate::test_util::{MockOchami, MockService};
+
+use super::{
+  types::{FruMovement, HardwareHistoryEvent},
+  utils::{fru_movements, get_fru_movements},
+};
+
+const DIMM: &str = "Memory.Samsung.M393A2K40BB1.1234";
+
+fn at(time: &str) -> DateTime<Utc> {
+  format!("2024-05-02T{}:00Z", time).parse().unwrap()
+}
+
+fn event(xname: &str, time: &str, event_type: &str) -> HardwareHistoryEvent {
+  HardwareHistoryEvent {
+    id: xname.to_string(),
+    fru_id: DIMM.to_string(),
+    timestamp: at(time),
+    event_type: event_type.to_string(),
+  }
+}
+
+#[test]
+fn test_fru_moved_during_window() {
+  let event_vec = vec![
+    event("x1000c0s1b0n0d1", "12:00", "Added"),
+    event("x1000c0s0b0n0d0", "08:00", "Added"),
+    event("x1000c0s0b0n0d0", "11:00", "Removed"),
+    event("x1000c0s1b0n0d1", "12:30", "Scanned"),
+  ];
+
+  assert_eq!(
+    fru_movements(event_vec, at("10:00")),
+    vec![FruMovement {
+      fru_id: DIMM.to_string(),
+      from_xname: "x1000c0s0b0n0d0".to_string(),
+      to_xname: "x1000c0s1b0n0d1".to_string(),
+      timestamp: at("12:30"),
+    }]
+  );
+}
+
+#[test]
+fn test_fru_stale_removal_after_move() {
+  // The removal from the old location is recorded after the FRU was added elsewhere
+  let event_vec = vec![
+    event("x1000c0s0b0n0d0", "08:00", "Added"),
+    event("x1000c0s1b0n0d1", "11:00", "Added"),
+    event("x1000c0s0b0n0d0", "11:30", "Removed"),
+  ];
+
+  let movement_vec = fru_movements(event_vec, at("10:00"));
+
+  assert_eq!(movement_vec.len(), 1);
+  assert_eq!(movement_vec[0].to_xname, "x1000c0s1b0n0d1");
+  assert_eq!(movement_vec[0].timestamp, at("11:00"));
+}
+
+#[test]
+fn test_fru_not_moved() {
+  // Moved before the window
+  let event_vec = vec![
+    event("x1000c0s0b0n0d0", "08:00", "Added"),
+    event("x1000c0s0b0n0d0", "08:30", "Removed"),
+    event("x1000c0s1b0n0d1", "09:00", "Added"),
+    event("x1000c0s1b0n0d1", "11:00", "Scanned"),
+  ];
+  assert!(fru_movements(event_vec, at("10:00")).is_empty());
+
+  // Removed without being seen elsewhere
+  let event_vec = vec![
+    event("x1000c0s0b0n0d0", "08:00", "Added"),
+    event("x1000c0s0b0n0d0", "11:00", "Removed"),
+  ];
+  assert!(fru_movements(event_vec, at("10:00")).is_empty());
+
+  // Moved and moved back
+  let event_vec = vec![
+    event("x1000c0s0b0n0d0", "08:00", "Added"),
+    event("x1000c0s1b0n0d1", "11:00", "Added"),
+    event("x1000c0s0b0n0d0", "12:00", "Added"),
+  ];
+  assert!(fru_movements(event_vec, at("10:00")).is_empty());
+
+  // Added during the window, it had no location at its start
+  let event_vec = vec![event("x1000c0s0b0n0d0", "11:00", "Added")];
+  assert!(fru_movements(event_vec, at("10:00")).is_empty());
+}
+
+#[tokio::test]
+async fn test_get_fru_movements() {
+  let mock = MockOchami::start().await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "GET",
+      "/Inventory/Hardware/History",
+      200,
+      Some(json!({
+        "Components": [
+          {
+            "ID": "x1000c0s1b0n0d1",
+            "History": [{
+              "ID": "x1000c0s1b0n0d1",
+              "FRUID": DIMM,
+              "Timestamp": "2024-05-02T12:00:00Z",
+              "EventType": "Added"
+            }]
+          },
+          {
+            "ID": "x1000c0s0b0n0d0",
+            "History": [
+              {
+                "ID": "x1000c0s0b0n0d0",
+                "FRUID": DIMM,
+                "Timestamp": "2024-05-02T08:00:00Z",
+                "EventType": "Added"
+              },
+              {
+                "ID": "x1000c0s0b0n0d0",
+                "FRUID": DIMM,
+                "Timestamp": "2024-05-02T11:00:00Z",
+                "EventType": "Removed"
+              }
+            ]
+          }
+        ]
+      })),
+    )
+    .await;
+
+  let movement_vec =
+    get_fru_movements(&mock.context(), at("10:00"), at("13:00"))
+      .await
+      .unwrap();
+
+  assert_eq!(movement_vec.len(), 1);
+  assert_eq!(movement_vec[0].from_xname, "x1000c0s0b0n0d0");
+  assert_eq!(movement_vec[0].to_xname, "x1000c0s1b0n0d1");
+
+  let request_vec = mock.server().received_requests().await.unwrap();
+  assert_eq!(
+    request_vec[0].url.query(),
+    Some("endtime=2024-05-02T13%3A00%3A00%2B00%3A00")
+  );
+}
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Hardware inventory event (eg: FRU added to or removed from a location)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct HardwareHistoryEvent {
+  /// Location (xname) of the FRU
+  #[serde(rename = "ID")]
+  pub id: String,
+  #[serde(rename = "FRUID")]
+  pub fru_id: String,
+  #[serde(rename = "Timestamp")]
+  pub timestamp: DateTime<Utc>,
+  /// Added, Removed, Scanned or DetectedChange
+  #[serde(rename = "EventType")]
+  pub event_type: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct HardwareHistory {
+  #[serde(rename = "ID")]
+  pub id: String,
+  #[serde(rename = "History", default)]
+  pub history: Vec<HardwareHistoryEvent>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct HardwareHistoryArray {
+  #[serde(rename = "Components", default)]
+  pub components: Vec<HardwareHistory>,
+}
+
+/// FRU found at a different location at the end of a time window than at its start
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct FruMovement {
+  pub fru_id: String,
+  pub from_xname: String,
+  pub to_xname: String,
+  /// Time the FRU was seen at its new location
+  pub timestamp: DateTime<Utc>,
+}
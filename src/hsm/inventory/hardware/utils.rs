@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::{error::Error, http::ApiContext};
+
+use super::{
+  client,
+  types::{FruMovement, HardwareHistoryEvent},
+};
+
+/// Report FRUs found at a different location at `end` than at `start`, eg: a DIMM moved
+/// to another node. FRUs removed without being seen elsewhere are not reported
+pub async fn get_fru_movements(
+  ctx: &ApiContext,
+  start: DateTime<Utc>,
+  end: DateTime<Utc>,
+) -> Result<Vec<FruMovement>, Error> {
+  let event_vec: Vec<HardwareHistoryEvent> =
+    client::get_history(ctx, None, None, None, Some(&end.to_rfc3339()))
+      .await?
+      .components
+      .into_iter()
+      .flat_map(|component| component.history)
+      .collect();
+
+  Ok(fru_movements(event_vec, start))
+}
+
+/// Compare each FRU location at `start` with its location after all events
+pub fn fru_movements(
+  mut event_vec: Vec<HardwareHistoryEvent>,
+  start: DateTime<Utc>,
+) -> Vec<FruMovement> {
+  event_vec.sort_by_key(|event| event.timestamp);
+
+  // FRU ID -> current location
+  let mut location_map: HashMap<&str, &HardwareHistoryEvent> = HashMap::new();
+  let mut start_location_map: Option<HashMap<String, String>> = None;
+
+  for event in &event_vec {
+    if event.timestamp > start && start_location_map.is_none() {
+      start_location_map = Some(snapshot(&location_map));
+    }
+
+    if event.event_type == "Removed" {
+      if location_map
+        .get(event.fru_id.as_str())
+        .is_some_and(|location| location.id == event.id)
+      {
+        location_map.remove(event.fru_id.as_str());
+      }
+    } else {
+      location_map.insert(&event.fru_id, event);
+    }
+  }
+
+  let start_location_map =
+    start_location_map.unwrap_or_else(|| snapshot(&location_map));
+
+  let mut movement_vec: Vec<FruMovement> = location_map
+    .into_iter()
+    .filter_map(|(fru_id, event)| {
+      let from_xname = start_location_map.get(fru_id)?;
+      (*from_xname != event.id).then(|| FruMovement {
+        fru_id: fru_id.to_string(),
+        from_xname: from_xname.clone(),
+        to_xname: event.id.clone(),
+        timestamp: event.timestamp,
+      })
+    })
+    .collect();
+
+  movement_vec.sort_by(|a, b| a.fru_id.cmp(&b.fru_id));
+
+  movement_vec
+}
+
+fn snapshot(
+  location_map: &HashMap<&str, &HardwareHistoryEvent>,
+) -> HashMap<String, String> {
+  location_map
+    .iter()
+    .map(|(fru_id, event)| (fru_id.to_string(), event.id.clone()))
+    .collect()
+}
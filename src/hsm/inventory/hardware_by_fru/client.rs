@@ -0,0 +1,136 @@
+use serde_json::Value;
+
+use crate::{
+  error::Error,
+  hsm::inventory::types::HWInventoryByFRU,
+  http::{ApiContext, MutationRequest},
+};
+
+pub async fn get(
+  ctx: &ApiContext,
+  fruid: Option<&str>,
+  r#type: Option<&str>,
+  manufacturer: Option<&str>,
+  partnumber: Option<&str>,
+  serialnumber: Option<&str>,
+) -> Result<Vec<HWInventoryByFRU>, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/Inventory/HardwareByFRU")?;
+
+  let response = client
+    .get(api_url)
+    .query(&[fruid, r#type, manufacturer, partnumber, serialnumber, fruid])
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn get_one(
+  ctx: &ApiContext,
+  fruid: &str,
+) -> Result<HWInventoryByFRU, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!("/Inventory/Hardware/{}", fruid))?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn delete_all(ctx: &ApiContext) -> Result<Value, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/Inventory/HardwareByFRU")?;
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn delete_one(ctx: &ApiContext, fruid: &str) -> Result<Value, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!("/Inventory/HardwareByFRU/{}", fruid))?;
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
@@ -1,7 +1,12 @@
 use serde_json::Value;
 
-use crate::{error::Error, hsm::inventory::types::HWInventoryByFRU};
+use crate::hsm::inventory::types::HWInventoryByFRU;
+use crate::{error::Error, http::ApiContext};
 
+use super::client;
+
+#[deprecated(note = "use `hardware_by_fru::client::get` with an `ApiContext`")]
+#[allow(clippy::too_many_arguments)]
 pub async fn get(
   auth_token: &str,
   base_url: &str,
@@ -12,134 +17,53 @@ pub async fn get(
   partnumber: Option<&str>,
   serialnumber: Option<&str>,
 ) -> Result<Vec<HWInventoryByFRU>, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url =
-    format!("{}/smd/hsm/v2/Inventory/HardwareByFRU", base_url);
-
-  let response = client
-    .get(api_url)
-    .query(&[fruid, r#type, manufacturer, partnumber, serialnumber, fruid])
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    fruid,
+    r#type,
+    manufacturer,
+    partnumber,
+    serialnumber,
+  )
+  .await
 }
 
+#[deprecated(
+  note = "use `hardware_by_fru::client::get_one` with an `ApiContext`"
+)]
 pub async fn get_one(
   auth_token: &str,
   base_url: &str,
   root_cert: &[u8],
   fruid: &str,
 ) -> Result<HWInventoryByFRU, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url =
-    format!("{}/smd/hsm/v2/Inventory/Hardware/{}", base_url, fruid);
-
-  let response = client.get(api_url).bearer_auth(auth_token).send().await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get_one(&ApiContext::parse(base_url, auth_token, root_cert)?, fruid)
+    .await
 }
 
+#[deprecated(
+  note = "use `hardware_by_fru::client::delete_all` with an `ApiContext`"
+)]
 pub async fn delete_all(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url =
-    base_url.to_owned() + "/smd/hsm/v2/Inventory/HardwareByFRU";
-
-  let response = client
-    .delete(api_url)
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::delete_all(&ApiContext::parse(base_url, auth_token, root_cert)?).await
 }
 
+#[deprecated(
+  note = "use `hardware_by_fru::client::delete_one` with an `ApiContext`"
+)]
 pub async fn delete_one(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   fruid: &str,
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!(
-    "{}/smd/hsm/v2/Inventory/HardwareByFRU/{}",
-    base_url, fruid
-  );
-
-  let response = client
-    .delete(api_url)
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::delete_one(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    fruid,
+  )
+  .await
 }
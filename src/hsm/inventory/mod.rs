@@ -1,5 +1,9 @@
+pub mod component_endpoint;
 pub mod ethernet_interfaces;
+pub mod export;
 pub mod hardware;
 pub mod hardware_by_fru;
 pub mod redfish_endpoint;
+#[cfg(test)]
+pub mod tests;
 pub mod types;
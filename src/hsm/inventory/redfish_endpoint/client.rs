@@ -0,0 +1,431 @@
+use std::collections::HashSet;
+
+use crate::{
+  error::Error,
+  hsm::types::{ActionResponse, ResourceUri},
+  http::{
+    is_transient_failure, new_idempotency_key, ApiContext, MutationRequest,
+    IDEMPOTENCY_KEY_HEADER,
+  },
+};
+
+use super::types::{RedfishEndpoint, RedfishEndpointArray};
+
+pub async fn get_query(
+  ctx: &ApiContext,
+  xname: &str,
+) -> Result<RedfishEndpointArray, Error> {
+  let client = ctx.client()?;
+  let api_url =
+    ctx.hsm_url(&format!("/Inventory/RedfishEndpoint/Query/{}", xname))?;
+
+  let response = client
+    .get(api_url)
+    .query(&[xname])
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn get_all(ctx: &ApiContext) -> Result<RedfishEndpointArray, Error> {
+  get(ctx, None, None, None, None, None, None, None).await
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn get(
+  ctx: &ApiContext,
+  id: Option<&str>,
+  fqdn: Option<&str>,
+  r#type: Option<&str>,
+  uuid: Option<&str>,
+  macaddr: Option<&str>,
+  ip_address: Option<&str>,
+  last_status: Option<&str>,
+) -> Result<RedfishEndpointArray, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/Inventory/RedfishEndpoints")?;
+
+  let response = client
+    .get(api_url)
+    .query(&[
+      ("id", id),
+      ("fqdn", fqdn),
+      ("type", r#type),
+      ("uuid", uuid),
+      ("macaddr", macaddr),
+      ("ipaddress", ip_address),
+      ("laststatus", last_status),
+    ])
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+/// Redfish endpoints of a list of xnames, in a single request
+pub async fn get_many(
+  ctx: &ApiContext,
+  xname_vec: &[String],
+) -> Result<RedfishEndpointArray, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/Inventory/RedfishEndpoints")?;
+
+  let query: Vec<(&str, &String)> =
+    xname_vec.iter().map(|xname| ("id", xname)).collect();
+
+  let response = client
+    .get(api_url)
+    .query(query.as_slice())
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn get_one(
+  ctx: &ApiContext,
+  xname: &str,
+) -> Result<RedfishEndpoint, Error> {
+  let client = ctx.client()?;
+  let api_url =
+    ctx.hsm_url(&format!("/Inventory/RedfishEndpoints/{}", xname))?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn post(
+  ctx: &ApiContext,
+  redfish_endpoint: RedfishEndpointArray,
+) -> Result<Vec<ResourceUri>, Error> {
+  post_with_idempotency_key(ctx, redfish_endpoint, &new_idempotency_key()).await
+}
+
+/// Same as `post`, attempts of the same logical operation share `idempotency_key`
+async fn post_with_idempotency_key(
+  ctx: &ApiContext,
+  redfish_endpoint: RedfishEndpointArray,
+  idempotency_key: &str,
+) -> Result<Vec<ResourceUri>, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/Inventory/RedfishEndpoints")?;
+
+  let response = client
+    .post(api_url)
+    .header(IDEMPOTENCY_KEY_HEADER, idempotency_key)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(&redfish_endpoint)
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+/// Same as `post`, retrying up to `max_attempts` times if HSM could not be reached or did
+/// not answer. Before each retry, endpoints already in HSM (created by a previous attempt)
+/// are removed from the request, so retries don't fail on conflicts
+pub async fn post_idempotent(
+  ctx: &ApiContext,
+  redfish_endpoint: RedfishEndpointArray,
+  max_attempts: usize,
+) -> Result<Vec<ResourceUri>, Error> {
+  let mut pending_vec = redfish_endpoint.redfish_endpoints.unwrap_or_default();
+  let mut resource_uri_vec = Vec::new();
+  let idempotency_key = new_idempotency_key();
+  let mut attempt = 1;
+
+  loop {
+    if attempt > 1 {
+      let pending_id_vec: Vec<String> = pending_vec
+        .iter()
+        .map(|redfish_endpoint| redfish_endpoint.id.clone())
+        .collect();
+
+      let existing_id_set: HashSet<String> = get_many(ctx, &pending_id_vec)
+        .await?
+        .redfish_endpoints
+        .unwrap_or_default()
+        .into_iter()
+        .map(|redfish_endpoint| redfish_endpoint.id)
+        .collect();
+
+      let mut remaining_vec = Vec::with_capacity(pending_vec.len());
+
+      for redfish_endpoint in pending_vec {
+        if existing_id_set.contains(&redfish_endpoint.id) {
+          log::info!(
+            "Redfish endpoint '{}' was created by a previous attempt",
+            redfish_endpoint.id
+          );
+          resource_uri_vec.push(ResourceUri {
+            uri: format!(
+              "{}/Inventory/RedfishEndpoints/{}",
+              ctx.service_paths.hsm, redfish_endpoint.id
+            ),
+          });
+        } else {
+          remaining_vec.push(redfish_endpoint);
+        }
+      }
+
+      pending_vec = remaining_vec;
+
+      if pending_vec.is_empty() {
+        return Ok(resource_uri_vec);
+      }
+    }
+
+    match post_with_idempotency_key(
+      ctx,
+      RedfishEndpointArray {
+        redfish_endpoints: Some(pending_vec.clone()),
+      },
+      &idempotency_key,
+    )
+    .await
+    {
+      Ok(created_vec) => {
+        resource_uri_vec.extend(created_vec);
+        return Ok(resource_uri_vec);
+      }
+      Err(e) if attempt < max_attempts && is_transient_failure(&e) => {
+        log::warn!(
+          "Attempt {}/{} to create {} redfish endpoints failed. Reason:\n{}",
+          attempt,
+          max_attempts,
+          pending_vec.len(),
+          e
+        );
+        attempt += 1;
+      }
+      Err(e) => return Err(e),
+    }
+  }
+}
+
+pub async fn put(
+  ctx: &ApiContext,
+  xname: &str,
+  redfish_endpoint: RedfishEndpoint,
+) -> Result<RedfishEndpoint, Error> {
+  let client = ctx.client()?;
+  let api_url =
+    ctx.hsm_url(&format!("/Inventory/RedfishEndpoints/{}", xname))?;
+
+  let response = client
+    .put(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(&redfish_endpoint)
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn delete_all(ctx: &ApiContext) -> Result<ActionResponse, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/Inventory/RedfishEndpoints")?;
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn delete_one(
+  ctx: &ApiContext,
+  xname: &str,
+) -> Result<ActionResponse, Error> {
+  let client = ctx.client()?;
+  let api_url =
+    ctx.hsm_url(&format!("/Inventory/RedfishEndpoints/{}", xname))?;
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+/// Trigger HSM inventory discovery of a list of redfish endpoints. If `force` is true,
+/// endpoints already being discovered are discovered again
+pub async fn post_discover(
+  ctx: &ApiContext,
+  xname_vec: &[String],
+  force: bool,
+) -> Result<Vec<ResourceUri>, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/Inventory/Discover")?;
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(&serde_json::json!({ "xnames": xname_vec, "force": force }))
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.text().await?;
+        return Err(Error::Message(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
@@ -1,59 +1,38 @@
 use serde_json::Value;
 
-use crate::error::Error;
+use crate::{error::Error, http::ApiContext};
 
-use super::types::{RedfishEndpoint, RedfishEndpointArray};
+use super::{
+  client,
+  types::{RedfishEndpoint, RedfishEndpointArray},
+};
 
+#[deprecated(
+  note = "use `redfish_endpoint::client::get_query` with an `ApiContext`"
+)]
 pub async fn get_query(
   auth_token: &str,
   base_url: &str,
   root_cert: &[u8],
   xname: &str,
 ) -> Result<RedfishEndpointArray, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!(
-    "{}/hsm/v2/Inventory/RedfishEndpoint/Query/{}",
-    base_url, xname
-  );
-
-  let response = client
-    .get(api_url)
-    .query(&[xname])
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.text().await?;
-        return Err(Error::Message(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get_query(&ApiContext::parse(base_url, auth_token, root_cert)?, xname)
+    .await
 }
 
+#[deprecated(
+  note = "use `redfish_endpoint::client::get_all` with an `ApiContext`"
+)]
 pub async fn get_all(
   auth_token: &str,
   base_url: &str,
   root_cert: &[u8],
 ) -> Result<RedfishEndpointArray, Error> {
-  get(
-    auth_token, base_url, root_cert, None, None, None, None,
-    None, None, None,
-  )
-  .await
+  client::get_all(&ApiContext::parse(base_url, auth_token, root_cert)?).await
 }
 
+#[deprecated(note = "use `redfish_endpoint::client::get` with an `ApiContext`")]
+#[allow(clippy::too_many_arguments)]
 pub async fn get(
   auth_token: &str,
   base_url: &str,
@@ -66,113 +45,51 @@ pub async fn get(
   ip_address: Option<&str>,
   last_status: Option<&str>,
 ) -> Result<RedfishEndpointArray, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url =
-    format!("{}/hsm/v2/Inventory/RedfishEndpoints", base_url);
-
-  let response = client
-    .get(api_url)
-    .query(&[
-      ("id", id),
-      ("fqdn", fqdn),
-      ("type", r#type),
-      ("uuid", uuid),
-      ("madaddr", macaddr),
-      ("ip_address", ip_address),
-      ("last_status", last_status),
-    ])
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.text().await?;
-        return Err(Error::Message(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    id,
+    fqdn,
+    r#type,
+    uuid,
+    macaddr,
+    ip_address,
+    last_status,
+  )
+  .await
 }
 
+#[deprecated(
+  note = "use `redfish_endpoint::client::get_one` with an `ApiContext`"
+)]
 pub async fn get_one(
   auth_token: &str,
   base_url: &str,
   root_cert: &[u8],
   xname: &str,
 ) -> Result<RedfishEndpoint, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!(
-    "{}/hsm/v2/Inventory/RedfishEndpoints/{}",
-    base_url, xname
-  );
-
-  let response = client.get(api_url).bearer_auth(auth_token).send().await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.text().await?;
-        return Err(Error::Message(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get_one(&ApiContext::parse(base_url, auth_token, root_cert)?, xname)
+    .await
 }
 
+#[deprecated(
+  note = "use `redfish_endpoint::client::post` with an `ApiContext`"
+)]
 pub async fn post(
   auth_token: &str,
   base_url: &str,
   root_cert: &[u8],
   redfish_endpoint: RedfishEndpointArray,
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url =
-    format!("{}/hsm/v2/Inventory/RedfishEndpoints", base_url);
-
-  let response = client
-    .post(api_url)
-    .bearer_auth(auth_token)
-    .json(&redfish_endpoint)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.text().await?;
-        return Err(Error::Message(error_payload));
-      }
-    }
-  }
+  let uri_vec = client::post(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    redfish_endpoint,
+  )
+  .await?;
 
-  response.json().await.map_err(Error::NetError)
+  Ok(serde_json::to_value(uri_vec)?)
 }
 
+#[deprecated(note = "use `redfish_endpoint::client::put` with an `ApiContext`")]
 pub async fn put(
   auth_token: &str,
   base_url: &str,
@@ -180,105 +97,43 @@ pub async fn put(
   xname: &str,
   redfish_endpoint: RedfishEndpoint,
 ) -> Result<RedfishEndpoint, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!(
-    "{}/hsm/v2/Inventory/RedfishEndpoints/{}",
-    base_url, xname
-  );
-
-  let response = client
-    .put(api_url)
-    .bearer_auth(auth_token)
-    .json(&redfish_endpoint)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.text().await?;
-        return Err(Error::Message(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::put(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    xname,
+    redfish_endpoint,
+  )
+  .await
 }
 
+#[deprecated(
+  note = "use `redfish_endpoint::client::delete_all` with an `ApiContext`"
+)]
 pub async fn delete_all(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  // NOTE: pre-existing bug — missing leading '/' before "hsm"
-  let api_url = base_url.to_owned() + "hsm/v2/Inventory/RedfishEndpoints";
-
-  let response = client
-    .delete(api_url)
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.text().await?;
-        return Err(Error::Message(error_payload));
-      }
-    }
-  }
+  let response =
+    client::delete_all(&ApiContext::parse(base_url, auth_token, root_cert)?)
+      .await?;
 
-  response.json().await.map_err(Error::NetError)
+  Ok(serde_json::to_value(response)?)
 }
 
+#[deprecated(
+  note = "use `redfish_endpoint::client::delete_one` with an `ApiContext`"
+)]
 pub async fn delete_one(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   xname: &str,
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!(
-    "{}/hsm/v2/Inventory/RedfishEndpoints/{}",
-    base_url, xname
-  );
-
-  let response = client
-    .delete(api_url)
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.text().await?;
-        return Err(Error::Message(error_payload));
-      }
-    }
-  }
+  let response = client::delete_one(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    xname,
+  )
+  .await?;
 
-  response.json().await.map_err(Error::NetError)
+  Ok(serde_json::to_value(response)?)
 }
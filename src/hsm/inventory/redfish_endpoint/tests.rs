@@ -0,0 +1,183 @@
+use std::time::Duration;
+
+use serde_json::json;
+use wiremock::{
+  matchers::{method, path},
+  Mock, ResponseTemplate,
+};
+
+use crate::{
+  hsm::inventory::redfish_endpoint::{client, types::RedfishEndpointArray},
+  http::IDEMPOTENCY_KEY_HEADER,
+  test_util::{MockOchami, MockService},
+};
+
+fn redfish_endpoint_array(xname_vec: &[&str]) -> RedfishEndpointArray {
+  let redfish_endpoint_vec: Vec<_> = xname_vec
+    .iter()
+    .map(|xname| json!({"ID": xname, "FQDN": format!("{}.local", xname)}))
+    .collect();
+
+  serde_json::from_value(json!({ "RedfishEndpoints": redfish_endpoint_vec }))
+    .unwrap()
+}
+
+fn idempotency_key_vec(request_vec: &[wiremock::Request]) -> Vec<String> {
+  request_vec
+    .iter()
+    .filter(|request| request.method.as_str() == "POST")
+    .map(|request| {
+      request.headers[IDEMPOTENCY_KEY_HEADER]
+        .to_str()
+        .unwrap()
+        .to_string()
+    })
+    .collect()
+}
+
+#[tokio::test]
+async fn test_get_query_keys() {
+  let mock = MockOchami::start().await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "GET",
+      "/Inventory/RedfishEndpoints",
+      200,
+      Some(json!({"RedfishEndpoints": []})),
+    )
+    .await;
+
+  client::get(
+    &mock.context(),
+    Some("x3000c0s1b0"),
+    None,
+    None,
+    None,
+    Some("a4:bf:01:38:ee:01"),
+    Some("10.254.1.10"),
+    Some("DiscoverOK"),
+  )
+  .await
+  .unwrap();
+
+  let request_vec = mock.server().received_requests().await.unwrap();
+  let query_vec: Vec<(String, String)> = request_vec[0]
+    .url
+    .query_pairs()
+    .map(|(key, value)| (key.to_string(), value.to_string()))
+    .collect();
+
+  assert_eq!(
+    query_vec,
+    vec![
+      ("id".to_string(), "x3000c0s1b0".to_string()),
+      ("macaddr".to_string(), "a4:bf:01:38:ee:01".to_string()),
+      ("ipaddress".to_string(), "10.254.1.10".to_string()),
+      ("laststatus".to_string(), "DiscoverOK".to_string()),
+    ]
+  );
+}
+
+#[tokio::test]
+async fn test_post_sends_a_new_idempotency_key_per_call() {
+  let mock = MockOchami::start().await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "POST",
+      "/Inventory/RedfishEndpoints",
+      201,
+      Some(json!([{"URI": "/hsm/v2/Inventory/RedfishEndpoints/x3000c0s1b0"}])),
+    )
+    .await;
+
+  let ctx = mock.context();
+
+  // Two intentional identical requests are not deduplicated
+  for _ in 0..2 {
+    client::post(&ctx, redfish_endpoint_array(&["x3000c0s1b0"]))
+      .await
+      .unwrap();
+  }
+
+  let request_vec = mock.server().received_requests().await.unwrap();
+  let key_vec = idempotency_key_vec(&request_vec);
+
+  assert_eq!(key_vec.len(), 2);
+  assert_ne!(key_vec[0], key_vec[1]);
+}
+
+#[tokio::test]
+async fn test_post_idempotent_reuses_key_and_queries_once() {
+  let mock = MockOchami::start().await;
+  let ctx = mock.context();
+  let endpoint_path = ctx.hsm_url("/Inventory/RedfishEndpoints").unwrap();
+
+  // First attempt times out after HSM created the first endpoint
+  Mock::given(method("POST"))
+    .and(path(endpoint_path.path()))
+    .respond_with(
+      ResponseTemplate::new(201)
+        .set_body_json(json!([]))
+        .set_delay(Duration::from_millis(500)),
+    )
+    .up_to_n_times(1)
+    .mount(mock.server())
+    .await;
+  Mock::given(method("POST"))
+    .and(path(endpoint_path.path()))
+    .respond_with(ResponseTemplate::new(201).set_body_json(json!([
+      {"URI": "/hsm/v2/Inventory/RedfishEndpoints/x3000c0s2b0"}
+    ])))
+    .mount(mock.server())
+    .await;
+  Mock::given(method("GET"))
+    .and(path(endpoint_path.path()))
+    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+      "RedfishEndpoints": [{"ID": "x3000c0s1b0"}]
+    })))
+    .mount(mock.server())
+    .await;
+
+  let short_timeout_ctx = ctx.clone().with_http_client(
+    ctx
+      .http_client
+      .clone()
+      .with_timeout(Some(Duration::from_millis(100))),
+  );
+
+  let resource_uri_vec = client::post_idempotent(
+    &short_timeout_ctx,
+    redfish_endpoint_array(&["x3000c0s1b0", "x3000c0s2b0"]),
+    2,
+  )
+  .await
+  .unwrap();
+
+  assert_eq!(resource_uri_vec.len(), 2);
+
+  let request_vec = mock.server().received_requests().await.unwrap();
+
+  let get_vec: Vec<_> = request_vec
+    .iter()
+    .filter(|request| request.method.as_str() == "GET")
+    .collect();
+  assert_eq!(get_vec.len(), 1);
+  assert_eq!(
+    get_vec[0].url.query(),
+    Some("id=x3000c0s1b0&id=x3000c0s2b0")
+  );
+
+  let key_vec = idempotency_key_vec(&request_vec);
+  assert_eq!(key_vec.len(), 2);
+  assert_eq!(key_vec[0], key_vec[1]);
+
+  // The retry only sends the endpoint not created yet
+  let retry_payload: serde_json::Value =
+    serde_json::from_slice(&request_vec.last().unwrap().body).unwrap();
+  assert_eq!(
+    retry_payload["RedfishEndpoints"],
+    json!([{"ID": "x3000c0s2b0", "FQDN": "x3000c0s2b0.local"}])
+  );
+}
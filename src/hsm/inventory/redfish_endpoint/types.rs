@@ -1,12 +1,17 @@
+#[cfg(feature = "backend-connector")]
 use manta_backend_dispatcher::types::hsm::inventory::{
   DiscoveryInfo as FrontEndDiscoveryInfo,
   RedfishEndpoint as FrontEndRedfishEndpoint,
   RedfishEndpointArray as FrontEndRedfishEndpointArray,
 };
 
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+use crate::hsm::inventory::types::MacAddress;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct DiscoveryInfo {
   #[serde(rename = "LastDiscoveryAttempt")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -19,6 +24,7 @@ pub struct DiscoveryInfo {
   redfish_version: Option<String>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndDiscoveryInfo> for DiscoveryInfo {
   fn from(info: FrontEndDiscoveryInfo) -> Self {
     DiscoveryInfo {
@@ -29,6 +35,7 @@ impl From<FrontEndDiscoveryInfo> for DiscoveryInfo {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndDiscoveryInfo> for DiscoveryInfo {
   fn into(self) -> FrontEndDiscoveryInfo {
     FrontEndDiscoveryInfo {
@@ -39,7 +46,8 @@ impl Into<FrontEndDiscoveryInfo> for DiscoveryInfo {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// BMC credentials are redacted in debug output
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct RedfishEndpoint {
   #[serde(rename = "ID")]
   pub id: String,
@@ -78,7 +86,8 @@ pub struct RedfishEndpoint {
   pub mac_required: Option<bool>,
   #[serde(rename = "MACAddr")]
   #[serde(skip_serializing_if = "Option::is_none")]
-  pub mac_addr: Option<String>,
+  #[serde(default, deserialize_with = "MacAddress::deserialize_lenient")]
+  pub mac_addr: Option<MacAddress>,
   #[serde(rename = "IPAddress")]
   #[serde(skip_serializing_if = "Option::is_none")]
   pub ip_address: Option<String>,
@@ -93,6 +102,7 @@ pub struct RedfishEndpoint {
   pub discovery_info: Option<DiscoveryInfo>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndRedfishEndpoint> for RedfishEndpoint {
   fn from(endpoint: FrontEndRedfishEndpoint) -> Self {
     RedfishEndpoint {
@@ -108,7 +118,9 @@ impl From<FrontEndRedfishEndpoint> for RedfishEndpoint {
       password: endpoint.password,
       use_ssdp: endpoint.use_ssdp,
       mac_required: endpoint.mac_required,
-      mac_addr: endpoint.mac_addr,
+      mac_addr: endpoint
+        .mac_addr
+        .and_then(|mac| MacAddress::parse_lenient(&mac)),
       ip_address: endpoint.ip_address,
       rediscover_on_update: endpoint.rediscover_on_update,
       template_id: endpoint.template_id,
@@ -117,6 +129,7 @@ impl From<FrontEndRedfishEndpoint> for RedfishEndpoint {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndRedfishEndpoint> for RedfishEndpoint {
   fn into(self) -> FrontEndRedfishEndpoint {
     FrontEndRedfishEndpoint {
@@ -132,7 +145,7 @@ impl Into<FrontEndRedfishEndpoint> for RedfishEndpoint {
       password: self.password,
       use_ssdp: self.use_ssdp,
       mac_required: self.mac_required,
-      mac_addr: self.mac_addr,
+      mac_addr: self.mac_addr.map(String::from),
       ip_address: self.ip_address,
       rediscover_on_update: self.rediscover_on_update,
       template_id: self.template_id,
@@ -141,13 +154,14 @@ impl Into<FrontEndRedfishEndpoint> for RedfishEndpoint {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct RedfishEndpointArray {
   #[serde(rename = "RedfishEndpoints")]
   #[serde(skip_serializing_if = "Option::is_none")]
   pub redfish_endpoints: Option<Vec<RedfishEndpoint>>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndRedfishEndpointArray> for RedfishEndpointArray {
   fn from(array: FrontEndRedfishEndpointArray) -> Self {
     RedfishEndpointArray {
@@ -158,6 +172,7 @@ impl From<FrontEndRedfishEndpointArray> for RedfishEndpointArray {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndRedfishEndpointArray> for RedfishEndpointArray {
   fn into(self) -> FrontEndRedfishEndpointArray {
     FrontEndRedfishEndpointArray {
@@ -170,3 +185,47 @@ impl Into<FrontEndRedfishEndpointArray> for RedfishEndpointArray {
     }
   }
 }
+
+impl fmt::Debug for RedfishEndpoint {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("RedfishEndpoint")
+      .field("id", &self.id)
+      .field("type", &self.r#type)
+      .field("name", &self.name)
+      .field("hostname", &self.hostname)
+      .field("domain", &self.domain)
+      .field("fqdn", &self.fqdn)
+      .field("enabled", &self.enabled)
+      .field("uuid", &self.uuid)
+      .field("user", &self.user.as_ref().map(|_| "***"))
+      .field("password", &self.password.as_ref().map(|_| "***"))
+      .field("use_ssdp", &self.use_ssdp)
+      .field("mac_required", &self.mac_required)
+      .field("mac_addr", &self.mac_addr)
+      .field("ip_address", &self.ip_address)
+      .field("rediscover_on_update", &self.rediscover_on_update)
+      .field("template_id", &self.template_id)
+      .field("discovery_info", &self.discovery_info)
+      .finish()
+  }
+}
+
+impl fmt::Display for RedfishEndpoint {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.id)?;
+
+    if let Some(fqdn) = &self.fqdn {
+      write!(f, " ({})", fqdn)?;
+    }
+
+    if let Some(last_status) = self
+      .discovery_info
+      .as_ref()
+      .and_then(|discovery_info| discovery_info.last_status.as_ref())
+    {
+      write!(f, ": {}", last_status)?;
+    }
+
+    Ok(())
+  }
+}
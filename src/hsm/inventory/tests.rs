@@ -0,0 +1,176 @@
+use serde_json::{json, Value};
+
+use crate::{
+  fixtures,
+  hsm::inventory::{
+    ethernet_interfaces::types::ComponentEthernetInterface,
+    export::{
+      csv_field, export_components, export_ethernet_interfaces,
+      export_redfish_endpoints, ExportFormat,
+    },
+    types::MacAddress,
+  },
+  test_util::{MockOchami, MockService},
+};
+
+#[test]
+fn test_mac_address_from_str() {
+  for mac_address in [
+    "aa:bb:cc:dd:ee:ff",
+    "aabbccddeeff",
+    "aa-bb-cc-dd-ee-ff",
+    "AA:BB:CC:DD:EE:FF",
+    "aabb.ccdd.eeff",
+  ] {
+    assert_eq!(
+      mac_address.parse::<MacAddress>().unwrap().as_str(),
+      "aa:bb:cc:dd:ee:ff"
+    );
+  }
+
+  for mac_address in ["", "aa:bb:cc:dd:ee", "aa:bb:cc:dd:ee:gg", "not a mac"] {
+    assert!(mac_address.parse::<MacAddress>().is_err());
+  }
+}
+
+#[test]
+fn test_mac_address_parse_lenient() {
+  assert_eq!(MacAddress::parse_lenient(""), None);
+  assert_eq!(
+    MacAddress::parse_lenient("AA-BB-CC-DD-EE-FF")
+      .unwrap()
+      .as_str(),
+    "aa:bb:cc:dd:ee:ff"
+  );
+  assert_eq!(
+    MacAddress::parse_lenient("00:11:22:33:44:55:66:77")
+      .unwrap()
+      .as_str(),
+    "00:11:22:33:44:55:66:77"
+  );
+}
+
+#[test]
+fn test_mac_address_round_trip_keeps_raw_value() {
+  let json =
+    r#"{"ID":"0011223344556677","MACAddress":"00:11:22:33:44:55:66:77"}"#;
+
+  let eth_interface: ComponentEthernetInterface =
+    serde_json::from_str(json).unwrap();
+
+  assert_eq!(serde_json::to_string(&eth_interface).unwrap(), json);
+}
+
+#[test]
+fn test_csv_field() {
+  assert_eq!(csv_field(""), "");
+  assert_eq!(csv_field("x1000c0s0b0n0"), "x1000c0s0b0n0");
+  assert_eq!(csv_field("Ready,Off"), "\"Ready,Off\"");
+  assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+  assert_eq!(csv_field("line\nbreak"), "\"line\nbreak\"");
+  assert_eq!(csv_field("carriage\rreturn"), "\"carriage\rreturn\"");
+}
+
+#[tokio::test]
+async fn test_export_components_csv() {
+  let mock = MockOchami::with_fixtures().await;
+  mock
+    .mock_json(
+      MockService::Hsm,
+      "POST",
+      "/State/Components/Query",
+      fixtures::COMPONENTS_JSON,
+    )
+    .await;
+
+  let mut output = Vec::new();
+
+  let count =
+    export_components(&mock.context(), ExportFormat::Csv, &mut output)
+      .await
+      .unwrap();
+
+  assert_eq!(count, 3);
+
+  let line_vec: Vec<&str> =
+    std::str::from_utf8(&output).unwrap().lines().collect();
+  assert_eq!(line_vec.len(), 4);
+  assert_eq!(
+    line_vec[0],
+    "ID,Type,State,Flag,Enabled,Role,SubRole,NID,Arch,Class"
+  );
+  assert!(line_vec[1].starts_with("x1000c0s0b0n0,Node,Ready,"));
+
+  let request_vec = mock.server().received_requests().await.unwrap();
+  let query = request_vec
+    .iter()
+    .find(|request| request.method.as_str() == "POST")
+    .unwrap();
+  assert_eq!(
+    query.body_json::<Value>().unwrap(),
+    json!({
+      "ComponentIDs": ["x1000c0s0b0n0", "x1000c0s0b0n1", "x3000c0s1b0n0"]
+    })
+  );
+}
+
+#[tokio::test]
+async fn test_export_ethernet_interfaces_ndjson() {
+  let mock = MockOchami::with_fixtures().await;
+
+  let mut output = Vec::new();
+
+  let count = export_ethernet_interfaces(
+    &mock.context(),
+    ExportFormat::Ndjson,
+    &mut output,
+  )
+  .await
+  .unwrap();
+
+  assert_eq!(count, 2);
+
+  let record_vec: Vec<Value> = std::str::from_utf8(&output)
+    .unwrap()
+    .lines()
+    .map(|line| serde_json::from_str(line).unwrap())
+    .collect();
+  assert_eq!(record_vec.len(), 2);
+  assert_eq!(record_vec[0]["MACAddress"], "a4:bf:01:38:ee:01");
+}
+
+#[tokio::test]
+async fn test_export_redfish_endpoints_without_credentials() {
+  let mock = MockOchami::start().await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "GET",
+      "/Inventory/RedfishEndpoints",
+      200,
+      Some(json!({
+        "RedfishEndpoints": [{
+          "ID": "x3000c0s1b0",
+          "Hostname": "x3000c0s1b0",
+          "User": "root",
+          "Password": "secret"
+        }]
+      })),
+    )
+    .await;
+
+  for format in [ExportFormat::Csv, ExportFormat::Ndjson] {
+    let mut output = Vec::new();
+
+    let count = export_redfish_endpoints(&mock.context(), format, &mut output)
+      .await
+      .unwrap();
+
+    assert_eq!(count, 1);
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("x3000c0s1b0"));
+    assert!(!output.contains("secret"));
+    assert!(!output.contains("root"));
+  }
+}
@@ -1,3 +1,4 @@
+#[cfg(feature = "backend-connector")]
 use manta_backend_dispatcher::types::{
   ArtifactSummary as FrontEndArtifactSummary,
   ArtifactType as FrontEndArtifactType, HSNNICFRUInfo as FrontEndHSNNICFRUInfo,
@@ -31,10 +32,13 @@ use manta_backend_dispatcher::types::{
 use serde::{Deserialize, Serialize};
 
 use serde_json::Value;
+use std::fmt;
 use std::str::FromStr;
 use std::string::ToString;
 use strum_macros::{AsRefStr, Display, EnumIter, EnumString, IntoStaticStr};
 
+use crate::error::Error;
+
 ///////////////////////////////////////////////////////////////////////////////
 // MESA - These are nonr official structs created from 'curl' response payload
 #[derive(
@@ -47,6 +51,9 @@ use strum_macros::{AsRefStr, Display, EnumIter, EnumString, IntoStaticStr};
   Serialize,
   Deserialize,
   Clone,
+  PartialEq,
+  Eq,
+  Hash,
 )]
 pub enum ArtifactType {
   Memory,
@@ -63,6 +70,7 @@ pub enum ArtifactType {
   RouterBMC,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndArtifactType> for ArtifactType {
   fn from(value: FrontEndArtifactType) -> Self {
     match value {
@@ -86,6 +94,7 @@ impl From<FrontEndArtifactType> for ArtifactType {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndArtifactType> for ArtifactType {
   fn into(self) -> FrontEndArtifactType {
     match self {
@@ -109,7 +118,7 @@ impl Into<FrontEndArtifactType> for ArtifactType {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct NodeSummary {
   pub xname: String,
   pub r#type: String,
@@ -366,6 +375,7 @@ impl Into<HWInvByLocNode> for NodeSummary {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndNodeSummary> for NodeSummary {
   fn from(value: FrontEndNodeSummary) -> Self {
     NodeSummary {
@@ -395,6 +405,7 @@ impl From<FrontEndNodeSummary> for NodeSummary {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndNodeSummary> for NodeSummary {
   fn into(self) -> FrontEndNodeSummary {
     FrontEndNodeSummary {
@@ -469,7 +480,7 @@ impl NodeSummary {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct ArtifactSummary {
   pub xname: String,
   pub r#type: ArtifactType,
@@ -694,6 +705,7 @@ impl Into<HWInvByLocHSNNIC> for ArtifactSummary {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndArtifactSummary> for ArtifactSummary {
   fn from(value: FrontEndArtifactSummary) -> Self {
     ArtifactSummary {
@@ -704,6 +716,7 @@ impl From<FrontEndArtifactSummary> for ArtifactSummary {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndArtifactSummary> for ArtifactSummary {
   fn into(self) -> FrontEndArtifactSummary {
     FrontEndArtifactSummary {
@@ -766,7 +779,7 @@ impl ArtifactSummary {
 
 ///////////////////////////////////////////////////////////////////////////////
 // CSM - structs from CSM API documentation. FIXME: need to address FRU structs properly with enums
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum ComponentType {
   CDU,
   CabinetCDU,
@@ -803,7 +816,7 @@ pub enum ComponentType {
   INVALID,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct ProcessorId {
   #[serde(rename = "EffectiveFamily")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -825,7 +838,7 @@ pub struct ProcessorId {
   pub vendor_id: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct RedfishProcessorFRUInfo {
   #[serde(rename = "InstructionSet")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -856,6 +869,7 @@ pub struct RedfishProcessorFRUInfo {
   pub total_threads: Option<usize>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndRedfishProcessorFRUInfo> for RedfishProcessorFRUInfo {
   fn from(value: FrontEndRedfishProcessorFRUInfo) -> Self {
     RedfishProcessorFRUInfo {
@@ -872,6 +886,7 @@ impl From<FrontEndRedfishProcessorFRUInfo> for RedfishProcessorFRUInfo {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndRedfishProcessorFRUInfo> for RedfishProcessorFRUInfo {
   fn into(self) -> FrontEndRedfishProcessorFRUInfo {
     FrontEndRedfishProcessorFRUInfo {
@@ -888,7 +903,7 @@ impl Into<FrontEndRedfishProcessorFRUInfo> for RedfishProcessorFRUInfo {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByFRUProcessor {
   #[serde(rename = "FRUID")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -905,6 +920,7 @@ pub struct HWInvByFRUProcessor {
   pub processor_fru_info: RedfishProcessorFRUInfo,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndHWInvByFRUProcessor> for HWInvByFRUProcessor {
   fn from(value: FrontEndHWInvByFRUProcessor) -> Self {
     HWInvByFRUProcessor {
@@ -919,6 +935,7 @@ impl From<FrontEndHWInvByFRUProcessor> for HWInvByFRUProcessor {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndHWInvByFRUProcessor> for HWInvByFRUProcessor {
   fn into(self) -> FrontEndHWInvByFRUProcessor {
     FrontEndHWInvByFRUProcessor {
@@ -931,7 +948,7 @@ impl Into<FrontEndHWInvByFRUProcessor> for HWInvByFRUProcessor {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct RedfishMemoryFRUInfo {
   #[serde(rename = "BaseModuleType")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -971,6 +988,7 @@ pub struct RedfishMemoryFRUInfo {
   pub serial_number: Option<String>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndRedfishMemoryFRUInfo> for RedfishMemoryFRUInfo {
   fn from(value: FrontEndRedfishMemoryFRUInfo) -> Self {
     RedfishMemoryFRUInfo {
@@ -990,6 +1008,7 @@ impl From<FrontEndRedfishMemoryFRUInfo> for RedfishMemoryFRUInfo {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndRedfishMemoryFRUInfo> for RedfishMemoryFRUInfo {
   fn into(self) -> FrontEndRedfishMemoryFRUInfo {
     FrontEndRedfishMemoryFRUInfo {
@@ -1009,7 +1028,7 @@ impl Into<FrontEndRedfishMemoryFRUInfo> for RedfishMemoryFRUInfo {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByFRUMemory {
   #[serde(rename = "FRUID")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1026,6 +1045,7 @@ pub struct HWInvByFRUMemory {
   pub memory_fru_info: RedfishMemoryFRUInfo,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndHWInvByFRUMemory> for HWInvByFRUMemory {
   fn from(value: FrontEndHWInvByFRUMemory) -> Self {
     HWInvByFRUMemory {
@@ -1038,6 +1058,7 @@ impl From<FrontEndHWInvByFRUMemory> for HWInvByFRUMemory {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndHWInvByFRUMemory> for HWInvByFRUMemory {
   fn into(self) -> FrontEndHWInvByFRUMemory {
     FrontEndHWInvByFRUMemory {
@@ -1050,7 +1071,7 @@ impl Into<FrontEndHWInvByFRUMemory> for HWInvByFRUMemory {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByFRUNodeAccel {
   #[serde(rename = "FRUID")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1068,6 +1089,7 @@ pub struct HWInvByFRUNodeAccel {
                                                     // docs, yes this is using the redfish for "processor"
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndHWInvByFRUNodeAccel> for HWInvByFRUNodeAccel {
   fn from(value: FrontEndHWInvByFRUNodeAccel) -> Self {
     HWInvByFRUNodeAccel {
@@ -1082,6 +1104,7 @@ impl From<FrontEndHWInvByFRUNodeAccel> for HWInvByFRUNodeAccel {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndHWInvByFRUNodeAccel> for HWInvByFRUNodeAccel {
   fn into(self) -> FrontEndHWInvByFRUNodeAccel {
     FrontEndHWInvByFRUNodeAccel {
@@ -1094,7 +1117,7 @@ impl Into<FrontEndHWInvByFRUNodeAccel> for HWInvByFRUNodeAccel {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct NodeHsnNicFRUInfo {
   #[serde(rename = "Manufacturer")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1113,6 +1136,7 @@ pub struct NodeHsnNicFRUInfo {
   pub serial_number: Option<String>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndHSNNICFRUInfo> for NodeHsnNicFRUInfo {
   fn from(value: FrontEndHSNNICFRUInfo) -> Self {
     NodeHsnNicFRUInfo {
@@ -1125,6 +1149,7 @@ impl From<FrontEndHSNNICFRUInfo> for NodeHsnNicFRUInfo {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndHSNNICFRUInfo> for NodeHsnNicFRUInfo {
   fn into(self) -> FrontEndHSNNICFRUInfo {
     FrontEndHSNNICFRUInfo {
@@ -1137,7 +1162,7 @@ impl Into<FrontEndHSNNICFRUInfo> for NodeHsnNicFRUInfo {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByFRUHSNNIC {
   #[serde(rename = "FRUID")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1154,6 +1179,7 @@ pub struct HWInvByFRUHSNNIC {
   pub node_hsn_nic_fru_info: NodeHsnNicFRUInfo,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndHWInvByFRUHSNNIC> for HWInvByFRUHSNNIC {
   fn from(value: FrontEndHWInvByFRUHSNNIC) -> Self {
     HWInvByFRUHSNNIC {
@@ -1166,6 +1192,7 @@ impl From<FrontEndHWInvByFRUHSNNIC> for HWInvByFRUHSNNIC {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndHWInvByFRUHSNNIC> for HWInvByFRUHSNNIC {
   fn into(self) -> FrontEndHWInvByFRUHSNNIC {
     FrontEndHWInvByFRUHSNNIC {
@@ -1178,7 +1205,7 @@ impl Into<FrontEndHWInvByFRUHSNNIC> for HWInvByFRUHSNNIC {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInventoryByFRU {
   #[serde(rename = "FRUID")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1193,7 +1220,7 @@ pub struct HWInventoryByFRU {
   pub hw_inventory_by_fru_type: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct RedfishChassisLocationInfo {
   #[serde(rename = "Id")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1209,7 +1236,7 @@ pub struct RedfishChassisLocationInfo {
   pub hostname: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocChassis {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1238,7 +1265,7 @@ pub struct HWInvByLocChassis {
   pub router_modules: Option<HWInvByLocRouterModule>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocNodeEnclosure {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1261,7 +1288,7 @@ pub struct HWInvByLocNodeEnclosure {
   pub node_enclosure_location_info: Option<RedfishChassisLocationInfo>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocComputeModule {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1287,7 +1314,7 @@ pub struct HWInvByLocComputeModule {
   pub node_enclosures: Option<HWInvByLocNodeEnclosure>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocHSNBoard {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1310,7 +1337,7 @@ pub struct HWInvByLocHSNBoard {
   pub hsn_board_location_info: Option<RedfishChassisLocationInfo>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocRouterModule {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1334,7 +1361,7 @@ pub struct HWInvByLocRouterModule {
   pub hsn_boards: Option<HWInvByLocHSNBoard>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocCabinet {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1360,7 +1387,7 @@ pub struct HWInvByLocCabinet {
   pub chassis: Option<HWInvByLocChassis>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocMgmtSwitch {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1383,7 +1410,7 @@ pub struct HWInvByLocMgmtSwitch {
   pub mgmt_switch_location_info: Option<RedfishChassisLocationInfo>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocMgmtHLSwitch {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1406,7 +1433,7 @@ pub struct HWInvByLocMgmtHLSwitch {
   pub mgmt_hl_switch_location_info: Option<RedfishChassisLocationInfo>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocCDUMgmtSwitch {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1429,7 +1456,7 @@ pub struct HWInvByLocCDUMgmtSwitch {
   pub cdu_mgmt_switch_location_info: Option<RedfishChassisLocationInfo>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct ProcessorSummary {
   #[serde(rename = "Count")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1439,6 +1466,7 @@ pub struct ProcessorSummary {
   model: Option<String>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndProcessorSummary> for ProcessorSummary {
   fn from(value: FrontEndProcessorSummary) -> Self {
     ProcessorSummary {
@@ -1448,6 +1476,7 @@ impl From<FrontEndProcessorSummary> for ProcessorSummary {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndProcessorSummary> for ProcessorSummary {
   fn into(self) -> FrontEndProcessorSummary {
     FrontEndProcessorSummary {
@@ -1457,13 +1486,14 @@ impl Into<FrontEndProcessorSummary> for ProcessorSummary {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct MemorySummary {
   #[serde(rename = "TotalSystemMemoryGiB")]
   #[serde(skip_serializing_if = "Option::is_none")]
   pub total_system_memory_gib: Option<u32>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndMemorySummary> for MemorySummary {
   fn from(value: FrontEndMemorySummary) -> Self {
     MemorySummary {
@@ -1472,6 +1502,7 @@ impl From<FrontEndMemorySummary> for MemorySummary {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndMemorySummary> for MemorySummary {
   fn into(self) -> FrontEndMemorySummary {
     FrontEndMemorySummary {
@@ -1480,7 +1511,7 @@ impl Into<FrontEndMemorySummary> for MemorySummary {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct RedfishSystemLocationInfo {
   #[serde(rename = "Id")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1502,6 +1533,7 @@ pub struct RedfishSystemLocationInfo {
   pub memory_summary: Option<MemorySummary>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndRedfishSystemLocationInfo> for RedfishSystemLocationInfo {
   fn from(value: FrontEndRedfishSystemLocationInfo) -> Self {
     RedfishSystemLocationInfo {
@@ -1515,6 +1547,7 @@ impl From<FrontEndRedfishSystemLocationInfo> for RedfishSystemLocationInfo {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndRedfishSystemLocationInfo> for RedfishSystemLocationInfo {
   fn into(self) -> FrontEndRedfishSystemLocationInfo {
     FrontEndRedfishSystemLocationInfo {
@@ -1528,7 +1561,7 @@ impl Into<FrontEndRedfishSystemLocationInfo> for RedfishSystemLocationInfo {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct RedfishProcessorLocationInfo {
   #[serde(rename = "Id")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1544,6 +1577,7 @@ pub struct RedfishProcessorLocationInfo {
   pub socket: Option<String>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndRedfishProcessorLocationInfo>
   for RedfishProcessorLocationInfo
 {
@@ -1557,6 +1591,7 @@ impl From<FrontEndRedfishProcessorLocationInfo>
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndRedfishProcessorLocationInfo>
   for RedfishProcessorLocationInfo
 {
@@ -1570,7 +1605,7 @@ impl Into<FrontEndRedfishProcessorLocationInfo>
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocProcessor {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1592,6 +1627,7 @@ pub struct HWInvByLocProcessor {
   pub processor_location_info: RedfishProcessorLocationInfo,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndHWInvByLocProcessor> for HWInvByLocProcessor {
   fn from(value: FrontEndHWInvByLocProcessor) -> Self {
     HWInvByLocProcessor {
@@ -1608,6 +1644,7 @@ impl From<FrontEndHWInvByLocProcessor> for HWInvByLocProcessor {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndHWInvByLocProcessor> for HWInvByLocProcessor {
   fn into(self) -> FrontEndHWInvByLocProcessor {
     FrontEndHWInvByLocProcessor {
@@ -1621,7 +1658,7 @@ impl Into<FrontEndHWInvByLocProcessor> for HWInvByLocProcessor {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocNodeAccel {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1645,6 +1682,7 @@ pub struct HWInvByLocNodeAccel {
                                                                       // docs, yes this is using the redfish for "processor"
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndHWInvByLocNodeAccel> for HWInvByLocNodeAccel {
   fn from(value: FrontEndHWInvByLocNodeAccel) -> Self {
     HWInvByLocNodeAccel {
@@ -1661,6 +1699,7 @@ impl From<FrontEndHWInvByLocNodeAccel> for HWInvByLocNodeAccel {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndHWInvByLocNodeAccel> for HWInvByLocNodeAccel {
   fn into(self) -> FrontEndHWInvByLocNodeAccel {
     FrontEndHWInvByLocNodeAccel {
@@ -1674,7 +1713,7 @@ impl Into<FrontEndHWInvByLocNodeAccel> for HWInvByLocNodeAccel {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct RedfishDriveLocationInfo {
   #[serde(rename = "Id")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1687,7 +1726,7 @@ pub struct RedfishDriveLocationInfo {
   pub description: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocDrive {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1710,7 +1749,7 @@ pub struct HWInvByLocDrive {
   pub drive_location_info: Option<RedfishDriveLocationInfo>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct MemoryLocation {
   #[serde(rename = "Socket")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1726,6 +1765,7 @@ pub struct MemoryLocation {
   pub slot: Option<u32>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndMemoryLocation> for MemoryLocation {
   fn from(value: FrontEndMemoryLocation) -> Self {
     MemoryLocation {
@@ -1737,6 +1777,7 @@ impl From<FrontEndMemoryLocation> for MemoryLocation {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndMemoryLocation> for MemoryLocation {
   fn into(self) -> FrontEndMemoryLocation {
     FrontEndMemoryLocation {
@@ -1748,7 +1789,7 @@ impl Into<FrontEndMemoryLocation> for MemoryLocation {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct RedfishMemoryLocationInfo {
   #[serde(rename = "Id")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1764,6 +1805,7 @@ pub struct RedfishMemoryLocationInfo {
   pub memory_location: Option<MemoryLocation>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndRedfishMemoryLocationInfo> for RedfishMemoryLocationInfo {
   fn from(value: FrontEndRedfishMemoryLocationInfo) -> Self {
     RedfishMemoryLocationInfo {
@@ -1775,6 +1817,7 @@ impl From<FrontEndRedfishMemoryLocationInfo> for RedfishMemoryLocationInfo {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndRedfishMemoryLocationInfo> for RedfishMemoryLocationInfo {
   fn into(self) -> FrontEndRedfishMemoryLocationInfo {
     FrontEndRedfishMemoryLocationInfo {
@@ -1786,7 +1829,7 @@ impl Into<FrontEndRedfishMemoryLocationInfo> for RedfishMemoryLocationInfo {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocMemory {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1808,6 +1851,7 @@ pub struct HWInvByLocMemory {
   pub memory_location_info: RedfishMemoryLocationInfo,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndHWInvByLocMemory> for HWInvByLocMemory {
   fn from(value: FrontEndHWInvByLocMemory) -> Self {
     HWInvByLocMemory {
@@ -1824,6 +1868,7 @@ impl From<FrontEndHWInvByLocMemory> for HWInvByLocMemory {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndHWInvByLocMemory> for HWInvByLocMemory {
   fn into(self) -> FrontEndHWInvByLocMemory {
     FrontEndHWInvByLocMemory {
@@ -1837,7 +1882,7 @@ impl Into<FrontEndHWInvByLocMemory> for HWInvByLocMemory {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct RedfishNodeAccelRiserLocationInfo {
   #[serde(rename = "Name")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1847,7 +1892,7 @@ pub struct RedfishNodeAccelRiserLocationInfo {
   pub description: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocNodeAccelRiser {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1870,7 +1915,7 @@ pub struct HWInvByLocNodeAccelRiser {
   pub node_accel_riser_location_info: Option<RedfishNodeAccelRiserLocationInfo>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HSNNICLocationInfo {
   #[serde(rename = "Id")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1883,6 +1928,7 @@ pub struct HSNNICLocationInfo {
   pub description: Option<String>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndHSNNICLocationInfo> for HSNNICLocationInfo {
   fn from(value: FrontEndHSNNICLocationInfo) -> Self {
     HSNNICLocationInfo {
@@ -1893,6 +1939,7 @@ impl From<FrontEndHSNNICLocationInfo> for HSNNICLocationInfo {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndHSNNICLocationInfo> for HSNNICLocationInfo {
   fn into(self) -> FrontEndHSNNICLocationInfo {
     FrontEndHSNNICLocationInfo {
@@ -1903,7 +1950,7 @@ impl Into<FrontEndHSNNICLocationInfo> for HSNNICLocationInfo {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocHSNNIC {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1927,6 +1974,7 @@ pub struct HWInvByLocHSNNIC {
   pub hsn_nic_location_info: HSNNICLocationInfo,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndHWInvByLocHSNNIC> for HWInvByLocHSNNIC {
   fn from(value: FrontEndHWInvByLocHSNNIC) -> Self {
     HWInvByLocHSNNIC {
@@ -1943,6 +1991,7 @@ impl From<FrontEndHWInvByLocHSNNIC> for HWInvByLocHSNNIC {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndHWInvByLocHSNNIC> for HWInvByLocHSNNIC {
   fn into(self) -> FrontEndHWInvByLocHSNNIC {
     FrontEndHWInvByLocHSNNIC {
@@ -1956,14 +2005,14 @@ impl Into<FrontEndHWInvByLocHSNNIC> for HWInvByLocHSNNIC {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Hardware {
   #[serde(rename = "Hardware")]
   #[serde(skip_serializing_if = "Option::is_none")]
   pub hardware: Option<Vec<HWInvByLocNode>>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocNode {
   #[serde(rename = "ID")]
   pub id: String,
@@ -2004,6 +2053,7 @@ pub struct HWInvByLocNode {
   pub node_hsn_nics: Option<Vec<HWInvByLocHSNNIC>>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndHWInvByLocNode> for HWInvByLocNode {
   fn from(value: FrontEndHWInvByLocNode) -> Self {
     HWInvByLocNode {
@@ -2043,6 +2093,7 @@ impl From<FrontEndHWInvByLocNode> for HWInvByLocNode {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndHWInvByLocNode> for HWInvByLocNode {
   fn into(self) -> FrontEndHWInvByLocNode {
     FrontEndHWInvByLocNode {
@@ -2076,7 +2127,7 @@ impl Into<FrontEndHWInvByLocNode> for HWInvByLocNode {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct RedfishPDULocationInfo {
   #[serde(rename = "Id")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -2092,7 +2143,7 @@ pub struct RedfishPDULocationInfo {
   pub uuid: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct RedfishOutletLocationInfo {
   #[serde(rename = "Id")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -2105,7 +2156,7 @@ pub struct RedfishOutletLocationInfo {
   pub description: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocOutlet {
   #[serde(rename = "ID")]
   pub id: String,
@@ -2128,7 +2179,7 @@ pub struct HWInvByLocOutlet {
   pub outlet_location_info: Option<RedfishOutletLocationInfo>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocPDU {
   #[serde(rename = "ID")]
   pub id: String,
@@ -2154,7 +2205,7 @@ pub struct HWInvByLocPDU {
   pub cabinet_pdu_power_connectors: Option<Vec<HWInvByLocOutlet>>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct RedfishCMMRectifierLocationInfo {
   #[serde(rename = "Name")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -2164,7 +2215,7 @@ pub struct RedfishCMMRectifierLocationInfo {
   pub firmware_version: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocCMMRectifier {
   #[serde(rename = "ID")]
   pub id: String,
@@ -2187,7 +2238,7 @@ pub struct HWInvByLocCMMRectifier {
   cmm_rectifier_location_info: Option<RedfishCMMRectifierLocationInfo>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct RedfishNodeEnclosurePowerSupplyLocationInfo {
   #[serde(rename = "Name")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -2197,7 +2248,7 @@ pub struct RedfishNodeEnclosurePowerSupplyLocationInfo {
   pub firmware_version: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocNodePowerSupply {
   #[serde(rename = "ID")]
   pub id: String,
@@ -2221,7 +2272,7 @@ pub struct HWInvByLocNodePowerSupply {
     Option<RedfishNodeEnclosurePowerSupplyLocationInfo>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct RedfishManagerLocationInfo {
   #[serde(rename = "Id")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -2243,7 +2294,7 @@ pub struct RedfishManagerLocationInfo {
   pub firmware_version: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocNodeBMC {
   #[serde(rename = "ID")]
   pub id: String,
@@ -2266,7 +2317,7 @@ pub struct HWInvByLocNodeBMC {
   pub node_bmc_location_info: Option<RedfishManagerLocationInfo>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByLocRouterBMC {
   #[serde(rename = "ID")]
   pub id: String,
@@ -2295,6 +2346,7 @@ pub struct HWInventoryList {
     pub hw_inventory: Vec<HWInventory>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndHWInventoryList> for HWInventoryList {
     fn from(value: FrontEndHWInventoryList) -> Self {
         HWInventoryList {
@@ -2307,6 +2359,7 @@ impl From<FrontEndHWInventoryList> for HWInventoryList {
     }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndHWInventoryList> for HWInventoryList {
     fn into(self) -> FrontEndHWInventoryList {
         FrontEndHWInventoryList {
@@ -2319,7 +2372,7 @@ impl Into<FrontEndHWInventoryList> for HWInventoryList {
     }
 } */
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInventory {
   #[serde(rename = "XName")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -2395,6 +2448,7 @@ pub struct HWInventory {
   pub router_bmc: Option<Vec<HWInvByLocRouterBMC>>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndHWInventory> for HWInventory {
   fn from(value: FrontEndHWInventory) -> Self {
     HWInventory {
@@ -2440,6 +2494,7 @@ impl From<FrontEndHWInventory> for HWInventory {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndHWInventory> for HWInventory {
   fn into(self) -> FrontEndHWInventory {
     FrontEndHWInventory {
@@ -2485,7 +2540,7 @@ impl Into<FrontEndHWInventory> for HWInventory {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInvByFRUNode {
   #[serde(rename = "FRUID")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -2502,6 +2557,7 @@ pub struct HWInvByFRUNode {
   pub node_fru_info: RedfishSystemFRUInfo,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndHWInvByFRUNode> for HWInvByFRUNode {
   fn from(value: FrontEndHWInvByFRUNode) -> Self {
     HWInvByFRUNode {
@@ -2514,6 +2570,7 @@ impl From<FrontEndHWInvByFRUNode> for HWInvByFRUNode {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndHWInvByFRUNode> for HWInvByFRUNode {
   fn into(self) -> FrontEndHWInvByFRUNode {
     FrontEndHWInvByFRUNode {
@@ -2526,7 +2583,7 @@ impl Into<FrontEndHWInvByFRUNode> for HWInvByFRUNode {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct RedfishSystemFRUInfo {
   #[serde(rename = "AssetTag")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -2557,6 +2614,7 @@ pub struct RedfishSystemFRUInfo {
   pub uuid: Option<String>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndRedfishSystemFRUInfo> for RedfishSystemFRUInfo {
   fn from(value: FrontEndRedfishSystemFRUInfo) -> Self {
     RedfishSystemFRUInfo {
@@ -2573,6 +2631,7 @@ impl From<FrontEndRedfishSystemFRUInfo> for RedfishSystemFRUInfo {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndRedfishSystemFRUInfo> for RedfishSystemFRUInfo {
   fn into(self) -> FrontEndRedfishSystemFRUInfo {
     FrontEndRedfishSystemFRUInfo {
@@ -2589,7 +2648,7 @@ impl Into<FrontEndRedfishSystemFRUInfo> for RedfishSystemFRUInfo {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct NodeLocationInfo {
   #[serde(rename = "Id")]
   pub id: String,
@@ -2610,6 +2669,7 @@ pub struct NodeLocationInfo {
   pub memory_summary: Option<MemorySummary>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndNodeLocationInfo> for NodeLocationInfo {
   fn from(value: FrontEndNodeLocationInfo) -> Self {
     NodeLocationInfo {
@@ -2623,6 +2683,7 @@ impl From<FrontEndNodeLocationInfo> for NodeLocationInfo {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndNodeLocationInfo> for NodeLocationInfo {
   fn into(self) -> FrontEndNodeLocationInfo {
     FrontEndNodeLocationInfo {
@@ -2636,7 +2697,7 @@ impl Into<FrontEndNodeLocationInfo> for NodeLocationInfo {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[serde(untagged)] // <-- this is important. More info https://serde.rs/enum-representations.html#untagged
 pub enum HWInventoryByLocation {
   /* HWInvByLocCabinet(HWInvByLocCabinet),
@@ -2663,6 +2724,7 @@ pub enum HWInventoryByLocation {
   HWInvByLocRouterBMC(HWInvByLocRouterBMC), */
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndHWInventoryByLocation> for HWInventoryByLocation {
   fn from(f: FrontEndHWInventoryByLocation) -> Self {
     match f {
@@ -2706,6 +2768,7 @@ impl From<FrontEndHWInventoryByLocation> for HWInventoryByLocation {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndHWInventoryByLocation> for HWInventoryByLocation {
   fn into(self) -> FrontEndHWInventoryByLocation {
     match self {
@@ -2731,13 +2794,14 @@ impl Into<FrontEndHWInventoryByLocation> for HWInventoryByLocation {
 }
 
 /// struct used in POST and GET endpoints that manage multiple instances of 'HWInventoryByLocation'
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct HWInventoryByLocationList {
   #[serde(rename = "Hardware")]
   #[serde(skip_serializing_if = "Option::is_none")]
   pub hardware: Option<Vec<HWInventoryByLocation>>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndHWInventoryByLocationList> for HWInventoryByLocationList {
   fn from(value: FrontEndHWInventoryByLocationList) -> Self {
     HWInventoryByLocationList {
@@ -2751,6 +2815,7 @@ impl From<FrontEndHWInventoryByLocationList> for HWInventoryByLocationList {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndHWInventoryByLocationList> for HWInventoryByLocationList {
   fn into(self) -> FrontEndHWInventoryByLocationList {
     FrontEndHWInventoryByLocationList {
@@ -2765,3 +2830,96 @@ impl Into<FrontEndHWInventoryByLocationList> for HWInventoryByLocationList {
     }
   }
 }
+
+/// MAC address normalized to lowercase colon separated hex (eg: a4:bf:01:2c:3d:4e).
+/// Parses colon, dash and dot separated formats as well as bare hex strings
+#[derive(
+  Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(try_from = "String", into = "String")]
+pub struct MacAddress(String);
+
+impl MacAddress {
+  pub fn as_str(&self) -> &str {
+    &self.0
+  }
+
+  /// MAC address without separators, the format HSM uses as ethernet interface ID
+  pub fn to_id(&self) -> String {
+    self.0.replace(':', "")
+  }
+
+  /// MAC address as returned by HSM. Empty strings are treated as missing, values which
+  /// are not EUI-48 MAC addresses (eg: EUI-64 addresses of InfiniBand interfaces) are
+  /// kept as they are so they are not lost when the resource is sent back
+  pub fn parse_lenient(value: &str) -> Option<MacAddress> {
+    if value.trim().is_empty() {
+      return None;
+    }
+
+    Some(value.parse().unwrap_or_else(|_| {
+      log::debug!("'{}' is not an EUI-48 MAC address, keep it as is", value);
+      MacAddress(value.to_string())
+    }))
+  }
+
+  /// Deserialize an optional MAC address with `parse_lenient`
+  pub fn deserialize_lenient<'de, D>(
+    deserializer: D,
+  ) -> Result<Option<MacAddress>, D::Error>
+  where
+    D: serde::Deserializer<'de>,
+  {
+    let value_opt: Option<String> = Option::deserialize(deserializer)?;
+    Ok(value_opt.and_then(|value| MacAddress::parse_lenient(&value)))
+  }
+}
+
+impl FromStr for MacAddress {
+  type Err = Error;
+
+  fn from_str(mac_address: &str) -> Result<Self, Self::Err> {
+    let hex: String = mac_address
+      .trim()
+      .chars()
+      .filter(|c| !matches!(c, ':' | '-' | '.'))
+      .collect();
+
+    if hex.len() != 12 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+      return Err(Error::Message(format!(
+        "Invalid MAC address '{}'",
+        mac_address
+      )));
+    }
+
+    let hex = hex.to_lowercase();
+
+    Ok(MacAddress(
+      (0..12)
+        .step_by(2)
+        .map(|i| &hex[i..i + 2])
+        .collect::<Vec<&str>>()
+        .join(":"),
+    ))
+  }
+}
+
+impl TryFrom<String> for MacAddress {
+  type Error = Error;
+
+  fn try_from(mac_address: String) -> Result<Self, Self::Error> {
+    mac_address.parse()
+  }
+}
+
+impl From<MacAddress> for String {
+  fn from(mac_address: MacAddress) -> Self {
+    mac_address.0
+  }
+}
+
+impl fmt::Display for MacAddress {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
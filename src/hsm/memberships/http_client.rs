@@ -1,9 +1,9 @@
-use serde_json::Value;
+use crate::{error::Error, http::ApiContext};
 
-use crate::error::Error;
-
-use super::types::Membership;
+use super::{client, types::Membership};
 
+#[deprecated(note = "use `memberships::client::get` with an `ApiContext`")]
+#[allow(clippy::too_many_arguments)]
 pub async fn get(
   shasta_token: &str,
   shasta_base_url: &str,
@@ -25,85 +25,40 @@ pub async fn get(
   partition: Option<&str>,
   group: Option<&str>,
 ) -> Result<Vec<Membership>, Error> {
-  let client = crate::http::build_client(shasta_root_cert)?;
-  let api_url = format!("{}/smd/hsm/v2/memberships", shasta_base_url);
-
-  let response = client
-    .get(api_url)
-    .query(&[
-      id,
-      r#type,
-      state,
-      flag,
-      role,
-      subrole,
-      enabled,
-      softwarestatus,
-      subtype,
-      arch,
-      class,
-      nid,
-      nid_start,
-      nid_end,
-      partition,
-      group,
-    ])
-    .header("Authorization", format!("Bearer {}", shasta_token))
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get(
+    &ApiContext::parse(shasta_base_url, shasta_token, shasta_root_cert)?,
+    id,
+    r#type,
+    state,
+    flag,
+    role,
+    subrole,
+    enabled,
+    softwarestatus,
+    subtype,
+    arch,
+    class,
+    nid,
+    nid_start,
+    nid_end,
+    partition,
+    group,
+  )
+  .await
 }
 
+#[deprecated(
+  note = "use `memberships::client::get_xname` with an `ApiContext`"
+)]
 pub async fn get_xname(
   shasta_token: &str,
   shasta_base_url: &str,
   shasta_root_cert: &[u8],
   xname: &str,
 ) -> Result<Membership, Error> {
-  log::info!("Get membership of node '{}'", xname);
-
-  let client = crate::http::build_client(shasta_root_cert)?;
-  let api_url =
-    format!("{}/smd/hsm/v2/memberships/{}", shasta_base_url, xname);
-
-  let response = client
-    .get(api_url)
-    .header("Authorization", format!("Bearer {}", shasta_token))
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get_xname(
+    &ApiContext::parse(shasta_base_url, shasta_token, shasta_root_cert)?,
+    xname,
+  )
+  .await
 }
@@ -1,3 +1,4 @@
+pub mod cache;
 pub mod component;
 pub mod defaults;
 pub mod group;
@@ -5,4 +6,7 @@ pub mod inventory;
 pub mod memberships;
 pub mod node_map;
 pub mod partition;
+pub mod service;
 pub mod state;
+pub mod subscription;
+pub mod types;
@@ -0,0 +1,199 @@
+use serde_json::Value;
+
+use crate::{
+  error::Error,
+  http::{ApiContext, MutationRequest},
+};
+
+use super::types::{NodeMap, NodeMapArray};
+
+pub async fn get_all(ctx: &ApiContext) -> Result<NodeMapArray, Error> {
+  get(ctx).await
+}
+
+pub async fn get(ctx: &ApiContext) -> Result<NodeMapArray, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/Defaults/NodeMaps")?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn get_one(ctx: &ApiContext, xname: &str) -> Result<NodeMap, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!("/Defaults/NodeMaps/{}", xname))?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn post(
+  ctx: &ApiContext,
+  node_maps: NodeMapArray,
+) -> Result<Value, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/Defaults/NodeMaps")?;
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(&node_maps)
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn put(
+  ctx: &ApiContext,
+  xname: &str,
+  node_map: NodeMap,
+) -> Result<Value, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!("/Defaults/NodeMaps/{}", xname))?;
+
+  let response = client
+    .put(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(&node_map)
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn delete_one(ctx: &ApiContext, xname: &str) -> Result<Value, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!("/Defaults/NodeMaps/{}", xname))?;
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn delete(ctx: &ApiContext) -> Result<Value, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/Defaults/NodeMaps")?;
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
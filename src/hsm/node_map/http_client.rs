@@ -1,112 +1,56 @@
 use serde_json::Value;
 
-use crate::error::Error;
+use crate::{error::Error, http::ApiContext};
 
-use super::types::{NodeMap, NodeMapArray};
+use super::{
+  client,
+  types::{NodeMap, NodeMapArray},
+};
 
+#[deprecated(note = "use `node_map::client::get_all` with an `ApiContext`")]
 pub async fn get_all(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
 ) -> Result<NodeMapArray, Error> {
-  get(base_url, auth_token, root_cert).await
+  client::get_all(&ApiContext::parse(base_url, auth_token, root_cert)?).await
 }
 
+#[deprecated(note = "use `node_map::client::get` with an `ApiContext`")]
 pub async fn get(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
 ) -> Result<NodeMapArray, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!("{}/hsm/v2/Defaults/NodeMaps", base_url);
-
-  let response = client.get(api_url).bearer_auth(auth_token).send().await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get(&ApiContext::parse(base_url, auth_token, root_cert)?).await
 }
 
+#[deprecated(note = "use `node_map::client::get_one` with an `ApiContext`")]
 pub async fn get_one(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   xname: &str,
 ) -> Result<NodeMap, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url =
-    format!("{}/hsm/v2/Defaults/NodeMaps/{}", base_url, xname);
-
-  let response = client.get(api_url).bearer_auth(auth_token).send().await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get_one(&ApiContext::parse(base_url, auth_token, root_cert)?, xname)
+    .await
 }
 
+#[deprecated(note = "use `node_map::client::post` with an `ApiContext`")]
 pub async fn post(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   node_maps: NodeMapArray,
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = base_url.to_owned() + "/hsm/v2/Defaults/NodeMaps";
-
-  let response = client
-    .post(api_url)
-    .bearer_auth(auth_token)
-    .json(&node_maps)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::post(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    node_maps,
+  )
+  .await
 }
 
+#[deprecated(note = "use `node_map::client::put` with an `ApiContext`")]
 pub async fn put(
   base_url: &str,
   auth_token: &str,
@@ -114,100 +58,33 @@ pub async fn put(
   xname: &str,
   node_map: NodeMap,
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url =
-    format!("{}/hsm/v2/Defaults/NodeMaps/{}", base_url, xname);
-
-  let response = client
-    .put(api_url)
-    .bearer_auth(auth_token)
-    .json(&node_map)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::put(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    xname,
+    node_map,
+  )
+  .await
 }
 
+#[deprecated(note = "use `node_map::client::delete_one` with an `ApiContext`")]
 pub async fn delete_one(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   xname: &str,
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url =
-    format!("{}/hsm/v2/Defaults/NodeMaps/{}", base_url, xname);
-
-  let response = client
-    .delete(api_url)
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::delete_one(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    xname,
+  )
+  .await
 }
 
+#[deprecated(note = "use `node_map::client::delete` with an `ApiContext`")]
 pub async fn delete(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!("{}/hsm/v2/Defaults/NodeMaps", base_url);
-
-  let response = client
-    .delete(api_url)
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::delete(&ApiContext::parse(base_url, auth_token, root_cert)?).await
 }
@@ -1,9 +1,13 @@
 use serde_json::Value;
 
-use crate::error::Error;
+use crate::{error::Error, http::ApiContext};
 
-use super::types::{Member, Partition};
+use super::{
+  client,
+  types::{Member, Partition},
+};
 
+#[deprecated(note = "use `partition::client::get` with an `ApiContext`")]
 pub async fn get(
   base_url: &str,
   auth_token: &str,
@@ -11,161 +15,70 @@ pub async fn get(
   name: Option<&str>,
   tag: Option<&str>,
 ) -> Result<Vec<Partition>, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!("{}/hsm/v2/partitions", base_url);
-
-  let response = client
-    .get(api_url)
-    .query(&[name, tag])
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    name,
+    tag,
+  )
+  .await
 }
 
+#[deprecated(note = "use `partition::client::get_one` with an `ApiContext`")]
 pub async fn get_one(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   partition_name: &str,
 ) -> Result<Partition, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url =
-    format!("{}/hsm/v2/partitions/{}", base_url, partition_name);
-
-  let response = client.get(api_url).bearer_auth(auth_token).send().await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get_one(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    partition_name,
+  )
+  .await
 }
 
+#[deprecated(note = "use `partition::client::get_names` with an `ApiContext`")]
 pub async fn get_names(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
 ) -> Result<Vec<String>, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!("{}/hsm/v2/partitions/names", base_url);
-
-  let response = client.get(api_url).bearer_auth(auth_token).send().await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get_names(&ApiContext::parse(base_url, auth_token, root_cert)?).await
 }
 
+#[deprecated(
+  note = "use `partition::client::get_members` with an `ApiContext`"
+)]
 pub async fn get_members(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   partition_name: &str,
 ) -> Result<Member, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url =
-    format!("{}/hsm/v2/partitions/{}/members", base_url, partition_name);
-
-  let response = client.get(api_url).bearer_auth(auth_token).send().await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get_members(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    partition_name,
+  )
+  .await
 }
 
+#[deprecated(note = "use `partition::client::post` with an `ApiContext`")]
 pub async fn post(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   partition: Partition,
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = base_url.to_owned() + "/hsm/v2/partitions";
-
-  let response = client
-    .post(api_url)
-    .bearer_auth(auth_token)
-    .json(&partition)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::post(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    partition,
+  )
+  .await
 }
 
+#[deprecated(
+  note = "use `partition::client::post_members` with an `ApiContext`"
+)]
 pub async fn post_members(
   base_url: &str,
   auth_token: &str,
@@ -173,71 +86,31 @@ pub async fn post_members(
   partition_name: &str,
   members: Member,
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url =
-    format!("{}/hsm/v2/partitions/{}/members", base_url, partition_name);
-
-  let response = client
-    .post(api_url)
-    .bearer_auth(auth_token)
-    .json(&members)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::post_members(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    partition_name,
+    members,
+  )
+  .await
 }
 
+#[deprecated(note = "use `partition::client::delete_one` with an `ApiContext`")]
 pub async fn delete_one(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   partition_name: &str,
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url =
-    format!("{}/hsm/v2/partitions/{}", base_url, partition_name);
-
-  let response = client
-    .delete(api_url)
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::delete_one(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    partition_name,
+  )
+  .await
 }
 
+#[deprecated(
+  note = "use `partition::client::delete_member` with an `ApiContext`"
+)]
 pub async fn delete_member(
   base_url: &str,
   auth_token: &str,
@@ -245,33 +118,10 @@ pub async fn delete_member(
   partition_name: &str,
   xname: &str,
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!(
-    "{}/hsm/v2/partitions/{}/members/{}",
-    base_url, partition_name, xname
-  );
-
-  let response = client
-    .delete(api_url)
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::delete_member(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    partition_name,
+    xname,
+  )
+  .await
 }
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{error::Error, hsm::component::types::ComponentArrayPostQuery};
+
+/// Values HSM accepts for the enumerated fields of a component
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ServiceValues {
+  #[serde(rename = "Arch", default)]
+  pub arch: Vec<String>,
+  #[serde(rename = "Class", default)]
+  pub class: Vec<String>,
+  #[serde(rename = "Flag", default)]
+  pub flag: Vec<String>,
+  #[serde(rename = "NetType", default)]
+  pub net_type: Vec<String>,
+  #[serde(rename = "Role", default)]
+  pub role: Vec<String>,
+  #[serde(rename = "SubRole", default)]
+  pub sub_role: Vec<String>,
+  #[serde(rename = "State", default)]
+  pub state: Vec<String>,
+  #[serde(rename = "Type", default)]
+  pub r#type: Vec<String>,
+}
+
+impl ServiceValues {
+  /// Check the enumerated fields of a component query against the values accepted by
+  /// HSM. Comparison is case insensitive, same as HSM
+  pub fn validate_query(
+    &self,
+    query: &ComponentArrayPostQuery,
+  ) -> Result<(), Error> {
+    let field_vec = [
+      ("arch", &query.arch, &self.arch),
+      ("class", &query.class, &self.class),
+      ("flag", &query.flag, &self.flag),
+      ("role", &query.role, &self.role),
+      ("subrole", &query.subrole, &self.sub_role),
+      ("state", &query.state, &self.state),
+      ("type", &query.r#type, &self.r#type),
+    ];
+
+    let invalid_vec: Vec<String> = field_vec
+      .into_iter()
+      .filter_map(|(field, value_opt, valid_vec)| {
+        let value = value_opt.as_ref()?;
+        (!is_valid(valid_vec, value)).then(|| {
+          format!("{} '{}' (valid: {})", field, value, valid_vec.join(", "))
+        })
+      })
+      .collect();
+
+    if invalid_vec.is_empty() {
+      Ok(())
+    } else {
+      Err(Error::Message(format!(
+        "Invalid component query values: {}",
+        invalid_vec.join("; ")
+      )))
+    }
+  }
+}
+
+fn is_valid(valid_vec: &[String], value: &str) -> bool {
+  valid_vec
+    .iter()
+    .any(|valid| valid.eq_ignore_ascii_case(value))
+}
@@ -0,0 +1,30 @@
+use crate::{
+  error::Error,
+  hsm::component::{
+    self,
+    types::{ComponentArray, ComponentArrayPostQuery},
+  },
+  http::ApiContext,
+};
+
+use super::http_client;
+
+/// Check the enumerated fields of a component query against the values the server
+/// accepts
+pub async fn validate_component_query(
+  ctx: &ApiContext,
+  query: &ComponentArrayPostQuery,
+) -> Result<(), Error> {
+  http_client::get_values(ctx).await?.validate_query(query)
+}
+
+/// Same as `component::client::post_query` but the query is validated first so
+/// typos are reported instead of silently matching no component
+pub async fn post_query_validated(
+  ctx: &ApiContext,
+  query: ComponentArrayPostQuery,
+) -> Result<ComponentArray, Error> {
+  validate_component_query(ctx, &query).await?;
+
+  component::client::post_query(ctx, query).await
+}
@@ -0,0 +1,424 @@
+use serde_json::Value;
+
+use crate::{
+  error::Error,
+  http::{ApiContext, MutationRequest},
+};
+
+use super::types::{
+  Component, ComponentArray, ComponentPostByNidQuery, ComponentPostQuery,
+};
+
+pub async fn get(
+  ctx: &ApiContext,
+  id: Option<&str>,
+  r#type: Option<&str>,
+  state: Option<&str>,
+  flag: Option<&str>,
+  role: Option<&str>,
+  subrole: Option<&str>,
+  enabled: Option<&str>,
+  software_status: Option<&str>,
+  subtype: Option<&str>,
+  arch: Option<&str>,
+  class: Option<&str>,
+  nid: Option<&str>,
+  nid_start: Option<&str>,
+  nid_end: Option<&str>,
+  partition: Option<&str>,
+  group: Option<&str>,
+  state_only: Option<bool>,
+  flag_only: Option<bool>,
+  role_only: Option<bool>,
+  nid_only: Option<&str>,
+) -> Result<ComponentArray, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/State/Components")?;
+
+  let response = client
+    .get(api_url)
+    .query(&[
+      id,
+      r#type,
+      state,
+      flag,
+      role,
+      subrole,
+      enabled,
+      software_status,
+      subtype,
+      arch,
+      class,
+      nid,
+      nid_start,
+      nid_end,
+      partition,
+      group,
+      state_only.map(|value| value.to_string()).as_deref(),
+      flag_only.map(|value| value.to_string()).as_deref(),
+      role_only.map(|value| value.to_string()).as_deref(),
+      nid_only,
+    ])
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn get_one(ctx: &ApiContext, id: &str) -> Result<Component, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!("/State/Components/{}", id))?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn get_by_nid(
+  ctx: &ApiContext,
+  nid: &str,
+) -> Result<Component, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!("/State/Components/ByNID/{}", nid))?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn get_query(
+  ctx: &ApiContext,
+  xname: &str,
+  r#type: Option<&str>,
+  state: Option<&str>,
+  flag: Option<&str>,
+  role: Option<&str>,
+  subrole: Option<&str>,
+  enabled: Option<&str>,
+  softwarestatus: Option<&str>,
+  subtype: Option<&str>,
+  arch: Option<&str>,
+  class: Option<&str>,
+  nid: Option<&str>,
+  nid_start: Option<&str>,
+  nid_end: Option<&str>,
+  partition: Option<&str>,
+  group: Option<&str>,
+  stateonly: Option<bool>,
+  flagonly: Option<bool>,
+  roleonly: Option<bool>,
+  nidonly: Option<&str>,
+) -> Result<ComponentArray, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!("/State/Components/Query/{}", xname))?;
+
+  let response = client
+    .get(api_url)
+    .query(&[
+      r#type,
+      state,
+      flag,
+      role,
+      subrole,
+      enabled,
+      softwarestatus,
+      subtype,
+      arch,
+      class,
+      nid,
+      nid_start,
+      nid_end,
+      partition,
+      group,
+      stateonly.map(|value| value.to_string()).as_deref(),
+      flagonly.map(|value| value.to_string()).as_deref(),
+      roleonly.map(|value| value.to_string()).as_deref(),
+      nidonly,
+    ])
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn post(
+  ctx: &ApiContext,
+  component: Component,
+) -> Result<Component, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/State/Components")?;
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(&component)
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  crate::hsm::cache::invalidate_components(ctx);
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn post_query(
+  ctx: &ApiContext,
+  component_query: ComponentPostQuery,
+) -> Result<ComponentArray, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/State/Components/Query")?;
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(&component_query)
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn post_by_nid_query(
+  ctx: &ApiContext,
+  component_by_nid_query: ComponentPostByNidQuery,
+) -> Result<Component, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/State/Components/ByNID/Query")?;
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(&component_by_nid_query)
+    .send()
+    .await?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn put(ctx: &ApiContext, component: Component) -> Result<(), Error> {
+  if component.id.is_none() {
+    return Err(Error::Message(
+      "ERROR - component.id not defined".to_string(),
+    ));
+  }
+
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!(
+    "/State/Components/{}",
+    component.id.as_ref().unwrap()
+  ))?;
+
+  let response = client
+    .put(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .json(&component)
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  crate::hsm::cache::invalidate_components(ctx);
+
+  Ok(())
+}
+
+pub async fn delete_all(ctx: &ApiContext) -> Result<Value, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url("/State/Components")?;
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  crate::hsm::cache::invalidate_components(ctx);
+
+  response.json().await.map_err(Error::NetError)
+}
+
+pub async fn delete_one(ctx: &ApiContext, id: &str) -> Result<Value, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.hsm_url(&format!("/State/Components/{}", id))?;
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if let Err(e) = response.error_for_status_ref() {
+    match response.status() {
+      reqwest::StatusCode::UNAUTHORIZED => {
+        let error_payload = response.text().await?;
+        return Err(Error::RequestError {
+          response: e,
+          payload: error_payload,
+        });
+      }
+      _ => {
+        let error_payload = response.json::<Value>().await?;
+        return Err(Error::OchamiError(error_payload));
+      }
+    }
+  }
+
+  crate::hsm::cache::invalidate_components(ctx);
+
+  response.json().await.map_err(Error::NetError)
+}
@@ -1,11 +1,16 @@
 use serde_json::Value;
 
-use crate::error::Error;
+use crate::{error::Error, http::ApiContext};
 
-use super::types::{
-  Component, ComponentArray, ComponentPostByNidQuery, ComponentPostQuery,
+use super::{
+  client,
+  types::{
+    Component, ComponentArray, ComponentPostByNidQuery, ComponentPostQuery,
+  },
 };
 
+#[deprecated(note = "use `components::client::get` with an `ApiContext`")]
+#[allow(clippy::too_many_arguments)]
 pub async fn get(
   auth_token: &str,
   base_url: &str,
@@ -31,120 +36,58 @@ pub async fn get(
   role_only: Option<bool>,
   nid_only: Option<&str>,
 ) -> Result<ComponentArray, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = base_url.to_owned() + "/smd/hsm/v2/State/Components";
-
-  let response = client
-    .get(api_url)
-    .query(&[
-      id,
-      r#type,
-      state,
-      flag,
-      role,
-      subrole,
-      enabled,
-      software_status,
-      subtype,
-      arch,
-      class,
-      nid,
-      nid_start,
-      nid_end,
-      partition,
-      group,
-      state_only.map(|value| value.to_string()).as_deref(),
-      flag_only.map(|value| value.to_string()).as_deref(),
-      role_only.map(|value| value.to_string()).as_deref(),
-      nid_only,
-    ])
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    id,
+    r#type,
+    state,
+    flag,
+    role,
+    subrole,
+    enabled,
+    software_status,
+    subtype,
+    arch,
+    class,
+    nid,
+    nid_start,
+    nid_end,
+    partition,
+    group,
+    state_only,
+    flag_only,
+    role_only,
+    nid_only,
+  )
+  .await
 }
 
+#[deprecated(note = "use `components::client::get_one` with an `ApiContext`")]
 pub async fn get_one(
   auth_token: &str,
   base_url: &str,
   root_cert: &[u8],
   id: &str,
 ) -> Result<Component, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url =
-    format!("{}/smd/hsm/v2/State/Components/{}", base_url, id);
-
-  let response = client.get(api_url).bearer_auth(auth_token).send().await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get_one(&ApiContext::parse(base_url, auth_token, root_cert)?, id)
+    .await
 }
 
+#[deprecated(
+  note = "use `components::client::get_by_nid` with an `ApiContext`"
+)]
 pub async fn get_by_nid(
   auth_token: &str,
   base_url: &str,
   root_cert: &[u8],
   nid: &str,
 ) -> Result<Component, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!(
-    "{}/smd/hsm/v2/State/Components/ByNID/{}",
-    base_url, nid
-  );
-
-  let response = client.get(api_url).bearer_auth(auth_token).send().await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get_by_nid(&ApiContext::parse(base_url, auth_token, root_cert)?, nid)
+    .await
 }
 
+#[deprecated(note = "use `components::client::get_query` with an `ApiContext`")]
+#[allow(clippy::too_many_arguments)]
 pub async fn get_query(
   auth_token: &str,
   base_url: &str,
@@ -170,274 +113,112 @@ pub async fn get_query(
   roleonly: Option<bool>,
   nidonly: Option<&str>,
 ) -> Result<ComponentArray, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!(
-    "{}/smd/hsm/v2/State/Components/Query/{}",
-    base_url, xname
-  );
-
-  let response = client
-    .get(api_url)
-    .query(&[
-      r#type,
-      state,
-      flag,
-      role,
-      subrole,
-      enabled,
-      softwarestatus,
-      subtype,
-      arch,
-      class,
-      nid,
-      nid_start,
-      nid_end,
-      partition,
-      group,
-      stateonly.map(|value| value.to_string()).as_deref(),
-      flagonly.map(|value| value.to_string()).as_deref(),
-      roleonly.map(|value| value.to_string()).as_deref(),
-      nidonly,
-    ])
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::get_query(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    xname,
+    r#type,
+    state,
+    flag,
+    role,
+    subrole,
+    enabled,
+    softwarestatus,
+    subtype,
+    arch,
+    class,
+    nid,
+    nid_start,
+    nid_end,
+    partition,
+    group,
+    stateonly,
+    flagonly,
+    roleonly,
+    nidonly,
+  )
+  .await
 }
 
+#[deprecated(note = "use `components::client::post` with an `ApiContext`")]
 pub async fn post(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   component: Component,
 ) -> Result<Component, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = base_url.to_owned() + "/smd/hsm/v2/State/Components";
-
-  let response = client
-    .post(api_url)
-    .bearer_auth(auth_token)
-    .json(&component)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::post(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    component,
+  )
+  .await
 }
 
+#[deprecated(
+  note = "use `components::client::post_query` with an `ApiContext`"
+)]
 pub async fn post_query(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   component_query: ComponentPostQuery,
 ) -> Result<ComponentArray, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url =
-    base_url.to_owned() + "/smd/hsm/v2/State/Components/Query";
-
-  let response = client
-    .post(api_url)
-    .bearer_auth(auth_token)
-    .json(&component_query)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::post_query(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    component_query,
+  )
+  .await
 }
 
+#[deprecated(
+  note = "use `components::client::post_by_nid_query` with an `ApiContext`"
+)]
 pub async fn post_by_nid_query(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   component_by_nid_query: ComponentPostByNidQuery,
 ) -> Result<Component, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url =
-    base_url.to_owned() + "/smd/hsm/v2/State/Components/ByNID/Query";
-
-  let response = client
-    .post(api_url)
-    .bearer_auth(auth_token)
-    .json(&component_by_nid_query)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::post_by_nid_query(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    component_by_nid_query,
+  )
+  .await
 }
 
+#[deprecated(note = "use `components::client::put` with an `ApiContext`")]
 pub async fn put(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   component: Component,
 ) -> Result<(), Error> {
-  if component.id.is_none() {
-    return Err(Error::Message(
-      "ERROR - component.id not defined".to_string(),
-    ));
-  }
-
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = format!(
-    "{}/smd/hsm/v2/State/Components/{}",
-    base_url,
-    component.id.as_ref().unwrap()
-  );
-
-  let response = client
-    .put(api_url)
-    .bearer_auth(auth_token)
-    .json(&component)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  Ok(())
+  client::put(
+    &ApiContext::parse(base_url, auth_token, root_cert)?,
+    component,
+  )
+  .await
 }
 
+#[deprecated(
+  note = "use `components::client::delete_all` with an `ApiContext`"
+)]
 pub async fn delete_all(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url = base_url.to_owned() + "/smd/hsm/v2/State/Components";
-
-  let response = client
-    .delete(api_url)
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::delete_all(&ApiContext::parse(base_url, auth_token, root_cert)?).await
 }
 
+#[deprecated(
+  note = "use `components::client::delete_one` with an `ApiContext`"
+)]
 pub async fn delete_one(
   base_url: &str,
   auth_token: &str,
   root_cert: &[u8],
   id: &str,
 ) -> Result<Value, Error> {
-  let client = crate::http::build_client(root_cert)?;
-  let api_url =
-    format!("{}/smd/hsm/v2/State/Components/{}", base_url, id);
-
-  let response = client
-    .delete(api_url)
-    .bearer_auth(auth_token)
-    .send()
-    .await?;
-
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        return Err(Error::RequestError {
-          response: e,
-          payload: error_payload,
-        });
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        return Err(Error::OchamiError(error_payload));
-      }
-    }
-  }
-
-  response.json().await.map_err(Error::NetError)
+  client::delete_one(&ApiContext::parse(base_url, auth_token, root_cert)?, id)
+    .await
 }
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -125,3 +127,24 @@ pub struct ComponentPostByNidQuery {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub nidonly: Option<bool>,
 }
+
+impl fmt::Display for Component {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let detail_vec: Vec<String> = [
+      self.r#type.clone(),
+      self.state.clone(),
+      self.role.clone(),
+      self.nid.map(|nid| format!("nid {}", nid)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    write!(
+      f,
+      "{} ({})",
+      self.id.as_deref().unwrap_or("unknown"),
+      detail_vec.join(", ")
+    )
+  }
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+/// States a component can be in, see the SMD API spec
+pub const HSM_STATES: [&str; 8] = [
+  "Unknown",
+  "Empty",
+  "Populated",
+  "Off",
+  "On",
+  "Standby",
+  "Halt",
+  "Ready",
+];
+
+/// SCN subscription, SMD posts a SCN to `url` each time a component matching
+/// the triggers changes
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ScnSubscription {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "ID")]
+  pub id: Option<u64>,
+  #[serde(rename = "Subscriber")]
+  pub subscriber: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "Enabled")]
+  pub enabled: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "Roles")]
+  pub roles: Option<Vec<String>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "SubRoles")]
+  pub sub_roles: Option<Vec<String>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "SoftwareStatus")]
+  pub software_status: Option<Vec<String>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "States")]
+  pub states: Option<Vec<String>>,
+  #[serde(rename = "Url")]
+  pub url: String,
+}
+
+impl ScnSubscription {
+  /// Subscription to every state change, whatever the component
+  pub fn all_states(subscriber: &str, url: &str) -> Self {
+    ScnSubscription {
+      subscriber: subscriber.to_string(),
+      states: Some(HSM_STATES.iter().map(|state| state.to_string()).collect()),
+      url: url.to_string(),
+      ..Default::default()
+    }
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ScnSubscriptionArray {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "SubscriptionList")]
+  pub subscription_list: Option<Vec<ScnSubscription>>,
+}
@@ -0,0 +1,48 @@
+#[cfg(feature = "backend-connector")]
+use manta_backend_dispatcher::types::HsmActionResponse as FrontEndHsmActionResponse;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// HSM response to delete and update requests, eg:
+/// `{"code": 0, "message": "deleted 1 entry"}`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct ActionResponse {
+  #[serde(deserialize_with = "deserialize_code")]
+  pub code: String,
+  pub message: String,
+}
+
+/// Location of a resource created by HSM, eg:
+/// `{"URI": "/hsm/v2/groups/compute/members/x1000c0s0b0n0"}`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct ResourceUri {
+  #[serde(rename = "URI")]
+  pub uri: String,
+}
+
+/// HSM returns the code as a number, accept strings as well
+fn deserialize_code<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  #[derive(Deserialize)]
+  #[serde(untagged)]
+  enum Code {
+    Number(i64),
+    Text(String),
+  }
+
+  Ok(match Code::deserialize(deserializer)? {
+    Code::Number(code) => code.to_string(),
+    Code::Text(code) => code,
+  })
+}
+
+#[cfg(feature = "backend-connector")]
+impl From<ActionResponse> for FrontEndHsmActionResponse {
+  fn from(value: ActionResponse) -> Self {
+    FrontEndHsmActionResponse {
+      code: value.code,
+      message: value.message,
+    }
+  }
+}
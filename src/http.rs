@@ -1,14 +1,664 @@
-use crate::error::Error;
+use std::{
+  fmt,
+  future::Future,
+  marker::PhantomData,
+  net::SocketAddr,
+  path::{Path, PathBuf},
+  str::FromStr,
+  sync::{Arc, OnceLock},
+  time::Duration,
+};
+
+use reqwest::{
+  header::{self, HeaderMap},
+  StatusCode,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::AsyncWriteExt;
+use url::Url;
+
+use crate::{
+  authentication::types::Authorizer,
+  error::Error,
+  progress::{ProgressEvent, ProgressSink},
+  service_paths::{endpoint_url, validate_base_url, ServicePaths},
+};
+
+/// Parse a PEM root certificate
+pub fn root_certificate(
+  root_cert: &[u8],
+) -> Result<reqwest::Certificate, Error> {
+  reqwest::Certificate::from_pem(root_cert).map_err(Error::NetError)
+}
+
+/// Connection tuning of the http clients built by this crate, for burst workloads sending
+/// thousands of requests
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionSettings {
+  /// Host names resolved to fixed addresses instead of querying DNS, eg:
+  /// `("api.example.com", "10.0.0.1:443")`
+  pub resolve_overrides: Vec<(String, SocketAddr)>,
+  /// Interval of the TCP keep-alive probes, reqwest default if `None`
+  pub tcp_keepalive: Option<Duration>,
+  /// How long idle connections stay in the pool, reqwest default (90s) if `None`
+  pub pool_idle_timeout: Option<Duration>,
+  /// Max idle connections kept per host, unlimited if `None`
+  pub pool_max_idle_per_host: Option<usize>,
+  /// Share one pooled client between the requests sent through an `HttpClient` and its
+  /// clones, so connections, and their TLS sessions, are reused instead of being opened
+  /// for every request. On by default. The pooled connections are bound to the tokio
+  /// runtime they were opened on, disable it if the same `HttpClient` is used from
+  /// several runtimes
+  pub reuse_clients: bool,
+}
+
+impl Default for ConnectionSettings {
+  fn default() -> Self {
+    ConnectionSettings {
+      resolve_overrides: Vec::new(),
+      tcp_keepalive: None,
+      pool_idle_timeout: None,
+      pool_max_idle_per_host: None,
+      reuse_clients: true,
+    }
+  }
+}
+
+fn client_builder(
+  certificate: reqwest::Certificate,
+  timeout: Option<Duration>,
+  connection_settings: &ConnectionSettings,
+) -> reqwest::ClientBuilder {
+  let mut builder =
+    reqwest::Client::builder().add_root_certificate(certificate);
+
+  if let Some(timeout) = timeout {
+    builder = builder.timeout(timeout);
+  }
+  if let Some(tcp_keepalive) = connection_settings.tcp_keepalive {
+    builder = builder.tcp_keepalive(tcp_keepalive);
+  }
+  for (host, address) in &connection_settings.resolve_overrides {
+    builder = builder.resolve(host, *address);
+  }
+  if let Some(pool_idle_timeout) = connection_settings.pool_idle_timeout {
+    builder = builder.pool_idle_timeout(pool_idle_timeout);
+  }
+  if let Some(pool_max_idle_per_host) =
+    connection_settings.pool_max_idle_per_host
+  {
+    builder = builder.pool_max_idle_per_host(pool_max_idle_per_host);
+  }
+
+  builder
+}
+
+/// Select the TLS stack, the platform one if the `native-tls` feature is enabled, rustls
+/// otherwise. `native-tls` wins if both features are enabled
+#[cfg(feature = "native-tls")]
+pub(crate) fn with_tls_backend(
+  builder: reqwest::ClientBuilder,
+) -> reqwest::ClientBuilder {
+  builder.use_native_tls()
+}
+
+#[cfg(all(feature = "rustls-tls", not(feature = "native-tls")))]
+pub(crate) fn with_tls_backend(
+  builder: reqwest::ClientBuilder,
+) -> reqwest::ClientBuilder {
+  builder.use_rustls_tls()
+}
 
 pub fn build_client(root_cert: &[u8]) -> Result<reqwest::Client, Error> {
-  let builder = reqwest::Client::builder()
-    .add_root_certificate(reqwest::Certificate::from_pem(root_cert)?)
-    .use_rustls_tls();
-  builder.build().map_err(Error::NetError)
+  let builder = client_builder(
+    root_certificate(root_cert)?,
+    None,
+    &ConnectionSettings::default(),
+  );
+  with_tls_backend(builder).build().map_err(Error::NetError)
 }
 
 pub fn build_client_no_tls(root_cert: &[u8]) -> Result<reqwest::Client, Error> {
-  let builder = reqwest::Client::builder()
-    .add_root_certificate(reqwest::Certificate::from_pem(root_cert)?);
+  let builder = client_builder(
+    root_certificate(root_cert)?,
+    None,
+    &ConnectionSettings::default(),
+  );
   builder.build().map_err(Error::NetError)
 }
+
+/// HTTP client of a deployment: root certificate, request timeout, connection tuning and
+/// the pooled client built from them. Cheap to clone, clones share the parsed root
+/// certificate and the pooled client. Held by `ApiContext`
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+  root_cert: Arc<[u8]>,
+  certificate: Arc<OnceLock<reqwest::Certificate>>,
+  timeout: Option<Duration>,
+  connection_settings: Arc<ConnectionSettings>,
+  client: Arc<OnceLock<reqwest::Client>>,
+}
+
+impl HttpClient {
+  pub fn new(root_cert: &[u8]) -> Self {
+    HttpClient {
+      root_cert: Arc::from(root_cert),
+      certificate: Arc::default(),
+      timeout: None,
+      connection_settings: Arc::new(ConnectionSettings::default()),
+      client: Arc::default(),
+    }
+  }
+
+  /// Timeout applied to each request, no timeout if `None`
+  pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+    self.timeout = timeout;
+    self.client = Arc::default();
+    self
+  }
+
+  /// Tune the connections for burst workloads, see `ConnectionSettings`
+  pub fn with_connection_settings(
+    mut self,
+    connection_settings: ConnectionSettings,
+  ) -> Self {
+    self.connection_settings = Arc::new(connection_settings);
+    self.client = Arc::default();
+    self
+  }
+
+  pub fn root_cert(&self) -> &[u8] {
+    &self.root_cert
+  }
+
+  /// Root certificate parsed on first use, then shared by the clones
+  pub fn certificate(&self) -> Result<reqwest::Certificate, Error> {
+    if let Some(certificate) = self.certificate.get() {
+      return Ok(certificate.clone());
+    }
+
+    let certificate = root_certificate(&self.root_cert)?;
+
+    Ok(self.certificate.get_or_init(|| certificate).clone())
+  }
+
+  pub fn timeout(&self) -> Option<Duration> {
+    self.timeout
+  }
+
+  pub fn connection_settings(&self) -> &ConnectionSettings {
+    &self.connection_settings
+  }
+
+  /// Client trusting the root certificate, with the timeout and connection settings. The
+  /// pooled client if `ConnectionSettings::reuse_clients` is set
+  pub fn client(&self) -> Result<reqwest::Client, Error> {
+    if let Some(client) = self.client.get() {
+      return Ok(client.clone());
+    }
+
+    let builder = client_builder(
+      self.certificate()?,
+      self.timeout,
+      &self.connection_settings,
+    );
+    let client = with_tls_backend(builder).build().map_err(Error::NetError)?;
+
+    if !self.connection_settings.reuse_clients {
+      return Ok(client);
+    }
+
+    // Another request may have built the pooled client in the meantime, keep the first
+    Ok(self.client.get_or_init(|| client).clone())
+  }
+}
+
+/// Deserialize a response body straight from its bytes, without building an intermediate
+/// `serde_json::Value`
+pub async fn json_from_bytes<T: DeserializeOwned>(
+  response: reqwest::Response,
+) -> Result<T, Error> {
+  let body = response.bytes().await?;
+  serde_json::from_slice(&body).map_err(Error::SerdeError)
+}
+
+/// Access token of the OpenCHAMI API. A distinct type from `BaseUrl` so swapping them is
+/// a compile error. Redacted in debug output
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct AuthToken(String);
+
+impl AuthToken {
+  pub fn new(auth_token: impl Into<String>) -> Self {
+    AuthToken(auth_token.into())
+  }
+
+  pub fn as_str(&self) -> &str {
+    &self.0
+  }
+}
+
+impl From<&str> for AuthToken {
+  fn from(auth_token: &str) -> Self {
+    AuthToken::new(auth_token)
+  }
+}
+
+impl From<String> for AuthToken {
+  fn from(auth_token: String) -> Self {
+    AuthToken(auth_token)
+  }
+}
+
+impl fmt::Debug for AuthToken {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("AuthToken(***)")
+  }
+}
+
+/// Validated base url of the OpenCHAMI API, eg: `https://api.example.com`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BaseUrl(Url);
+
+impl BaseUrl {
+  pub fn parse(base_url: &str) -> Result<Self, Error> {
+    validate_base_url(base_url).map(BaseUrl)
+  }
+
+  pub fn as_str(&self) -> &str {
+    self.0.as_str().trim_end_matches('/')
+  }
+
+  pub fn as_url(&self) -> &Url {
+    &self.0
+  }
+}
+
+impl FromStr for BaseUrl {
+  type Err = Error;
+
+  fn from_str(base_url: &str) -> Result<Self, Self::Err> {
+    BaseUrl::parse(base_url)
+  }
+}
+
+impl fmt::Display for BaseUrl {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+/// Connection details shared by every request to OpenCHAMI services. Base url and token
+/// are typed so they can't be mixed up. Cheap to clone, the HTTP client configuration is
+/// shared
+#[derive(Debug, Clone)]
+pub struct ApiContext {
+  pub base_url: BaseUrl,
+  pub auth_token: AuthToken,
+  pub http_client: HttpClient,
+  pub service_paths: Arc<ServicePaths>,
+  pub authorizer: Option<Arc<Authorizer>>,
+  pub execution_mode: ExecutionMode,
+  pub cache_ttl: Option<Duration>,
+}
+
+impl ApiContext {
+  pub fn new(
+    base_url: BaseUrl,
+    auth_token: AuthToken,
+    root_cert: &[u8],
+  ) -> Self {
+    ApiContext {
+      base_url,
+      auth_token,
+      http_client: HttpClient::new(root_cert),
+      service_paths: Arc::new(ServicePaths::default()),
+      authorizer: None,
+      execution_mode: ExecutionMode::default(),
+      cache_ttl: None,
+    }
+  }
+
+  /// Build a context from untyped values, failing if `base_url` is not a valid url
+  pub fn parse(
+    base_url: &str,
+    auth_token: &str,
+    root_cert: &[u8],
+  ) -> Result<Self, Error> {
+    Ok(ApiContext::new(
+      BaseUrl::parse(base_url)?,
+      AuthToken::from(auth_token),
+      root_cert,
+    ))
+  }
+
+  /// Same context with another access token, eg: after a token refresh
+  pub fn with_auth_token(&self, auth_token: impl Into<AuthToken>) -> Self {
+    ApiContext {
+      auth_token: auth_token.into(),
+      ..self.clone()
+    }
+  }
+
+  /// Same context with other service path prefixes, eg: for a deployment serving SMD
+  /// under `/smd/hsm/v2`
+  pub fn with_service_paths(mut self, service_paths: ServicePaths) -> Self {
+    self.service_paths = Arc::new(service_paths);
+    self
+  }
+
+  /// Same context with another HTTP client configuration, eg: with a timeout
+  pub fn with_http_client(mut self, http_client: HttpClient) -> Self {
+    self.http_client = http_client;
+    self
+  }
+
+  /// Same context with the policy engine asked by `authentication::utils::can_i`, token
+  /// claims are inspected if `None`
+  pub fn with_authorizer(mut self, authorizer: Option<Authorizer>) -> Self {
+    self.authorizer = authorizer.map(Arc::new);
+    self
+  }
+
+  /// Same context describing the requests changing the services state instead of
+  /// sending them when set to `ExecutionMode::DryRun`, see `MutationRequest`
+  pub fn with_execution_mode(mut self, execution_mode: ExecutionMode) -> Self {
+    self.execution_mode = execution_mode;
+    self
+  }
+
+  /// Same context reading HSM groups and components through `hsm::cache`, entries are
+  /// kept for `cache_ttl`. The cache is not used if `None`, the default
+  pub fn with_cache_ttl(mut self, cache_ttl: Option<Duration>) -> Self {
+    self.cache_ttl = cache_ttl;
+    self
+  }
+
+  /// Url of an SMD endpoint, eg: `ctx.hsm_url("/groups")`
+  pub fn hsm_url(&self, path: &str) -> Result<Url, Error> {
+    endpoint_url(self.base_url.as_str(), &self.service_paths.hsm, path)
+  }
+
+  /// Url of a BSS endpoint, eg: `ctx.bss_url("/bootparameters")`
+  pub fn bss_url(&self, path: &str) -> Result<Url, Error> {
+    endpoint_url(self.base_url.as_str(), &self.service_paths.bss, path)
+  }
+
+  /// Url of a PCS endpoint, eg: `ctx.pcs_url("/transitions")`
+  pub fn pcs_url(&self, path: &str) -> Result<Url, Error> {
+    endpoint_url(self.base_url.as_str(), &self.service_paths.pcs, path)
+  }
+
+  /// Url of a cloud-init endpoint, eg: `ctx.cloud_init_url("/admin/groups")`
+  pub fn cloud_init_url(&self, path: &str) -> Result<Url, Error> {
+    endpoint_url(self.base_url.as_str(), &self.service_paths.cloud_init, path)
+  }
+
+  /// HTTP client trusting the context root certificate, see `HttpClient::client`
+  pub fn client(&self) -> Result<reqwest::Client, Error> {
+    self.http_client.client()
+  }
+}
+
+/// Whether mutating calls are sent to the services or only described
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExecutionMode {
+  #[default]
+  Apply,
+  DryRun,
+}
+
+impl ExecutionMode {
+  pub fn is_dry_run(&self) -> bool {
+    *self == ExecutionMode::DryRun
+  }
+}
+
+impl From<bool> for ExecutionMode {
+  /// `true` means dry run
+  fn from(dry_run: bool) -> Self {
+    if dry_run {
+      ExecutionMode::DryRun
+    } else {
+      ExecutionMode::Apply
+    }
+  }
+}
+
+/// Description of a HTTP request, returned instead of sending it in dry-run
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PlannedRequest {
+  pub method: String,
+  pub url: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub body: Option<Value>,
+}
+
+impl PlannedRequest {
+  /// Describe a request, bodies which are not JSON are kept as a string
+  pub fn from_request(request: &reqwest::Request) -> Self {
+    let body = request
+      .body()
+      .and_then(reqwest::Body::as_bytes)
+      .map(|body| {
+        serde_json::from_slice(body).unwrap_or_else(|_| {
+          Value::String(String::from_utf8_lossy(body).into_owned())
+        })
+      });
+
+    PlannedRequest {
+      method: request.method().to_string(),
+      url: request.url().to_string(),
+      body,
+    }
+  }
+}
+
+impl fmt::Display for PlannedRequest {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} {}", self.method, self.url)?;
+
+    if let Some(body) = &self.body {
+      write!(f, " {}", body)?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Result of a `MutationRequest`, either sent or only described because the context is
+/// in dry-run
+#[derive(Debug)]
+pub enum MutationResponse {
+  Sent(reqwest::Response),
+  Planned(PlannedRequest),
+}
+
+impl MutationResponse {
+  /// Response of the request sent. Fails with `Error::DryRun` if the request was only
+  /// planned, for callers with no value to return for a request not sent
+  pub fn into_sent(self) -> Result<reqwest::Response, Error> {
+    match self {
+      MutationResponse::Sent(response) => Ok(response),
+      MutationResponse::Planned(request) => Err(Error::DryRun(request)),
+    }
+  }
+}
+
+/// Send a request changing the state of a service. Every write of this crate goes
+/// through `send_mutation`, so a context in dry-run sends none of them
+pub trait MutationRequest {
+  /// Send the request, or if `ctx` is in dry-run log it and return its description
+  /// without sending it
+  fn send_mutation(
+    self,
+    ctx: &ApiContext,
+  ) -> impl Future<Output = Result<MutationResponse, Error>> + Send;
+}
+
+impl MutationRequest for reqwest::RequestBuilder {
+  async fn send_mutation(
+    self,
+    ctx: &ApiContext,
+  ) -> Result<MutationResponse, Error> {
+    if !ctx.execution_mode.is_dry_run() {
+      return self
+        .send()
+        .await
+        .map(MutationResponse::Sent)
+        .map_err(Error::NetError);
+    }
+
+    let planned_request = PlannedRequest::from_request(&self.build()?);
+
+    log::info!("Dry-run, not sent: {}", planned_request);
+
+    Ok(MutationResponse::Planned(planned_request))
+  }
+}
+
+/// Unparsed response of a request, whatever its status. Useful to debug or to read
+/// fields the typed models in this crate do not know about yet
+#[derive(Debug)]
+pub struct RawResponse<T> {
+  pub status: StatusCode,
+  pub headers: HeaderMap,
+  pub body: String,
+  typed: PhantomData<fn() -> T>,
+}
+
+impl<T> RawResponse<T> {
+  pub async fn from_response(
+    response: reqwest::Response,
+  ) -> Result<Self, Error> {
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = response.text().await?;
+
+    Ok(RawResponse {
+      status,
+      headers,
+      body,
+      typed: PhantomData,
+    })
+  }
+
+  /// Whether the status code is 2xx
+  pub fn is_success(&self) -> bool {
+    self.status.is_success()
+  }
+}
+
+impl<T: DeserializeOwned> RawResponse<T> {
+  /// Body parsed into the typed model the non raw function would return
+  pub fn typed(&self) -> Result<T, Error> {
+    serde_json::from_str(&self.body).map_err(Error::SerdeError)
+  }
+}
+
+/// `ETag` and `Last-Modified` of a response, sent back as `If-None-Match` and
+/// `If-Modified-Since` so the server answers 304 if the resource did not change
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Validators {
+  pub etag: Option<String>,
+  pub last_modified: Option<String>,
+}
+
+impl Validators {
+  pub fn from_headers(headers: &HeaderMap) -> Self {
+    let header_value = |name| {
+      headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+    };
+
+    Validators {
+      etag: header_value(header::ETAG),
+      last_modified: header_value(header::LAST_MODIFIED),
+    }
+  }
+
+  /// Add the conditional headers to a request
+  pub fn apply(
+    &self,
+    request: reqwest::RequestBuilder,
+  ) -> reqwest::RequestBuilder {
+    let mut request = request;
+
+    if let Some(etag) = &self.etag {
+      request = request.header(header::IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = &self.last_modified {
+      request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+    }
+
+    request
+  }
+}
+
+/// Response body written to a file instead of being deserialized in memory, see
+/// `spill_to_file`. Parsed on demand with `parse`
+#[derive(Debug)]
+pub struct SpilledResponse<T> {
+  pub path: PathBuf,
+  pub bytes: u64,
+  typed: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> SpilledResponse<T> {
+  /// Deserialize the file into the typed model the in memory function would return
+  pub fn parse(&self) -> Result<T, Error> {
+    let file = std::fs::File::open(&self.path)?;
+    serde_json::from_reader(std::io::BufReader::new(file))
+      .map_err(Error::SerdeError)
+  }
+}
+
+/// Write a response body to `path` chunk by chunk, reporting the bytes written to
+/// `progress`
+pub async fn spill_to_file<T>(
+  response: reqwest::Response,
+  path: &Path,
+  progress: &dyn ProgressSink,
+) -> Result<SpilledResponse<T>, Error> {
+  let mut response = response;
+  let total = response.content_length();
+  let mut file = tokio::fs::File::create(path).await?;
+  let mut bytes = 0;
+
+  while let Some(chunk) = response.chunk().await? {
+    file.write_all(&chunk).await?;
+    bytes += chunk.len() as u64;
+    progress.report(ProgressEvent::Transferred { bytes, total });
+  }
+
+  file.flush().await?;
+
+  Ok(SpilledResponse {
+    path: path.to_path_buf(),
+    bytes,
+    typed: PhantomData,
+  })
+}
+
+/// Header identifying a POST request across retries. Services supporting it don't apply
+/// the same request twice, for the others the `post_idempotent` functions check what the
+/// previous attempts created before retrying
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// New idempotency key, generated once per logical operation and sent with each of its
+/// attempts
+pub fn new_idempotency_key() -> String {
+  uuid::Uuid::new_v4().to_string()
+}
+
+/// Returns true if no response was received (timeout or connection error). The request
+/// may or may not have been processed by the service, retrying it blindly could apply it
+/// twice
+pub fn is_transient_failure(error: &Error) -> bool {
+  matches!(
+    error,
+    Error::NetError(e) if e.is_timeout() || e.is_connect() || e.is_request()
+  )
+}
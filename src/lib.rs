@@ -1,8 +1,56 @@
+#[cfg(not(any(feature = "rustls-tls", feature = "native-tls")))]
+compile_error!(
+  "No TLS backend, enable either the `rustls-tls` or the `native-tls` feature"
+);
+
+#[cfg(all(any(test, feature = "proptest"), feature = "hsm", feature = "bss"))]
+pub mod arbitrary;
 pub mod authentication;
+#[cfg(feature = "backend-connector")]
 pub mod backend_connector;
+#[cfg(all(feature = "blocking", any(feature = "hsm", feature = "bss")))]
+pub mod blocking;
+#[cfg(feature = "bss")]
 pub mod bss;
+#[cfg(feature = "cloud-init")]
+pub mod cloud_init;
+pub mod config;
+#[cfg(feature = "pcs")]
+pub mod discovery;
+#[cfg(all(feature = "bss", feature = "cloud-init", feature = "pcs"))]
+pub mod drift;
 pub mod error;
+#[cfg(any(test, feature = "fixtures"))]
+pub mod fixtures;
+#[cfg(all(feature = "bss", feature = "pcs"))]
+pub mod health;
+pub mod hostlist;
+#[cfg(feature = "hsm")]
 pub mod hsm;
 pub mod http;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(all(feature = "bss", feature = "pcs"))]
+pub mod mutation;
+#[cfg(feature = "hsm")]
 pub mod node;
+#[cfg(feature = "pcs")]
 pub mod pcs;
+pub mod progress;
+#[cfg(all(feature = "bss", feature = "cloud-init", feature = "pcs"))]
+pub mod provisioning;
+#[cfg(feature = "pcs")]
+pub mod reports;
+pub mod service_paths;
+#[cfg(all(feature = "bss", feature = "cloud-init", feature = "pcs"))]
+pub mod session;
+#[cfg(all(feature = "bss", feature = "cloud-init"))]
+pub mod session_template;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+#[cfg(any(feature = "hsm", feature = "bss"))]
+pub mod utils;
+#[cfg(feature = "hsm")]
+pub mod watcher;
+#[cfg(all(feature = "bss", feature = "hsm"))]
+pub mod workflows;
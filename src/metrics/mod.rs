@@ -0,0 +1,244 @@
+#[cfg(test)]
+pub mod tests;
+
+use std::{
+  collections::{BTreeMap, HashMap, HashSet},
+  time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+
+use crate::{
+  error::Error,
+  hsm,
+  http::ApiContext,
+  pcs::{self, power_status::types::PowerState},
+};
+
+/// Power states reported, nodes missing from PCS are counted as `unknown`
+const POWER_STATES: [&str; 4] = ["on", "off", "undefined", "unknown"];
+
+/// HSM states reported, gauges of states with no node are set to 0
+const HSM_STATES: [&str; 8] = [
+  "Unknown",
+  "Empty",
+  "Populated",
+  "Off",
+  "On",
+  "Standby",
+  "Halt",
+  "Ready",
+];
+
+/// Number of nodes of a HSM group per power and HSM state
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GroupSample {
+  pub group_label: String,
+  pub power_states: BTreeMap<String, usize>,
+  pub hsm_states: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FleetSample {
+  pub sampled_at: DateTime<Utc>,
+  pub groups: Vec<GroupSample>,
+}
+
+/// Count the nodes of each group per power state (PCS) and HSM state (SMD). All groups
+/// are sampled if `group_label_vec` is empty
+pub async fn sample(
+  ctx: &ApiContext,
+  group_label_vec: &[String],
+) -> Result<FleetSample, Error> {
+  let mut member_map: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+  if group_label_vec.is_empty() {
+    for group in hsm::cache::get_all_groups(ctx).await? {
+      let member_vec = group.get_members();
+      member_map.insert(group.label, member_vec);
+    }
+  } else {
+    for group_label in group_label_vec {
+      let members = hsm::cache::get_members(ctx, group_label).await?;
+      member_map.insert(group_label.clone(), members.ids.unwrap_or_default());
+    }
+  }
+
+  let xname_set: HashSet<&str> = member_map
+    .values()
+    .flat_map(|member_vec| member_vec.iter().map(String::as_str))
+    .collect();
+  let xname_vec: Vec<&str> = xname_set.into_iter().collect();
+
+  let power_state_map: HashMap<String, &'static str> = if xname_vec.is_empty() {
+    HashMap::new()
+  } else {
+    pcs::power_status::client::post(ctx, Some(&xname_vec), None, None)
+      .await?
+      .status
+      .into_iter()
+      .map(|status| {
+        let power_state = match status.power_state {
+          Some(PowerState::On) => "on",
+          Some(PowerState::Off) => "off",
+          Some(PowerState::Undefined) => "undefined",
+          None => "unknown",
+        };
+        (status.xname, power_state)
+      })
+      .collect()
+  };
+
+  let hsm_state_map: HashMap<String, String> =
+    hsm::component::client::get_all(ctx)
+      .await?
+      .components
+      .unwrap_or_default()
+      .into_iter()
+      .filter_map(|component| Some((component.id?, component.state?)))
+      .collect();
+
+  let group_sample_vec = member_map
+    .into_iter()
+    .map(|(group_label, member_vec)| {
+      let mut group_sample = GroupSample {
+        group_label,
+        ..Default::default()
+      };
+
+      for xname in &member_vec {
+        *group_sample
+          .power_states
+          .entry(
+            power_state_map
+              .get(xname)
+              .copied()
+              .unwrap_or("unknown")
+              .to_string(),
+          )
+          .or_default() += 1;
+
+        *group_sample
+          .hsm_states
+          .entry(
+            hsm_state_map
+              .get(xname)
+              .cloned()
+              .unwrap_or_else(|| "Unknown".to_string()),
+          )
+          .or_default() += 1;
+      }
+
+      group_sample
+    })
+    .collect();
+
+  Ok(FleetSample {
+    sampled_at: Utc::now(),
+    groups: group_sample_vec,
+  })
+}
+
+/// Publish a sample through the `metrics` facade as the gauges
+/// `ochami_group_nodes_power_state{group, state}` and
+/// `ochami_group_nodes_hsm_state{group, state}`
+pub fn record(fleet_sample: &FleetSample) {
+  for group_sample in &fleet_sample.groups {
+    for power_state in POWER_STATES {
+      ::metrics::gauge!(
+        "ochami_group_nodes_power_state",
+        "group" => group_sample.group_label.clone(),
+        "state" => power_state,
+      )
+      .set(
+        group_sample
+          .power_states
+          .get(power_state)
+          .copied()
+          .unwrap_or_default() as f64,
+      );
+    }
+
+    let hsm_state_set: HashSet<&str> = HSM_STATES
+      .into_iter()
+      .chain(group_sample.hsm_states.keys().map(String::as_str))
+      .collect();
+
+    for hsm_state in hsm_state_set {
+      ::metrics::gauge!(
+        "ochami_group_nodes_hsm_state",
+        "group" => group_sample.group_label.clone(),
+        "state" => hsm_state.to_string(),
+      )
+      .set(
+        group_sample
+          .hsm_states
+          .get(hsm_state)
+          .copied()
+          .unwrap_or_default() as f64,
+      );
+    }
+  }
+}
+
+/// Samples the node states and publishes them with `record`, see `run`
+pub struct Exporter {
+  ctx: ApiContext,
+  group_label_vec: Vec<String>,
+  interval: Duration,
+}
+
+impl Exporter {
+  pub fn new(
+    ctx: &ApiContext,
+    group_label_vec: &[String],
+    interval: Duration,
+  ) -> Self {
+    Exporter {
+      ctx: ctx.clone(),
+      group_label_vec: group_label_vec.to_vec(),
+      interval,
+    }
+  }
+
+  /// Replace the token used to query HSM and PCS, eg: after it was renewed
+  pub fn set_auth_token(&mut self, auth_token: &str) {
+    self.ctx = self.ctx.with_auth_token(auth_token);
+  }
+
+  /// Sample and publish the node states once
+  pub async fn export(&self) -> Result<FleetSample, Error> {
+    let fleet_sample = sample(&self.ctx, &self.group_label_vec).await?;
+
+    record(&fleet_sample);
+
+    Ok(fleet_sample)
+  }
+
+  /// Sample and publish the node states every `interval` until the future is
+  /// dropped. Tokens sent on `auth_token_rx` replace the current one from the next
+  /// sample on. Failed samples are logged and counted in
+  /// `ochami_fleet_sample_failures_total`, the gauges keep their last value
+  pub async fn run(
+    &mut self,
+    mut auth_token_rx: Option<watch::Receiver<String>>,
+  ) {
+    loop {
+      if let Some(rx) = auth_token_rx.as_mut() {
+        if rx.has_changed().unwrap_or(false) {
+          let auth_token = rx.borrow_and_update().clone();
+          self.set_auth_token(&auth_token);
+        }
+      }
+
+      if let Err(e) = self.export().await {
+        log::warn!("Could not sample node states. Reason:\n{}", e);
+        ::metrics::counter!("ochami_fleet_sample_failures_total").increment(1);
+      }
+
+      tokio::time::sleep(self.interval).await;
+    }
+  }
+}
@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use serde_json::json;
+
+use crate::test_util::{MockOchami, MockService};
+
+use super::{sample, Exporter};
+
+#[tokio::test]
+async fn test_sample_counts_all_groups() {
+  let mock = MockOchami::with_fixtures().await;
+
+  let fleet_sample = sample(&mock.context(), &[]).await.unwrap();
+
+  assert_eq!(fleet_sample.groups.len(), 2);
+
+  let compute = &fleet_sample.groups[0];
+  assert_eq!(compute.group_label, "compute");
+  assert_eq!(
+    compute.power_states,
+    [("on".to_string(), 1), ("undefined".to_string(), 1)].into()
+  );
+  assert_eq!(
+    compute.hsm_states,
+    [("Off".to_string(), 1), ("Ready".to_string(), 1)].into()
+  );
+
+  // Missing from the PCS power status
+  let management = &fleet_sample.groups[1];
+  assert_eq!(management.group_label, "management");
+  assert_eq!(management.power_states, [("unknown".to_string(), 1)].into());
+  assert_eq!(management.hsm_states, [("Ready".to_string(), 1)].into());
+}
+
+#[tokio::test]
+async fn test_sample_counts_nodes_unknown_to_hsm() {
+  let mock = MockOchami::with_fixtures().await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "GET",
+      "/groups/spare/members",
+      200,
+      Some(json!({ "ids": ["x1000c0s0b0n0", "x9000c0s0b0n0"] })),
+    )
+    .await;
+
+  let fleet_sample = sample(&mock.context(), &["spare".to_string()])
+    .await
+    .unwrap();
+
+  assert_eq!(fleet_sample.groups.len(), 1);
+
+  let spare = &fleet_sample.groups[0];
+  assert_eq!(
+    spare.power_states,
+    [("on".to_string(), 1), ("unknown".to_string(), 1)].into()
+  );
+  assert_eq!(
+    spare.hsm_states,
+    [("Ready".to_string(), 1), ("Unknown".to_string(), 1)].into()
+  );
+}
+
+#[tokio::test]
+async fn test_sample_empty_group() {
+  let mock = MockOchami::start().await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "GET",
+      "/groups/empty/members",
+      200,
+      Some(json!({ "ids": [] })),
+    )
+    .await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "GET",
+      "/State/Components",
+      200,
+      Some(json!({ "Components": [] })),
+    )
+    .await;
+
+  let fleet_sample = sample(&mock.context(), &["empty".to_string()])
+    .await
+    .unwrap();
+
+  assert!(fleet_sample.groups[0].power_states.is_empty());
+  assert!(fleet_sample.groups[0].hsm_states.is_empty());
+
+  // No node to query PCS for
+  let request_vec = mock.server().received_requests().await.unwrap();
+  assert!(request_vec
+    .iter()
+    .all(|request| !request.url.path().ends_with("/power-status")));
+}
+
+#[tokio::test]
+async fn test_exporter_uses_renewed_token() {
+  let mock = MockOchami::with_fixtures().await;
+
+  let mut exporter =
+    Exporter::new(&mock.context(), &[], Duration::from_millis(20));
+
+  let (auth_token_tx, auth_token_rx) =
+    tokio::sync::watch::channel("expired".to_string());
+  auth_token_tx.send("renewed".to_string()).unwrap();
+
+  let _ = tokio::time::timeout(
+    Duration::from_millis(100),
+    exporter.run(Some(auth_token_rx)),
+  )
+  .await;
+
+  let request_vec = mock.server().received_requests().await.unwrap();
+  assert!(!request_vec.is_empty());
+  assert!(request_vec.iter().all(|request| {
+    request.headers.get("authorization").unwrap() == "Bearer renewed"
+  }));
+}
@@ -0,0 +1,260 @@
+use serde_json::json;
+
+use crate::{
+  hsm::component::types::{ComponentArrayPostArray, ComponentCreate},
+  http::{ExecutionMode, PlannedRequest},
+  mutation::{
+    types::{Journal, Mutation, MutationOutcome},
+    utils::{execute, execute_journaled, inverse, rollback},
+  },
+  test_util::{MockOchami, MockService},
+};
+
+fn component_create(xname: &str) -> ComponentCreate {
+  ComponentCreate {
+    id: xname.to_string(),
+    state: "Populated".to_string(),
+    flag: None,
+    enabled: Some(true),
+    software_status: None,
+    role: Some("Compute".to_string()),
+    sub_role: None,
+    nid: None,
+    subtype: None,
+    net_type: None,
+    arch: None,
+    class: None,
+  }
+}
+
+#[tokio::test]
+async fn test_inverse_create_components_queries_affected_ids() {
+  let mock = MockOchami::start().await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "POST",
+      "/State/Components/Query",
+      200,
+      Some(json!({"Components": [
+        {"ID": "x1000c0s0b0n0", "State": "Ready", "Role": "Service"}
+      ]})),
+    )
+    .await;
+
+  let inverse_vec = inverse(
+    &mock.context(),
+    &Mutation::CreateComponents(ComponentArrayPostArray {
+      components: vec![
+        component_create("x1000c0s0b0n0"),
+        component_create("x1000c0s0b0n1"),
+      ],
+      force: None,
+    }),
+  )
+  .await
+  .unwrap();
+
+  let request_vec = mock.server().received_requests().await.unwrap();
+  assert_eq!(request_vec.len(), 1);
+  let query: serde_json::Value =
+    serde_json::from_slice(&request_vec[0].body).unwrap();
+  assert_eq!(
+    query["ComponentIDs"],
+    json!(["x1000c0s0b0n0", "x1000c0s0b0n1"])
+  );
+
+  // The new component is deleted, the existing one restored
+  assert_eq!(inverse_vec.len(), 2);
+  assert!(matches!(
+    &inverse_vec[0],
+    Mutation::DeleteComponent { xname } if xname == "x1000c0s0b0n1"
+  ));
+  match &inverse_vec[1] {
+    Mutation::CreateComponents(component_array) => {
+      assert_eq!(component_array.components.len(), 1);
+      assert_eq!(component_array.components[0].id, "x1000c0s0b0n0");
+      assert_eq!(
+        component_array.components[0].role.as_deref(),
+        Some("Service")
+      );
+      assert_eq!(component_array.force, Some(true));
+    }
+    other => panic!("Unexpected inverse '{}'", other),
+  }
+}
+
+#[tokio::test]
+async fn test_inverse_delete_component() {
+  let mock = MockOchami::start().await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "GET",
+      "/State/Components/x1000c0s0b0n0",
+      200,
+      Some(json!({"ID": "x1000c0s0b0n0", "State": "Ready", "Role": "Compute"})),
+    )
+    .await;
+  mock
+    .mock_error(
+      MockService::Hsm,
+      "GET",
+      "/State/Components/x1000c0s0b0n1",
+      404,
+      "not found",
+    )
+    .await;
+
+  let ctx = mock.context();
+
+  let inverse_vec = inverse(
+    &ctx,
+    &Mutation::DeleteComponent {
+      xname: "x1000c0s0b0n0".to_string(),
+    },
+  )
+  .await
+  .unwrap();
+  assert!(matches!(
+    inverse_vec.as_slice(),
+    [Mutation::CreateComponents(component_array)]
+      if component_array.components[0].id == "x1000c0s0b0n0"
+  ));
+
+  // Nothing to restore if the component does not exist
+  let inverse_vec = inverse(
+    &ctx,
+    &Mutation::DeleteComponent {
+      xname: "x1000c0s0b0n1".to_string(),
+    },
+  )
+  .await
+  .unwrap();
+  assert!(inverse_vec.is_empty());
+}
+
+#[tokio::test]
+async fn test_inverse_create_ethernet_interface() {
+  let mock = MockOchami::start().await;
+
+  let inverse_vec = inverse(
+    &mock.context(),
+    &Mutation::CreateEthernetInterface(
+      serde_json::from_value(json!({
+        "MACAddress": "aa:bb:cc:dd:ee:ff",
+        "ComponentID": "x1000c0s0b0n0"
+      }))
+      .unwrap(),
+    ),
+  )
+  .await
+  .unwrap();
+
+  // No request is needed, HSM derives the id from the MAC address
+  assert!(mock.server().received_requests().await.unwrap().is_empty());
+  assert!(matches!(
+    inverse_vec.as_slice(),
+    [Mutation::DeleteEthernetInterface { id }] if id == "aabbccddeeff"
+  ));
+}
+
+#[tokio::test]
+async fn test_rollback_reverts_last_entry_first() {
+  let mock = MockOchami::start().await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "DELETE",
+      "/groups/compute/members/[^/]+",
+      200,
+      None,
+    )
+    .await;
+
+  let mut journal = Journal::new();
+  for xname in ["x1000c0s0b0n0", "x1000c0s0b0n1"] {
+    let mutation = Mutation::AddGroupMember {
+      group_label: "compute".to_string(),
+      xname: xname.to_string(),
+    };
+    let inverse_vec = inverse(&mock.context(), &mutation).await.unwrap();
+    journal.record(mutation, inverse_vec);
+  }
+
+  rollback(&mock.context(), &mut journal).await.unwrap();
+  assert!(journal.is_empty());
+
+  let path_vec: Vec<String> = mock
+    .server()
+    .received_requests()
+    .await
+    .unwrap()
+    .into_iter()
+    .map(|request| request.url.path().to_string())
+    .collect();
+  assert_eq!(path_vec.len(), 2);
+  assert!(path_vec[0].ends_with("/members/x1000c0s0b0n1"));
+  assert!(path_vec[1].ends_with("/members/x1000c0s0b0n0"));
+}
+
+#[tokio::test]
+async fn test_rollback_keeps_entries_not_reverted() {
+  let mock = MockOchami::start().await;
+  mock
+    .mock_error(
+      MockService::Hsm,
+      "DELETE",
+      "/groups/compute/members/[^/]+",
+      500,
+      "unavailable",
+    )
+    .await;
+
+  let mut journal = Journal::new();
+  journal.record(
+    Mutation::AddGroupMember {
+      group_label: "compute".to_string(),
+      xname: "x1000c0s0b0n0".to_string(),
+    },
+    vec![Mutation::RemoveGroupMember {
+      group_label: "compute".to_string(),
+      xname: "x1000c0s0b0n0".to_string(),
+    }],
+  );
+
+  assert!(rollback(&mock.context(), &mut journal).await.is_err());
+  // The rollback can be resumed
+  assert_eq!(journal.len(), 1);
+}
+
+#[tokio::test]
+async fn test_execute_dry_run_plans_client_request() {
+  let mock = MockOchami::start().await;
+  let ctx = mock.context().with_execution_mode(ExecutionMode::DryRun);
+
+  let mutation = Mutation::AddGroupMember {
+    group_label: "compute".to_string(),
+    xname: "x1000c0s0b0n0".to_string(),
+  };
+
+  let outcome = execute(&ctx, &mutation).await.unwrap();
+
+  assert_eq!(
+    outcome,
+    MutationOutcome::Planned(PlannedRequest {
+      method: "POST".to_string(),
+      url: ctx.hsm_url("/groups/compute/members").unwrap().to_string(),
+      body: Some(json!({"id": "x1000c0s0b0n0"})),
+    })
+  );
+
+  // Planned mutations are not journaled
+  let mut journal = Journal::new();
+  execute_journaled(&ctx, &mut journal, mutation)
+    .await
+    .unwrap();
+  assert!(journal.is_empty());
+
+  assert!(mock.server().received_requests().await.unwrap().is_empty());
+}
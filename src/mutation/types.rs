@@ -0,0 +1,159 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  bss::types::BootParameters,
+  hostlist,
+  hsm::{
+    component::types::ComponentArrayPostArray,
+    inventory::{
+      ethernet_interfaces::types::ComponentEthernetInterface,
+      redfish_endpoint::types::RedfishEndpointArray,
+    },
+  },
+  http::PlannedRequest,
+  pcs::transitions::types::PowerOperation,
+};
+
+/// A call changing the state of an OpenCHAMI service
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum Mutation {
+  AddGroupMember {
+    group_label: String,
+    xname: String,
+  },
+  RemoveGroupMember {
+    group_label: String,
+    xname: String,
+  },
+  SetBootParameters(BootParameters),
+  DeleteBootParameters {
+    hosts: Vec<String>,
+  },
+  PowerTransition {
+    operation: PowerOperation,
+    xnames: Vec<String>,
+  },
+  CreateComponents(ComponentArrayPostArray),
+  DeleteComponent {
+    xname: String,
+  },
+  CreateEthernetInterface(ComponentEthernetInterface),
+  DeleteEthernetInterface {
+    id: String,
+  },
+  CreateRedfishEndpoints(RedfishEndpointArray),
+  DeleteRedfishEndpoint {
+    xname: String,
+  },
+}
+
+impl fmt::Display for Mutation {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Mutation::AddGroupMember { group_label, xname } => {
+        write!(f, "add '{}' to HSM group '{}'", xname, group_label)
+      }
+      Mutation::RemoveGroupMember { group_label, xname } => {
+        write!(f, "remove '{}' from HSM group '{}'", xname, group_label)
+      }
+      Mutation::SetBootParameters(boot_parameters) => write!(
+        f,
+        "set boot parameters for {}",
+        hostlist::compress(&boot_parameters.hosts)
+      ),
+      Mutation::DeleteBootParameters { hosts } => {
+        write!(
+          f,
+          "delete boot parameters for {}",
+          hostlist::compress(hosts)
+        )
+      }
+      Mutation::PowerTransition { operation, xnames } => write!(
+        f,
+        "power transition '{}' on {}",
+        operation,
+        hostlist::compress(xnames)
+      ),
+      Mutation::CreateComponents(component_array) => write!(
+        f,
+        "create {} HSM components",
+        component_array.components.len()
+      ),
+      Mutation::DeleteComponent { xname } => {
+        write!(f, "delete HSM component '{}'", xname)
+      }
+      Mutation::CreateEthernetInterface(eth_interface) => write!(
+        f,
+        "create ethernet interface '{}' for '{}'",
+        eth_interface
+          .mac_address
+          .as_ref()
+          .map(|mac_address| mac_address.as_str())
+          .unwrap_or_default(),
+        eth_interface.component_id.as_deref().unwrap_or_default()
+      ),
+      Mutation::DeleteEthernetInterface { id } => {
+        write!(f, "delete ethernet interface '{}'", id)
+      }
+      Mutation::CreateRedfishEndpoints(redfish_endpoint_array) => write!(
+        f,
+        "create redfish endpoints {}",
+        hostlist::compress(
+          &redfish_endpoint_array
+            .redfish_endpoints
+            .iter()
+            .flatten()
+            .map(|redfish_endpoint| redfish_endpoint.id.clone())
+            .collect::<Vec<String>>()
+        )
+      ),
+      Mutation::DeleteRedfishEndpoint { xname } => {
+        write!(f, "delete redfish endpoint '{}'", xname)
+      }
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MutationOutcome {
+  Applied,
+  /// Dry-run, the request that would have been sent
+  Planned(PlannedRequest),
+}
+
+/// A mutation applied and the mutations reverting it, captured before it was applied
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JournalEntry {
+  pub mutation: Mutation,
+  /// Mutations restoring the state before `mutation`, applied in order. Empty if the
+  /// mutation can not be reverted (eg: a node restart)
+  pub inverse: Vec<Mutation>,
+}
+
+/// Mutations applied by a workflow, in order, so they can be reverted with
+/// `mutation::utils::rollback`. Serializable so it can be persisted while the workflow
+/// runs
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Journal {
+  pub entries: Vec<JournalEntry>,
+}
+
+impl Journal {
+  pub fn new() -> Self {
+    Journal::default()
+  }
+
+  pub fn record(&mut self, mutation: Mutation, inverse: Vec<Mutation>) {
+    self.entries.push(JournalEntry { mutation, inverse });
+  }
+
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+}
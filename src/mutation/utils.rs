@@ -0,0 +1,372 @@
+use std::collections::HashSet;
+
+use crate::{
+  bss::{self, types::BootParameters},
+  error::Error,
+  hsm::{
+    self,
+    component::{
+      types::{
+        ComponentArrayPostArray, ComponentArrayPostQuery, ComponentCreate,
+      },
+      utils::to_component_create,
+    },
+    group::types::Member,
+    inventory::redfish_endpoint::types::RedfishEndpointArray,
+  },
+  http::ApiContext,
+  pcs::{
+    self, power_status::types::PowerState, transitions::types::PowerOperation,
+  },
+};
+
+use super::types::{Journal, Mutation, MutationOutcome};
+
+/// Apply a mutation, or if `ctx` is in dry-run return the request that would be sent
+/// without sending it
+pub async fn execute(
+  ctx: &ApiContext,
+  mutation: &Mutation,
+) -> Result<MutationOutcome, Error> {
+  match apply(ctx, mutation).await {
+    Ok(()) => Ok(MutationOutcome::Applied),
+    Err(Error::DryRun(request)) => {
+      log::info!("Dry-run, {}: {}", mutation, request);
+      Ok(MutationOutcome::Planned(request))
+    }
+    Err(e) => Err(e),
+  }
+}
+
+/// Send the request of a mutation through the client of its service, the request is
+/// only planned if `ctx` is in dry-run
+async fn apply(ctx: &ApiContext, mutation: &Mutation) -> Result<(), Error> {
+  log::info!("{}", mutation);
+
+  match mutation {
+    Mutation::AddGroupMember { group_label, xname } => {
+      hsm::group::client::post_member(
+        ctx,
+        group_label,
+        Member {
+          id: Some(xname.clone()),
+        },
+      )
+      .await?;
+    }
+    Mutation::RemoveGroupMember { group_label, xname } => {
+      hsm::group::client::delete_member(ctx, group_label, xname).await?;
+    }
+    Mutation::SetBootParameters(boot_parameters) => {
+      bss::client::put(ctx, boot_parameters).await?;
+    }
+    Mutation::DeleteBootParameters { hosts } => {
+      bss::client::delete(
+        ctx,
+        &BootParameters {
+          hosts: hosts.clone(),
+          ..Default::default()
+        },
+      )
+      .await?;
+    }
+    Mutation::PowerTransition { operation, xnames } => {
+      pcs::transitions::client::post(ctx, *operation, xnames).await?;
+    }
+    Mutation::CreateComponents(component_array) => {
+      hsm::component::client::post(ctx, component_array.clone()).await?;
+    }
+    Mutation::DeleteComponent { xname } => {
+      hsm::component::client::delete_one(ctx, xname).await?;
+    }
+    Mutation::CreateEthernetInterface(eth_interface) => {
+      hsm::inventory::ethernet_interfaces::client::post(
+        ctx,
+        eth_interface.clone(),
+      )
+      .await?;
+    }
+    Mutation::DeleteEthernetInterface { id } => {
+      hsm::inventory::ethernet_interfaces::client::delete_one(ctx, id).await?;
+    }
+    Mutation::CreateRedfishEndpoints(redfish_endpoint_array) => {
+      hsm::inventory::redfish_endpoint::client::post(
+        ctx,
+        redfish_endpoint_array.clone(),
+      )
+      .await?;
+    }
+    Mutation::DeleteRedfishEndpoint { xname } => {
+      hsm::inventory::redfish_endpoint::client::delete_one(ctx, xname).await?;
+    }
+  }
+
+  Ok(())
+}
+
+/// Execute mutations in order, stopping at the first error
+pub async fn execute_all(
+  ctx: &ApiContext,
+  mutation_vec: &[Mutation],
+) -> Result<Vec<MutationOutcome>, Error> {
+  let mut outcome_vec = Vec::with_capacity(mutation_vec.len());
+
+  for mutation in mutation_vec {
+    outcome_vec.push(execute(ctx, mutation).await?);
+  }
+
+  Ok(outcome_vec)
+}
+
+/// Apply a mutation and record it in `journal`, with the mutations reverting it. The
+/// state needed to revert the mutation is read before it is applied. Nothing is recorded
+/// if the mutation fails or if `ctx` is in dry-run
+pub async fn execute_journaled(
+  ctx: &ApiContext,
+  journal: &mut Journal,
+  mutation: Mutation,
+) -> Result<(), Error> {
+  let inverse_vec = inverse(ctx, &mutation).await?;
+
+  if let MutationOutcome::Applied = execute(ctx, &mutation).await? {
+    journal.record(mutation, inverse_vec);
+  }
+
+  Ok(())
+}
+
+/// Revert the mutations recorded in `journal`, last one first. Stops at the first error,
+/// entries not reverted yet are left in `journal` so the rollback can be resumed
+pub async fn rollback(
+  ctx: &ApiContext,
+  journal: &mut Journal,
+) -> Result<(), Error> {
+  while let Some(entry) = journal.entries.last() {
+    if entry.inverse.is_empty() {
+      log::warn!("Can not revert '{}', skipping", entry.mutation);
+    }
+
+    for mutation in &entry.inverse {
+      execute(ctx, mutation).await.map_err(|e| {
+        Error::Message(format!(
+          "Rollback of '{}' failed while trying to {}. Reason:\n{}",
+          entry.mutation, mutation, e
+        ))
+      })?;
+    }
+
+    journal.entries.pop();
+  }
+
+  Ok(())
+}
+
+/// Mutations restoring the current state once `mutation` is applied
+pub async fn inverse(
+  ctx: &ApiContext,
+  mutation: &Mutation,
+) -> Result<Vec<Mutation>, Error> {
+  let inverse_vec = match mutation {
+    Mutation::AddGroupMember { group_label, xname } => {
+      vec![Mutation::RemoveGroupMember {
+        group_label: group_label.clone(),
+        xname: xname.clone(),
+      }]
+    }
+    Mutation::RemoveGroupMember { group_label, xname } => {
+      vec![Mutation::AddGroupMember {
+        group_label: group_label.clone(),
+        xname: xname.clone(),
+      }]
+    }
+    Mutation::SetBootParameters(BootParameters { hosts, .. })
+    | Mutation::DeleteBootParameters { hosts } => {
+      // BSS returns all boot parameters when no host is given
+      if hosts.is_empty() {
+        return Err(Error::Message(format!(
+          "Can not capture the state before '{}', no host given",
+          mutation
+        )));
+      }
+
+      let current_vec = bss::client::get(ctx, &Some(hosts.clone())).await?;
+
+      let configured_host_set: HashSet<&String> = current_vec
+        .iter()
+        .flat_map(|boot_parameters| boot_parameters.hosts.iter())
+        .collect();
+
+      let unconfigured_host_vec: Vec<String> = hosts
+        .iter()
+        .filter(|host| !configured_host_set.contains(host))
+        .cloned()
+        .collect();
+
+      let mut inverse_vec: Vec<Mutation> = current_vec
+        .iter()
+        .cloned()
+        .map(Mutation::SetBootParameters)
+        .collect();
+
+      if matches!(mutation, Mutation::SetBootParameters(_))
+        && !unconfigured_host_vec.is_empty()
+      {
+        inverse_vec.push(Mutation::DeleteBootParameters {
+          hosts: unconfigured_host_vec,
+        });
+      }
+
+      inverse_vec
+    }
+    Mutation::PowerTransition { operation, xnames } => {
+      // Only the nodes whose power state is changed by the transition are reverted
+      let (is_changed, inverse_operation): (fn(&PowerState) -> bool, _) =
+        match operation {
+          PowerOperation::On => (
+            |state| matches!(state, PowerState::Off),
+            PowerOperation::Off,
+          ),
+          PowerOperation::Off
+          | PowerOperation::SoftOff
+          | PowerOperation::ForceOff => {
+            (|state| matches!(state, PowerState::On), PowerOperation::On)
+          }
+          _ => return Ok(Vec::new()),
+        };
+
+      let xname_vec: Vec<&str> = xnames.iter().map(String::as_str).collect();
+
+      let changed_xname_vec: Vec<String> =
+        pcs::power_status::client::post(ctx, Some(&xname_vec), None, None)
+          .await?
+          .status
+          .into_iter()
+          .filter(|status| status.power_state.as_ref().is_some_and(is_changed))
+          .map(|status| status.xname)
+          .collect();
+
+      if changed_xname_vec.is_empty() {
+        Vec::new()
+      } else {
+        vec![Mutation::PowerTransition {
+          operation: inverse_operation,
+          xnames: changed_xname_vec,
+        }]
+      }
+    }
+    Mutation::CreateComponents(component_array) => {
+      let xname_vec: Vec<String> = component_array
+        .components
+        .iter()
+        .map(|component| component.id.clone())
+        .collect();
+
+      let current_vec = hsm::component::client::post_query(
+        ctx,
+        ComponentArrayPostQuery {
+          component_ids: Some(xname_vec),
+          ..Default::default()
+        },
+      )
+      .await?
+      .components
+      .unwrap_or_default();
+
+      // Components overwritten are restored with their current state
+      let restore_vec: Vec<ComponentCreate> = current_vec
+        .into_iter()
+        .filter_map(to_component_create)
+        .collect();
+
+      let mut inverse_vec: Vec<Mutation> = component_array
+        .components
+        .iter()
+        .filter(|new| !restore_vec.iter().any(|current| current.id == new.id))
+        .map(|component| Mutation::DeleteComponent {
+          xname: component.id.clone(),
+        })
+        .collect();
+
+      if !restore_vec.is_empty() {
+        inverse_vec.push(Mutation::CreateComponents(ComponentArrayPostArray {
+          components: restore_vec,
+          force: Some(true),
+        }));
+      }
+
+      inverse_vec
+    }
+    Mutation::DeleteComponent { xname } => {
+      let current_rslt =
+        hsm::component::client::get_one_raw(ctx, xname).await?;
+
+      if current_rslt.status == reqwest::StatusCode::NOT_FOUND {
+        return Ok(Vec::new());
+      }
+
+      if !current_rslt.is_success() {
+        return Err(Error::Message(format!(
+          "Could not capture the state before '{}': {} {}",
+          mutation, current_rslt.status, current_rslt.body
+        )));
+      }
+
+      serde_json::from_str(&current_rslt.body)
+        .map(to_component_create)?
+        .map(|component| {
+          Mutation::CreateComponents(ComponentArrayPostArray {
+            components: vec![component],
+            force: Some(true),
+          })
+        })
+        .into_iter()
+        .collect()
+    }
+    Mutation::CreateEthernetInterface(eth_interface) => {
+      // HSM rejects an interface that already exists, so a successful creation is
+      // reverted by deleting it. HSM uses the MAC address without separators as id
+      // when none is given
+      eth_interface
+        .id
+        .clone()
+        .or_else(|| {
+          eth_interface
+            .mac_address
+            .as_ref()
+            .map(|mac_address| mac_address.as_str().replace(':', ""))
+        })
+        .filter(|eth_interface_id| !eth_interface_id.is_empty())
+        .map(|id| Mutation::DeleteEthernetInterface { id })
+        .into_iter()
+        .collect()
+    }
+    Mutation::DeleteEthernetInterface { id } => {
+      let eth_interface =
+        hsm::inventory::ethernet_interfaces::client::get_one(ctx, id).await?;
+
+      vec![Mutation::CreateEthernetInterface(eth_interface)]
+    }
+    Mutation::CreateRedfishEndpoints(redfish_endpoint_array) => {
+      // HSM rejects endpoints that already exist, so a successful creation is
+      // reverted by deleting them
+      redfish_endpoint_array
+        .redfish_endpoints
+        .iter()
+        .flatten()
+        .map(|redfish_endpoint| Mutation::DeleteRedfishEndpoint {
+          xname: redfish_endpoint.id.clone(),
+        })
+        .collect()
+    }
+    Mutation::DeleteRedfishEndpoint { xname } => {
+      let redfish_endpoint =
+        hsm::inventory::redfish_endpoint::client::get_one(ctx, xname).await?;
+
+      vec![Mutation::CreateRedfishEndpoints(RedfishEndpointArray {
+        redfish_endpoints: Some(vec![redfish_endpoint]),
+      })]
+    }
+  };
+
+  Ok(inverse_vec)
+}
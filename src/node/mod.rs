@@ -1 +1,4 @@
+#[cfg(test)]
+pub mod tests;
+pub mod types;
 pub mod utils;
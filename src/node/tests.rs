@@ -0,0 +1,229 @@
+use regex::Regex;
+use serde_json::json;
+
+use crate::{
+  fixtures,
+  test_util::{MockOchami, MockService},
+};
+
+use super::{
+  types::{NodeAliases, Xname, XnameType},
+  utils::{get_node_aliases, get_xnames_from_nid_regex, nid_range_from_regex},
+};
+
+#[test]
+fn test_xname_parse() {
+  let xname: Xname = "x1000c0s7b0n1".parse().unwrap();
+
+  assert_eq!(xname.r#type(), XnameType::Node);
+  assert_eq!(xname.cabinet(), 1000);
+  assert_eq!(
+    xname.segments(),
+    [
+      ("x".to_string(), 1000),
+      ("c".to_string(), 0),
+      ("s".to_string(), 7),
+      ("b".to_string(), 0),
+      ("n".to_string(), 1),
+    ]
+  );
+  assert_eq!(xname.bmc().unwrap().to_string(), "x1000c0s7b0");
+  assert_eq!(xname.node_enclosure().unwrap().to_string(), "x1000c0s7e0");
+  assert_eq!(xname.parent().unwrap().r#type(), XnameType::NodeBmc);
+}
+
+#[test]
+fn test_xname_parse_invalid() {
+  for invalid in [
+    "",
+    "x",
+    "c0s7",
+    "X1000c0",
+    "x1000c",
+    "x1000c0s7b0n1-",
+    "x1000c0 s7",
+    "x99999999999c0",
+  ] {
+    assert!(invalid.parse::<Xname>().is_err(), "{}", invalid);
+  }
+}
+
+#[test]
+fn test_xname_round_trip() {
+  for xname in ["x1000", "x1000c0s7b0n1", "x3000c0r15", "x1000c0s7b0n1p0"] {
+    assert_eq!(xname.parse::<Xname>().unwrap().to_string(), xname);
+
+    let json = serde_json::to_string(&xname.parse::<Xname>().unwrap()).unwrap();
+    assert_eq!(json, format!("\"{}\"", xname));
+    assert_eq!(
+      serde_json::from_str::<Xname>(&json).unwrap(),
+      xname.parse::<Xname>().unwrap()
+    );
+  }
+}
+
+#[test]
+fn test_xname_leading_zeros_are_normalized() {
+  let xname: Xname = "x1000c0s07b0n01".parse().unwrap();
+
+  assert_eq!(xname.to_string(), "x1000c0s7b0n1");
+  assert_eq!(xname, "x1000c0s7b0n1".parse::<Xname>().unwrap());
+}
+
+#[test]
+fn test_xname_ordering_is_numeric() {
+  let mut xname_vec: Vec<Xname> = ["x1000c0s10", "x1000c0s2", "x999c0s1"]
+    .iter()
+    .map(|xname| xname.parse().unwrap())
+    .collect();
+  xname_vec.sort();
+
+  let xname_vec: Vec<String> =
+    xname_vec.iter().map(ToString::to_string).collect();
+  assert_eq!(xname_vec, ["x999c0s1", "x1000c0s2", "x1000c0s10"]);
+}
+
+#[test]
+fn test_nid_range_from_regex() {
+  assert_eq!(nid_range_from_regex("nid000042"), Some((42, 42)));
+  assert_eq!(nid_range_from_regex("nid00001.*"), Some((10, 19)));
+  assert_eq!(nid_range_from_regex("^nid0000[0-9]+"), Some((0, 99)));
+  assert_eq!(nid_range_from_regex("nid1"), Some((100000, 199999)));
+}
+
+#[test]
+fn test_nid_range_from_regex_optional_last_digit() {
+  // The '2' may be missing, the window only relies on the digits before it
+  assert_eq!(nid_range_from_regex("nid000012?"), Some((10, 19)));
+  assert_eq!(nid_range_from_regex("nid00001{0,1}"), Some((0, 99)));
+}
+
+#[test]
+fn test_nid_range_from_regex_no_window() {
+  for regex in [
+    "nid.*",
+    "nid[0-9]+",
+    "x1000c0s0b0n0",
+    "nid000001|nid000002",
+    "nid1234567",
+    "1?",
+  ] {
+    assert_eq!(nid_range_from_regex(regex), None, "{}", regex);
+  }
+}
+
+#[tokio::test]
+async fn test_get_xnames_from_nid_regex_deduplicates() {
+  let mock = MockOchami::with_fixtures().await;
+
+  // Both regexes match nid000001, queried once per window
+  let regex_vec = [
+    Regex::new("nid00000[12]").unwrap(),
+    Regex::new("nid000001").unwrap(),
+    Regex::new("nid000001").unwrap(),
+  ];
+
+  let xname_vec = get_xnames_from_nid_regex(&mock.context(), &regex_vec)
+    .await
+    .unwrap();
+
+  assert_eq!(xname_vec, ["x1000c0s0b0n0", "x1000c0s0b0n1"]);
+
+  let request_vec = mock.server().received_requests().await.unwrap();
+  assert_eq!(request_vec.len(), 2);
+}
+
+#[test]
+fn test_node_aliases_resolution() {
+  let mut node_aliases = NodeAliases::default();
+  node_aliases.insert("x1000c0s0b0n0", "nid000001", false);
+  node_aliases.insert("x1000c0s0b0n0", "login01", true);
+  node_aliases.insert("x1000c0s0b0n0", "login01-alt", true);
+  // Alias already taken by another node
+  node_aliases.insert("x1000c0s0b0n1", "login01", true);
+
+  assert_eq!(
+    node_aliases.xname("nid000001").as_deref(),
+    Some("x1000c0s0b0n0")
+  );
+  assert_eq!(
+    node_aliases.xname("login01").as_deref(),
+    Some("x1000c0s0b0n0")
+  );
+  // Xnames resolve to themselves, even if unknown
+  assert_eq!(
+    node_aliases.xname("x3000c0s1b0n0").as_deref(),
+    Some("x3000c0s1b0n0")
+  );
+  assert_eq!(node_aliases.xname("nid000099"), None);
+
+  // First hostname registered, NIDs are not hostnames
+  assert_eq!(node_aliases.hostname("x1000c0s0b0n0"), Some("login01"));
+  assert_eq!(node_aliases.hostname("x3000c0s1b0n0"), None);
+}
+
+#[test]
+fn test_node_aliases_resolve() {
+  let mut node_aliases = NodeAliases::default();
+  node_aliases.insert("x1000c0s0b0n0", "nid000001", false);
+  node_aliases.insert("x1000c0s0b0n1", "nid000002", false);
+
+  let name_vec = ["nid000002", "x3000c0s1b0n0", "nid000001"]
+    .map(str::to_string)
+    .to_vec();
+  assert_eq!(
+    node_aliases.resolve(&name_vec).unwrap(),
+    ["x1000c0s0b0n1", "x3000c0s1b0n0", "x1000c0s0b0n0"]
+  );
+
+  let name_vec = ["nid000001", "nid000099", "login01"]
+    .map(str::to_string)
+    .to_vec();
+  let e = node_aliases.resolve(&name_vec).unwrap_err().to_string();
+  assert!(e.contains("Could not resolve nodes: nid000099, login01"));
+}
+
+#[tokio::test]
+async fn test_get_node_aliases() {
+  let mock = MockOchami::start().await;
+  mock
+    .mock_json(
+      MockService::Hsm,
+      "GET",
+      "/State/Components",
+      fixtures::COMPONENTS_JSON,
+    )
+    .await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "GET",
+      "/Inventory/EthernetInterfaces",
+      200,
+      Some(json!([
+        { "ComponentID": "x3000c0s1b0n0", "Description": " ncn-m001 " },
+        // Not a hostname, several words
+        { "ComponentID": "x1000c0s0b0n0", "Description": "Node Maintenance Network" },
+        { "ComponentID": "x1000c0s0b0n1", "Description": "x1000c0s0b0n1" },
+      ])),
+    )
+    .await;
+
+  let node_aliases = get_node_aliases(&mock.context()).await.unwrap();
+
+  assert_eq!(
+    node_aliases.xname("ncn-m001").as_deref(),
+    Some("x3000c0s1b0n0")
+  );
+  assert_eq!(node_aliases.hostname("x3000c0s1b0n0"), Some("ncn-m001"));
+  assert_eq!(node_aliases.hostname("x1000c0s0b0n0"), None);
+  assert_eq!(node_aliases.hostname("x1000c0s0b0n1"), None);
+  assert_eq!(
+    node_aliases.xname("nid000002").as_deref(),
+    Some("x1000c0s0b0n1")
+  );
+  assert_eq!(
+    node_aliases.xname("nid100001").as_deref(),
+    Some("x3000c0s1b0n0")
+  );
+}
@@ -0,0 +1,293 @@
+use std::{collections::HashMap, fmt, ops::RangeInclusive, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+/// Type of a component according to the segments of its xname
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum XnameType {
+  /// eg: x1000
+  Cabinet,
+  /// eg: x1000c0
+  Chassis,
+  /// eg: x1000c0s7
+  Slot,
+  /// eg: x1000c0s7b0
+  NodeBmc,
+  /// eg: x1000c0s7b0n1
+  Node,
+  /// Any other valid xname (eg: x1000c0r15, x1000c0s7b0n1p0)
+  Other,
+}
+
+/// Validated xname. Each segment is made of a prefix (eg: 'c' for chassis) and a
+/// number. Xnames are sorted segment by segment comparing numbers numerically, so
+/// x1000c0s10 sorts after x1000c0s2.
+/// Parsing normalizes the numbers like HSM does: leading zeros are dropped, so
+/// x1000c01 equals x1000c1 and is displayed as x1000c1
+#[derive(
+  Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(try_from = "String", into = "String")]
+pub struct Xname {
+  segment_vec: Vec<(String, u32)>,
+}
+
+impl Xname {
+  /// Returns true if `xname` has a valid xname syntax
+  pub fn is_valid(xname: &str) -> bool {
+    xname.parse::<Xname>().is_ok()
+  }
+
+  pub fn r#type(&self) -> XnameType {
+    let prefix_vec: Vec<&str> = self
+      .segment_vec
+      .iter()
+      .map(|(prefix, _)| prefix.as_str())
+      .collect();
+
+    match prefix_vec.as_slice() {
+      ["x"] => XnameType::Cabinet,
+      ["x", "c"] => XnameType::Chassis,
+      ["x", "c", "s"] => XnameType::Slot,
+      ["x", "c", "s", "b"] => XnameType::NodeBmc,
+      ["x", "c", "s", "b", "n"] => XnameType::Node,
+      _ => XnameType::Other,
+    }
+  }
+
+  pub fn is_node(&self) -> bool {
+    self.r#type() == XnameType::Node
+  }
+
+  /// Cabinet number, eg: 1000 for x1000c0s7b0n1
+  pub fn cabinet(&self) -> u32 {
+    self.segment_vec[0].1
+  }
+
+  /// Segments of the xname, eg: [("x", 1000), ("c", 0)] for x1000c0
+  pub fn segments(&self) -> &[(String, u32)] {
+    &self.segment_vec
+  }
+
+  /// Parent component, eg: x1000c0s7b0 for x1000c0s7b0n1. None for cabinets
+  pub fn parent(&self) -> Option<Xname> {
+    (self.segment_vec.len() > 1)
+      .then(|| self.truncate(self.segment_vec.len() - 1))
+  }
+
+  /// BMC managing the component, eg: x1000c0s7b0 for x1000c0s7b0n1. Only defined for
+  /// nodes, node BMCs and their descendants
+  pub fn bmc(&self) -> Option<Xname> {
+    let bmc = self.truncate(4);
+    (bmc.r#type() == XnameType::NodeBmc).then_some(bmc)
+  }
+
+  /// Node enclosure holding the component, eg: x1000c0s7e0 for x1000c0s7b0n1. The
+  /// enclosure number is the same as the BMC number
+  pub fn node_enclosure(&self) -> Option<Xname> {
+    let bmc = self.bmc()?;
+    let mut segment_vec = bmc.segment_vec;
+    let (_, bmc_number) = segment_vec.pop()?;
+    segment_vec.push(("e".to_string(), bmc_number));
+
+    Some(Xname { segment_vec })
+  }
+
+  /// Returns true if `other` is a descendant of this component, eg: x1000c0 is an
+  /// ancestor of x1000c0s7b0n1
+  pub fn is_ancestor_of(&self, other: &Xname) -> bool {
+    other.segment_vec.len() > self.segment_vec.len()
+      && other.segment_vec.starts_with(&self.segment_vec)
+  }
+
+  /// Components of type `xname_type` under this component, taken from `xname_vec`
+  /// (eg: the components in HSM). Result is sorted
+  pub fn children_of_type(
+    &self,
+    xname_vec: &[Xname],
+    xname_type: XnameType,
+  ) -> Vec<Xname> {
+    let mut child_vec: Vec<Xname> = xname_vec
+      .iter()
+      .filter(|xname| {
+        xname.r#type() == xname_type && self.is_ancestor_of(xname)
+      })
+      .cloned()
+      .collect();
+
+    child_vec.sort();
+    child_vec.dedup();
+
+    child_vec
+  }
+
+  fn truncate(&self, len: usize) -> Xname {
+    Xname {
+      segment_vec: self.segment_vec.iter().take(len).cloned().collect(),
+    }
+  }
+}
+
+impl FromStr for Xname {
+  type Err = Error;
+
+  fn from_str(xname: &str) -> Result<Self, Self::Err> {
+    let invalid = || Error::Message(format!("Invalid xname '{}'", xname));
+
+    if !xname.starts_with('x') {
+      return Err(invalid());
+    }
+
+    let mut segment_vec: Vec<(String, u32)> = Vec::new();
+    let mut rest = xname;
+
+    while !rest.is_empty() {
+      let prefix_len = rest
+        .find(|c: char| !c.is_ascii_lowercase())
+        .ok_or_else(invalid)?;
+      let (prefix, tail) = rest.split_at(prefix_len);
+
+      let number_len = tail
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(tail.len());
+      let (number, tail) = tail.split_at(number_len);
+
+      if prefix.is_empty() || number.is_empty() {
+        return Err(invalid());
+      }
+
+      segment_vec
+        .push((prefix.to_string(), number.parse().map_err(|_| invalid())?));
+      rest = tail;
+    }
+
+    Ok(Xname { segment_vec })
+  }
+}
+
+impl TryFrom<String> for Xname {
+  type Error = Error;
+
+  fn try_from(xname: String) -> Result<Self, Self::Error> {
+    xname.parse()
+  }
+}
+
+impl TryFrom<&str> for Xname {
+  type Error = Error;
+
+  fn try_from(xname: &str) -> Result<Self, Self::Error> {
+    xname.parse()
+  }
+}
+
+impl From<Xname> for String {
+  fn from(xname: Xname) -> Self {
+    xname.to_string()
+  }
+}
+
+/// Normalized xname, see `Xname`
+impl fmt::Display for Xname {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (prefix, number) in &self.segment_vec {
+      write!(f, "{}{}", prefix, number)?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Hostnames and other aliases (eg: nid000001) of nodes, to accept user friendly names
+/// where xnames are expected
+#[derive(Debug, Clone, Default)]
+pub struct NodeAliases {
+  xname_by_alias: HashMap<String, String>,
+  hostname_by_xname: HashMap<String, String>,
+}
+
+impl NodeAliases {
+  /// Register an alias of a node. The first hostname registered for a node is the one
+  /// returned by `hostname`
+  pub fn insert(&mut self, xname: &str, alias: &str, is_hostname: bool) {
+    self
+      .xname_by_alias
+      .entry(alias.to_string())
+      .or_insert_with(|| xname.to_string());
+
+    if is_hostname {
+      self
+        .hostname_by_xname
+        .entry(xname.to_string())
+        .or_insert_with(|| alias.to_string());
+    }
+  }
+
+  /// Xname of a node given its xname or any of its aliases
+  pub fn xname(&self, name: &str) -> Option<String> {
+    if Xname::is_valid(name) {
+      return Some(name.to_string());
+    }
+
+    self.xname_by_alias.get(name).cloned()
+  }
+
+  /// Hostname of a node
+  pub fn hostname(&self, xname: &str) -> Option<&str> {
+    self.hostname_by_xname.get(xname).map(String::as_str)
+  }
+
+  /// Convert a list of xnames or aliases into xnames. Fails listing the names which
+  /// could not be resolved
+  pub fn resolve(&self, name_vec: &[String]) -> Result<Vec<String>, Error> {
+    let mut xname_vec = Vec::new();
+    let mut unknown_vec = Vec::new();
+
+    for name in name_vec {
+      match self.xname(name) {
+        Some(xname) => xname_vec.push(xname),
+        None => unknown_vec.push(name.as_str()),
+      }
+    }
+
+    if unknown_vec.is_empty() {
+      Ok(xname_vec)
+    } else {
+      Err(Error::Message(format!(
+        "Could not resolve nodes: {}",
+        unknown_vec.join(", ")
+      )))
+    }
+  }
+}
+
+/// NID ranges nodes are allocated from, optionally one per role (eg: Management nodes
+/// from 1-99, Compute nodes from 1000-9999)
+#[derive(Debug, Clone)]
+pub struct NidRanges {
+  pub default: RangeInclusive<usize>,
+  pub by_role: HashMap<String, RangeInclusive<usize>>,
+}
+
+impl NidRanges {
+  pub fn new(default: RangeInclusive<usize>) -> Self {
+    NidRanges {
+      default,
+      by_role: HashMap::new(),
+    }
+  }
+
+  pub fn with_role(mut self, role: &str, range: RangeInclusive<usize>) -> Self {
+    self.by_role.insert(role.to_string(), range);
+    self
+  }
+
+  /// Range of a role, the default range if the role has none
+  pub fn range(&self, role_opt: Option<&str>) -> &RangeInclusive<usize> {
+    role_opt
+      .and_then(|role| self.by_role.get(role))
+      .unwrap_or(&self.default)
+  }
+}
@@ -1,6 +1,14 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
 use regex::Regex;
 
-use crate::hsm;
+use crate::{
+  error::Error,
+  hsm::{self, component::types::ComponentArrayPostQuery},
+  http::ApiContext,
+};
+
+use super::types::{NidRanges, NodeAliases, Xname};
 
 /// Validate xname is correct (it uses regex taken from HPE Cray CSM docs)
 pub fn validate_xname_format(xname: &str) -> bool {
@@ -11,26 +19,347 @@ pub fn validate_xname_format(xname: &str) -> bool {
   xname_re.is_match(xname)
 }
 
+/// Width of NIDs in their long form, eg: nid000001
+const NID_WIDTH: u32 = 6;
+
+/// Derive the window of NIDs a regex over long NIDs (eg: nid00001.*) can match, from the
+/// digits following the 'nid' prefix. Returns None if no window can be derived, eg:
+/// alternations or no literal digits
+pub fn nid_range_from_regex(regex: &str) -> Option<(u32, u32)> {
+  if regex.contains('|') {
+    return None;
+  }
+
+  let rest = regex.trim_start_matches('^').strip_prefix("nid")?;
+  let mut digit_vec: Vec<char> =
+    rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+
+  // A quantifier after the last digit makes it optional
+  if rest[digit_vec.len()..].starts_with(['?', '*', '+', '{']) {
+    digit_vec.pop();
+  }
+
+  if digit_vec.is_empty() || digit_vec.len() as u32 > NID_WIDTH {
+    return None;
+  }
+
+  let prefix: u32 = digit_vec.iter().collect::<String>().parse().ok()?;
+  let scale = 10u32.pow(NID_WIDTH - digit_vec.len() as u32);
+
+  Some((prefix * scale, (prefix + 1) * scale - 1))
+}
+
+/// Expand a hostlist of long NIDs (eg: nid00000[1-3]) into NID numbers. Fails if the
+/// hostlist is malformed or any name is not 'nid' followed by a number
+pub fn parse_nid_hostlist(hostlist: &str) -> Result<Vec<usize>, Error> {
+  let nid_long_vec = hostlist_parser::parse(hostlist).map_err(|e| {
+    Error::Message(format!(
+      "Could not parse '{}' as a hostlist. Reason:\n{}",
+      hostlist, e
+    ))
+  })?;
+
+  nid_long_vec
+    .iter()
+    .map(|nid_long| {
+      nid_long
+        .strip_prefix("nid")
+        .filter(|nid| !nid.is_empty())
+        .and_then(|nid| nid.parse().ok())
+        .ok_or_else(|| {
+          Error::Message(format!(
+            "Nid '{}' not valid, expected 'nid' followed by a number",
+            nid_long
+          ))
+        })
+    })
+    .collect()
+}
+
+/// Get the xnames of the nodes whose long NID (eg: nid000001) matches any of the
+/// regexes. When a NID window can be derived from a regex only the nodes in that window
+/// are fetched from HSM, otherwise all nodes are fetched. Xnames are sorted
+pub async fn get_xnames_from_nid_regex(
+  ctx: &ApiContext,
+  regex_vec: &[Regex],
+) -> Result<Vec<String>, Error> {
+  let range_opt_set: BTreeSet<Option<(u32, u32)>> = regex_vec
+    .iter()
+    .map(|regex| nid_range_from_regex(regex.as_str()))
+    .collect();
+
+  let window_set: BTreeSet<Option<(u32, u32)>> =
+    if range_opt_set.contains(&None) {
+      BTreeSet::from([None])
+    } else {
+      range_opt_set
+    };
+
+  let mut xname_set: BTreeSet<String> = BTreeSet::new();
+
+  for window_opt in window_set {
+    let (nid_start, nid_end) = match window_opt {
+      Some((start, end)) => (Some(start.to_string()), Some(end.to_string())),
+      None => (None, None),
+    };
+
+    log::debug!("Get nodes from HSM in NID window {:?}", window_opt);
+
+    let component_vec = hsm::component::client::get(
+      ctx,
+      None,
+      Some("Node"),
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      nid_start.as_deref(),
+      nid_end.as_deref(),
+      None,
+      None,
+      None,
+      None,
+      None,
+      Some("true"),
+    )
+    .await?
+    .components
+    .unwrap_or_default();
+
+    for component in component_vec {
+      let (Some(xname), Some(nid)) = (component.id, component.nid) else {
+        continue;
+      };
+
+      let nid_long = format!("nid{:06}", nid);
+
+      if regex_vec.iter().any(|regex| regex.is_match(&nid_long)) {
+        xname_set.insert(xname);
+      }
+    }
+  }
+
+  Ok(xname_set.into_iter().collect())
+}
+
+/// Get the NID of a list of nodes, the inverse of `nid_to_xname`. Targets can be
+/// xnames or hostlist expressions (eg: x1000c0s[0-3]b0n0). All nodes are fetched with
+/// a single HSM query. Returns (xname, nid) pairs in the order of the input, nodes not
+/// found in HSM or without NID are skipped
+pub async fn xname_to_nid(
+  ctx: &ApiContext,
+  target_vec: &[String],
+) -> Result<Vec<(String, usize)>, Error> {
+  let mut xname_vec: Vec<String> = Vec::new();
+  let mut seen_set: HashSet<String> = HashSet::new();
+
+  for target in target_vec {
+    let expanded_vec = hostlist_parser::parse(target).map_err(|e| {
+      Error::Message(format!(
+        "Could not parse '{}' as a hostlist. Reason:\n{}",
+        target, e
+      ))
+    })?;
+
+    for xname in expanded_vec {
+      if seen_set.insert(xname.clone()) {
+        xname_vec.push(xname);
+      }
+    }
+  }
+
+  if xname_vec.is_empty() {
+    return Ok(Vec::new());
+  }
+
+  let nid_map: HashMap<String, usize> = hsm::component::client::post_query(
+    ctx,
+    ComponentArrayPostQuery {
+      component_ids: Some(xname_vec.clone()),
+      nid_only: Some(true),
+      ..Default::default()
+    },
+  )
+  .await?
+  .components
+  .unwrap_or_default()
+  .into_iter()
+  .filter_map(|component| Some((component.id?, component.nid?)))
+  .collect();
+
+  Ok(
+    xname_vec
+      .into_iter()
+      .filter_map(|xname| {
+        let nid = *nid_map.get(&xname)?;
+        Some((xname, nid))
+      })
+      .collect(),
+  )
+}
+
+/// Collect node aliases from HSM: hostnames taken from the description of the node
+/// ethernet interfaces (when it is a single word) and long NIDs (eg: nid000001)
+pub async fn get_node_aliases(ctx: &ApiContext) -> Result<NodeAliases, Error> {
+  let mut node_aliases = NodeAliases::default();
+
+  let eth_interface_vec = hsm::inventory::ethernet_interfaces::client::get(
+    ctx, None, None, None, None, None, None, None,
+  )
+  .await?;
+
+  for eth_interface in eth_interface_vec {
+    let (Some(xname), Some(description)) =
+      (eth_interface.component_id, eth_interface.description)
+    else {
+      continue;
+    };
+
+    let hostname = description.trim();
+
+    if !hostname.is_empty()
+      && !hostname.contains(char::is_whitespace)
+      && hostname != xname
+    {
+      node_aliases.insert(&xname, hostname, true);
+    }
+  }
+
+  let component_vec = hsm::component::client::get_all_nodes(ctx, Some("true"))
+    .await?
+    .components
+    .unwrap_or_default();
+
+  for component in component_vec {
+    if let (Some(xname), Some(nid)) = (component.id, component.nid) {
+      node_aliases.insert(&xname, &format!("nid{:06}", nid), false);
+    }
+  }
+
+  Ok(node_aliases)
+}
+
+/// Allocate `count` NIDs not used by any HSM component nor in `excluded_nid_vec`, from
+/// the range of `role_opt` (or the default range). Lowest free NIDs are allocated
+/// first. Fails if the range has not enough free NIDs
+pub async fn allocate_nids(
+  ctx: &ApiContext,
+  nid_ranges: &NidRanges,
+  role_opt: Option<&str>,
+  count: usize,
+  excluded_nid_vec: &[usize],
+) -> Result<Vec<usize>, Error> {
+  let mut used_nid_set: HashSet<usize> =
+    hsm::component::client::get_all_nodes(ctx, Some("true"))
+      .await?
+      .components
+      .unwrap_or_default()
+      .into_iter()
+      .filter_map(|component| component.nid)
+      .collect();
+  used_nid_set.extend(excluded_nid_vec);
+
+  let range = nid_ranges.range(role_opt);
+
+  let nid_vec: Vec<usize> = range
+    .clone()
+    .filter(|nid| !used_nid_set.contains(nid))
+    .take(count)
+    .collect();
+
+  if nid_vec.len() < count {
+    return Err(Error::Message(format!(
+      "Only {} free NIDs left in range {}-{}, {} requested",
+      nid_vec.len(),
+      range.start(),
+      range.end(),
+      count
+    )));
+  }
+
+  Ok(nid_vec)
+}
+
+/// Sort xnames numerically (x1000c0s10 after x1000c0s2). Strings which are not valid
+/// xnames are moved to the end, sorted alphabetically
+pub fn sort_xnames(xname_vec: &mut [String]) {
+  xname_vec.sort_by_cached_key(|xname| {
+    let parsed_opt = xname.parse::<Xname>().ok();
+    (parsed_opt.is_none(), parsed_opt, xname.clone())
+  });
+}
+
+/// Validates the format of a list of xnames and, if `hsm_group_name_vec` is not empty,
+/// that each of them belongs to at least one of those HSM groups. Returns the xnames
+/// which failed with the reason, empty if all are valid
+pub async fn validate_xnames_format_and_membership(
+  ctx: &ApiContext,
+  xnames: &[&str],
+  hsm_group_name_vec: &[String],
+) -> Result<Vec<(String, String)>, Error> {
+  let hsm_group_members: Vec<String> = if hsm_group_name_vec.is_empty() {
+    Vec::new()
+  } else {
+    hsm::group::utils::get_member_vec_from_hsm_name_vec(ctx, hsm_group_name_vec)
+      .await?
+  };
+
+  let failed_vec = xnames
+    .iter()
+    .filter_map(|&xname| {
+      if !validate_xname_format(xname) {
+        Some((xname.to_string(), "invalid xname format".to_string()))
+      } else if !hsm_group_name_vec.is_empty()
+        && !hsm_group_members.iter().any(|member| member == xname)
+      {
+        Some((
+          xname.to_string(),
+          format!(
+            "not a member of HSM groups {}",
+            hsm_group_name_vec.join(", ")
+          ),
+        ))
+      } else {
+        None
+      }
+    })
+    .collect();
+
+  Ok(failed_vec)
+}
+
 /// Validates a list of xnames.
 /// Checks xnames strings are valid
 /// If hsm_group_name_opt provided, then checks all xnames belongs to that hsm_group
-pub async fn validate_xnames_format_and_membership_agaisnt_single_hsm(
-  shasta_token: &str,
-  shasta_base_url: &str,
-  shasta_root_cert: &[u8],
+pub async fn validate_xnames_format_and_membership_against_single_hsm(
+  ctx: &ApiContext,
   xnames: &[&str],
   hsm_group_name_opt: Option<&str>,
 ) -> bool {
   let hsm_group_members: Vec<String> =
     if let Some(hsm_group_name) = hsm_group_name_opt {
-      hsm::group::utils::get_member_vec_from_hsm_name_vec_2(
-        shasta_token,
-        shasta_base_url,
-        shasta_root_cert,
+      match hsm::group::utils::get_member_vec_from_hsm_name_vec(
+        ctx,
         &[hsm_group_name.to_string()],
       )
       .await
-      .unwrap()
+      {
+        Ok(hsm_group_members) => hsm_group_members,
+        Err(e) => {
+          log::error!(
+            "Could not get members of HSM group '{}': {}",
+            hsm_group_name,
+            e
+          );
+          return false;
+        }
+      }
     } else {
       Vec::new()
     };
@@ -45,3 +374,27 @@ pub async fn validate_xnames_format_and_membership_agaisnt_single_hsm(
 
   true
 }
+
+#[deprecated(
+  note = "use `validate_xnames_format_and_membership_against_single_hsm` with an `ApiContext`"
+)]
+pub async fn validate_xnames_format_and_membership_agaisnt_single_hsm(
+  shasta_token: &str,
+  shasta_base_url: &str,
+  shasta_root_cert: &[u8],
+  xnames: &[&str],
+  hsm_group_name_opt: Option<&str>,
+) -> bool {
+  let ctx =
+    match ApiContext::parse(shasta_base_url, shasta_token, shasta_root_cert) {
+      Ok(ctx) => ctx,
+      Err(_) => return false,
+    };
+
+  validate_xnames_format_and_membership_against_single_hsm(
+    &ctx,
+    xnames,
+    hsm_group_name_opt,
+  )
+  .await
+}
@@ -0,0 +1,54 @@
+use crate::{error::Error, http::ApiContext};
+
+use super::types::PcsHealth;
+
+/// Get the status of the PCS dependencies (key/value store, locking, task runner, etc)
+pub async fn get_health(ctx: &ApiContext) -> Result<PcsHealth, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.pcs_url("/health")?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if response.status().is_success() {
+    response.json().await.map_err(Error::NetError)
+  } else {
+    let payload = response.text().await.map_err(Error::NetError)?;
+    Err(Error::Message(payload))
+  }
+}
+
+/// Returns Ok if PCS is running
+pub async fn get_liveness(ctx: &ApiContext) -> Result<(), Error> {
+  get_probe(ctx, "liveness").await
+}
+
+/// Returns Ok if PCS is ready to accept requests
+pub async fn get_readiness(ctx: &ApiContext) -> Result<(), Error> {
+  get_probe(ctx, "readiness").await
+}
+
+async fn get_probe(ctx: &ApiContext, probe: &str) -> Result<(), Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.pcs_url(&format!("/{}", probe))?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await?;
+
+  if response.status().is_success() {
+    Ok(())
+  } else {
+    let status = response.status();
+    let payload = response.text().await.map_err(Error::NetError)?;
+    Err(Error::Message(format!(
+      "PCS {} check failed ({}): {}",
+      probe, status, payload
+    )))
+  }
+}
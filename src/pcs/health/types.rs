@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Status of the PCS dependencies as returned by the `/health` endpoint
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct PcsHealth {
+  #[serde(rename = "KvStore")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub kv_store: Option<String>,
+  #[serde(rename = "StorageBackend")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub storage_backend: Option<String>,
+  #[serde(rename = "DistLocking")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub dist_locking: Option<String>,
+  #[serde(rename = "Vault")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub vault: Option<String>,
+  #[serde(rename = "TaskRunner")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub task_runner: Option<Value>,
+}
@@ -1,3 +1,8 @@
+pub mod health;
 pub mod power_cap;
 pub mod power_status;
+#[cfg(test)]
+pub mod tests;
 pub mod transitions;
+pub mod types;
+pub mod utils;
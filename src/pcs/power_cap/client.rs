@@ -0,0 +1,101 @@
+use crate::{
+  error::Error,
+  http::{ApiContext, MutationRequest},
+  pcs::utils::error_from_response,
+};
+
+use super::types::{PowerCapPatch, PowerCapTaskInfo, PowerCapTaskList};
+
+/// Get the list of power cap tasks (snapshots and patches)
+pub async fn get(ctx: &ApiContext) -> Result<PowerCapTaskList, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.pcs_url("/power-cap")?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await
+    .map_err(Error::NetError)?;
+
+  if response.status().is_success() {
+    response.json().await.map_err(Error::NetError)
+  } else {
+    Err(error_from_response(response).await)
+  }
+}
+
+/// Get the details of a power cap task, including the power cap values of each component
+/// when the task is completed
+pub async fn get_task_id(
+  ctx: &ApiContext,
+  task_id: &str,
+) -> Result<PowerCapTaskInfo, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.pcs_url(&format!("/power-cap/{}", task_id))?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await
+    .map_err(Error::NetError)?;
+
+  if response.status().is_success() {
+    response.json().await.map_err(Error::NetError)
+  } else {
+    Err(error_from_response(response).await)
+  }
+}
+
+/// Create a power cap snapshot task reading the current power caps of a list of xnames.
+/// Returns the task id, use `get_task_id` to fetch the results
+pub async fn post_snapshot(
+  ctx: &ApiContext,
+  xname_vec: Vec<&str>,
+) -> Result<PowerCapTaskInfo, Error> {
+  log::info!("Create PCS power snapshot for nodes:\n{:?}", xname_vec);
+
+  let client = ctx.client()?;
+  let api_url = ctx.pcs_url("/power-cap/snapshot")?;
+
+  let response = client
+    .post(api_url)
+    .json(&serde_json::json!({ "xnames": xname_vec }))
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await
+    .map_err(Error::NetError)?;
+
+  if response.status().is_success() {
+    response.json().await.map_err(Error::NetError)
+  } else {
+    Err(error_from_response(response).await)
+  }
+}
+
+/// Create a power cap task updating the power caps of a list of xnames.
+/// Returns the task id, use `get_task_id` to check the results
+pub async fn patch(
+  ctx: &ApiContext,
+  power_cap: PowerCapPatch,
+) -> Result<PowerCapTaskInfo, Error> {
+  log::info!("Create PCS power cap:\n{:#?}", power_cap);
+
+  let client = ctx.client()?;
+  let api_url = ctx.pcs_url("/power-cap")?;
+
+  let response = client
+    .patch(api_url)
+    .json(&power_cap)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if response.status().is_success() {
+    response.json().await.map_err(Error::NetError)
+  } else {
+    Err(error_from_response(response).await)
+  }
+}
@@ -1,109 +1,91 @@
-use serde_json::Value;
+use crate::{error::Error, http::ApiContext};
 
-use crate::error::Error;
-
-use super::types::{PowerCapComponent, PowerCapTaskInfo};
+use super::{
+  client,
+  types::{
+    PowerCapComponent, PowerCapControl, PowerCapPatch, PowerCapPatchComponent,
+    PowerCapTaskInfo, PowerCapTaskList,
+  },
+};
 
+#[deprecated(note = "use `power_cap::client::get` with an `ApiContext`")]
 pub async fn get(
   shasta_base_url: &str,
   shasta_token: &str,
   shasta_root_cert: &[u8],
-) -> Result<PowerCapTaskInfo, Error> {
-  let client = crate::http::build_client(shasta_root_cert)?;
-  let api_url = format!("{}/power-control/v1/power-cap", shasta_base_url);
-
-  let response = client
-    .get(api_url)
-    .bearer_auth(shasta_token)
-    .send()
-    .await
-    .map_err(Error::NetError)?;
-
-  if response.status().is_success() {
-    response.json().await.map_err(Error::NetError)
-  } else {
-    let payload = response.json::<Value>().await.map_err(Error::NetError)?;
-    Err(Error::OchamiError(payload))
-  }
+) -> Result<PowerCapTaskList, Error> {
+  client::get(&ApiContext::parse(
+    shasta_base_url,
+    shasta_token,
+    shasta_root_cert,
+  )?)
+  .await
 }
 
+#[deprecated(
+  note = "use `power_cap::client::get_task_id` with an `ApiContext`"
+)]
 pub async fn get_task_id(
   shasta_base_url: &str,
   shasta_token: &str,
   shasta_root_cert: &[u8],
   task_id: &str,
 ) -> Result<PowerCapTaskInfo, Error> {
-  let client = crate::http::build_client(shasta_root_cert)?;
-  let api_url =
-    format!("{}/power-control/v1/power-cap/{}", shasta_base_url, task_id);
-
-  let response = client
-    .get(api_url)
-    .bearer_auth(shasta_token)
-    .send()
-    .await
-    .map_err(Error::NetError)?;
-
-  if response.status().is_success() {
-    response.json().await.map_err(Error::NetError)
-  } else {
-    let payload = response.json::<Value>().await.map_err(Error::NetError)?;
-    Err(Error::OchamiError(payload))
-  }
+  client::get_task_id(
+    &ApiContext::parse(shasta_base_url, shasta_token, shasta_root_cert)?,
+    task_id,
+  )
+  .await
 }
 
+#[deprecated(
+  note = "use `power_cap::client::post_snapshot` with an `ApiContext`"
+)]
 pub async fn post_snapshot(
   shasta_base_url: &str,
   shasta_token: &str,
   shasta_root_cert: &[u8],
   xname_vec: Vec<&str>,
 ) -> Result<PowerCapTaskInfo, Error> {
-  log::info!("Create PCS power snapshot for nodes:\n{:?}", xname_vec);
-
-  let client = crate::http::build_client(shasta_root_cert)?;
-  let api_url =
-    shasta_base_url.to_owned() + "/power-control/v1/power-cap/snapshot";
-
-  let response = client
-    .put(api_url)
-    .json(&serde_json::json!({ "xnames": xname_vec }))
-    .bearer_auth(shasta_token)
-    .send()
-    .await
-    .map_err(Error::NetError)?;
-
-  if response.status().is_success() {
-    response.json().await.map_err(Error::NetError)
-  } else {
-    let payload = response.json::<Value>().await.map_err(Error::NetError)?;
-    Err(Error::OchamiError(payload))
-  }
+  client::post_snapshot(
+    &ApiContext::parse(shasta_base_url, shasta_token, shasta_root_cert)?,
+    xname_vec,
+  )
+  .await
 }
 
+#[deprecated(note = "use `power_cap::client::patch` with an `ApiContext`")]
 pub async fn patch(
   shasta_base_url: &str,
   shasta_token: &str,
   shasta_root_cert: &[u8],
   power_cap: Vec<PowerCapComponent>,
 ) -> Result<PowerCapTaskInfo, Error> {
-  log::info!("Create PCS power cap:\n{:#?}", power_cap);
-
-  let client = crate::http::build_client(shasta_root_cert)?;
-  let api_url =
-    shasta_base_url.to_owned() + "/power-control/v1/power-cap/snapshot";
-
-  let response = client
-    .put(api_url)
-    .json(&power_cap)
-    .bearer_auth(shasta_token)
-    .send()
-    .await
-    .map_err(Error::NetError)?;
-
-  if response.status().is_success() {
-    response.json().await.map_err(Error::NetError)
-  } else {
-    let payload = response.json::<Value>().await.map_err(Error::NetError)?;
-    Err(Error::OchamiError(payload))
-  }
+  let power_cap = PowerCapPatch {
+    components: power_cap
+      .into_iter()
+      .filter_map(|component| {
+        Some(PowerCapPatchComponent {
+          xname: component.xname?,
+          controls: component
+            .power_cap_limits
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|limit| {
+              Some(PowerCapControl {
+                name: limit.name?,
+                value: limit.current_value?,
+              })
+            })
+            .collect(),
+        })
+      })
+      .collect(),
+  };
+
+  client::patch(
+    &ApiContext::parse(shasta_base_url, shasta_token, shasta_root_cert)?,
+    power_cap,
+  )
+  .await
 }
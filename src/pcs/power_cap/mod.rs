@@ -1,2 +1,4 @@
+pub mod client;
 pub mod http_client;
 pub mod types;
+pub mod utils;
@@ -9,19 +9,21 @@ pub struct PowerCapTaskList {
 pub struct TaskCounts {
   pub total: usize,
   pub new: usize,
+  #[serde(rename = "in-progress", alias = "in_progress")]
   pub in_progress: usize,
   pub failed: usize,
   pub succeeded: usize,
+  #[serde(rename = "un-supported", alias = "un_supported")]
   pub un_supported: usize,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Limit {
   #[serde(skip_serializing_if = "Option::is_none")]
-  #[serde(rename = "hostsLimitMax")]
+  #[serde(rename = "hostLimitMax", alias = "hostsLimitMax")]
   pub hosts_limit_max: Option<usize>,
   #[serde(skip_serializing_if = "Option::is_none")]
-  #[serde(rename = "hostsLimitMin")]
+  #[serde(rename = "hostLimitMin", alias = "hostsLimitMin")]
   pub hosts_limit_min: Option<usize>,
   #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename = "powerupPower")]
@@ -52,14 +54,14 @@ pub struct PowerCapComponent {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub limits: Option<Limit>,
   #[serde(skip_serializing_if = "Option::is_none")]
-  #[serde(rename = "power_cap_limits")]
-  pub power_cap_limits: Option<PowerCapLimit>,
+  #[serde(rename = "powerCapLimits", alias = "power_cap_limits")]
+  pub power_cap_limits: Option<Vec<PowerCapLimit>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PowerCapTaskInfo {
   #[serde(skip_serializing_if = "Option::is_none")]
-  #[serde(rename = "taskId")]
+  #[serde(rename = "taskID", alias = "taskId")]
   pub task_id: Option<String>,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub r#type: Option<String>, // TODO: convert to enum. Valid values are `snapshot` and `patch`
@@ -78,3 +80,50 @@ pub struct PowerCapTaskInfo {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub components: Option<Vec<PowerCapComponent>>,
 }
+
+/// Power cap control to apply, `name` is the control name as reported in
+/// `PowerCapLimit::name` (eg: `Node Power Limit`) and `value` the new limit in watts
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PowerCapControl {
+  pub name: String,
+  pub value: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PowerCapPatchComponent {
+  pub xname: String,
+  pub controls: Vec<PowerCapControl>,
+}
+
+/// Payload to update power caps
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PowerCapPatch {
+  pub components: Vec<PowerCapPatchComponent>,
+}
+
+impl PowerCapPatch {
+  /// Build a payload applying the same control value to a list of xnames
+  pub fn new(xname_vec: &[&str], control_name: &str, value: usize) -> Self {
+    PowerCapPatch {
+      components: xname_vec
+        .iter()
+        .map(|xname| PowerCapPatchComponent {
+          xname: xname.to_string(),
+          controls: vec![PowerCapControl {
+            name: control_name.to_string(),
+            value,
+          }],
+        })
+        .collect(),
+    }
+  }
+}
+
+/// Result of applying a power cap to a set of nodes, verified with a snapshot
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct PowerCapResult {
+  /// Nodes reporting the requested power cap
+  pub applied: Vec<String>,
+  /// Nodes which rejected the power cap with the reason
+  pub rejected: Vec<(String, String)>,
+}
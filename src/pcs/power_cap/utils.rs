@@ -0,0 +1,136 @@
+use std::time::Instant;
+
+use crate::{
+  error::Error, hsm, http::ApiContext, pcs::transitions::types::PollOptions,
+};
+
+use super::{
+  client,
+  types::{PowerCapPatch, PowerCapResult, PowerCapTaskInfo},
+};
+
+/// Poll PCS until a power cap task completes. Returns an error if the task does not
+/// complete within `poll_options.max_wait`
+pub async fn wait_task(
+  ctx: &ApiContext,
+  task_id: &str,
+  poll_options: &PollOptions,
+) -> Result<PowerCapTaskInfo, Error> {
+  let start = Instant::now();
+
+  loop {
+    let task = client::get_task_id(ctx, task_id).await?;
+
+    if task.task_status.as_deref() == Some("completed") {
+      return Ok(task);
+    }
+
+    if start.elapsed() >= poll_options.max_wait {
+      return Err(Error::Message(format!(
+        "PCS power cap task '{}' did not complete after {:?}",
+        task_id, poll_options.max_wait
+      )));
+    }
+
+    tokio::time::sleep(poll_options.interval).await;
+  }
+}
+
+/// Apply a power cap (in watts) to all members of a HSM group and verify with a
+/// snapshot that each node reports the new value. `control_name` is the power cap
+/// control to update (eg: `Node Power Limit`)
+pub async fn set_group_power_cap(
+  ctx: &ApiContext,
+  group_label: &str,
+  control_name: &str,
+  value: usize,
+) -> Result<PowerCapResult, Error> {
+  let xname_vec = hsm::cache::get_members(ctx, group_label)
+    .await?
+    .ids
+    .unwrap_or_default();
+
+  if xname_vec.is_empty() {
+    return Err(Error::Message(format!(
+      "HSM group '{}' has no members",
+      group_label
+    )));
+  }
+
+  set_power_cap(ctx, &xname_vec, control_name, value).await
+}
+
+/// Apply a power cap (in watts) to a list of nodes and verify with a snapshot that each
+/// node reports the new value
+pub async fn set_power_cap(
+  ctx: &ApiContext,
+  xname_vec: &[String],
+  control_name: &str,
+  value: usize,
+) -> Result<PowerCapResult, Error> {
+  let poll_options = PollOptions::default();
+  let xname_str_vec: Vec<&str> = xname_vec.iter().map(String::as_str).collect();
+
+  let patch_task =
+    client::patch(ctx, PowerCapPatch::new(&xname_str_vec, control_name, value))
+      .await?;
+
+  let patch_task =
+    wait_task(ctx, get_task_id(&patch_task)?, &poll_options).await?;
+
+  let snapshot_task = client::post_snapshot(ctx, xname_str_vec).await?;
+
+  let snapshot_task =
+    wait_task(ctx, get_task_id(&snapshot_task)?, &poll_options).await?;
+
+  let patch_component_vec = patch_task.components.unwrap_or_default();
+  let snapshot_component_vec = snapshot_task.components.unwrap_or_default();
+
+  let mut result = PowerCapResult::default();
+
+  for xname in xname_vec {
+    let patch_error = patch_component_vec
+      .iter()
+      .find(|component| component.xname.as_ref() == Some(xname))
+      .and_then(|component| component.error.clone())
+      .filter(|error| !error.is_empty());
+
+    if let Some(error) = patch_error {
+      result.rejected.push((xname.clone(), error));
+      continue;
+    }
+
+    let current_value = snapshot_component_vec
+      .iter()
+      .find(|component| component.xname.as_ref() == Some(xname))
+      .and_then(|component| component.power_cap_limits.as_ref())
+      .and_then(|limit_vec| {
+        limit_vec
+          .iter()
+          .find(|limit| limit.name.as_deref() == Some(control_name))
+      })
+      .and_then(|limit| limit.current_value);
+
+    match current_value {
+      Some(current_value) if current_value == value => {
+        result.applied.push(xname.clone())
+      }
+      Some(current_value) => result.rejected.push((
+        xname.clone(),
+        format!("power cap is {} watts", current_value),
+      )),
+      None => result.rejected.push((
+        xname.clone(),
+        format!("control '{}' not reported by snapshot", control_name),
+      )),
+    }
+  }
+
+  Ok(result)
+}
+
+fn get_task_id(task: &PowerCapTaskInfo) -> Result<&str, Error> {
+  task.task_id.as_deref().ok_or_else(|| {
+    Error::Message("PCS did not return a power cap task id".to_string())
+  })
+}
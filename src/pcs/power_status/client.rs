@@ -0,0 +1,39 @@
+use serde_json::json;
+
+use crate::{error::Error, http::ApiContext, pcs::utils::error_from_response};
+
+use super::types::PowerStatusAll;
+
+pub async fn post(
+  ctx: &ApiContext,
+  xname_vec_opt: Option<&[&str]>,
+  power_state_filter_opt: Option<&str>,
+  management_state_filter_opt: Option<&str>,
+) -> Result<PowerStatusAll, Error> {
+  let client = ctx.client()?;
+
+  let api_url = ctx.pcs_url("/power-status")?;
+
+  let body = json!({
+      "xname": xname_vec_opt.map(|xname_vec| xname_vec.iter().map(|&x| x.to_string()).collect::<Vec<String>>()).unwrap_or_default(),
+      "powerStateFilter": power_state_filter_opt.unwrap_or(""),
+      "managementStateFilter": management_state_filter_opt.unwrap_or(""),
+  });
+
+  let response = client
+    .post(api_url)
+    .json(&body)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await
+    .map_err(|error| {
+      log::error!("Failed POST query: {:?}", error);
+      Error::NetError(error)
+    })?;
+
+  if response.status().is_success() {
+    response.json().await.map_err(Error::NetError)
+  } else {
+    Err(error_from_response(response).await)
+  }
+}
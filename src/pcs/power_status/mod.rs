@@ -1,2 +1,3 @@
+pub mod client;
 pub mod http_client;
 pub mod types;
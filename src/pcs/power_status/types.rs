@@ -1,13 +1,16 @@
+#[cfg(feature = "backend-connector")]
 use manta_backend_dispatcher::types::pcs::power_status::types::{
   ManagementState as FrontEndManagementState, PowerState as FrontEndPowerState,
   PowerStatus as FrontEndPowerStatus, PowerStatusAll as FrontEndPowerStatusAll,
 };
 
-use crate::pcs::transitions::types::Operation;
+use crate::pcs::transitions::types::PowerOperation;
+
+use std::fmt;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 pub enum PowerState {
   #[serde(rename = "on")]
   On,
@@ -16,6 +19,7 @@ pub enum PowerState {
   #[serde(rename = "undefined")]
   Undefined,
 }
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndPowerState> for PowerState {
   fn from(value: FrontEndPowerState) -> Self {
     match value {
@@ -25,6 +29,7 @@ impl From<FrontEndPowerState> for PowerState {
     }
   }
 }
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndPowerState> for PowerState {
   fn into(self) -> FrontEndPowerState {
     match self {
@@ -35,13 +40,14 @@ impl Into<FrontEndPowerState> for PowerState {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 pub enum ManagementState {
   #[serde(rename = "unavailable")]
   Unavailable,
   #[serde(rename = "available")]
   Available,
 }
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndManagementState> for ManagementState {
   fn from(value: FrontEndManagementState) -> Self {
     match value {
@@ -50,6 +56,7 @@ impl From<FrontEndManagementState> for ManagementState {
     }
   }
 }
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndManagementState> for ManagementState {
   fn into(self) -> FrontEndManagementState {
     match self {
@@ -59,7 +66,7 @@ impl Into<FrontEndManagementState> for ManagementState {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PowerStatus {
   pub xname: String,
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -68,15 +75,22 @@ pub struct PowerStatus {
   #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename = "managementState")]
   pub management_state: Option<ManagementState>,
-  #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename = "error")]
   error: Option<String>,
   #[serde(rename = "supportedPowerTransitions")]
-  pub supported_power_transitions: Vec<Operation>,
+  pub supported_power_transitions: Vec<PowerOperation>,
   #[serde(rename = "lastUpdated")]
   pub last_updated: String,
 }
 
+impl PowerStatus {
+  /// Error reported by PCS while getting the power status of the component
+  pub fn error(&self) -> Option<&str> {
+    self.error.as_deref()
+  }
+}
+
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndPowerStatus> for PowerStatus {
   fn from(value: FrontEndPowerStatus) -> Self {
     PowerStatus {
@@ -91,13 +105,14 @@ impl From<FrontEndPowerStatus> for PowerStatus {
       supported_power_transitions: value
         .supported_power_transitions
         .into_iter()
-        .map(|v| Operation::from(v))
+        .map(|v| PowerOperation::from(v))
         .collect(),
       last_updated: value.last_updated,
     }
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndPowerStatus> for PowerStatus {
   fn into(self) -> FrontEndPowerStatus {
     FrontEndPowerStatus {
@@ -117,11 +132,12 @@ impl Into<FrontEndPowerStatus> for PowerStatus {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PowerStatusAll {
   pub status: Vec<PowerStatus>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndPowerStatusAll> for PowerStatusAll {
   fn from(value: FrontEndPowerStatusAll) -> Self {
     PowerStatusAll {
@@ -130,6 +146,7 @@ impl From<FrontEndPowerStatusAll> for PowerStatusAll {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndPowerStatusAll> for PowerStatusAll {
   fn into(self) -> FrontEndPowerStatusAll {
     FrontEndPowerStatusAll {
@@ -137,3 +154,44 @@ impl Into<FrontEndPowerStatusAll> for PowerStatusAll {
     }
   }
 }
+
+impl fmt::Display for PowerState {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      PowerState::On => write!(f, "on"),
+      PowerState::Off => write!(f, "off"),
+      PowerState::Undefined => write!(f, "undefined"),
+    }
+  }
+}
+
+impl fmt::Display for ManagementState {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ManagementState::Unavailable => write!(f, "unavailable"),
+      ManagementState::Available => write!(f, "available"),
+    }
+  }
+}
+
+impl fmt::Display for PowerStatus {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{}: power {}, management {}",
+      self.xname,
+      self
+        .power_state
+        .map_or("unknown".to_string(), |state| state.to_string()),
+      self
+        .management_state
+        .map_or("unknown".to_string(), |state| state.to_string())
+    )?;
+
+    if let Some(error) = &self.error {
+      write!(f, " (error: {})", error)?;
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,212 @@
+use std::{sync::Mutex, time::Duration};
+
+use serde_json::json;
+use wiremock::{
+  matchers::{method, path},
+  Mock, ResponseTemplate,
+};
+
+use crate::{
+  fixtures,
+  pcs::{
+    types::EmergencyConfirmation,
+    utils::{
+      force_off_all, power_on_group, power_on_staged,
+      DEFAULT_TRANSITION_CHUNK_SIZE,
+    },
+  },
+  progress::ProgressEvent,
+  test_util::{MockOchami, MockService},
+};
+
+#[tokio::test]
+async fn test_force_off_all_posts_every_chunk() {
+  let mock = MockOchami::start().await;
+  let ctx = mock.context();
+  let transitions_path =
+    ctx.pcs_url("/transitions").unwrap().path().to_string();
+
+  // The first chunk fails, the second one is created
+  Mock::given(method("POST"))
+    .and(path(transitions_path.as_str()))
+    .respond_with(
+      ResponseTemplate::new(500).set_body_string("PCS is having a bad day"),
+    )
+    .up_to_n_times(1)
+    .mount(mock.server())
+    .await;
+  mock
+    .mock(
+      MockService::Pcs,
+      "POST",
+      "/transitions",
+      200,
+      Some(json!({
+        "transitionID": "8f2a1c47-3b1e-4c0a-9e2f-5d6b7a8c9d0e",
+        "operation": "force-off"
+      })),
+    )
+    .await;
+  mock
+    .mock_json(
+      MockService::Pcs,
+      "GET",
+      "/transitions/[^/]+",
+      fixtures::TRANSITION_JSON,
+    )
+    .await;
+
+  let outcome = force_off_all(
+    &ctx,
+    &["x[1000-1001]c0s[0-63]b0n[0-7]".to_string()],
+    EmergencyConfirmation::new(EmergencyConfirmation::PHRASE).unwrap(),
+  )
+  .await
+  .unwrap();
+
+  let post_count = mock
+    .server()
+    .received_requests()
+    .await
+    .unwrap()
+    .iter()
+    .filter(|request| request.method.as_str() == "POST")
+    .count();
+  assert_eq!(post_count, 2);
+
+  // Nodes of the failed chunk, plus the failed node of the transition fixture
+  assert_eq!(outcome.failed.len(), DEFAULT_TRANSITION_CHUNK_SIZE + 1);
+  assert_eq!(outcome.succeeded, vec!["x1000c0s0b0n0".to_string()]);
+}
+
+#[tokio::test]
+async fn test_power_on_group_keeps_outcome_of_every_chunk() {
+  let mock = MockOchami::start().await;
+  let ctx = mock.context();
+  let transitions_path =
+    ctx.pcs_url("/transitions").unwrap().path().to_string();
+
+  mock
+    .mock(
+      MockService::Hsm,
+      "GET",
+      "/groups/compute/members",
+      200,
+      Some(json!({
+        "ids": ["x1000c0s0b0n0", "x1000c0s0b0n1", "x1000c0s1b0n0", "x1000c0s1b0n1"]
+      })),
+    )
+    .await;
+
+  // The first chunk is created, the second one fails
+  Mock::given(method("POST"))
+    .and(path(transitions_path.as_str()))
+    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+      "transitionID": "8f2a1c47-3b1e-4c0a-9e2f-5d6b7a8c9d0e",
+      "operation": "on"
+    })))
+    .up_to_n_times(1)
+    .mount(mock.server())
+    .await;
+  Mock::given(method("POST"))
+    .and(path(transitions_path.as_str()))
+    .respond_with(
+      ResponseTemplate::new(500).set_body_string("PCS is having a bad day"),
+    )
+    .mount(mock.server())
+    .await;
+  mock
+    .mock_json(
+      MockService::Pcs,
+      "GET",
+      "/transitions/[^/]+",
+      fixtures::TRANSITION_JSON,
+    )
+    .await;
+
+  let outcome = power_on_group(&ctx, "compute", Some(2)).await.unwrap();
+
+  // Outcome of the first chunk is kept
+  assert_eq!(outcome.succeeded, vec!["x1000c0s0b0n0".to_string()]);
+
+  // Nodes of the failed chunk are reported with the reason
+  for xname in ["x1000c0s1b0n0", "x1000c0s1b0n1"] {
+    let (_, reason) = outcome
+      .failed
+      .iter()
+      .find(|(failed_xname, _)| failed_xname == xname)
+      .unwrap();
+    assert!(reason.contains("PCS is having a bad day"), "{}", reason);
+  }
+}
+
+#[tokio::test]
+async fn test_power_on_staged_reports_failed_wave() {
+  let mock = MockOchami::start().await;
+  let ctx = mock.context();
+  let transitions_path =
+    ctx.pcs_url("/transitions").unwrap().path().to_string();
+
+  // The first wave is created, the second one fails
+  Mock::given(method("POST"))
+    .and(path(transitions_path.as_str()))
+    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+      "transitionID": "8f2a1c47-3b1e-4c0a-9e2f-5d6b7a8c9d0e",
+      "operation": "on"
+    })))
+    .up_to_n_times(1)
+    .mount(mock.server())
+    .await;
+  Mock::given(method("POST"))
+    .and(path(transitions_path.as_str()))
+    .respond_with(
+      ResponseTemplate::new(500).set_body_string("PCS is having a bad day"),
+    )
+    .mount(mock.server())
+    .await;
+  mock
+    .mock_json(
+      MockService::Pcs,
+      "GET",
+      "/transitions/[^/]+",
+      fixtures::TRANSITION_JSON,
+    )
+    .await;
+
+  let failed_item_vec = Mutex::new(Vec::new());
+  let finished_opt = Mutex::new(None);
+  let progress = |event: ProgressEvent<'_>| match event {
+    ProgressEvent::ItemFailed { item, .. } => {
+      failed_item_vec.lock().unwrap().push(item.to_string())
+    }
+    ProgressEvent::Finished { completed, failed } => {
+      *finished_opt.lock().unwrap() = Some((completed, failed))
+    }
+    _ => {}
+  };
+
+  let xname_vec = vec![
+    "x1000c0s0b0n0".to_string(),
+    "x1000c0s0b0n1".to_string(),
+    "x1000c0s1b0n0".to_string(),
+    "x1000c0s1b0n1".to_string(),
+  ];
+
+  let outcome = power_on_staged(&ctx, &xname_vec, 2, Duration::ZERO, &progress)
+    .await
+    .unwrap();
+
+  assert_eq!(outcome.succeeded, vec!["x1000c0s0b0n0".to_string()]);
+
+  let failed_item_vec = failed_item_vec.into_inner().unwrap();
+  assert!(failed_item_vec.contains(&"x1000c0s1b0n0".to_string()));
+  assert!(failed_item_vec.contains(&"x1000c0s1b0n1".to_string()));
+
+  assert_eq!(
+    finished_opt.into_inner().unwrap(),
+    Some((
+      outcome.succeeded.len(),
+      outcome.failed.len() + outcome.unsupported.len() + outcome.pending.len()
+    ))
+  );
+}
@@ -0,0 +1,577 @@
+use std::{collections::HashSet, time};
+
+use serde_json::Value;
+
+use crate::{
+  error::Error,
+  http::{
+    is_transient_failure, new_idempotency_key, ApiContext, MutationRequest,
+    IDEMPOTENCY_KEY_HEADER,
+  },
+  pcs::{
+    transitions::types::{
+      Location, PollOptions, PowerOperation, TimedTransition, TimeoutPolicy,
+      TransitionEvent, TransitionFilter, TransitionHandle, TransitionOutcome,
+      TransitionResponse, TransitionResponseList, TransitionStartOutput,
+      TransitionStatus,
+    },
+    utils::error_from_response,
+  },
+};
+
+use super::types::Transition;
+
+impl TransitionHandle {
+  /// Get the current status of the transition
+  pub async fn status(&self) -> Result<TransitionResponse, Error> {
+    get(&self.ctx, &self.id).await
+  }
+
+  /// Wait until the transition completes or `timeout` expires, whichever happens first.
+  /// Returns an error if the transition did not complete in time
+  pub async fn wait(
+    &self,
+    timeout: time::Duration,
+  ) -> Result<TransitionResponse, Error> {
+    let start = time::Instant::now();
+
+    loop {
+      let transition = self.status().await?;
+
+      if transition.is_completed() {
+        return Ok(transition);
+      }
+
+      if start.elapsed() >= timeout {
+        return Err(Error::Message(format!(
+          "PCS transition '{}' did not complete after {} seconds. Status: {}",
+          self.id,
+          timeout.as_secs(),
+          transition.transition_status
+        )));
+      }
+
+      tokio::time::sleep(PollOptions::default().interval).await;
+    }
+  }
+
+  /// Abort the transition. Tasks already completed are not reverted
+  pub async fn abort(&self) -> Result<Value, Error> {
+    delete(&self.ctx, &self.id).await
+  }
+}
+
+/// Get all PCS transitions, both in-flight and historical (PCS keeps transitions until
+/// their `automaticExpirationTime`)
+pub async fn get_all(
+  ctx: &ApiContext,
+) -> Result<Vec<TransitionResponse>, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.pcs_url("/transitions")?;
+
+  log::debug!("PCS transition URL: {}", api_url);
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await
+    .map_err(Error::NetError)?;
+
+  if response.status().is_success() {
+    response
+      .json::<TransitionResponseList>()
+      .await
+      .map_err(Error::NetError)
+      .map(|transition_list| transition_list.transitions)
+  } else {
+    Err(error_from_response(response).await)
+  }
+}
+
+/// Get PCS transitions matching a filter, see `TransitionFilter`
+pub async fn get_filtered(
+  ctx: &ApiContext,
+  filter: &TransitionFilter,
+) -> Result<Vec<TransitionResponse>, Error> {
+  get_all(ctx)
+    .await
+    .map(|transition_vec| filter.apply(transition_vec))
+}
+
+/// Get a PCS transition, including the status of each task (one task per xname)
+pub async fn get(
+  ctx: &ApiContext,
+  transition_id: &str,
+) -> Result<TransitionResponse, Error> {
+  let client = ctx.client()?;
+  let api_url = ctx.pcs_url(&format!("/transitions/{}", transition_id))?;
+
+  let response = client
+    .get(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send()
+    .await
+    .map_err(Error::NetError)?;
+
+  if response.status().is_success() {
+    let payload = response.json().await.map_err(Error::NetError);
+    log::debug!("PCS transition details\n{:#?}", payload);
+    payload
+  } else {
+    Err(error_from_response(response).await)
+  }
+}
+
+#[deprecated(
+  since = "1.0.0-beta.16",
+  note = "Please use function `get` instead"
+)]
+pub async fn get_by_id(
+  ctx: &ApiContext,
+  id: &str,
+) -> Result<TransitionResponse, Error> {
+  get(ctx, id).await
+}
+
+/// Create a PCS transition and return immediately with a handle to track it
+pub async fn post(
+  ctx: &ApiContext,
+  operation: PowerOperation,
+  xname_vec: &Vec<String>,
+) -> Result<TransitionHandle, Error> {
+  post_with_task_deadline(ctx, operation, xname_vec, None).await
+}
+
+/// Same as `post` but sets the time (in minutes) PCS gives each task to complete before
+/// marking it as failed. If `None`, PCS uses its default deadline
+pub async fn post_with_task_deadline(
+  ctx: &ApiContext,
+  operation: PowerOperation,
+  xname_vec: &Vec<String>,
+  task_deadline_minutes: Option<usize>,
+) -> Result<TransitionHandle, Error> {
+  post_with_idempotency_key(
+    ctx,
+    operation,
+    xname_vec,
+    task_deadline_minutes,
+    &new_idempotency_key(),
+  )
+  .await
+}
+
+/// Same as `post_with_task_deadline`, attempts of the same logical operation share
+/// `idempotency_key`
+async fn post_with_idempotency_key(
+  ctx: &ApiContext,
+  operation: PowerOperation,
+  xname_vec: &Vec<String>,
+  task_deadline_minutes: Option<usize>,
+  idempotency_key: &str,
+) -> Result<TransitionHandle, Error> {
+  log::info!("Create PCS transition '{}' on {:?}", operation, xname_vec);
+
+  let location_vec: Vec<Location> = xname_vec
+    .iter()
+    .map(|xname| Location {
+      xname: xname.to_string(),
+      deputy_key: None,
+    })
+    .collect();
+
+  let request_payload = Transition {
+    operation,
+    task_deadline_minutes,
+    location: location_vec,
+  };
+
+  let client = ctx.client()?;
+  let api_url = ctx.pcs_url("/transitions")?;
+
+  let response = client
+    .post(api_url)
+    .header(IDEMPOTENCY_KEY_HEADER, idempotency_key)
+    .json(&request_payload)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if response.status().is_success() {
+    let transition_start = response
+      .json::<TransitionStartOutput>()
+      .await
+      .map_err(Error::NetError)?;
+
+    Ok(TransitionHandle {
+      id: transition_start.transition_id,
+      ctx: ctx.clone(),
+    })
+  } else {
+    Err(error_from_response(response).await)
+  }
+}
+
+/// Same as `post_with_task_deadline`, retrying up to `max_attempts` times if PCS could
+/// not be reached or did not answer. PCS does not support idempotency keys, so before
+/// each retry the transitions created since the first attempt are searched for one with
+/// the same operation and nodes, and returned instead of creating a duplicate
+pub async fn post_idempotent(
+  ctx: &ApiContext,
+  operation: PowerOperation,
+  xname_vec: &Vec<String>,
+  task_deadline_minutes: Option<usize>,
+  max_attempts: usize,
+) -> Result<TransitionHandle, Error> {
+  // Allow some clock skew between this host and PCS
+  let filter = TransitionFilter {
+    operation: Some(operation),
+    ..Default::default()
+  }
+  .newer_than(time::Duration::from_secs(60));
+
+  let idempotency_key = new_idempotency_key();
+  let mut attempt = 1;
+
+  loop {
+    if attempt > 1 {
+      if let Some(transition_id) =
+        find_transition(ctx, &filter, xname_vec).await?
+      {
+        log::info!(
+          "PCS transition '{}' was created by a previous attempt",
+          transition_id
+        );
+
+        return Ok(TransitionHandle {
+          id: transition_id,
+          ctx: ctx.clone(),
+        });
+      }
+    }
+
+    match post_with_idempotency_key(
+      ctx,
+      operation,
+      xname_vec,
+      task_deadline_minutes,
+      &idempotency_key,
+    )
+    .await
+    {
+      Err(e) if attempt < max_attempts && is_transient_failure(&e) => {
+        log::warn!(
+          "Attempt {}/{} to create PCS transition '{}' failed. Reason:\n{}",
+          attempt,
+          max_attempts,
+          operation,
+          e
+        );
+        attempt += 1;
+      }
+      result => return result,
+    }
+  }
+}
+
+/// Id of a transition matching `filter` with one task per node in `xname_vec`
+async fn find_transition(
+  ctx: &ApiContext,
+  filter: &TransitionFilter,
+  xname_vec: &[String],
+) -> Result<Option<String>, Error> {
+  let xname_set: HashSet<&String> = xname_vec.iter().collect();
+
+  let candidate_vec = get_filtered(ctx, filter).await?;
+
+  for candidate in candidate_vec {
+    // Transition list does not include the tasks
+    let transition = get(ctx, &candidate.transition_id).await?;
+
+    let task_xname_set: HashSet<&String> =
+      transition.tasks.iter().map(|task| &task.xname).collect();
+
+    if task_xname_set == xname_set {
+      return Ok(Some(transition.transition_id));
+    }
+  }
+
+  Ok(None)
+}
+
+/// Abort a PCS transition
+pub async fn delete(
+  ctx: &ApiContext,
+  transition_id: &str,
+) -> Result<Value, Error> {
+  log::info!("Abort PCS transition '{}'", transition_id);
+
+  let client = ctx.client()?;
+  let api_url = ctx.pcs_url(&format!("/transitions/{}", transition_id))?;
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(ctx.auth_token.as_str())
+    .send_mutation(ctx)
+    .await?
+    .into_sent()?;
+
+  if response.status().is_success() {
+    response.json().await.map_err(Error::NetError)
+  } else {
+    Err(error_from_response(response).await)
+  }
+}
+
+pub async fn post_block(
+  ctx: &ApiContext,
+  operation: PowerOperation,
+  xname_vec: &Vec<String>,
+) -> Result<TransitionResponse, Error> {
+  let node_reset = post(ctx, operation, xname_vec).await?;
+
+  log::info!("PCS transition ID: {}", node_reset.id);
+
+  wait_to_complete(ctx, &node_reset.id).await
+}
+
+/// Same as `post_block` but nodes whose task failed are sent again to PCS in a new
+/// transition, up to `max_retries` times. Returns the combined result, nodes are reported
+/// as failed only if their last attempt failed
+pub async fn post_block_with_retries(
+  ctx: &ApiContext,
+  operation: PowerOperation,
+  xname_vec: &Vec<String>,
+  max_retries: usize,
+) -> Result<TransitionOutcome, Error> {
+  let mut outcome = post_block(ctx, operation, xname_vec).await?.outcome();
+
+  for retry in 1..=max_retries {
+    if outcome.failed.is_empty() {
+      break;
+    }
+
+    let failed_xname_vec: Vec<String> =
+      outcome.failed.drain(..).map(|(xname, _)| xname).collect();
+
+    log::info!(
+      "Retry {}/{} PCS transition '{}' on {:?}",
+      retry,
+      max_retries,
+      operation,
+      failed_xname_vec
+    );
+
+    let retry_outcome = post_block(ctx, operation, &failed_xname_vec)
+      .await?
+      .outcome();
+
+    outcome.merge(retry_outcome);
+  }
+
+  Ok(outcome)
+}
+
+/// Same as `post_block` but with configurable polling and a callback called with the
+/// progress of the transition after each query to PCS
+pub async fn post_block_with_options(
+  ctx: &ApiContext,
+  operation: PowerOperation,
+  xname_vec: &Vec<String>,
+  poll_options: &PollOptions,
+  progress: impl FnMut(TransitionStatus),
+) -> Result<TransitionResponse, Error> {
+  let node_reset = post_with_task_deadline(
+    ctx,
+    operation,
+    xname_vec,
+    poll_options.task_deadline_minutes,
+  )
+  .await?;
+
+  log::info!("PCS transition ID: {}", node_reset.id);
+
+  wait_to_complete_with_options(ctx, &node_reset.id, poll_options, progress)
+    .await
+}
+
+pub async fn wait_to_complete(
+  ctx: &ApiContext,
+  transition_id: &str,
+) -> Result<TransitionResponse, Error> {
+  wait_to_complete_with_options(
+    ctx,
+    transition_id,
+    &PollOptions::default(),
+    |status| log::info!("{}", status),
+  )
+  .await
+}
+
+/// Poll PCS until the transition completes or `poll_options.max_wait` expires. `progress`
+/// is called after each query to PCS.
+/// Returns the last transition status fetched, callers should check
+/// `TransitionResponse::is_completed` to know if the transition timed out
+pub async fn wait_to_complete_with_options(
+  ctx: &ApiContext,
+  transition_id: &str,
+  poll_options: &PollOptions,
+  mut progress: impl FnMut(TransitionStatus),
+) -> Result<TransitionResponse, Error> {
+  let start = time::Instant::now();
+
+  loop {
+    let transition: TransitionResponse = get(ctx, transition_id).await?;
+
+    progress(TransitionStatus::new(&transition, start.elapsed()));
+
+    if transition.is_completed() || start.elapsed() >= poll_options.max_wait {
+      return Ok(transition);
+    }
+
+    tokio::time::sleep(poll_options.interval).await;
+  }
+}
+
+/// Same as `post_block` but waits according to `policy`: `events` is notified of the
+/// progress, of the nodes left pending at the soft deadline, and the transition is aborted
+/// at the hard deadline
+pub async fn post_block_with_policy(
+  ctx: &ApiContext,
+  operation: PowerOperation,
+  xname_vec: &Vec<String>,
+  policy: &TimeoutPolicy,
+  events: impl FnMut(TransitionEvent),
+) -> Result<TimedTransition, Error> {
+  let handle = post_with_task_deadline(
+    ctx,
+    operation,
+    xname_vec,
+    policy.task_deadline_minutes,
+  )
+  .await?;
+
+  log::info!("PCS transition ID: {}", handle.id);
+
+  wait_with_policy(ctx, &handle.id, policy, events).await
+}
+
+/// Poll PCS until the transition completes, applying the deadlines of `policy`.
+/// Queries failing with a transient error are retried until the hard deadline. If
+/// the status can not be fetched anymore the transition is aborted and the error
+/// returned
+pub async fn wait_with_policy(
+  ctx: &ApiContext,
+  transition_id: &str,
+  policy: &TimeoutPolicy,
+  mut events: impl FnMut(TransitionEvent),
+) -> Result<TimedTransition, Error> {
+  let start = time::Instant::now();
+  let mut soft_deadline_exceeded = false;
+  let mut stragglers = Vec::new();
+
+  loop {
+    let transition = match get(ctx, transition_id).await {
+      Ok(transition) => transition,
+      Err(e)
+        if is_transient_failure(&e)
+          && start.elapsed() < policy.hard_deadline =>
+      {
+        log::warn!(
+          "Could not get the status of PCS transition '{}', retrying in {:?}. Reason:\n{}",
+          transition_id,
+          policy.interval,
+          e
+        );
+
+        tokio::time::sleep(policy.interval).await;
+        continue;
+      }
+      Err(e) => {
+        log::warn!(
+          "Could not get the status of PCS transition '{}', aborting. Reason:\n{}",
+          transition_id,
+          e
+        );
+
+        if let Err(abort_error) = delete(ctx, transition_id).await {
+          log::warn!(
+            "Could not abort PCS transition '{}'. Reason:\n{}",
+            transition_id,
+            abort_error
+          );
+        }
+
+        return Err(e);
+      }
+    };
+
+    let elapsed = start.elapsed();
+    let status = TransitionStatus::new(&transition, elapsed);
+
+    events(TransitionEvent::Progress(status.clone()));
+
+    if transition.is_completed() {
+      return Ok(TimedTransition {
+        outcome: transition.outcome(),
+        transition,
+        soft_deadline_exceeded,
+        aborted: false,
+        stragglers,
+      });
+    }
+
+    if elapsed >= policy.hard_deadline {
+      stragglers = transition.outcome().pending;
+
+      log::warn!(
+        "PCS transition '{}' did not complete after {:?}, aborting. Pending nodes: {:?}",
+        transition_id,
+        policy.hard_deadline,
+        stragglers
+      );
+
+      events(TransitionEvent::HardDeadline {
+        status,
+        stragglers: stragglers.clone(),
+      });
+
+      delete(ctx, transition_id).await?;
+
+      // Status once aborted, tasks not finished are reported as pending
+      let transition = get(ctx, transition_id).await?;
+
+      return Ok(TimedTransition {
+        outcome: transition.outcome(),
+        transition,
+        soft_deadline_exceeded,
+        aborted: true,
+        stragglers,
+      });
+    }
+
+    if !soft_deadline_exceeded
+      && policy
+        .soft_deadline
+        .is_some_and(|soft_deadline| elapsed >= soft_deadline)
+    {
+      soft_deadline_exceeded = true;
+      stragglers = transition.outcome().pending;
+
+      log::warn!(
+        "PCS transition '{}' is taking longer than {:?}. Pending nodes: {:?}",
+        transition_id,
+        policy.soft_deadline.unwrap_or_default(),
+        stragglers
+      );
+
+      events(TransitionEvent::SoftDeadline {
+        status,
+        stragglers: stragglers.clone(),
+      });
+    }
+
+    tokio::time::sleep(policy.interval).await;
+  }
+}
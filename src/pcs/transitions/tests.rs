@@ -0,0 +1,213 @@
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use wiremock::{
+  matchers::{method, path},
+  Mock, ResponseTemplate,
+};
+
+use crate::{fixtures, http::ApiContext, test_util::MockOchami};
+
+use super::{
+  client::wait_with_policy,
+  types::{TimeoutPolicy, TransitionEvent},
+};
+
+const TRANSITION_ID: &str = "8f2a1c47-3b1e-4c0a-9e2f-5d6b7a8c9d0e";
+
+fn policy(hard_deadline: Duration) -> TimeoutPolicy {
+  TimeoutPolicy {
+    interval: Duration::from_millis(10),
+    soft_deadline: None,
+    hard_deadline,
+    ..Default::default()
+  }
+}
+
+fn in_progress_transition() -> Value {
+  let mut transition: Value =
+    serde_json::from_str(fixtures::TRANSITION_JSON).unwrap();
+  transition["transitionStatus"] = json!("in-progress");
+  transition["tasks"][1]["taskStatus"] = json!("in-progress");
+  transition
+}
+
+fn short_timeout_context(ctx: &ApiContext) -> ApiContext {
+  ctx.clone().with_http_client(
+    ctx
+      .http_client
+      .clone()
+      .with_timeout(Some(Duration::from_millis(100))),
+  )
+}
+
+async fn mock_abort(mock: &MockOchami, transition_path: &str) {
+  Mock::given(method("DELETE"))
+    .and(path(transition_path))
+    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+      "transitionID": TRANSITION_ID,
+      "abortStatus": "Accepted"
+    })))
+    .mount(mock.server())
+    .await;
+}
+
+async fn delete_request_count(mock: &MockOchami) -> usize {
+  mock
+    .server()
+    .received_requests()
+    .await
+    .unwrap()
+    .iter()
+    .filter(|request| request.method.as_str() == "DELETE")
+    .count()
+}
+
+#[tokio::test]
+async fn test_wait_with_policy_retries_transient_errors() {
+  let mock = MockOchami::start().await;
+  let ctx = mock.context();
+  let transition_path = ctx
+    .pcs_url(&format!("/transitions/{}", TRANSITION_ID))
+    .unwrap()
+    .path()
+    .to_string();
+
+  // First query times out
+  Mock::given(method("GET"))
+    .and(path(transition_path.as_str()))
+    .respond_with(
+      ResponseTemplate::new(200)
+        .set_body_raw(fixtures::TRANSITION_JSON, "application/json")
+        .set_delay(Duration::from_millis(500)),
+    )
+    .up_to_n_times(1)
+    .mount(mock.server())
+    .await;
+  Mock::given(method("GET"))
+    .and(path(transition_path.as_str()))
+    .respond_with(
+      ResponseTemplate::new(200)
+        .set_body_raw(fixtures::TRANSITION_JSON, "application/json"),
+    )
+    .mount(mock.server())
+    .await;
+
+  let timed_transition = wait_with_policy(
+    &short_timeout_context(&ctx),
+    TRANSITION_ID,
+    &policy(Duration::from_secs(5)),
+    |_| {},
+  )
+  .await
+  .unwrap();
+
+  assert!(!timed_transition.aborted);
+  assert_eq!(timed_transition.outcome.succeeded, vec!["x1000c0s0b0n0"]);
+  assert_eq!(delete_request_count(&mock).await, 0);
+}
+
+#[tokio::test]
+async fn test_wait_with_policy_aborts_when_status_fails() {
+  let mock = MockOchami::start().await;
+  let ctx = mock.context();
+  let transition_path = ctx
+    .pcs_url(&format!("/transitions/{}", TRANSITION_ID))
+    .unwrap()
+    .path()
+    .to_string();
+
+  Mock::given(method("GET"))
+    .and(path(transition_path.as_str()))
+    .respond_with(ResponseTemplate::new(500).set_body_json(json!({
+      "title": "Internal Server Error",
+      "detail": "PCS is having a bad day"
+    })))
+    .mount(mock.server())
+    .await;
+  mock_abort(&mock, &transition_path).await;
+
+  let result = wait_with_policy(
+    &ctx,
+    TRANSITION_ID,
+    &policy(Duration::from_secs(5)),
+    |_| {},
+  )
+  .await;
+
+  assert!(result.is_err());
+  assert_eq!(delete_request_count(&mock).await, 1);
+}
+
+#[tokio::test]
+async fn test_wait_with_policy_aborts_when_transient_errors_outlast_deadline() {
+  let mock = MockOchami::start().await;
+  let ctx = mock.context();
+  let transition_path = ctx
+    .pcs_url(&format!("/transitions/{}", TRANSITION_ID))
+    .unwrap()
+    .path()
+    .to_string();
+
+  Mock::given(method("GET"))
+    .and(path(transition_path.as_str()))
+    .respond_with(
+      ResponseTemplate::new(200)
+        .set_body_raw(fixtures::TRANSITION_JSON, "application/json")
+        .set_delay(Duration::from_millis(500)),
+    )
+    .mount(mock.server())
+    .await;
+  mock_abort(&mock, &transition_path).await;
+
+  let result = wait_with_policy(
+    &short_timeout_context(&ctx),
+    TRANSITION_ID,
+    &policy(Duration::from_millis(250)),
+    |_| {},
+  )
+  .await;
+
+  assert!(result.is_err());
+  assert_eq!(delete_request_count(&mock).await, 1);
+}
+
+#[tokio::test]
+async fn test_wait_with_policy_aborts_at_hard_deadline() {
+  let mock = MockOchami::start().await;
+  let ctx = mock.context();
+  let transition_path = ctx
+    .pcs_url(&format!("/transitions/{}", TRANSITION_ID))
+    .unwrap()
+    .path()
+    .to_string();
+
+  Mock::given(method("GET"))
+    .and(path(transition_path.as_str()))
+    .respond_with(
+      ResponseTemplate::new(200).set_body_json(in_progress_transition()),
+    )
+    .mount(mock.server())
+    .await;
+  mock_abort(&mock, &transition_path).await;
+
+  let mut straggler_vec = Vec::new();
+
+  let timed_transition = wait_with_policy(
+    &ctx,
+    TRANSITION_ID,
+    &policy(Duration::from_millis(50)),
+    |event| {
+      if let TransitionEvent::HardDeadline { stragglers, .. } = event {
+        straggler_vec = stragglers;
+      }
+    },
+  )
+  .await
+  .unwrap();
+
+  assert!(timed_transition.aborted);
+  assert_eq!(timed_transition.stragglers, vec!["x1000c0s0b0n1"]);
+  assert_eq!(straggler_vec, vec!["x1000c0s0b0n1"]);
+  assert_eq!(delete_request_count(&mock).await, 1);
+}
@@ -1,14 +1,20 @@
+#[cfg(feature = "backend-connector")]
 use manta_backend_dispatcher::types::pcs::transitions::types::{
   Location as FrontEndLocation, Operation as FrontEndOperation,
   Task as FrontEndTask, TaskCounts as FrontEndTaskCounts,
   Transition as FrontEndTransition,
   TransitionResponse as FrontEndTransitionResponse,
+  TransitionStartOutput as FrontEndTransitionStartOutput,
 };
 
+use std::{collections::HashMap, fmt, str::FromStr, time::Duration};
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use strum_macros::Display;
 
-use crate::error::Error;
+use crate::{
+  error::Error, hostlist, http::ApiContext, pcs::types::PcsErrorKind,
+};
 
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Location {
@@ -18,6 +24,7 @@ pub struct Location {
   pub deputy_key: Option<String>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndLocation> for Location {
   fn from(value: FrontEndLocation) -> Self {
     Location {
@@ -26,6 +33,7 @@ impl From<FrontEndLocation> for Location {
     }
   }
 }
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndLocation> for Location {
   fn into(self) -> FrontEndLocation {
     FrontEndLocation {
@@ -35,80 +43,116 @@ impl Into<FrontEndLocation> for Location {
   }
 }
 
-// this matches the query, not the response
-#[derive(Display, Debug, Serialize, Deserialize)]
-pub enum Operation {
-  #[serde(rename = "On")]
+/// Power operation supported by PCS transitions. Serialized in lower case as in the PCS
+/// responses, PCS accepts any case in requests
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PowerOperation {
+  #[serde(rename = "on", alias = "On")]
   On,
-  #[serde(rename = "Off")]
+  #[serde(rename = "off", alias = "Off")]
   Off,
-  #[serde(rename = "Soft-Off")]
+  #[serde(rename = "soft-off", alias = "Soft-Off")]
   SoftOff,
-  #[serde(rename = "Soft-Restart")]
+  #[serde(rename = "soft-restart", alias = "Soft-Restart")]
   SoftRestart,
-  #[serde(rename = "Hard-Restart")]
+  #[serde(rename = "hard-restart", alias = "Hard-Restart")]
   HardRestart,
-  #[serde(rename = "Init")]
+  #[serde(rename = "init", alias = "Init")]
   Init,
-  #[serde(rename = "Force-Off")]
+  #[serde(rename = "force-off", alias = "Force-Off")]
   ForceOff,
 }
 
-impl Operation {
-  pub fn from_str(operation: &str) -> Result<Operation, Error> {
-    match operation {
-      "on" => Ok(Operation::On),
-      "off" => Ok(Operation::Off),
-      "soft-off" => Ok(Operation::SoftOff),
-      "soft-restart" => Ok(Operation::SoftRestart),
-      "hard-restart" => Ok(Operation::HardRestart),
-      "init" => Ok(Operation::Init),
-      "force-off" => Ok(Operation::ForceOff),
-      _ => Err(Error::Message("Operation not valid".to_string())),
+#[deprecated(
+  since = "1.0.0-beta.16",
+  note = "Please use `PowerOperation` instead"
+)]
+pub type Operation = PowerOperation;
+
+impl PowerOperation {
+  /// Operation name as used by PCS and the CLI (eg: `soft-off`)
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      PowerOperation::On => "on",
+      PowerOperation::Off => "off",
+      PowerOperation::SoftOff => "soft-off",
+      PowerOperation::SoftRestart => "soft-restart",
+      PowerOperation::HardRestart => "hard-restart",
+      PowerOperation::Init => "init",
+      PowerOperation::ForceOff => "force-off",
+    }
+  }
+}
+
+impl fmt::Display for PowerOperation {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.as_str())
+  }
+}
+
+impl FromStr for PowerOperation {
+  type Err = Error;
+
+  fn from_str(operation: &str) -> Result<PowerOperation, Error> {
+    match operation.to_lowercase().as_str() {
+      "on" => Ok(PowerOperation::On),
+      "off" => Ok(PowerOperation::Off),
+      "soft-off" => Ok(PowerOperation::SoftOff),
+      "soft-restart" => Ok(PowerOperation::SoftRestart),
+      "hard-restart" => Ok(PowerOperation::HardRestart),
+      "init" => Ok(PowerOperation::Init),
+      "force-off" => Ok(PowerOperation::ForceOff),
+      _ => Err(Error::Message(format!(
+        "Operation '{}' not valid",
+        operation
+      ))),
     }
   }
 }
 
-impl From<FrontEndOperation> for Operation {
+#[cfg(feature = "backend-connector")]
+impl From<FrontEndOperation> for PowerOperation {
   fn from(value: FrontEndOperation) -> Self {
     match value {
-      FrontEndOperation::On => Operation::On,
-      FrontEndOperation::Off => Operation::Off,
-      FrontEndOperation::SoftOff => Operation::SoftOff,
-      FrontEndOperation::SoftRestart => Operation::SoftRestart,
-      FrontEndOperation::HardRestart => Operation::HardRestart,
-      FrontEndOperation::Init => Operation::Init,
-      FrontEndOperation::ForceOff => Operation::ForceOff,
+      FrontEndOperation::On => PowerOperation::On,
+      FrontEndOperation::Off => PowerOperation::Off,
+      FrontEndOperation::SoftOff => PowerOperation::SoftOff,
+      FrontEndOperation::SoftRestart => PowerOperation::SoftRestart,
+      FrontEndOperation::HardRestart => PowerOperation::HardRestart,
+      FrontEndOperation::Init => PowerOperation::Init,
+      FrontEndOperation::ForceOff => PowerOperation::ForceOff,
     }
   }
 }
-impl Into<FrontEndOperation> for Operation {
+#[cfg(feature = "backend-connector")]
+impl Into<FrontEndOperation> for PowerOperation {
   fn into(self) -> FrontEndOperation {
     match self {
-      Operation::On => FrontEndOperation::On,
-      Operation::Off => FrontEndOperation::Off,
-      Operation::SoftOff => FrontEndOperation::SoftOff,
-      Operation::SoftRestart => FrontEndOperation::SoftRestart,
-      Operation::HardRestart => FrontEndOperation::HardRestart,
-      Operation::Init => FrontEndOperation::Init,
-      Operation::ForceOff => FrontEndOperation::ForceOff,
+      PowerOperation::On => FrontEndOperation::On,
+      PowerOperation::Off => FrontEndOperation::Off,
+      PowerOperation::SoftOff => FrontEndOperation::SoftOff,
+      PowerOperation::SoftRestart => FrontEndOperation::SoftRestart,
+      PowerOperation::HardRestart => FrontEndOperation::HardRestart,
+      PowerOperation::Init => FrontEndOperation::Init,
+      PowerOperation::ForceOff => FrontEndOperation::ForceOff,
     }
   }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Transition {
-  pub operation: Operation,
+  pub operation: PowerOperation,
   #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename = "taskDeadlineMinutes")]
   pub task_deadline_minutes: Option<usize>,
   pub location: Vec<Location>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndTransition> for Transition {
   fn from(value: FrontEndTransition) -> Self {
     Transition {
-      operation: Operation::from(value.operation),
+      operation: PowerOperation::from(value.operation),
       task_deadline_minutes: value.task_deadline_minutes,
       location: value
         .location
@@ -119,6 +163,7 @@ impl From<FrontEndTransition> for Transition {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndTransition> for Transition {
   fn into(self) -> FrontEndTransition {
     FrontEndTransition {
@@ -129,16 +174,19 @@ impl Into<FrontEndTransition> for Transition {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct TaskCounts {
   pub total: usize,
   pub new: usize,
+  #[serde(rename = "in-progress", alias = "in_progress")]
   pub in_progress: usize,
   pub failed: usize,
   pub succeeded: usize,
+  #[serde(rename = "un-supported", alias = "un_supported")]
   pub un_supported: usize,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndTaskCounts> for TaskCounts {
   fn from(value: FrontEndTaskCounts) -> Self {
     TaskCounts {
@@ -152,6 +200,7 @@ impl From<FrontEndTaskCounts> for TaskCounts {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndTaskCounts> for TaskCounts {
   fn into(self) -> FrontEndTaskCounts {
     FrontEndTaskCounts {
@@ -170,11 +219,14 @@ pub struct Task {
   pub xname: String,
   #[serde(rename = "taskStatus")]
   pub task_status: String,
+  #[serde(default)]
   #[serde(rename = "taskStatusDescription")]
   pub task_status_description: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
   pub error: Option<String>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndTask> for Task {
   fn from(value: FrontEndTask) -> Self {
     Task {
@@ -186,6 +238,7 @@ impl From<FrontEndTask> for Task {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<manta_backend_dispatcher::types::pcs::transitions::types::Task>
   for Task
 {
@@ -207,16 +260,95 @@ pub struct TransitionResponse {
   pub transition_id: String,
   #[serde(rename = "createTime")]
   pub create_time: String,
+  #[serde(default)]
   #[serde(rename = "automaticExpirationTime")]
   pub automatic_expiration_time: String,
   #[serde(rename = "transitionStatus")]
   pub transition_status: String,
-  pub operation: Operation,
+  pub operation: PowerOperation,
+  #[serde(default)]
   #[serde(rename = "taskCounts")]
   pub task_counts: TaskCounts,
+  // NOTE: PCS does not return the list of tasks when listing transitions
+  #[serde(default)]
   pub tasks: Vec<Task>,
 }
 
+impl TransitionResponse {
+  /// Returns true if PCS won't process the transition any further, either because all its
+  /// tasks finished or because it was aborted
+  pub fn is_completed(&self) -> bool {
+    matches!(self.transition_status.as_str(), "completed" | "aborted")
+  }
+
+  /// Per node result of the transition, parsed from its list of tasks
+  pub fn outcome(&self) -> TransitionOutcome {
+    let mut outcome = TransitionOutcome::default();
+
+    for task in &self.tasks {
+      match task.task_status.as_str() {
+        "succeeded" => outcome.succeeded.push(task.xname.clone()),
+        "failed" => {
+          let reason = task
+            .error
+            .clone()
+            .filter(|error| !error.is_empty())
+            .unwrap_or_else(|| task.task_status_description.clone());
+          outcome.failed.push((task.xname.clone(), reason));
+        }
+        "unsupported" | "un-supported" => {
+          outcome.unsupported.push(task.xname.clone())
+        }
+        _ => outcome.pending.push(task.xname.clone()),
+      }
+    }
+
+    outcome
+  }
+}
+
+/// Per node result of one or more PCS transitions
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct TransitionOutcome {
+  pub succeeded: Vec<String>,
+  /// Nodes which failed with the reason reported by PCS
+  pub failed: Vec<(String, String)>,
+  /// Nodes which don't support the power operation requested
+  pub unsupported: Vec<String>,
+  /// Nodes whose task did not finish (eg: transition aborted or still running)
+  pub pending: Vec<String>,
+}
+
+impl TransitionOutcome {
+  /// Returns true if no node failed or was left pending
+  pub fn is_success(&self) -> bool {
+    self.failed.is_empty() && self.pending.is_empty()
+  }
+
+  /// Failed nodes grouped by the category of their error
+  pub fn failed_by_kind(&self) -> HashMap<PcsErrorKind, Vec<String>> {
+    let mut failed_by_kind: HashMap<PcsErrorKind, Vec<String>> = HashMap::new();
+
+    for (xname, reason) in &self.failed {
+      failed_by_kind
+        .entry(PcsErrorKind::classify(None, reason))
+        .or_default()
+        .push(xname.clone());
+    }
+
+    failed_by_kind
+  }
+
+  /// Append the results of another transition
+  pub fn merge(&mut self, other: TransitionOutcome) {
+    self.succeeded.extend(other.succeeded);
+    self.failed.extend(other.failed);
+    self.unsupported.extend(other.unsupported);
+    self.pending.extend(other.pending);
+  }
+}
+
+#[cfg(feature = "backend-connector")]
 impl From<FrontEndTransitionResponse> for TransitionResponse {
   fn from(value: FrontEndTransitionResponse) -> Self {
     TransitionResponse {
@@ -224,13 +356,14 @@ impl From<FrontEndTransitionResponse> for TransitionResponse {
       create_time: value.create_time,
       automatic_expiration_time: value.automatic_expiration_time,
       transition_status: value.transition_status,
-      operation: Operation::from(value.operation),
+      operation: PowerOperation::from(value.operation),
       task_counts: TaskCounts::from(value.task_counts),
       tasks: value.tasks.into_iter().map(|v| Task::from(v)).collect(),
     }
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<FrontEndTransitionResponse> for TransitionResponse {
   fn into(self) -> FrontEndTransitionResponse {
     FrontEndTransitionResponse {
@@ -245,11 +378,93 @@ impl Into<FrontEndTransitionResponse> for TransitionResponse {
   }
 }
 
+/// Response returned by PCS when a transition is created
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransitionStartOutput {
+  #[serde(rename = "transitionID")]
+  pub transition_id: String,
+  pub operation: PowerOperation,
+}
+
+#[cfg(feature = "backend-connector")]
+impl From<FrontEndTransitionStartOutput> for TransitionStartOutput {
+  fn from(value: FrontEndTransitionStartOutput) -> Self {
+    TransitionStartOutput {
+      transition_id: value.transition_id,
+      operation: PowerOperation::from(value.operation),
+    }
+  }
+}
+
+#[cfg(feature = "backend-connector")]
+impl From<TransitionStartOutput> for FrontEndTransitionStartOutput {
+  fn from(value: TransitionStartOutput) -> Self {
+    FrontEndTransitionStartOutput {
+      transition_id: value.transition_id,
+      operation: value.operation.into(),
+    }
+  }
+}
+
+/// Handle to a PCS transition created with `client::post`. It keeps the details needed
+/// to query or abort the transition so callers can drive their own progress reporting
+#[derive(Debug, Clone)]
+pub struct TransitionHandle {
+  pub id: String,
+  pub(crate) ctx: ApiContext,
+}
+
+/// Settings used to create a transition and poll PCS while waiting for it to complete
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct PollOptions {
+  /// Time between two consecutive queries to PCS
+  pub interval: Duration,
+  /// Max time to wait for the transition to complete
+  pub max_wait: Duration,
+  /// Time (in minutes) PCS gives each task to complete, sent when the transition is
+  /// created. Increase for sites with slow BMCs
+  pub task_deadline_minutes: Option<usize>,
+}
+
+impl Default for PollOptions {
+  fn default() -> Self {
+    PollOptions {
+      interval: Duration::from_secs(3),
+      max_wait: Duration::from_secs(900),
+      task_deadline_minutes: None,
+    }
+  }
+}
+
+/// Progress of a transition reported while waiting for it to complete
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TransitionStatus {
+  pub transition_id: String,
+  pub operation: PowerOperation,
+  pub transition_status: String,
+  pub task_counts: TaskCounts,
+  pub elapsed: Duration,
+}
+
+impl TransitionStatus {
+  pub fn new(transition: &TransitionResponse, elapsed: Duration) -> Self {
+    TransitionStatus {
+      transition_id: transition.transition_id.clone(),
+      operation: transition.operation,
+      transition_status: transition.transition_status.clone(),
+      task_counts: transition.task_counts.clone(),
+      elapsed,
+    }
+  }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TransitionResponseList {
   pub transitions: Vec<TransitionResponse>,
 }
 
+#[cfg(feature = "backend-connector")]
 impl From<Vec<FrontEndTransitionResponse>> for TransitionResponseList {
   fn from(value: Vec<FrontEndTransitionResponse>) -> Self {
     TransitionResponseList {
@@ -261,8 +476,256 @@ impl From<Vec<FrontEndTransitionResponse>> for TransitionResponseList {
   }
 }
 
+#[cfg(feature = "backend-connector")]
 impl Into<Vec<FrontEndTransitionResponse>> for TransitionResponseList {
   fn into(self) -> Vec<FrontEndTransitionResponse> {
     self.transitions.into_iter().map(|v| v.into()).collect()
   }
 }
+
+/// Result of validating a list of xnames against HSM before creating a transition
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct ValidationReport {
+  /// Nodes PCS can operate on
+  pub valid: Vec<String>,
+  /// Nodes missing in HSM
+  pub not_found: Vec<String>,
+  /// Nodes which type (as per HSM) is not power controllable, with their type
+  pub wrong_type: Vec<(String, String)>,
+  /// Nodes locked in HSM
+  pub locked: Vec<String>,
+}
+
+impl ValidationReport {
+  pub fn is_valid(&self) -> bool {
+    self.not_found.is_empty()
+      && self.wrong_type.is_empty()
+      && self.locked.is_empty()
+  }
+
+  /// Returns an error listing the invalid nodes, if any
+  pub fn ensure_valid(&self) -> Result<(), Error> {
+    if self.is_valid() {
+      return Ok(());
+    }
+
+    let mut error_vec = Vec::new();
+
+    if !self.not_found.is_empty() {
+      error_vec.push(format!(
+        "not found: {}",
+        hostlist::compress(&self.not_found)
+      ));
+    }
+    if !self.wrong_type.is_empty() {
+      error_vec.push(format!(
+        "not power controllable: {}",
+        self
+          .wrong_type
+          .iter()
+          .map(|(xname, r#type)| format!("{} ({})", xname, r#type))
+          .collect::<Vec<String>>()
+          .join(", ")
+      ));
+    }
+    if !self.locked.is_empty() {
+      error_vec.push(format!("locked: {}", hostlist::compress(&self.locked)));
+    }
+
+    Err(Error::Message(format!(
+      "Nodes not valid for power operation: {}",
+      error_vec.join("; ")
+    )))
+  }
+}
+
+/// Filter and paging applied to the list of transitions returned by PCS. PCS does not
+/// support filtering nor paging, so transitions are filtered once fetched
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct TransitionFilter {
+  /// Transition status (eg: `in-progress`, `completed`, `aborted`)
+  pub status: Option<String>,
+  pub operation: Option<PowerOperation>,
+  /// Keep transitions created at or after this time
+  pub created_after: Option<DateTime<Utc>>,
+  /// Keep transitions created before this time
+  pub created_before: Option<DateTime<Utc>>,
+  /// Number of transitions to skip, transitions are sorted newest first
+  pub offset: usize,
+  /// Max number of transitions to return
+  pub limit: Option<usize>,
+}
+
+impl TransitionFilter {
+  /// Keep transitions older than `age`
+  pub fn older_than(mut self, age: Duration) -> Self {
+    self.created_before = chrono::Duration::from_std(age)
+      .ok()
+      .and_then(|age| Utc::now().checked_sub_signed(age));
+    self
+  }
+
+  /// Keep transitions newer than `age`
+  pub fn newer_than(mut self, age: Duration) -> Self {
+    self.created_after = chrono::Duration::from_std(age)
+      .ok()
+      .and_then(|age| Utc::now().checked_sub_signed(age));
+    self
+  }
+
+  pub fn is_match(&self, transition: &TransitionResponse) -> bool {
+    if self.status.as_ref().is_some_and(|status| {
+      !status.eq_ignore_ascii_case(&transition.transition_status)
+    }) {
+      return false;
+    }
+
+    if self
+      .operation
+      .is_some_and(|operation| operation != transition.operation)
+    {
+      return false;
+    }
+
+    if self.created_after.is_none() && self.created_before.is_none() {
+      return true;
+    }
+
+    let Ok(create_time) = DateTime::parse_from_rfc3339(&transition.create_time)
+    else {
+      return false;
+    };
+
+    self
+      .created_after
+      .is_none_or(|created_after| create_time >= created_after)
+      && self
+        .created_before
+        .is_none_or(|created_before| create_time < created_before)
+  }
+
+  /// Filter, sort (newest first) and page a list of transitions
+  pub fn apply(
+    &self,
+    transition_vec: Vec<TransitionResponse>,
+  ) -> Vec<TransitionResponse> {
+    let mut transition_vec: Vec<TransitionResponse> = transition_vec
+      .into_iter()
+      .filter(|transition| self.is_match(transition))
+      .collect();
+
+    transition_vec.sort_by(|a, b| b.create_time.cmp(&a.create_time));
+
+    transition_vec
+      .into_iter()
+      .skip(self.offset)
+      .take(self.limit.unwrap_or(usize::MAX))
+      .collect()
+  }
+}
+
+impl fmt::Display for TransitionResponse {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{} '{}' {}: {} of {} tasks succeeded, {} failed",
+      self.transition_id,
+      self.operation,
+      self.transition_status,
+      self.task_counts.succeeded,
+      self.task_counts.total,
+      self.task_counts.failed
+    )
+  }
+}
+
+impl fmt::Display for TransitionStatus {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "Power '{}' summary - status: {}, failed: {}, in-progress: {}, succeeded: {}, total: {}. Elapsed {} seconds",
+      self.operation,
+      self.transition_status,
+      self.task_counts.failed,
+      self.task_counts.in_progress,
+      self.task_counts.succeeded,
+      self.task_counts.total,
+      self.elapsed.as_secs()
+    )
+  }
+}
+
+/// Escalating deadlines applied while waiting for a transition. Past `soft_deadline` a
+/// warning is reported once, past `hard_deadline` the transition is aborted. Nodes whose
+/// task is still running at a deadline are reported as stragglers
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct TimeoutPolicy {
+  /// Time between two consecutive queries to PCS
+  pub interval: Duration,
+  /// Time after which the transition is reported as slow, `None` to disable
+  pub soft_deadline: Option<Duration>,
+  /// Time after which the transition is aborted
+  pub hard_deadline: Duration,
+  /// Time (in minutes) PCS gives each task to complete, sent when the transition is
+  /// created
+  pub task_deadline_minutes: Option<usize>,
+}
+
+impl Default for TimeoutPolicy {
+  fn default() -> Self {
+    let poll_options = PollOptions::default();
+
+    TimeoutPolicy {
+      interval: poll_options.interval,
+      soft_deadline: Some(poll_options.max_wait / 3),
+      hard_deadline: poll_options.max_wait,
+      task_deadline_minutes: poll_options.task_deadline_minutes,
+    }
+  }
+}
+
+/// Event reported while waiting for a transition with a `TimeoutPolicy`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum TransitionEvent {
+  /// Status after each query to PCS
+  Progress(TransitionStatus),
+  /// The soft deadline passed, with the nodes still pending
+  SoftDeadline {
+    status: TransitionStatus,
+    stragglers: Vec<String>,
+  },
+  /// The hard deadline passed and the transition is being aborted, with the nodes still
+  /// pending
+  HardDeadline {
+    status: TransitionStatus,
+    stragglers: Vec<String>,
+  },
+}
+
+/// Result of waiting for a transition with a `TimeoutPolicy`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TimedTransition {
+  /// Last transition status fetched from PCS
+  pub transition: TransitionResponse,
+  pub outcome: TransitionOutcome,
+  pub soft_deadline_exceeded: bool,
+  /// True if the transition was aborted because the hard deadline passed
+  pub aborted: bool,
+  /// Nodes still pending at the last deadline passed
+  pub stragglers: Vec<String>,
+}
+
+impl fmt::Display for TransitionOutcome {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{} succeeded, {} failed, {} unsupported, {} pending",
+      self.succeeded.len(),
+      self.failed.len(),
+      self.unsupported.len(),
+      self.pending.len()
+    )
+  }
+}
@@ -0,0 +1,146 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+/// Category of a PCS error, either returned by the API or reported by a task. Lets
+/// callers tell apart errors worth retrying from the ones needing manual intervention
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PcsErrorKind {
+  /// BMC or node could not be reached (network, timeout, BMC down)
+  NodeUnreachable,
+  /// BMC rejected the credentials PCS used
+  BmcCredentials,
+  /// Power operation not supported by the component
+  UnsupportedOperation,
+  /// Transition, task or component not found
+  NotFound,
+  /// Request not valid
+  BadRequest,
+  /// Request conflicts with the current state, e.g. a transition already running
+  Conflict,
+  /// PCS or one of its dependencies is not available
+  ServiceUnavailable,
+  Other,
+}
+
+impl PcsErrorKind {
+  /// Classify an error using the HTTP status (if any) and the message reported by PCS.
+  /// Task errors have no HTTP status and are classified by their message only
+  pub fn classify(status_opt: Option<u16>, message: &str) -> Self {
+    let message = message.to_lowercase();
+    let contains_any =
+      |pattern_vec: &[&str]| pattern_vec.iter().any(|p| message.contains(p));
+
+    match status_opt {
+      Some(404) => PcsErrorKind::NotFound,
+      Some(400) => PcsErrorKind::BadRequest,
+      Some(409) => PcsErrorKind::Conflict,
+      Some(503) => PcsErrorKind::ServiceUnavailable,
+      _ if contains_any(&[
+        "unreachable",
+        "unable to connect",
+        "connection refused",
+        "connection reset",
+        "no route to host",
+        "timed out",
+        "timeout",
+      ]) =>
+      {
+        PcsErrorKind::NodeUnreachable
+      }
+      _ if contains_any(&[
+        "credential",
+        "authentication",
+        "password",
+        "unauthorized",
+      ]) =>
+      {
+        PcsErrorKind::BmcCredentials
+      }
+      _ if contains_any(&["not supported", "unsupported"]) => {
+        PcsErrorKind::UnsupportedOperation
+      }
+      _ if contains_any(&["not found"]) => PcsErrorKind::NotFound,
+      _ => PcsErrorKind::Other,
+    }
+  }
+
+  /// Returns true if the same request may succeed if retried later
+  pub fn is_retryable(&self) -> bool {
+    matches!(
+      self,
+      PcsErrorKind::NodeUnreachable | PcsErrorKind::ServiceUnavailable
+    )
+  }
+
+  /// HTTP status that best describes this kind of error
+  pub fn status(&self) -> u16 {
+    match self {
+      PcsErrorKind::BadRequest => 400,
+      PcsErrorKind::NotFound => 404,
+      PcsErrorKind::Conflict => 409,
+      PcsErrorKind::UnsupportedOperation => 422,
+      PcsErrorKind::BmcCredentials => 502,
+      PcsErrorKind::ServiceUnavailable => 503,
+      PcsErrorKind::NodeUnreachable => 504,
+      PcsErrorKind::Other => 500,
+    }
+  }
+}
+
+impl fmt::Display for PcsErrorKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let kind = match self {
+      PcsErrorKind::NodeUnreachable => "node unreachable",
+      PcsErrorKind::BmcCredentials => "BMC credentials",
+      PcsErrorKind::UnsupportedOperation => "unsupported operation",
+      PcsErrorKind::NotFound => "not found",
+      PcsErrorKind::BadRequest => "bad request",
+      PcsErrorKind::Conflict => "conflict",
+      PcsErrorKind::ServiceUnavailable => "service unavailable",
+      PcsErrorKind::Other => "other",
+    };
+
+    write!(f, "{}", kind)
+  }
+}
+
+/// Error payload returned by PCS (RFC 7807 problem details)
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct Problem {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub r#type: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub title: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub detail: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub status: Option<u16>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub instance: Option<String>,
+}
+
+/// Explicit confirmation required to run emergency power operations. Can only be built
+/// from the exact phrase `EmergencyConfirmation::PHRASE`, so these operations can't be
+/// triggered by accident
+#[derive(Debug)]
+pub struct EmergencyConfirmation {
+  _private: (),
+}
+
+impl EmergencyConfirmation {
+  pub const PHRASE: &'static str = "I understand this will force off all nodes";
+
+  pub fn new(phrase: &str) -> Result<Self, Error> {
+    if phrase == Self::PHRASE {
+      Ok(EmergencyConfirmation { _private: () })
+    } else {
+      Err(Error::Message(format!(
+        "Emergency power operation not confirmed. Type '{}' to confirm",
+        Self::PHRASE
+      )))
+    }
+  }
+}
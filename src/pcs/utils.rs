@@ -0,0 +1,477 @@
+use std::{collections::HashSet, time::Duration};
+
+use crate::{
+  error::Error,
+  hsm::{self, component::types::ComponentArrayPostQuery},
+  http::ApiContext,
+  node::types::Xname,
+  progress::{ProgressEvent, ProgressSink},
+};
+
+use super::{
+  transitions::{
+    self,
+    types::{PollOptions, PowerOperation, TransitionOutcome, ValidationReport},
+  },
+  types::{EmergencyConfirmation, PcsErrorKind, Problem},
+};
+
+/// Max number of nodes sent to PCS in a single transition when operating on HSM groups
+pub const DEFAULT_TRANSITION_CHUNK_SIZE: usize = 1000;
+
+/// Power on all members of a HSM group.
+/// Nodes are sent to PCS in chunks of `chunk_size_opt` nodes (defaults to
+/// `DEFAULT_TRANSITION_CHUNK_SIZE`), one transition per chunk
+pub async fn power_on_group(
+  ctx: &ApiContext,
+  group_label: &str,
+  chunk_size_opt: Option<usize>,
+) -> Result<TransitionOutcome, Error> {
+  power_group(ctx, group_label, PowerOperation::On, chunk_size_opt).await
+}
+
+/// Power off all members of a HSM group. If `force` is true nodes are powered off
+/// ungracefully ('force-off'), otherwise a graceful shutdown is requested ('soft-off')
+pub async fn power_off_group(
+  ctx: &ApiContext,
+  group_label: &str,
+  force: bool,
+  chunk_size_opt: Option<usize>,
+) -> Result<TransitionOutcome, Error> {
+  let operation = if force {
+    PowerOperation::ForceOff
+  } else {
+    PowerOperation::SoftOff
+  };
+
+  power_group(ctx, group_label, operation, chunk_size_opt).await
+}
+
+/// Restart all members of a HSM group. If `force` is true nodes are restarted
+/// ungracefully ('hard-restart'), otherwise a graceful restart is requested ('soft-restart')
+pub async fn reset_group(
+  ctx: &ApiContext,
+  group_label: &str,
+  force: bool,
+  chunk_size_opt: Option<usize>,
+) -> Result<TransitionOutcome, Error> {
+  let operation = if force {
+    PowerOperation::HardRestart
+  } else {
+    PowerOperation::SoftRestart
+  };
+
+  power_group(ctx, group_label, operation, chunk_size_opt).await
+}
+
+/// Resolve the members of a HSM group and run a blocking PCS transition on them, one
+/// transition per chunk of nodes. Returns the per node results of all transitions, nodes
+/// of a chunk whose transition failed are reported in `TransitionOutcome::failed`
+async fn power_group(
+  ctx: &ApiContext,
+  group_label: &str,
+  operation: PowerOperation,
+  chunk_size_opt: Option<usize>,
+) -> Result<TransitionOutcome, Error> {
+  let xname_vec = hsm::cache::get_members(ctx, group_label)
+    .await?
+    .ids
+    .unwrap_or_default();
+
+  if xname_vec.is_empty() {
+    return Err(Error::Message(format!(
+      "HSM group '{}' has no members",
+      group_label
+    )));
+  }
+
+  let chunk_size = chunk_size_opt
+    .unwrap_or(DEFAULT_TRANSITION_CHUNK_SIZE)
+    .max(1);
+
+  log::info!(
+    "Power '{}' {} nodes in HSM group '{}' in chunks of {} nodes",
+    operation,
+    xname_vec.len(),
+    group_label,
+    chunk_size
+  );
+
+  let mut outcome = TransitionOutcome::default();
+
+  for xname_chunk in xname_vec.chunks(chunk_size) {
+    match transitions::client::post_block(ctx, operation, &xname_chunk.to_vec())
+      .await
+    {
+      Ok(transition) => outcome.merge(transition.outcome()),
+      Err(e) => {
+        log::error!("Could not power '{}' chunk of nodes: {}", operation, e);
+        outcome.failed.extend(
+          xname_chunk
+            .iter()
+            .map(|xname| (xname.clone(), e.to_string())),
+        );
+      }
+    }
+  }
+
+  Ok(outcome)
+}
+
+/// Power on nodes in waves of `batch_size` nodes, waiting `delay` between waves to avoid
+/// power surges and BMC storms. Each wave is a blocking PCS transition, `progress`
+/// receives the result of each node once its wave is done. Nodes of a wave whose
+/// transition failed are reported as failed and the next waves still run
+pub async fn power_on_staged(
+  ctx: &ApiContext,
+  xname_vec: &[String],
+  batch_size: usize,
+  delay: Duration,
+  progress: &dyn ProgressSink,
+) -> Result<TransitionOutcome, Error> {
+  let batch_size = batch_size.max(1);
+  let num_batches = xname_vec.len().div_ceil(batch_size);
+
+  log::info!(
+    "Power on {} nodes in {} batches of {} nodes every {:?}",
+    xname_vec.len(),
+    num_batches,
+    batch_size,
+    delay
+  );
+
+  progress.report(ProgressEvent::Started {
+    operation: "power on",
+    total: xname_vec.len(),
+  });
+
+  let mut outcome = TransitionOutcome::default();
+
+  for (i, xname_batch) in xname_vec.chunks(batch_size).enumerate() {
+    if i > 0 {
+      tokio::time::sleep(delay).await;
+    }
+
+    log::debug!("Power on batch {} of {}", i + 1, num_batches);
+
+    let batch_outcome = match transitions::client::post_block(
+      ctx,
+      PowerOperation::On,
+      &xname_batch.to_vec(),
+    )
+    .await
+    {
+      Ok(transition) => transition.outcome(),
+      Err(e) => {
+        log::error!("Could not power on batch {}: {}", i + 1, e);
+        TransitionOutcome {
+          failed: xname_batch
+            .iter()
+            .map(|xname| (xname.clone(), e.to_string()))
+            .collect(),
+          ..Default::default()
+        }
+      }
+    };
+
+    report_outcome(progress, &batch_outcome);
+
+    outcome.merge(batch_outcome);
+  }
+
+  progress.report(ProgressEvent::Finished {
+    completed: outcome.succeeded.len(),
+    failed: outcome.failed.len()
+      + outcome.unsupported.len()
+      + outcome.pending.len(),
+  });
+
+  Ok(outcome)
+}
+
+fn report_outcome(progress: &dyn ProgressSink, outcome: &TransitionOutcome) {
+  for xname in &outcome.succeeded {
+    progress.report(ProgressEvent::ItemCompleted { item: xname });
+  }
+
+  for (xname, reason) in &outcome.failed {
+    progress.report(ProgressEvent::ItemFailed {
+      item: xname,
+      reason,
+    });
+  }
+
+  for xname in &outcome.unsupported {
+    progress.report(ProgressEvent::ItemFailed {
+      item: xname,
+      reason: "power operation not supported",
+    });
+  }
+
+  for xname in &outcome.pending {
+    progress.report(ProgressEvent::ItemFailed {
+      item: xname,
+      reason: "transition did not finish",
+    });
+  }
+}
+
+/// HSM component types PCS can operate on
+pub const POWER_CONTROLLABLE_TYPES: [&str; 7] = [
+  "Node",
+  "NodeBMC",
+  "RouterBMC",
+  "ChassisBMC",
+  "RouterModule",
+  "HSNBoard",
+  "CabinetPDUPowerConnector",
+];
+
+/// Check the xnames exist in HSM, are power controllable and are not locked. Use before
+/// creating a transition to avoid tasks failing straight away, eg:
+/// `validate_xnames(..).await?.ensure_valid()?`
+pub async fn validate_xnames(
+  ctx: &ApiContext,
+  xname_vec: &[String],
+) -> Result<ValidationReport, Error> {
+  let component_vec = hsm::component::client::post_query(
+    ctx,
+    ComponentArrayPostQuery {
+      component_ids: Some(xname_vec.to_vec()),
+      ..Default::default()
+    },
+  )
+  .await?
+  .components
+  .unwrap_or_default();
+
+  let mut report = ValidationReport::default();
+
+  for xname in xname_vec {
+    let Some(component) = component_vec
+      .iter()
+      .find(|component| component.id.as_deref() == Some(xname.as_str()))
+    else {
+      report.not_found.push(xname.clone());
+      continue;
+    };
+
+    let r#type = component.r#type.clone().unwrap_or_default();
+
+    if !POWER_CONTROLLABLE_TYPES.contains(&r#type.as_str()) {
+      report.wrong_type.push((xname.clone(), r#type));
+    } else if component.locked.unwrap_or(false) {
+      report.locked.push(xname.clone());
+    } else {
+      report.valid.push(xname.clone());
+    }
+  }
+
+  Ok(report)
+}
+
+/// Power on nodes ('on')
+pub async fn power_on(
+  ctx: &ApiContext,
+  target_vec: &[String],
+) -> Result<TransitionOutcome, Error> {
+  power_nodes(ctx, PowerOperation::On, target_vec).await
+}
+
+/// Power off nodes ('off'). PCS decides whether the shutdown is graceful or not
+pub async fn power_off(
+  ctx: &ApiContext,
+  target_vec: &[String],
+) -> Result<TransitionOutcome, Error> {
+  power_nodes(ctx, PowerOperation::Off, target_vec).await
+}
+
+/// Gracefully shut down nodes ('soft-off')
+pub async fn power_soft_off(
+  ctx: &ApiContext,
+  target_vec: &[String],
+) -> Result<TransitionOutcome, Error> {
+  power_nodes(ctx, PowerOperation::SoftOff, target_vec).await
+}
+
+/// Ungracefully power off nodes ('force-off')
+pub async fn power_force_off(
+  ctx: &ApiContext,
+  target_vec: &[String],
+) -> Result<TransitionOutcome, Error> {
+  power_nodes(ctx, PowerOperation::ForceOff, target_vec).await
+}
+
+/// Gracefully restart nodes ('soft-restart')
+pub async fn power_soft_restart(
+  ctx: &ApiContext,
+  target_vec: &[String],
+) -> Result<TransitionOutcome, Error> {
+  power_nodes(ctx, PowerOperation::SoftRestart, target_vec).await
+}
+
+/// Ungracefully restart nodes ('hard-restart')
+pub async fn power_hard_restart(
+  ctx: &ApiContext,
+  target_vec: &[String],
+) -> Result<TransitionOutcome, Error> {
+  power_nodes(ctx, PowerOperation::HardRestart, target_vec).await
+}
+
+/// Power off nodes (if on) and power them on again ('init'). Useful to recover nodes
+/// stuck during firmware updates or boot
+pub async fn power_init(
+  ctx: &ApiContext,
+  target_vec: &[String],
+) -> Result<TransitionOutcome, Error> {
+  power_nodes(ctx, PowerOperation::Init, target_vec).await
+}
+
+/// Run a blocking PCS transition on a list of nodes and return the per node results.
+/// Each target can be an xname, a hostlist expression (eg: `x1000c[0-3]s0b0n[0-1]`) or a
+/// HSM group label, see `resolve_xnames`
+pub async fn power_nodes(
+  ctx: &ApiContext,
+  operation: PowerOperation,
+  target_vec: &[String],
+) -> Result<TransitionOutcome, Error> {
+  let xname_vec = resolve_xnames(ctx, target_vec).await?;
+
+  transitions::client::post_block(ctx, operation, &xname_vec)
+    .await
+    .map(|transition| transition.outcome())
+}
+
+/// Expand a list of xnames, hostlist expressions and HSM group labels into a list of
+/// xnames. Hostlists are expanded with the hostlist parser, anything which does not
+/// expand to xnames is considered a HSM group label and replaced by its members.
+/// Duplicates are removed keeping the order of the input
+pub async fn resolve_xnames(
+  ctx: &ApiContext,
+  target_vec: &[String],
+) -> Result<Vec<String>, Error> {
+  let mut xname_vec: Vec<String> = Vec::new();
+  let mut seen_set: HashSet<String> = HashSet::new();
+
+  for target in target_vec {
+    let expanded_vec = hostlist_parser::parse(target).unwrap_or_default();
+
+    let target_xname_vec = if !expanded_vec.is_empty()
+      && expanded_vec.iter().all(|xname| Xname::is_valid(xname))
+    {
+      expanded_vec
+    } else {
+      hsm::cache::get_members(ctx, target)
+        .await
+        .map_err(|e| {
+          Error::Message(format!(
+            "'{}' is neither a list of xnames nor a HSM group. Reason:\n{}",
+            target, e
+          ))
+        })?
+        .ids
+        .unwrap_or_default()
+    };
+
+    for xname in target_xname_vec {
+      if seen_set.insert(xname.clone()) {
+        xname_vec.push(xname);
+      }
+    }
+  }
+
+  Ok(xname_vec)
+}
+
+/// Convert a failed PCS response into an `Error::PcsError`, using the problem details
+/// returned by PCS if any. Unauthorized requests are returned as `Error::RequestError`
+pub(crate) async fn error_from_response(response: reqwest::Response) -> Error {
+  let status = response.status();
+  let request_error = response.error_for_status_ref().err();
+
+  let payload = match response.text().await {
+    Ok(payload) => payload,
+    Err(e) => return Error::NetError(e),
+  };
+
+  if status == reqwest::StatusCode::UNAUTHORIZED {
+    if let Some(request_error) = request_error {
+      return Error::RequestError {
+        response: request_error,
+        payload,
+      };
+    }
+  }
+
+  let detail = serde_json::from_str::<Problem>(&payload)
+    .ok()
+    .and_then(|problem| problem.detail.or(problem.title))
+    .unwrap_or(payload);
+
+  Error::PcsError {
+    kind: PcsErrorKind::classify(Some(status.as_u16()), &detail),
+    detail,
+  }
+}
+
+/// Emergency power off. Nodes are forced off ('force-off') without waiting for the OS to
+/// shut down. All transitions are created straight away (one per chunk of
+/// `DEFAULT_TRANSITION_CHUNK_SIZE` nodes) so PCS runs them in parallel, then waited for.
+/// Chunks whose transition could not be created or followed are reported in
+/// `TransitionOutcome::failed`, the other chunks are still forced off
+/// Targets can be xnames, hostlist expressions or HSM group labels
+pub async fn force_off_all(
+  ctx: &ApiContext,
+  target_vec: &[String],
+  _confirmation: EmergencyConfirmation,
+) -> Result<TransitionOutcome, Error> {
+  let xname_vec = resolve_xnames(ctx, target_vec).await?;
+
+  log::warn!("Emergency force off of {} nodes", xname_vec.len());
+
+  let mut outcome = TransitionOutcome::default();
+  let mut transition_handle_vec = Vec::new();
+
+  // A chunk failing must not stop the others from being forced off, its nodes are
+  // reported as failed with the reason
+  for xname_chunk in xname_vec.chunks(DEFAULT_TRANSITION_CHUNK_SIZE) {
+    match transitions::client::post(
+      ctx,
+      PowerOperation::ForceOff,
+      &xname_chunk.to_vec(),
+    )
+    .await
+    {
+      Ok(transition_handle) => {
+        transition_handle_vec.push((xname_chunk, transition_handle))
+      }
+      Err(e) => {
+        log::error!("Could not create force off transition: {}", e);
+        outcome.failed.extend(
+          xname_chunk
+            .iter()
+            .map(|xname| (xname.clone(), e.to_string())),
+        );
+      }
+    }
+  }
+
+  for (xname_chunk, transition_handle) in transition_handle_vec {
+    match transition_handle
+      .wait(PollOptions::default().max_wait)
+      .await
+    {
+      Ok(transition) => outcome.merge(transition.outcome()),
+      Err(e) => {
+        log::error!("Could not get force off transition status: {}", e);
+        outcome.failed.extend(
+          xname_chunk
+            .iter()
+            .map(|xname| (xname.clone(), e.to_string())),
+        );
+      }
+    }
+  }
+
+  Ok(outcome)
+}
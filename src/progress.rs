@@ -0,0 +1,74 @@
+/// Progress of a bulk operation over a list of items (eg: xnames)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProgressEvent<'a> {
+  /// `total` items are about to be processed
+  Started {
+    operation: &'a str,
+    total: usize,
+  },
+  ItemCompleted {
+    item: &'a str,
+  },
+  ItemFailed {
+    item: &'a str,
+    reason: &'a str,
+  },
+  Finished {
+    completed: usize,
+    failed: usize,
+  },
+  /// `bytes` of a download written so far, out of `total` if the server sent its length
+  Transferred {
+    bytes: u64,
+    total: Option<u64>,
+  },
+}
+
+/// Receives the progress of bulk operations, eg: to render a progress bar or log
+/// structured progress. Implemented by closures taking a `ProgressEvent`
+pub trait ProgressSink: Send + Sync {
+  fn report(&self, event: ProgressEvent<'_>);
+}
+
+impl<F> ProgressSink for F
+where
+  F: Fn(ProgressEvent<'_>) + Send + Sync,
+{
+  fn report(&self, event: ProgressEvent<'_>) {
+    self(event)
+  }
+}
+
+/// Ignores progress
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl ProgressSink for NoProgress {
+  fn report(&self, _event: ProgressEvent<'_>) {}
+}
+
+/// Logs progress with the `log` crate, failures as warnings
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogProgress;
+
+impl ProgressSink for LogProgress {
+  fn report(&self, event: ProgressEvent<'_>) {
+    match event {
+      ProgressEvent::Started { operation, total } => {
+        log::info!("{}: {} items", operation, total)
+      }
+      ProgressEvent::ItemCompleted { item } => log::info!("{}: done", item),
+      ProgressEvent::ItemFailed { item, reason } => {
+        log::warn!("{}: failed. Reason:\n{}", item, reason)
+      }
+      ProgressEvent::Finished { completed, failed } => {
+        log::info!("finished: {} completed, {} failed", completed, failed)
+      }
+      ProgressEvent::Transferred { bytes, total } => match total {
+        Some(total) => log::debug!("transferred {} of {} bytes", bytes, total),
+        None => log::debug!("transferred {} bytes", bytes),
+      },
+    }
+  }
+}
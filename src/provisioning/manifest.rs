@@ -0,0 +1,280 @@
+use crate::{
+  error::Error,
+  hsm::inventory::{
+    ethernet_interfaces::types::ComponentEthernetInterface,
+    redfish_endpoint::types::RedfishEndpoint, types::MacAddress,
+  },
+  http::{ApiContext, ExecutionMode},
+  node::{
+    self,
+    types::{NidRanges, Xname},
+  },
+  utils::split_csv_line,
+};
+
+use super::{
+  types::{ManifestRow, ManifestRowResult, NodeSpec, ProvisioningSpec},
+  utils::provision_nodes,
+};
+
+const CSV_COLUMNS: [&str; 6] =
+  ["xname", "nid", "role", "macs", "bmc_address", "groups"];
+
+/// Parse a YAML manifest, a list of rows
+pub fn parse_yaml(manifest: &str) -> Result<Vec<ManifestRow>, Error> {
+  serde_yaml::from_str(manifest).map_err(|e| {
+    Error::Message(format!("Could not parse manifest. Reason:\n{}", e))
+  })
+}
+
+/// Parse a CSV manifest. The first line is a header naming the columns (xname, nid,
+/// role, macs, bmc_address, groups), only xname is mandatory. Empty lines and lines
+/// starting with '#' are ignored. Fields are split with `utils::split_csv_line`, rows
+/// with more fields than the header are rejected
+pub fn parse_csv(manifest: &str) -> Result<Vec<ManifestRow>, Error> {
+  let mut line_iter = manifest
+    .lines()
+    .enumerate()
+    .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'));
+
+  let (header_index, header) = line_iter
+    .next()
+    .ok_or_else(|| Error::Message("Manifest is empty".to_string()))?;
+
+  let header_vec: Vec<String> = split_csv_line(header, ',')
+    .ok_or_else(|| unterminated_quote(header_index))?
+    .into_iter()
+    .map(|column| column.to_lowercase())
+    .collect();
+
+  if let Some(column) = header_vec
+    .iter()
+    .find(|column| !CSV_COLUMNS.contains(&column.as_str()))
+  {
+    return Err(Error::Message(format!(
+      "Unknown manifest column '{}', valid columns: {}",
+      column,
+      CSV_COLUMNS.join(", ")
+    )));
+  }
+
+  if !header_vec.iter().any(|column| column == "xname") {
+    return Err(Error::Message("Manifest has no 'xname' column".to_string()));
+  }
+
+  line_iter
+    .map(|(line_number, line)| {
+      let field_vec = split_csv_line(line, ',')
+        .ok_or_else(|| unterminated_quote(line_number))?;
+
+      if field_vec.len() > header_vec.len() {
+        return Err(Error::Message(format!(
+          "Line {}: {} fields, the header has {} columns",
+          line_number + 1,
+          field_vec.len(),
+          header_vec.len()
+        )));
+      }
+
+      let mut row = ManifestRow::default();
+
+      for (column, value) in header_vec.iter().zip(&field_vec) {
+        let value = value.as_str();
+
+        if value.is_empty() {
+          continue;
+        }
+
+        match column.as_str() {
+          "xname" => row.xname = value.to_string(),
+          "nid" => {
+            row.nid = Some(value.parse().map_err(|_| {
+              Error::Message(format!(
+                "Line {}: NID '{}' is not a number",
+                line_number + 1,
+                value
+              ))
+            })?)
+          }
+          "role" => row.role = Some(value.to_string()),
+          "macs" => row.macs = split_list(value),
+          "bmc_address" => row.bmc_address = Some(value.to_string()),
+          "groups" => row.groups = split_list(value),
+          _ => {}
+        }
+      }
+
+      if row.xname.is_empty() {
+        return Err(Error::Message(format!(
+          "Line {}: xname is missing",
+          line_number + 1
+        )));
+      }
+
+      Ok(row)
+    })
+    .collect()
+}
+
+fn unterminated_quote(line_number: usize) -> Error {
+  Error::Message(format!(
+    "Line {}: quoted field is not terminated",
+    line_number + 1
+  ))
+}
+
+/// Allocate a free NID to each row without one, from the range of the row role. Call
+/// before `import` to avoid NID collisions
+pub async fn assign_nids(
+  ctx: &ApiContext,
+  row_vec: &mut [ManifestRow],
+  nid_ranges: &NidRanges,
+) -> Result<(), Error> {
+  let mut role_vec: Vec<Option<String>> = row_vec
+    .iter()
+    .filter(|row| row.nid.is_none())
+    .map(|row| row.role.clone())
+    .collect();
+  role_vec.sort();
+  role_vec.dedup();
+
+  // NIDs already taken by the manifest, roles may share a range
+  let mut taken_nid_vec: Vec<usize> =
+    row_vec.iter().filter_map(|row| row.nid).collect();
+
+  for role_opt in role_vec {
+    let mut row_missing_nid_vec: Vec<&mut ManifestRow> = row_vec
+      .iter_mut()
+      .filter(|row| row.nid.is_none() && row.role == role_opt)
+      .collect();
+
+    let nid_vec: Vec<usize> = node::utils::allocate_nids(
+      ctx,
+      nid_ranges,
+      role_opt.as_deref(),
+      row_missing_nid_vec.len(),
+      &taken_nid_vec,
+    )
+    .await?;
+
+    for (row, nid) in row_missing_nid_vec.iter_mut().zip(nid_vec) {
+      row.nid = Some(nid);
+      taken_nid_vec.push(nid);
+    }
+  }
+
+  Ok(())
+}
+
+/// Onboard the nodes of a manifest: HSM components, ethernet interfaces, redfish
+/// endpoints and HSM group memberships. Rows are processed independently, a failing row
+/// is rolled back and reported without stopping the import. In dry-run, nothing is
+/// changed and the steps that would be applied are returned
+pub async fn import(
+  ctx: &ApiContext,
+  row_vec: &[ManifestRow],
+  execution_mode: ExecutionMode,
+) -> Vec<ManifestRowResult> {
+  let mut result_vec = Vec::new();
+
+  for (index, row) in row_vec.iter().enumerate() {
+    let spec_rslt = to_node_spec(row).map(|node| ProvisioningSpec {
+      nodes: vec![node],
+      boot_parameters: None,
+    });
+
+    let result = match spec_rslt {
+      Ok(spec) => provision_nodes(ctx, &spec, execution_mode, |step| {
+        log::info!("Row {}: {}", index + 1, step)
+      })
+      .await
+      .map_err(|e| e.to_string()),
+      Err(e) => Err(e.to_string()),
+    };
+
+    if let Err(e) = &result {
+      log::error!("Row {} ({}) failed: {}", index + 1, row.xname, e);
+    }
+
+    result_vec.push(ManifestRowResult {
+      row: index + 1,
+      xname: row.xname.clone(),
+      steps: result.as_ref().cloned().unwrap_or_default(),
+      error: result.err(),
+    });
+  }
+
+  result_vec
+}
+
+fn to_node_spec(row: &ManifestRow) -> Result<NodeSpec, Error> {
+  let xname: Xname = row.xname.parse()?;
+
+  let mac_address_vec = row
+    .macs
+    .iter()
+    .map(|mac_address| mac_address.parse::<MacAddress>())
+    .collect::<Result<Vec<MacAddress>, Error>>()?;
+
+  let bmc = match &row.bmc_address {
+    Some(bmc_address) => {
+      let bmc_xname = xname.bmc().ok_or_else(|| {
+        Error::Message(format!("'{}' has no BMC, not a node xname", xname))
+      })?;
+
+      Some(RedfishEndpoint {
+        id: bmc_xname.to_string(),
+        r#type: None,
+        name: None,
+        hostname: Some(bmc_address.clone()),
+        domain: None,
+        fqdn: Some(bmc_address.clone()),
+        enabled: Some(true),
+        uuid: None,
+        user: None,
+        password: None,
+        use_ssdp: None,
+        mac_required: None,
+        mac_addr: None,
+        ip_address: None,
+        rediscover_on_update: Some(true),
+        template_id: None,
+        discovery_info: None,
+      })
+    }
+    None => None,
+  };
+
+  Ok(NodeSpec {
+    xname: row.xname.clone(),
+    nid: row.nid,
+    role: row.role.clone(),
+    sub_role: None,
+    arch: None,
+    class: None,
+    ethernet_interfaces: mac_address_vec
+      .into_iter()
+      .map(|mac_address| ComponentEthernetInterface {
+        id: None,
+        description: None,
+        mac_address: Some(mac_address),
+        ip_addresses: None,
+        last_update: None,
+        component_id: Some(row.xname.clone()),
+        parent_hms_type: None,
+      })
+      .collect(),
+    bmc,
+    groups: row.groups.clone(),
+    cloud_init: None,
+  })
+}
+
+fn split_list(value: &str) -> Vec<String> {
+  value
+    .split(';')
+    .map(str::trim)
+    .filter(|item| !item.is_empty())
+    .map(str::to_string)
+    .collect()
+}
@@ -0,0 +1,5 @@
+pub mod manifest;
+#[cfg(test)]
+pub mod tests;
+pub mod types;
+pub mod utils;
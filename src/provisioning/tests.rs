@@ -0,0 +1,206 @@
+use serde_json::json;
+
+use crate::{
+  http::ExecutionMode,
+  provisioning::{
+    manifest::parse_csv, types::ProvisioningSpec, utils::provision_nodes,
+  },
+  test_util::{MockOchami, MockService},
+};
+
+fn spec() -> ProvisioningSpec {
+  serde_json::from_value(json!({
+    "nodes": [{
+      "xname": "x1000c0s0b0n0",
+      "role": "Compute",
+      "ethernet_interfaces": [{"MACAddress": "aa:bb:cc:dd:ee:ff"}]
+    }]
+  }))
+  .unwrap()
+}
+
+async fn received(mock: &MockOchami, http_method: &str) -> Vec<String> {
+  mock
+    .server()
+    .received_requests()
+    .await
+    .unwrap()
+    .into_iter()
+    .filter(|request| request.method.as_str() == http_method)
+    .map(|request| String::from_utf8_lossy(&request.body).to_string())
+    .collect()
+}
+
+#[tokio::test]
+async fn test_rollback_restores_existing_component() {
+  let mock = MockOchami::start().await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "POST",
+      "/State/Components/Query",
+      200,
+      Some(json!({"Components": [
+        {"ID": "x1000c0s0b0n0", "State": "Ready", "Role": "Service"}
+      ]})),
+    )
+    .await;
+  mock
+    .mock(MockService::Hsm, "POST", "/State/Components", 204, None)
+    .await;
+  mock
+    .mock_error(
+      MockService::Hsm,
+      "POST",
+      "/Inventory/EthernetInterfaces",
+      409,
+      "conflict",
+    )
+    .await;
+
+  let result =
+    provision_nodes(&mock.context(), &spec(), ExecutionMode::Apply, |_| {})
+      .await;
+  assert!(result.is_err());
+
+  assert!(received(&mock, "DELETE").await.is_empty());
+  let post_vec = received(&mock, "POST").await;
+  // Query, component, ethernet interface, then the component as it was before
+  assert_eq!(post_vec.len(), 4);
+  let query: serde_json::Value = serde_json::from_str(&post_vec[0]).unwrap();
+  assert_eq!(query["ComponentIDs"], json!(["x1000c0s0b0n0"]));
+  let restored: serde_json::Value = serde_json::from_str(&post_vec[3]).unwrap();
+  assert_eq!(restored["Components"][0]["Role"], "Service");
+  assert_eq!(restored["Components"][0]["State"], "Ready");
+  assert_eq!(restored["Force"], true);
+}
+
+#[tokio::test]
+async fn test_rollback_deletes_new_component() {
+  let mock = MockOchami::start().await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "POST",
+      "/State/Components/Query",
+      200,
+      Some(json!({"Components": []})),
+    )
+    .await;
+  mock
+    .mock(MockService::Hsm, "POST", "/State/Components", 204, None)
+    .await;
+  mock
+    .mock_error(
+      MockService::Hsm,
+      "POST",
+      "/Inventory/EthernetInterfaces",
+      409,
+      "conflict",
+    )
+    .await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "DELETE",
+      "/State/Components/[^/]+",
+      200,
+      Some(json!({"code": 0, "message": "deleted 1 entry"})),
+    )
+    .await;
+
+  let result =
+    provision_nodes(&mock.context(), &spec(), ExecutionMode::Apply, |_| {})
+      .await;
+  assert!(result.unwrap_err().to_string().contains("rolled back"));
+
+  let delete_path_vec: Vec<String> = mock
+    .server()
+    .received_requests()
+    .await
+    .unwrap()
+    .into_iter()
+    .filter(|request| request.method.as_str() == "DELETE")
+    .map(|request| request.url.path().to_string())
+    .collect();
+  assert_eq!(delete_path_vec.len(), 1);
+  assert!(delete_path_vec[0].ends_with("/State/Components/x1000c0s0b0n0"));
+}
+
+#[test]
+fn test_parse_csv() {
+  let manifest = "\
+# Rack 1
+Xname, NID , role,macs,bmc_address,groups
+
+x1000c0s0b0n0,1,Compute,aa:bb:cc:dd:ee:01;aa:bb:cc:dd:ee:02,10.1.0.1,compute;gpu
+x1000c0s0b0n1,,,,,
+";
+
+  let row_vec = parse_csv(manifest).unwrap();
+
+  assert_eq!(row_vec.len(), 2);
+  assert_eq!(row_vec[0].xname, "x1000c0s0b0n0");
+  assert_eq!(row_vec[0].nid, Some(1));
+  assert_eq!(row_vec[0].role.as_deref(), Some("Compute"));
+  assert_eq!(row_vec[0].macs, ["aa:bb:cc:dd:ee:01", "aa:bb:cc:dd:ee:02"]);
+  assert_eq!(row_vec[0].bmc_address.as_deref(), Some("10.1.0.1"));
+  assert_eq!(row_vec[0].groups, ["compute", "gpu"]);
+  assert_eq!(row_vec[1].xname, "x1000c0s0b0n1");
+  assert_eq!(row_vec[1].nid, None);
+  assert!(row_vec[1].groups.is_empty());
+}
+
+#[test]
+fn test_parse_csv_quoted_fields() {
+  let manifest = "\
+xname,role,groups
+\"x1000c0s0b0n0\", \"Compute, GPU\" ,\"compute;\"\"gpu\"\"\"
+x1000c0s0b0n1,\"\",compute
+";
+
+  let row_vec = parse_csv(manifest).unwrap();
+
+  assert_eq!(row_vec[0].xname, "x1000c0s0b0n0");
+  assert_eq!(row_vec[0].role.as_deref(), Some("Compute, GPU"));
+  assert_eq!(row_vec[0].groups, ["compute", "\"gpu\""]);
+  assert_eq!(row_vec[1].role, None);
+}
+
+#[test]
+fn test_parse_csv_short_rows() {
+  // Missing trailing fields are empty
+  let row_vec = parse_csv("xname,nid,role\nx1000c0s0b0n0,7\n").unwrap();
+
+  assert_eq!(row_vec[0].nid, Some(7));
+  assert_eq!(row_vec[0].role, None);
+}
+
+#[test]
+fn test_parse_csv_malformed() {
+  for (manifest, error) in [
+    ("", "Manifest is empty"),
+    ("# only a comment\n\n", "Manifest is empty"),
+    ("nid,role\n1,Compute\n", "no 'xname' column"),
+    (
+      "xname,rack\nx1000c0s0b0n0,1\n",
+      "Unknown manifest column 'rack'",
+    ),
+    (
+      "xname,nid\nx1000c0s0b0n0,one\n",
+      "Line 2: NID 'one' is not a number",
+    ),
+    (
+      "xname,nid\nx1000c0s0b0n0,1,Compute\n",
+      "Line 2: 3 fields, the header has 2 columns",
+    ),
+    (
+      "xname,role\n\nx1000c0s0b0n0,\"Compute\n",
+      "Line 3: quoted field is not terminated",
+    ),
+    ("xname,nid\n,1\n", "Line 2: xname is missing"),
+  ] {
+    let e = parse_csv(manifest).unwrap_err().to_string();
+    assert!(e.contains(error), "{:?}: {}", manifest, e);
+  }
+}
@@ -0,0 +1,118 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  bss::types::BootParameters,
+  cloud_init::types::InstanceInfo,
+  hostlist,
+  hsm::inventory::{
+    ethernet_interfaces::types::ComponentEthernetInterface,
+    redfish_endpoint::types::RedfishEndpoint,
+  },
+};
+
+/// Node to provision
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NodeSpec {
+  pub xname: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub nid: Option<usize>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub role: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub sub_role: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub arch: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub class: Option<String>,
+  #[serde(default)]
+  pub ethernet_interfaces: Vec<ComponentEthernetInterface>,
+  /// Redfish endpoint of the node BMC
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub bmc: Option<RedfishEndpoint>,
+  /// HSM groups the node is added to
+  #[serde(default)]
+  pub groups: Vec<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub cloud_init: Option<InstanceInfo>,
+}
+
+/// Declarative description of the nodes to provision
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ProvisioningSpec {
+  pub nodes: Vec<NodeSpec>,
+  /// Boot parameters applied to all nodes, `hosts` is filled with the nodes xnames
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub boot_parameters: Option<BootParameters>,
+}
+
+/// Row of a node onboarding manifest. In CSV manifests MAC addresses and group labels
+/// are separated by ';'
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ManifestRow {
+  pub xname: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub nid: Option<usize>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub role: Option<String>,
+  #[serde(default)]
+  pub macs: Vec<String>,
+  /// Hostname or IP address of the node BMC
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub bmc_address: Option<String>,
+  #[serde(default)]
+  pub groups: Vec<String>,
+}
+
+/// Result of onboarding a single manifest row
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ManifestRowResult {
+  /// Position of the row in the manifest, starting at 1
+  pub row: usize,
+  pub xname: String,
+  /// Steps applied, or that would be applied in dry-run
+  pub steps: Vec<ProvisioningStep>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub error: Option<String>,
+}
+
+/// Single change applied to the system while provisioning nodes
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum ProvisioningStep {
+  CreateComponent { xname: String },
+  CreateEthernetInterface { xname: String, mac_address: String },
+  CreateRedfishEndpoint { xname: String, bmc: String },
+  AddGroupMember { group_label: String, xname: String },
+  SetBootParameters { xnames: Vec<String> },
+  SetCloudInit { xname: String },
+}
+
+impl fmt::Display for ProvisioningStep {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ProvisioningStep::CreateComponent { xname } => {
+        write!(f, "create HSM component '{}'", xname)
+      }
+      ProvisioningStep::CreateEthernetInterface { xname, mac_address } => {
+        write!(
+          f,
+          "create ethernet interface '{}' for '{}'",
+          mac_address, xname
+        )
+      }
+      ProvisioningStep::CreateRedfishEndpoint { xname, bmc } => {
+        write!(f, "create redfish endpoint '{}' for '{}'", bmc, xname)
+      }
+      ProvisioningStep::AddGroupMember { group_label, xname } => {
+        write!(f, "add '{}' to HSM group '{}'", xname, group_label)
+      }
+      ProvisioningStep::SetBootParameters { xnames } => {
+        write!(f, "set boot parameters for {}", hostlist::compress(xnames))
+      }
+      ProvisioningStep::SetCloudInit { xname } => {
+        write!(f, "set cloud-init data for '{}'", xname)
+      }
+    }
+  }
+}
@@ -0,0 +1,238 @@
+use crate::{
+  bss::types::BootParameters,
+  cloud_init,
+  error::Error,
+  hsm::{
+    component::types::{ComponentArrayPostArray, ComponentCreate},
+    inventory::{
+      ethernet_interfaces::types::ComponentEthernetInterface,
+      redfish_endpoint::types::RedfishEndpointArray,
+    },
+  },
+  http::{ApiContext, ExecutionMode},
+  mutation::{
+    types::{Journal, Mutation},
+    utils::{execute_journaled, rollback},
+  },
+};
+
+use super::types::{NodeSpec, ProvisioningSpec, ProvisioningStep};
+
+/// List the changes needed to provision the nodes in a spec, in the order they are
+/// applied
+pub fn plan(spec: &ProvisioningSpec) -> Vec<ProvisioningStep> {
+  let mut step_vec = Vec::new();
+
+  for node in &spec.nodes {
+    step_vec.push(ProvisioningStep::CreateComponent {
+      xname: node.xname.clone(),
+    });
+
+    for eth_interface in &node.ethernet_interfaces {
+      step_vec.push(ProvisioningStep::CreateEthernetInterface {
+        xname: node.xname.clone(),
+        mac_address: eth_interface
+          .mac_address
+          .clone()
+          .map(String::from)
+          .unwrap_or_default(),
+      });
+    }
+
+    if let Some(bmc) = &node.bmc {
+      step_vec.push(ProvisioningStep::CreateRedfishEndpoint {
+        xname: node.xname.clone(),
+        bmc: bmc.id.clone(),
+      });
+    }
+
+    for group_label in &node.groups {
+      step_vec.push(ProvisioningStep::AddGroupMember {
+        group_label: group_label.clone(),
+        xname: node.xname.clone(),
+      });
+    }
+  }
+
+  if spec.boot_parameters.is_some() && !spec.nodes.is_empty() {
+    step_vec.push(ProvisioningStep::SetBootParameters {
+      xnames: spec.nodes.iter().map(|node| node.xname.clone()).collect(),
+    });
+  }
+
+  for node in spec.nodes.iter().filter(|node| node.cloud_init.is_some()) {
+    step_vec.push(ProvisioningStep::SetCloudInit {
+      xname: node.xname.clone(),
+    });
+  }
+
+  step_vec
+}
+
+/// Provision nodes from a declarative spec: HSM components, ethernet interfaces, redfish
+/// endpoints, HSM group memberships, BSS boot parameters and cloud-init data.
+/// `progress` is called before each step. In dry-run, nothing is changed and the steps
+/// that would be applied are returned.
+/// Each step is recorded in a `mutation::types::Journal`. If a step fails, the steps
+/// already applied are reverted (in reverse order) and the error is returned
+pub async fn provision_nodes(
+  ctx: &ApiContext,
+  spec: &ProvisioningSpec,
+  execution_mode: ExecutionMode,
+  mut progress: impl FnMut(&ProvisioningStep),
+) -> Result<Vec<ProvisioningStep>, Error> {
+  let step_vec = plan(spec);
+
+  if execution_mode.is_dry_run() {
+    step_vec.iter().for_each(&mut progress);
+    return Ok(step_vec);
+  }
+
+  let mut journal = Journal::new();
+
+  for step in &step_vec {
+    progress(step);
+
+    let step_rslt = match step_mutation(spec, step)? {
+      Some(mutation) => execute_journaled(ctx, &mut journal, mutation).await,
+      None => apply_step(ctx, spec, step).await,
+    };
+
+    let e = match step_rslt {
+      Ok(()) => continue,
+      Err(e) => e,
+    };
+
+    log::error!("Provisioning step '{}' failed. Rolling back", step);
+
+    return Err(match rollback(ctx, &mut journal).await {
+      Ok(()) => Error::Message(format!(
+        "Could not {}. Changes were rolled back. Reason:\n{}",
+        step, e
+      )),
+      Err(rollback_error) => Error::Message(format!(
+        "Could not {}. Reason:\n{}\nRollback incomplete, {} change(s) not reverted:\n{}",
+        step,
+        e,
+        journal.len(),
+        rollback_error
+      )),
+    });
+  }
+
+  Ok(step_vec)
+}
+
+fn get_node<'a>(
+  spec: &'a ProvisioningSpec,
+  xname: &str,
+) -> Result<&'a NodeSpec, Error> {
+  spec
+    .nodes
+    .iter()
+    .find(|node| node.xname == xname)
+    .ok_or_else(|| Error::Message(format!("Node '{}' not in spec", xname)))
+}
+
+/// Mutation applying a step, recorded in the journal so it can be reverted. `None` for
+/// the steps applied with `apply_step` instead
+fn step_mutation(
+  spec: &ProvisioningSpec,
+  step: &ProvisioningStep,
+) -> Result<Option<Mutation>, Error> {
+  let mutation = match step {
+    ProvisioningStep::CreateComponent { xname } => {
+      let node = get_node(spec, xname)?;
+
+      Mutation::CreateComponents(ComponentArrayPostArray {
+        components: vec![ComponentCreate {
+          id: node.xname.clone(),
+          state: "Populated".to_string(),
+          flag: None,
+          enabled: Some(true),
+          software_status: None,
+          role: node.role.clone(),
+          sub_role: node.sub_role.clone(),
+          nid: node.nid,
+          subtype: None,
+          net_type: None,
+          arch: node.arch.clone(),
+          class: node.class.clone(),
+        }],
+        force: None,
+      })
+    }
+    ProvisioningStep::CreateEthernetInterface { xname, mac_address } => {
+      let eth_interface = get_node(spec, xname)?
+        .ethernet_interfaces
+        .iter()
+        .find(|eth_interface| {
+          eth_interface
+            .mac_address
+            .as_ref()
+            .map(|eth_mac_address| eth_mac_address.as_str())
+            .unwrap_or_default()
+            == mac_address
+        })
+        .cloned()
+        .map(|eth_interface| ComponentEthernetInterface {
+          component_id: Some(xname.clone()),
+          ..eth_interface
+        })
+        .ok_or_else(|| {
+          Error::Message(format!(
+            "Ethernet interface '{}' not in spec",
+            mac_address
+          ))
+        })?;
+
+      Mutation::CreateEthernetInterface(eth_interface)
+    }
+    ProvisioningStep::CreateRedfishEndpoint { xname, .. } => {
+      let bmc = get_node(spec, xname)?.bmc.clone();
+
+      Mutation::CreateRedfishEndpoints(RedfishEndpointArray {
+        redfish_endpoints: bmc.map(|bmc| vec![bmc]),
+      })
+    }
+    ProvisioningStep::AddGroupMember { group_label, xname } => {
+      Mutation::AddGroupMember {
+        group_label: group_label.clone(),
+        xname: xname.clone(),
+      }
+    }
+    ProvisioningStep::SetBootParameters { xnames } => {
+      Mutation::SetBootParameters(BootParameters {
+        hosts: xnames.clone(),
+        ..spec.boot_parameters.clone().unwrap_or_default()
+      })
+    }
+    ProvisioningStep::SetCloudInit { .. } => return Ok(None),
+  };
+
+  Ok(Some(mutation))
+}
+
+/// Apply a step with no `Mutation`. It is not reverted on rollback
+async fn apply_step(
+  ctx: &ApiContext,
+  spec: &ProvisioningSpec,
+  step: &ProvisioningStep,
+) -> Result<(), Error> {
+  match step {
+    ProvisioningStep::SetCloudInit { xname } => {
+      let instance_info = get_node(spec, xname)?
+        .cloud_init
+        .clone()
+        .unwrap_or_default();
+
+      cloud_init::http_client::put_instance_info(ctx, xname, &instance_info)
+        .await
+        .map(|_| ())
+    }
+    _ => Err(Error::Message(format!(
+      "Provisioning step '{}' is applied as a mutation",
+      step
+    ))),
+  }
+}
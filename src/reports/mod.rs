@@ -0,0 +1,418 @@
+#[cfg(test)]
+pub mod tests;
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  error::Error,
+  hsm::{
+    self,
+    component::{self, types::ComponentArrayPostQuery},
+  },
+  http::ApiContext,
+  node::types::Xname,
+  pcs::{
+    self,
+    power_status::types::{ManagementState, PowerState},
+  },
+};
+
+/// Number of components per State, Flag, Role and Enabled
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ComponentSummary {
+  pub total: usize,
+  pub by_state: BTreeMap<String, usize>,
+  pub by_flag: BTreeMap<String, usize>,
+  pub by_role: BTreeMap<String, usize>,
+  pub enabled: usize,
+  pub disabled: usize,
+}
+
+/// Count the components matching `filters` grouped by State, Flag, Role and Enabled,
+/// with a single query. SMD only accepts POST queries listing `ComponentIDs`, so the
+/// other filters are sent as query parameters of `GET /State/Components`. The `*only`
+/// options are ignored, every field is needed
+pub async fn component_summary(
+  ctx: &ApiContext,
+  filters: &ComponentArrayPostQuery,
+) -> Result<ComponentSummary, Error> {
+  let component_array = if filters.component_ids.is_some() {
+    component::client::post_query(
+      ctx,
+      ComponentArrayPostQuery {
+        state_only: None,
+        falg_only: None,
+        role_only: None,
+        nid_only: None,
+        ..filters.clone()
+      },
+    )
+    .await?
+  } else {
+    component::client::get(
+      ctx,
+      None,
+      filters.r#type.as_deref(),
+      filters.state.as_deref(),
+      filters.flag.as_deref(),
+      filters.role.as_deref(),
+      filters.subrole.as_deref(),
+      filters.enabled.as_deref(),
+      filters.software_status.as_deref(),
+      filters.subtype.as_deref(),
+      filters.arch.as_deref(),
+      filters.class.as_deref(),
+      filters.nid.as_deref(),
+      filters.nid_start.as_deref(),
+      filters.nid_end.as_deref(),
+      filters.partition.as_deref(),
+      filters.group.as_deref(),
+      None,
+      None,
+      None,
+      None,
+    )
+    .await?
+  };
+
+  let component_vec = component_array.components.unwrap_or_default();
+
+  let enabled = component_vec
+    .iter()
+    .filter(|component| component.enabled == Some(true))
+    .count();
+
+  let mut summary = ComponentSummary {
+    total: component_vec.len(),
+    enabled,
+    disabled: component_vec.len() - enabled,
+    ..Default::default()
+  };
+
+  for component in component_vec {
+    *summary
+      .by_state
+      .entry(component.state.unwrap_or_else(|| "Unknown".to_string()))
+      .or_default() += 1;
+    *summary
+      .by_flag
+      .entry(component.flag.unwrap_or_else(|| "Unknown".to_string()))
+      .or_default() += 1;
+    *summary
+      .by_role
+      .entry(component.role.unwrap_or_else(|| "Unknown".to_string()))
+      .or_default() += 1;
+  }
+
+  Ok(summary)
+}
+
+/// Inconsistencies between redfish endpoints, component endpoints, components and
+/// ethernet interfaces
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct InventoryReconciliation {
+  /// Redfish endpoints (BMCs) which expose no component endpoint
+  pub bmcs_without_components: Vec<String>,
+  /// Component endpoints with no matching HSM component
+  pub component_endpoints_without_component: Vec<String>,
+  /// Nodes with no ethernet interface, they can't be booted
+  pub components_without_mac: Vec<String>,
+  /// MAC addresses claimed by more than one component
+  pub duplicated_macs: BTreeMap<String, Vec<String>>,
+}
+
+impl InventoryReconciliation {
+  /// Returns true if no inconsistency was found
+  pub fn is_consistent(&self) -> bool {
+    self.bmcs_without_components.is_empty()
+      && self.component_endpoints_without_component.is_empty()
+      && self.components_without_mac.is_empty()
+      && self.duplicated_macs.is_empty()
+  }
+}
+
+/// Cross reference redfish endpoints, component endpoints, components and ethernet
+/// interfaces and report orphans and duplicated MAC addresses
+pub async fn reconcile_inventory(
+  ctx: &ApiContext,
+) -> Result<InventoryReconciliation, Error> {
+  let redfish_endpoint_vec =
+    hsm::inventory::redfish_endpoint::client::get_all(ctx)
+      .await?
+      .redfish_endpoints
+      .unwrap_or_default();
+
+  let component_endpoint_vec =
+    hsm::inventory::component_endpoint::http_client::get_all(ctx)
+      .await?
+      .component_endpoints
+      .unwrap_or_default();
+
+  // Revalidated, a report on stale components would be misleading
+  let component_vec = hsm::cache::get_all_components_revalidated(ctx)
+    .await?
+    .components
+    .unwrap_or_default();
+
+  let eth_interface_vec = hsm::inventory::ethernet_interfaces::client::get(
+    ctx, None, None, None, None, None, None, None,
+  )
+  .await?;
+
+  let mut report = InventoryReconciliation::default();
+
+  let component_id_set: HashSet<&str> = component_vec
+    .iter()
+    .filter_map(|component| component.id.as_deref())
+    .collect();
+
+  let bmc_with_component_set: HashSet<&str> = component_endpoint_vec
+    .iter()
+    .filter_map(|component_endpoint| {
+      component_endpoint.redfish_endpoint_id.as_deref()
+    })
+    .collect();
+
+  report.bmcs_without_components = redfish_endpoint_vec
+    .iter()
+    .filter(|redfish_endpoint| {
+      !bmc_with_component_set.contains(redfish_endpoint.id.as_str())
+    })
+    .map(|redfish_endpoint| redfish_endpoint.id.clone())
+    .collect();
+
+  report.component_endpoints_without_component = component_endpoint_vec
+    .iter()
+    .filter(|component_endpoint| {
+      !component_id_set.contains(component_endpoint.id.as_str())
+    })
+    .map(|component_endpoint| component_endpoint.id.clone())
+    .collect();
+
+  let xname_with_mac_set: HashSet<&str> = eth_interface_vec
+    .iter()
+    .filter(|eth_interface| eth_interface.mac_address.is_some())
+    .filter_map(|eth_interface| eth_interface.component_id.as_deref())
+    .collect();
+
+  report.components_without_mac = component_vec
+    .iter()
+    .filter(|component| component.r#type.as_deref() == Some("Node"))
+    .filter_map(|component| component.id.clone())
+    .filter(|xname| !xname_with_mac_set.contains(xname.as_str()))
+    .collect();
+
+  // MAC address -> components claiming it
+  let mut mac_map: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+  for eth_interface in &eth_interface_vec {
+    if let (Some(mac_address), Some(xname)) =
+      (&eth_interface.mac_address, &eth_interface.component_id)
+    {
+      mac_map
+        .entry(mac_address.to_string())
+        .or_default()
+        .insert(xname.clone());
+    }
+  }
+
+  for component_endpoint in &component_endpoint_vec {
+    // Component endpoints report the MAC of the BMC or node interface they represent
+    if let Some(mac_address) = &component_endpoint.mac_addr {
+      if let Some(xname_set) = mac_map.get_mut(mac_address.as_str()) {
+        xname_set.insert(component_endpoint.id.clone());
+      }
+    }
+  }
+
+  report.duplicated_macs = mac_map
+    .into_iter()
+    .filter(|(_, xname_set)| xname_set.len() > 1)
+    .map(|(mac_address, xname_set)| {
+      (mac_address, xname_set.into_iter().collect())
+    })
+    .collect();
+
+  report.bmcs_without_components.sort();
+  report.component_endpoints_without_component.sort();
+  report.components_without_mac.sort();
+
+  Ok(report)
+}
+
+/// Overall health of a node, from best to worst
+#[derive(
+  Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub enum NodeHealthStatus {
+  Healthy,
+  Degraded,
+  Unreachable,
+}
+
+/// Health of a node combining HSM, PCS and redfish endpoint information, with the
+/// reasons why the node is not healthy
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NodeHealth {
+  pub xname: String,
+  pub status: NodeHealthStatus,
+  pub reasons: Vec<String>,
+}
+
+impl NodeHealth {
+  fn report(&mut self, status: NodeHealthStatus, reason: String) {
+    self.status = self.status.max(status);
+    self.reasons.push(reason);
+  }
+}
+
+/// Health of a list of nodes joining the HSM component state, the PCS power status and
+/// the discovery status of the node BMC redfish endpoint
+pub async fn node_health(
+  ctx: &ApiContext,
+  xname_vec: &[String],
+) -> Result<Vec<NodeHealth>, Error> {
+  let component_map: HashMap<String, component::types::Component> =
+    component::client::post_query(
+      ctx,
+      ComponentArrayPostQuery {
+        component_ids: Some(xname_vec.to_vec()),
+        ..Default::default()
+      },
+    )
+    .await?
+    .components
+    .unwrap_or_default()
+    .into_iter()
+    .filter_map(|component| Some((component.id.clone()?, component)))
+    .collect();
+
+  let xname_str_vec: Vec<&str> = xname_vec.iter().map(String::as_str).collect();
+
+  let power_status_map: HashMap<String, pcs::power_status::types::PowerStatus> =
+    pcs::power_status::client::post(ctx, Some(&xname_str_vec), None, None)
+      .await?
+      .status
+      .into_iter()
+      .map(|power_status| (power_status.xname.clone(), power_status))
+      .collect();
+
+  let bmc_status_map: HashMap<String, Option<String>> =
+    hsm::inventory::redfish_endpoint::client::get_all(ctx)
+      .await?
+      .redfish_endpoints
+      .unwrap_or_default()
+      .into_iter()
+      .map(|redfish_endpoint| {
+        (
+          redfish_endpoint.id,
+          redfish_endpoint
+            .discovery_info
+            .and_then(|discovery_info| discovery_info.last_status),
+        )
+      })
+      .collect();
+
+  let health_vec = xname_vec
+    .iter()
+    .map(|xname| {
+      let mut health = NodeHealth {
+        xname: xname.clone(),
+        status: NodeHealthStatus::Healthy,
+        reasons: Vec::new(),
+      };
+
+      match component_map.get(xname) {
+        Some(component) => {
+          let state = component.state.as_deref().unwrap_or("Unknown");
+          if state != "Ready" {
+            health.report(
+              NodeHealthStatus::Degraded,
+              format!("HSM state is {}", state),
+            );
+          }
+
+          if let Some(flag) = component.flag.as_deref().filter(|f| *f != "OK") {
+            health.report(
+              NodeHealthStatus::Degraded,
+              format!("HSM flag is {}", flag),
+            );
+          }
+
+          if component.enabled == Some(false) {
+            health.report(
+              NodeHealthStatus::Degraded,
+              "disabled in HSM".to_string(),
+            );
+          }
+        }
+        None => health.report(
+          NodeHealthStatus::Unreachable,
+          "not found in HSM".to_string(),
+        ),
+      }
+
+      match power_status_map.get(xname) {
+        Some(power_status) => {
+          if let Some(ManagementState::Unavailable) =
+            power_status.management_state
+          {
+            health.report(
+              NodeHealthStatus::Unreachable,
+              "BMC unavailable to PCS".to_string(),
+            );
+          }
+
+          if let Some(PowerState::Off) = power_status.power_state {
+            health
+              .report(NodeHealthStatus::Degraded, "powered off".to_string());
+          }
+
+          if let Some(error) = power_status.error() {
+            health.report(
+              NodeHealthStatus::Degraded,
+              format!("PCS error: {}", error),
+            );
+          }
+        }
+        None => health.report(
+          NodeHealthStatus::Degraded,
+          "no power status in PCS".to_string(),
+        ),
+      }
+
+      let bmc_opt = xname
+        .parse::<Xname>()
+        .ok()
+        .and_then(|xname| xname.bmc())
+        .map(|bmc| bmc.to_string());
+
+      match bmc_opt.and_then(|bmc| bmc_status_map.get(&bmc)) {
+        Some(Some(last_status)) if last_status == "DiscoverOK" => {}
+        Some(Some(last_status)) if last_status.contains("Fail") => health
+          .report(
+            NodeHealthStatus::Unreachable,
+            format!("BMC discovery status is {}", last_status),
+          ),
+        Some(Some(last_status)) => health.report(
+          NodeHealthStatus::Degraded,
+          format!("BMC discovery status is {}", last_status),
+        ),
+        Some(None) => health.report(
+          NodeHealthStatus::Degraded,
+          "BMC never discovered".to_string(),
+        ),
+        None => health.report(
+          NodeHealthStatus::Degraded,
+          "no redfish endpoint for the node BMC".to_string(),
+        ),
+      }
+
+      health
+    })
+    .collect();
+
+  Ok(health_vec)
+}
@@ -0,0 +1,89 @@
+use serde_json::json;
+
+use crate::{
+  hsm::component::types::ComponentArrayPostQuery,
+  reports::component_summary,
+  test_util::{MockOchami, MockService},
+};
+
+#[tokio::test]
+async fn test_component_summary_fleet() {
+  let mock = MockOchami::with_fixtures().await;
+
+  let summary =
+    component_summary(&mock.context(), &ComponentArrayPostQuery::default())
+      .await
+      .unwrap();
+
+  assert_eq!(summary.total, 3);
+  assert_eq!(summary.enabled, 2);
+  assert_eq!(summary.disabled, 1);
+  assert_eq!(summary.by_state["Ready"], 2);
+  assert_eq!(summary.by_state["Off"], 1);
+  assert_eq!(summary.by_flag["Warning"], 1);
+  assert_eq!(summary.by_role["Compute"], 2);
+  assert_eq!(summary.by_role["Management"], 1);
+
+  // One GET, no POST query without ComponentIDs
+  let request_vec = mock.server().received_requests().await.unwrap();
+  assert_eq!(request_vec.len(), 1);
+  assert_eq!(request_vec[0].method.as_str(), "GET");
+}
+
+#[tokio::test]
+async fn test_component_summary_filters_are_query_parameters() {
+  let mock = MockOchami::with_fixtures().await;
+
+  component_summary(
+    &mock.context(),
+    &ComponentArrayPostQuery {
+      role: Some("Compute".to_string()),
+      state_only: Some(true),
+      ..Default::default()
+    },
+  )
+  .await
+  .unwrap();
+
+  let request_vec = mock.server().received_requests().await.unwrap();
+  assert_eq!(request_vec.len(), 1);
+  assert_eq!(request_vec[0].url.query(), Some("role=Compute"));
+}
+
+#[tokio::test]
+async fn test_component_summary_by_ids() {
+  let mock = MockOchami::start().await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "POST",
+      "/State/Components/Query",
+      200,
+      Some(json!({"Components": [
+        {"ID": "x1000c0s0b0n0", "State": "Ready", "Flag": "OK",
+         "Enabled": true, "Role": "Compute"}
+      ]})),
+    )
+    .await;
+
+  let summary = component_summary(
+    &mock.context(),
+    &ComponentArrayPostQuery {
+      component_ids: Some(vec!["x1000c0s0b0n0".to_string()]),
+      role_only: Some(true),
+      ..Default::default()
+    },
+  )
+  .await
+  .unwrap();
+
+  assert_eq!(summary.total, 1);
+  assert_eq!(summary.enabled, 1);
+  assert_eq!(summary.by_flag["OK"], 1);
+
+  let request_vec = mock.server().received_requests().await.unwrap();
+  assert_eq!(request_vec.len(), 1);
+  let query: serde_json::Value =
+    serde_json::from_slice(&request_vec[0].body).unwrap();
+  assert_eq!(query, json!({"ComponentIDs": ["x1000c0s0b0n0"]}));
+}
@@ -0,0 +1,105 @@
+use url::Url;
+
+use crate::{error::Error, http::ApiContext};
+
+/// Path prefixes of the OpenCHAMI services, appended to the base url. Defaults to the
+/// SMD v2, BSS v1, PCS v1 and cloud-init APIs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServicePaths {
+  pub hsm: String,
+  pub bss: String,
+  pub pcs: String,
+  pub cloud_init: String,
+}
+
+impl Default for ServicePaths {
+  fn default() -> Self {
+    ServicePaths {
+      hsm: "/hsm/v2".to_string(),
+      bss: "/boot/v1".to_string(),
+      pcs: "/power-control/v1".to_string(),
+      cloud_init: "/cloud-init".to_string(),
+    }
+  }
+}
+
+/// SMD path prefixes probed by `detect_hsm_path`, newest API first
+const HSM_PATH_CANDIDATES: [&str; 4] =
+  ["/hsm/v3", "/hsm/v2", "/smd/hsm/v3", "/smd/hsm/v2"];
+
+/// Parse and check the base url of the API, eg: `https://api.example.com`. The value is
+/// not echoed back in the error as it may be a token passed by mistake
+pub fn validate_base_url(base_url: &str) -> Result<Url, Error> {
+  let url = Url::parse(base_url).map_err(|e| {
+    Error::InvalidBaseUrl(format!(
+      "{}, expected an absolute http(s) url (base url and token swapped?)",
+      e
+    ))
+  })?;
+
+  if !matches!(url.scheme(), "http" | "https") || url.cannot_be_a_base() {
+    return Err(Error::InvalidBaseUrl(format!(
+      "'{}' is not a http(s) url",
+      url
+    )));
+  }
+
+  Ok(url)
+}
+
+/// Url of `path` under `service_path`, both relative to `base_url`
+pub fn endpoint_url(
+  base_url: &str,
+  service_path: &str,
+  path: &str,
+) -> Result<Url, Error> {
+  let mut url = validate_base_url(base_url)?;
+
+  if !url.path().ends_with('/') {
+    let base_path = format!("{}/", url.path());
+    url.set_path(&base_path);
+  }
+
+  let relative_path =
+    [service_path.trim_matches('/'), path.trim_start_matches('/')]
+      .iter()
+      .filter(|segment| !segment.is_empty())
+      .copied()
+      .collect::<Vec<&str>>()
+      .join("/");
+
+  url
+    .join(&relative_path)
+    .map_err(|e| Error::InvalidBaseUrl(e.to_string()))
+}
+
+/// Find the SMD path prefix served at `base_url` by probing the readiness endpoint of
+/// each known API version. The prefix found is not applied, see
+/// `ApiContext::with_service_paths`
+pub async fn detect_hsm_path(ctx: &ApiContext) -> Result<String, Error> {
+  let client = ctx.client()?;
+
+  for hsm_path in HSM_PATH_CANDIDATES {
+    let api_url =
+      format!("{}{}/service/ready", ctx.base_url.as_str(), hsm_path);
+
+    let response_rslt = client
+      .get(api_url)
+      .bearer_auth(ctx.auth_token.as_str())
+      .send()
+      .await;
+
+    if let Ok(response) = response_rslt {
+      if response.status().is_success() {
+        log::info!("SMD API found at '{}'", hsm_path);
+        return Ok(hsm_path.to_string());
+      }
+    }
+  }
+
+  Err(Error::Message(format!(
+    "Could not find SMD API at '{}', tried {:?}",
+    ctx.base_url.as_str(),
+    HSM_PATH_CANDIDATES
+  )))
+}
@@ -0,0 +1,103 @@
+use std::fmt;
+
+#[cfg(feature = "backend-connector")]
+use manta_backend_dispatcher::types::bos::session::Operation as FrontEndOperation;
+use serde::{Deserialize, Serialize};
+
+use crate::pcs::transitions::types::{
+  PollOptions, PowerOperation, TransitionOutcome,
+};
+
+/// Action a session performs on the nodes of a session template
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionOperation {
+  /// Set boot parameters and power on the nodes
+  Boot,
+  /// Set boot parameters and restart the nodes
+  Reboot,
+  /// Power off the nodes, boot parameters are left untouched
+  Shutdown,
+}
+
+impl SessionOperation {
+  /// PCS operation used to carry out the session
+  pub fn power_operation(&self) -> PowerOperation {
+    match self {
+      SessionOperation::Boot => PowerOperation::On,
+      SessionOperation::Reboot => PowerOperation::HardRestart,
+      SessionOperation::Shutdown => PowerOperation::SoftOff,
+    }
+  }
+
+  /// Returns true if the session updates the boot parameters of the nodes
+  pub fn sets_boot_parameters(&self) -> bool {
+    !matches!(self, SessionOperation::Shutdown)
+  }
+}
+
+impl fmt::Display for SessionOperation {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      SessionOperation::Boot => write!(f, "boot"),
+      SessionOperation::Reboot => write!(f, "reboot"),
+      SessionOperation::Shutdown => write!(f, "shutdown"),
+    }
+  }
+}
+
+#[cfg(feature = "backend-connector")]
+impl From<FrontEndOperation> for SessionOperation {
+  fn from(operation: FrontEndOperation) -> Self {
+    match operation {
+      FrontEndOperation::Boot => SessionOperation::Boot,
+      FrontEndOperation::Reboot => SessionOperation::Reboot,
+      FrontEndOperation::Shutdown => SessionOperation::Shutdown,
+    }
+  }
+}
+
+#[cfg(feature = "backend-connector")]
+impl From<SessionOperation> for FrontEndOperation {
+  fn from(operation: SessionOperation) -> Self {
+    match operation {
+      SessionOperation::Boot => FrontEndOperation::Boot,
+      SessionOperation::Reboot => FrontEndOperation::Reboot,
+      SessionOperation::Shutdown => FrontEndOperation::Shutdown,
+    }
+  }
+}
+
+/// Settings of a session
+#[derive(Debug, Clone, Default)]
+pub struct SessionSettings {
+  /// Restricts the nodes targeted, accepts xnames, hostlists and HSM group labels
+  pub limit: Vec<String>,
+  /// Only set the boot parameters, nodes are not power cycled
+  pub stage: bool,
+  pub poll_options: PollOptions,
+}
+
+/// Result of running a session against the nodes of a session template
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SessionReport {
+  pub template_name: String,
+  pub operation: SessionOperation,
+  /// Nodes targeted by the session
+  pub xnames: Vec<String>,
+  /// True if boot parameters were set in BSS
+  pub boot_parameters_set: bool,
+  /// Outcome of the PCS transition, none if the session was only staged
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub outcome: Option<TransitionOutcome>,
+}
+
+impl SessionReport {
+  /// Returns true if every node targeted reached the requested state
+  pub fn is_success(&self) -> bool {
+    self
+      .outcome
+      .as_ref()
+      .is_none_or(|outcome| outcome.is_success())
+  }
+}
@@ -0,0 +1,93 @@
+use crate::{
+  bss,
+  error::Error,
+  http::ApiContext,
+  node,
+  pcs::{self, transitions::types::TransitionStatus},
+  session_template,
+};
+
+use super::types::{SessionOperation, SessionReport, SessionSettings};
+
+/// Run a session against the nodes of a session template. Boot parameters of the
+/// template are set in BSS (except for shutdown), then the nodes are power cycled
+/// through PCS unless the session is staged. `progress` is called each time PCS is
+/// polled
+pub async fn run(
+  ctx: &ApiContext,
+  template_name: &str,
+  operation: SessionOperation,
+  settings: &SessionSettings,
+  progress: impl FnMut(TransitionStatus),
+) -> Result<SessionReport, Error> {
+  let template = session_template::utils::get_and_filter(
+    ctx,
+    Some(template_name),
+    &[],
+    &[],
+    None,
+  )
+  .await?
+  .pop()
+  .ok_or_else(|| {
+    Error::Message(format!("Session template '{}' not found", template_name))
+  })?;
+
+  let mut xname_vec = template.members.clone();
+
+  if !settings.limit.is_empty() {
+    let limit_xname_vec =
+      pcs::utils::resolve_xnames(ctx, &settings.limit).await?;
+    xname_vec.retain(|xname| limit_xname_vec.contains(xname));
+  }
+
+  node::utils::sort_xnames(&mut xname_vec);
+
+  if xname_vec.is_empty() {
+    return Err(Error::Message(format!(
+      "No node in session template '{}' matches the limit",
+      template_name
+    )));
+  }
+
+  let boot_parameters_set = operation.sets_boot_parameters();
+
+  if boot_parameters_set {
+    let mut boot_parameters = template.boot_parameters();
+    boot_parameters.hosts = xname_vec.clone();
+
+    log::info!(
+      "Set boot parameters of session template '{}' on {} nodes",
+      template_name,
+      xname_vec.len()
+    );
+
+    bss::client::put(ctx, &boot_parameters).await?;
+  }
+
+  let outcome = if settings.stage {
+    log::info!(
+      "Session on template '{}' staged, nodes not power cycled",
+      template_name
+    );
+    None
+  } else {
+    let transition = pcs::transitions::client::post_block_with_options(
+      ctx,
+      operation.power_operation(),
+      &xname_vec,
+      &settings.poll_options,
+      progress,
+    )
+    .await?;
+    Some(transition.outcome())
+  };
+
+  Ok(SessionReport {
+    template_name: template_name.to_string(),
+    operation,
+    xnames: xname_vec,
+    boot_parameters_set,
+    outcome,
+  })
+}
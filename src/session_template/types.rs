@@ -0,0 +1,79 @@
+#[cfg(feature = "backend-connector")]
+use std::collections::HashMap;
+
+#[cfg(feature = "backend-connector")]
+use manta_backend_dispatcher::types::bos::session_template::{
+  BootSet as FrontEndBootSet, BosSessionTemplate as FrontEndBosSessionTemplate,
+  Cfs as FrontEndCfs,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::bss::types::BootParameters;
+
+/// Boot definition of a HSM group: the boot parameters shared by all its members and
+/// the cloud-init group configuring them. OpenCHAMI has no session template service,
+/// templates are derived from HSM, BSS and cloud-init
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SessionTemplate {
+  /// Same as the HSM group label
+  pub name: String,
+  pub hsm_group: String,
+  pub members: Vec<String>,
+  pub kernel: String,
+  pub initrd: String,
+  pub params: String,
+  /// Cloud-init group with the same name as the HSM group, if any
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub cloud_init_group: Option<String>,
+}
+
+impl SessionTemplate {
+  /// Boot parameters to apply to the template members
+  pub fn boot_parameters(&self) -> BootParameters {
+    BootParameters {
+      hosts: self.members.clone(),
+      kernel: self.kernel.clone(),
+      initrd: self.initrd.clone(),
+      params: self.params.clone(),
+      ..Default::default()
+    }
+  }
+}
+
+#[cfg(feature = "backend-connector")]
+impl From<SessionTemplate> for FrontEndBosSessionTemplate {
+  fn from(template: SessionTemplate) -> Self {
+    let cfs =
+      template
+        .cloud_init_group
+        .clone()
+        .map(|cloud_init_group| FrontEndCfs {
+          configuration: Some(cloud_init_group),
+        });
+
+    let boot_set = FrontEndBootSet {
+      name: Some(template.name.clone()),
+      path: Some(template.kernel),
+      cfs: cfs.clone(),
+      r#type: None,
+      etag: None,
+      kernel_parameters: Some(template.params),
+      node_list: None,
+      node_roles_groups: None,
+      node_groups: Some(vec![template.hsm_group]),
+      arch: None,
+      rootfs_provider: None,
+      rootfs_provider_passthrough: None,
+    };
+
+    FrontEndBosSessionTemplate {
+      name: Some(template.name),
+      tenant: None,
+      description: None,
+      enable_cfs: Some(cfs.is_some()),
+      cfs,
+      boot_sets: Some(HashMap::from([("compute".to_string(), boot_set)])),
+      links: None,
+    }
+  }
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use crate::{bss, cloud_init, error::Error, hsm, http::ApiContext};
+
+use super::types::SessionTemplate;
+
+/// Build a session template for each HSM group whose members all share the same boot
+/// parameters in BSS. Templates are sorted by name
+pub async fn get_all(ctx: &ApiContext) -> Result<Vec<SessionTemplate>, Error> {
+  let group_vec = hsm::group::client::get_all(ctx).await?;
+
+  let boot_parameter_vec = bss::client::get_all(ctx).await?;
+
+  let cloud_init_group_name_vec: Vec<String> =
+    cloud_init::http_client::get_all_groups(ctx)
+      .await?
+      .into_iter()
+      .map(|group| group.name)
+      .collect();
+
+  let boot_parameters_by_xname: HashMap<&str, &bss::types::BootParameters> =
+    boot_parameter_vec
+      .iter()
+      .flat_map(|boot_parameters| {
+        boot_parameters
+          .hosts
+          .iter()
+          .map(move |xname| (xname.as_str(), boot_parameters))
+      })
+      .collect();
+
+  let mut template_vec: Vec<SessionTemplate> = group_vec
+    .into_iter()
+    .filter_map(|group| {
+      let mut member_vec = group.get_members();
+      member_vec.sort();
+
+      let first_boot_parameters =
+        boot_parameters_by_xname.get(member_vec.first()?.as_str())?;
+
+      let shared = member_vec.iter().all(|xname| {
+        boot_parameters_by_xname.get(xname.as_str()).is_some_and(
+          |boot_parameters| {
+            boot_parameters.kernel == first_boot_parameters.kernel
+              && boot_parameters.initrd == first_boot_parameters.initrd
+              && boot_parameters.params == first_boot_parameters.params
+          },
+        )
+      });
+
+      if !shared {
+        log::debug!(
+          "Members of HSM group '{}' don't share boot parameters, no template",
+          group.label
+        );
+        return None;
+      }
+
+      Some(SessionTemplate {
+        name: group.label.clone(),
+        hsm_group: group.label.clone(),
+        members: member_vec.clone(),
+        kernel: first_boot_parameters.kernel.clone(),
+        initrd: first_boot_parameters.initrd.clone(),
+        params: first_boot_parameters.params.clone(),
+        cloud_init_group: cloud_init_group_name_vec
+          .contains(&group.label)
+          .then(|| group.label.clone()),
+      })
+    })
+    .collect();
+
+  template_vec.sort_by(|a, b| a.name.cmp(&b.name));
+
+  Ok(template_vec)
+}
+
+/// Get session templates filtered by name, HSM groups or members (templates targeting
+/// any of the members). At most `limit_opt` templates are returned
+pub async fn get_and_filter(
+  ctx: &ApiContext,
+  name_opt: Option<&str>,
+  hsm_group_label_vec: &[String],
+  member_vec: &[String],
+  limit_opt: Option<usize>,
+) -> Result<Vec<SessionTemplate>, Error> {
+  let mut template_vec: Vec<SessionTemplate> = get_all(ctx)
+    .await?
+    .into_iter()
+    .filter(|template| name_opt.is_none_or(|name| template.name == name))
+    .filter(|template| {
+      hsm_group_label_vec.is_empty()
+        || hsm_group_label_vec.contains(&template.hsm_group)
+    })
+    .filter(|template| {
+      member_vec.is_empty()
+        || template
+          .members
+          .iter()
+          .any(|xname| member_vec.contains(xname))
+    })
+    .collect();
+
+  if let Some(limit) = limit_opt {
+    template_vec.truncate(limit);
+  }
+
+  Ok(template_vec)
+}
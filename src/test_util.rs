@@ -0,0 +1,204 @@
+use serde_json::Value;
+use wiremock::{
+  matchers::{method, path, path_regex},
+  Mock, MockServer, ResponseTemplate,
+};
+
+use crate::{fixtures, http::ApiContext, service_paths::ServicePaths};
+
+/// Root certificate to pass to the clients talking to a `MockOchami`. The mock server
+/// is plain http, the certificate is only needed to build the clients
+pub const MOCK_ROOT_CERT: &[u8] = b"-----BEGIN CERTIFICATE-----
+MIIBijCCAS+gAwIBAgIUOb4HzRQvd/E1MpifNnKE7dy6gQ8wCgYIKoZIzj0EAwIw
+GTEXMBUGA1UEAwwOb2NoYW1pLXJzIG1vY2swIBcNMjYxMDE1MDkxMzEyWhgPMjEy
+NjA5MjEwOTEzMTJaMBkxFzAVBgNVBAMMDm9jaGFtaS1ycyBtb2NrMFkwEwYHKoZI
+zj0CAQYIKoZIzj0DAQcDQgAEA306dABQLZSVXZQ5Di/dMVBL8ZEHQgTqtAUs16pB
+iTfoiYx3qd5kUcvaKK4WUL5o/ZL0ynPlMaLI14nvvDKvRaNTMFEwHQYDVR0OBBYE
+FFoPSbtO4KS4ZKqOPIvLtW0qPqKYMB8GA1UdIwQYMBaAFFoPSbtO4KS4ZKqOPIvL
+tW0qPqKYMA8GA1UdEwEB/wQFMAMBAf8wCgYIKoZIzj0EAwIDSQAwRgIhAKw0zqGP
+4dkzNmng+6CfzIGoz4ba0NU/QgTdGxvu66ZZAiEAlPoMlPzLB4Ux4vYnjNngHRJw
+zjo/XpwR9lupjxR6424=
+-----END CERTIFICATE-----
+";
+
+/// Access token accepted by `MockOchami`, the mock does not check tokens
+pub const MOCK_AUTH_TOKEN: &str = "mock-token";
+
+/// Service of the OpenCHAMI API, used to build the path of a mocked endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockService {
+  Hsm,
+  Bss,
+  Pcs,
+  CloudInit,
+}
+
+/// Mock OpenCHAMI API listening on a random local port. Endpoints are mocked with the
+/// `mock_*` methods, requests to endpoints not mocked get a 404. Endpoints are mocked
+/// under the service paths of the mock, the defaults unless set with
+/// `with_service_paths`
+pub struct MockOchami {
+  server: MockServer,
+  service_paths: ServicePaths,
+}
+
+impl MockOchami {
+  pub async fn start() -> Self {
+    MockOchami {
+      server: MockServer::start().await,
+      service_paths: ServicePaths::default(),
+    }
+  }
+
+  /// Mock the endpoints under `service_paths` instead of the default ones
+  pub fn with_service_paths(mut self, service_paths: ServicePaths) -> Self {
+    self.service_paths = service_paths;
+    self
+  }
+
+  /// Mock server with the HSM components, groups and ethernet interfaces, BSS boot
+  /// parameters, PCS power status and transition of `crate::fixtures`
+  pub async fn with_fixtures() -> Self {
+    let mock = MockOchami::start().await;
+
+    mock
+      .mock_json(
+        MockService::Hsm,
+        "GET",
+        "/State/Components",
+        fixtures::COMPONENTS_JSON,
+      )
+      .await;
+    mock
+      .mock_json(MockService::Hsm, "GET", "/groups", fixtures::GROUPS_JSON)
+      .await;
+    mock
+      .mock_json(
+        MockService::Hsm,
+        "GET",
+        "/Inventory/EthernetInterfaces",
+        fixtures::ETHERNET_INTERFACES_JSON,
+      )
+      .await;
+    mock
+      .mock_json(
+        MockService::Bss,
+        "GET",
+        "/bootparameters",
+        fixtures::BOOT_PARAMETERS_JSON,
+      )
+      .await;
+    mock
+      .mock_json(
+        MockService::Pcs,
+        "POST",
+        "/power-status",
+        fixtures::POWER_STATUS_JSON,
+      )
+      .await;
+    mock
+      .mock_json(
+        MockService::Pcs,
+        "GET",
+        "/transitions/[^/]+",
+        fixtures::TRANSITION_JSON,
+      )
+      .await;
+
+    mock
+  }
+
+  /// Base url to pass to the clients
+  pub fn base_url(&self) -> String {
+    self.server.uri()
+  }
+
+  /// Request context for the clients, authenticated with `MOCK_AUTH_TOKEN`
+  pub fn context(&self) -> ApiContext {
+    ApiContext::parse(&self.base_url(), MOCK_AUTH_TOKEN, MOCK_ROOT_CERT)
+      .expect("Invalid mock base url")
+      .with_service_paths(self.service_paths.clone())
+  }
+
+  /// Underlying wiremock server, to register custom mocks or inspect the requests
+  /// received
+  pub fn server(&self) -> &MockServer {
+    &self.server
+  }
+
+  /// Respond to `http_method` requests on `endpoint` of `service` with `status` and a
+  /// json body. `endpoint` is a regex when it contains `[`, eg: `/groups/[^/]+`
+  pub async fn mock(
+    &self,
+    service: MockService,
+    http_method: &str,
+    endpoint: &str,
+    status: u16,
+    body: Option<Value>,
+  ) {
+    let mut response = ResponseTemplate::new(status);
+
+    if let Some(body) = body {
+      response = response.set_body_json(body);
+    }
+
+    self.mount(service, http_method, endpoint, response).await;
+  }
+
+  /// Respond with a 200 and a json payload given as a string, eg: a `crate::fixtures`
+  /// constant
+  pub async fn mock_json(
+    &self,
+    service: MockService,
+    http_method: &str,
+    endpoint: &str,
+    json: &str,
+  ) {
+    let response = ResponseTemplate::new(200)
+      .set_body_raw(json.as_bytes().to_vec(), "application/json");
+
+    self.mount(service, http_method, endpoint, response).await;
+  }
+
+  /// Respond with an error status and a raw payload, eg: a 401 with the message of the
+  /// auth server
+  pub async fn mock_error(
+    &self,
+    service: MockService,
+    http_method: &str,
+    endpoint: &str,
+    status: u16,
+    payload: &str,
+  ) {
+    let response = ResponseTemplate::new(status).set_body_string(payload);
+
+    self.mount(service, http_method, endpoint, response).await;
+  }
+
+  async fn mount(
+    &self,
+    service: MockService,
+    http_method: &str,
+    endpoint: &str,
+    response: ResponseTemplate,
+  ) {
+    let ctx = self.context();
+
+    let url = match service {
+      MockService::Hsm => ctx.hsm_url(endpoint),
+      MockService::Bss => ctx.bss_url(endpoint),
+      MockService::Pcs => ctx.pcs_url(endpoint),
+      MockService::CloudInit => ctx.cloud_init_url(endpoint),
+    }
+    .expect("Invalid mock endpoint");
+
+    let mock = if endpoint.contains('[') {
+      Mock::given(method(http_method))
+        .and(path_regex(format!("^{}$", url.path())))
+    } else {
+      Mock::given(method(http_method)).and(path(url.path()))
+    };
+
+    mock.respond_with(response).mount(&self.server).await;
+  }
+}
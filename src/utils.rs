@@ -0,0 +1,185 @@
+use std::{collections::HashMap, future::Future, hash::Hash, net::Ipv4Addr};
+
+use futures_util::{stream, StreamExt};
+
+use crate::error::Error;
+#[cfg(feature = "hsm")]
+use crate::node::types::Xname;
+
+/// Run `op` on each node, with at most `concurrency` operations in flight, and return
+/// the result of each node. Unlike loops stopping at the first error (or ignoring
+/// them), every node gets a result
+#[cfg(feature = "hsm")]
+pub async fn for_each_node<T, F, Fut>(
+  xname_vec: &[Xname],
+  concurrency: usize,
+  op: F,
+) -> HashMap<Xname, Result<T, Error>>
+where
+  F: Fn(Xname) -> Fut,
+  Fut: Future<Output = Result<T, Error>>,
+{
+  for_each_item(xname_vec, concurrency, op).await
+}
+
+/// Same as `for_each_node` for any kind of item, eg HSM group labels. Operations run
+/// concurrently on the calling task, so `op` can borrow from the caller instead of
+/// cloning its arguments into spawned tasks
+pub async fn for_each_item<K, T, F, Fut>(
+  item_vec: &[K],
+  concurrency: usize,
+  op: F,
+) -> HashMap<K, Result<T, Error>>
+where
+  K: Clone + Eq + Hash,
+  F: Fn(K) -> Fut,
+  Fut: Future<Output = Result<T, Error>>,
+{
+  stream::iter(item_vec.iter().cloned())
+    .map(|item| {
+      let future = op(item.clone());
+      async move { (item, future.await) }
+    })
+    .buffer_unordered(concurrency.max(1))
+    .collect()
+    .await
+}
+
+/// Summarize the failures of `for_each_node`, None if all nodes succeeded
+#[cfg(feature = "hsm")]
+pub fn summarize_failures<T>(
+  result_map: &HashMap<Xname, Result<T, Error>>,
+) -> Option<String> {
+  let mut failure_vec: Vec<(&Xname, &Error)> = result_map
+    .iter()
+    .filter_map(|(xname, result)| result.as_ref().err().map(|e| (xname, e)))
+    .collect();
+
+  if failure_vec.is_empty() {
+    return None;
+  }
+
+  failure_vec.sort_by(|a, b| a.0.cmp(b.0));
+
+  Some(
+    failure_vec
+      .iter()
+      .map(|(xname, e)| format!("{}: {}", xname, e))
+      .collect::<Vec<String>>()
+      .join("\n"),
+  )
+}
+
+/// Max number of addresses `expand_addresses` returns, a /16
+pub const MAX_EXPANDED_ADDRESSES: usize = 65536;
+
+/// Expand a list of BMC addresses. Each entry can be an IPv4 CIDR (eg: `10.1.0.0/24`,
+/// network and broadcast addresses are skipped), an IPv4 range (eg:
+/// `10.1.0.10-10.1.0.20`) or a single IP address or hostname.
+/// Fails if the list expands to more than `MAX_EXPANDED_ADDRESSES` addresses
+pub fn expand_addresses(address_vec: &[String]) -> Result<Vec<String>, Error> {
+  let mut expanded_vec = Vec::new();
+
+  let check_size = |expanded_len: usize, first: u32, last: u32| {
+    let size = (last as usize + 1).saturating_sub(first as usize);
+
+    if expanded_len + size > MAX_EXPANDED_ADDRESSES {
+      Err(Error::Message(format!(
+        "Addresses expand to more than {} addresses",
+        MAX_EXPANDED_ADDRESSES
+      )))
+    } else {
+      Ok(())
+    }
+  };
+
+  for address in address_vec {
+    if let Some((network, prefix_len)) = address.split_once('/') {
+      let network: Ipv4Addr = network.parse().map_err(|_| {
+        Error::Message(format!("'{}' is not a valid CIDR", address))
+      })?;
+      let prefix_len: u32 = prefix_len
+        .parse()
+        .ok()
+        .filter(|prefix_len| *prefix_len <= 32)
+        .ok_or_else(|| {
+          Error::Message(format!("'{}' is not a valid CIDR", address))
+        })?;
+
+      let mask = u32::MAX.checked_shl(32 - prefix_len).unwrap_or(0);
+      let first = u32::from(network) & mask;
+      let last = first | !mask;
+
+      let (first, last) = if prefix_len >= 31 {
+        (first, last)
+      } else {
+        (first + 1, last - 1)
+      };
+
+      check_size(expanded_vec.len(), first, last)?;
+
+      expanded_vec
+        .extend((first..=last).map(|ip| Ipv4Addr::from(ip).to_string()));
+    } else if let Some((start, end)) =
+      address.split_once('-').and_then(|(start, end)| {
+        Some((
+          start.parse::<Ipv4Addr>().ok()?,
+          end.parse::<Ipv4Addr>().ok()?,
+        ))
+      })
+    {
+      check_size(expanded_vec.len(), u32::from(start), u32::from(end))?;
+
+      expanded_vec.extend(
+        (u32::from(start)..=u32::from(end))
+          .map(|ip| Ipv4Addr::from(ip).to_string()),
+      );
+    } else {
+      check_size(expanded_vec.len(), 0, 0)?;
+
+      expanded_vec.push(address.clone());
+    }
+  }
+
+  Ok(expanded_vec)
+}
+
+/// Split a line of a CSV (or TSV) file into fields, trimmed. A field can be quoted
+/// with '"' to contain the delimiter, a quote in a quoted field is doubled. Fields can
+/// not span lines. None if a quoted field is not terminated
+pub fn split_csv_line(line: &str, delimiter: char) -> Option<Vec<String>> {
+  let mut field_vec = Vec::new();
+  let mut field = String::new();
+  let mut in_quotes = false;
+  let mut char_iter = line.chars().peekable();
+
+  while let Some(c) = char_iter.next() {
+    match c {
+      '"' if in_quotes => {
+        if char_iter.peek() == Some(&'"') {
+          char_iter.next();
+          field.push('"');
+        } else {
+          in_quotes = false;
+        }
+      }
+      '"' if field.trim().is_empty() => {
+        field.clear();
+        in_quotes = true;
+      }
+      c if c == delimiter && !in_quotes => {
+        field_vec.push(field.trim().to_string());
+        field.clear();
+      }
+      c => field.push(c),
+    }
+  }
+
+  if in_quotes {
+    return None;
+  }
+
+  field_vec.push(field.trim().to_string());
+
+  Some(field_vec)
+}
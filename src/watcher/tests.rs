@@ -0,0 +1,184 @@
+use std::sync::{Arc, Mutex};
+
+use serde_json::json;
+
+use crate::{
+  http::ApiContext,
+  test_util::{MockOchami, MockService},
+};
+
+use super::{
+  types::{Scn, StateChange, WatchFilter, WatcherSettings},
+  utils::Watcher,
+};
+
+fn scn(xname_vec: &[&str], state: &str) -> Scn {
+  Scn {
+    components: xname_vec.iter().map(|xname| xname.to_string()).collect(),
+    state: Some(state.to_string()),
+    ..Default::default()
+  }
+}
+
+#[test]
+fn test_ingest_scn_deduplicates_and_filters() {
+  let mut watcher = Watcher::new(
+    &ApiContext::parse("https://localhost", "token", &[]).unwrap(),
+    WatcherSettings::default(),
+  );
+
+  let notified = Arc::new(Mutex::new(Vec::<StateChange>::new()));
+  let notified_clone = notified.clone();
+
+  watcher.on(
+    WatchFilter::xnames(&["x1000c0s0b0n0".to_string()]).leaving("Ready"),
+    move |state_change| {
+      notified_clone.lock().unwrap().push(state_change.clone())
+    },
+  );
+
+  watcher.ingest_scn(&scn(&["x1000c0s0b0n0", "x1000c0s0b0n1"], "Ready"));
+  // Same state again, not a change
+  assert!(watcher
+    .ingest_scn(&scn(&["x1000c0s0b0n0"], "Ready"))
+    .is_empty());
+  watcher.ingest_scn(&scn(&["x1000c0s0b0n0", "x1000c0s0b0n1"], "Off"));
+  watcher.ingest_scn(&scn(&["x1000c0s0b0n0"], "Off"));
+
+  assert_eq!(watcher.state("x1000c0s0b0n1"), Some("Off"));
+  assert_eq!(
+    *notified.lock().unwrap(),
+    vec![StateChange {
+      xname: "x1000c0s0b0n0".to_string(),
+      previous: Some("Ready".to_string()),
+      current: "Off".to_string(),
+    }]
+  );
+}
+
+#[test]
+fn test_watch_filter_group() {
+  let state_change = StateChange {
+    xname: "x1000c0s0b0n0".to_string(),
+    previous: Some("Ready".to_string()),
+    current: "Standby".to_string(),
+  };
+
+  let member_set = ["x1000c0s0b0n0".to_string()].into_iter().collect();
+
+  let filter = WatchFilter::group("compute").leaving("Ready");
+
+  assert!(filter.matches(&state_change, Some(&member_set)));
+  assert!(!filter.matches(&state_change, None));
+  assert!(!filter
+    .entering("Off")
+    .matches(&state_change, Some(&member_set)));
+}
+
+#[tokio::test]
+async fn test_poll_queries_only_the_watched_group() {
+  let mock = MockOchami::start().await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "GET",
+      "/groups/compute/members",
+      200,
+      Some(json!({ "ids": ["x1000c0s0b0n0"] })),
+    )
+    .await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "POST",
+      "/State/Components/Query",
+      200,
+      Some(json!({
+        "Components": [{ "ID": "x1000c0s0b0n0", "State": "Ready" }]
+      })),
+    )
+    .await;
+
+  let mut watcher = Watcher::new(&mock.context(), WatcherSettings::default());
+  watcher.on(WatchFilter::group("compute"), |_| {});
+
+  watcher.poll().await.unwrap();
+
+  assert_eq!(watcher.state("x1000c0s0b0n0"), Some("Ready"));
+
+  let request_vec = mock.server().received_requests().await.unwrap();
+  assert!(request_vec
+    .iter()
+    .all(|request| !request.url.path().ends_with("/State/Components")));
+
+  let query = request_vec
+    .iter()
+    .find(|request| request.url.path().ends_with("/State/Components/Query"))
+    .unwrap();
+  assert_eq!(
+    query.body_json::<serde_json::Value>().unwrap(),
+    json!({ "ComponentIDs": ["x1000c0s0b0n0"], "stateonly": true })
+  );
+}
+
+#[tokio::test]
+async fn test_subscription_is_recreated_and_deleted() {
+  let mock = MockOchami::start().await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "POST",
+      "/Subscriptions/SCN",
+      200,
+      Some(json!({
+        "ID": 1,
+        "Subscriber": "watcher@ncn-m001",
+        "States": ["Ready"],
+        "Url": "https://ncn-m001/scn"
+      })),
+    )
+    .await;
+  // SMD lost the subscription, eg: after its database was reset
+  mock
+    .mock(
+      MockService::Hsm,
+      "GET",
+      "/Subscriptions/SCN",
+      200,
+      Some(json!({ "SubscriptionList": [] })),
+    )
+    .await;
+  mock
+    .mock(
+      MockService::Hsm,
+      "DELETE",
+      "/Subscriptions/SCN/1",
+      200,
+      Some(json!({ "code": 0, "message": "deleted 1 entry" })),
+    )
+    .await;
+
+  let mut watcher = Watcher::new(&mock.context(), WatcherSettings::default());
+
+  let id = watcher
+    .subscribe("watcher@ncn-m001", "https://ncn-m001/scn")
+    .await
+    .unwrap();
+  assert_eq!(id, 1);
+
+  watcher.renew_subscription().await.unwrap();
+  watcher.unsubscribe().await.unwrap();
+  // Nothing left to renew
+  watcher.renew_subscription().await.unwrap();
+
+  let request_vec = mock.server().received_requests().await.unwrap();
+  let method_vec: Vec<&str> = request_vec
+    .iter()
+    .map(|request| request.method.as_str())
+    .collect();
+  assert_eq!(method_vec, vec!["POST", "GET", "POST", "DELETE"]);
+
+  let subscription = request_vec[0].body_json::<serde_json::Value>().unwrap();
+  assert_eq!(subscription["Subscriber"], "watcher@ncn-m001");
+  assert_eq!(subscription["States"].as_array().unwrap().len(), 8);
+}
@@ -0,0 +1,137 @@
+use std::{collections::HashSet, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+/// State Change Notification sent by SMD to its subscribers. The watcher does not run an
+/// http server, SCNs received by the caller are forwarded through `Watcher::run` or
+/// `Watcher::ingest_scn`
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Scn {
+  #[serde(rename = "Components")]
+  pub components: Vec<String>,
+  #[serde(rename = "State")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub state: Option<String>,
+  #[serde(rename = "Flag")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub flag: Option<String>,
+  #[serde(rename = "Enabled")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub enabled: Option<bool>,
+  #[serde(rename = "Role")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub role: Option<String>,
+  #[serde(rename = "SubRole")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub sub_role: Option<String>,
+  #[serde(rename = "SoftwareStatus")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub software_status: Option<String>,
+}
+
+/// Change of the HSM state of a component. `previous` is `None` the first time the
+/// component is seen after the initial snapshot
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct StateChange {
+  pub xname: String,
+  pub previous: Option<String>,
+  pub current: String,
+}
+
+/// Selects the state changes a callback is notified of. Empty fields match everything,
+/// eg: `WatchFilter::group("compute").leaving("Ready")`
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct WatchFilter {
+  /// Only components member of this HSM group. Members are refreshed on every poll
+  pub group_label: Option<String>,
+  /// Only these components
+  pub xname_vec: Vec<String>,
+  /// Only changes from this state to a different one
+  pub leaving: Option<String>,
+  /// Only changes to this state
+  pub entering: Option<String>,
+}
+
+impl WatchFilter {
+  pub fn group(group_label: &str) -> Self {
+    WatchFilter {
+      group_label: Some(group_label.to_string()),
+      ..Default::default()
+    }
+  }
+
+  pub fn xnames(xname_vec: &[String]) -> Self {
+    WatchFilter {
+      xname_vec: xname_vec.to_vec(),
+      ..Default::default()
+    }
+  }
+
+  pub fn leaving(mut self, state: &str) -> Self {
+    self.leaving = Some(state.to_string());
+    self
+  }
+
+  pub fn entering(mut self, state: &str) -> Self {
+    self.entering = Some(state.to_string());
+    self
+  }
+
+  /// `group_member_set` are the members of `group_label`, ignored if no group is set
+  pub fn matches(
+    &self,
+    state_change: &StateChange,
+    group_member_set: Option<&HashSet<String>>,
+  ) -> bool {
+    if self.group_label.is_some()
+      && !group_member_set
+        .is_some_and(|member_set| member_set.contains(&state_change.xname))
+    {
+      return false;
+    }
+
+    if !self.xname_vec.is_empty()
+      && !self.xname_vec.contains(&state_change.xname)
+    {
+      return false;
+    }
+
+    if let Some(leaving) = &self.leaving {
+      if state_change.previous.as_ref() != Some(leaving)
+        || &state_change.current == leaving
+      {
+        return false;
+      }
+    }
+
+    if let Some(entering) = &self.entering {
+      if &state_change.current != entering {
+        return false;
+      }
+    }
+
+    true
+  }
+}
+
+/// Settings of the polling used when no SCN is received, and to reconcile missed SCNs
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct WatcherSettings {
+  /// Time between two polls of HSM when no SCN channel is used
+  pub poll_interval: Duration,
+  /// Time between two polls of HSM while SCNs are received
+  pub reconcile_interval: Duration,
+  /// Max time between two attempts while HSM can not be reached
+  pub max_backoff: Duration,
+}
+
+impl Default for WatcherSettings {
+  fn default() -> Self {
+    WatcherSettings {
+      poll_interval: Duration::from_secs(10),
+      reconcile_interval: Duration::from_secs(300),
+      max_backoff: Duration::from_secs(120),
+    }
+  }
+}
@@ -0,0 +1,360 @@
+use std::{
+  collections::{HashMap, HashSet},
+  time::Duration,
+};
+
+use tokio::sync::mpsc::Receiver;
+
+use crate::{
+  error::Error,
+  hsm::{
+    self, component,
+    component::types::{ComponentArray, ComponentArrayPostQuery},
+    subscription::types::ScnSubscription,
+  },
+  http::ApiContext,
+};
+
+use super::types::{Scn, StateChange, WatchFilter, WatcherSettings};
+
+type Callback = Box<dyn FnMut(&StateChange) + Send>;
+
+/// Tracks the HSM state of components and notifies the callbacks registered with
+/// `on` when it changes. Changes come from SCNs forwarded by the caller and from
+/// polling HSM, a change seen by both is only notified once
+pub struct Watcher {
+  ctx: ApiContext,
+  settings: WatcherSettings,
+  state_map: HashMap<String, String>,
+  group_member_map: HashMap<String, HashSet<String>>,
+  callback_vec: Vec<(WatchFilter, Callback)>,
+  subscription_opt: Option<ScnSubscription>,
+  initialized: bool,
+}
+
+impl Watcher {
+  pub fn new(ctx: &ApiContext, settings: WatcherSettings) -> Self {
+    Watcher {
+      ctx: ctx.clone(),
+      settings,
+      state_map: HashMap::new(),
+      group_member_map: HashMap::new(),
+      callback_vec: Vec::new(),
+      subscription_opt: None,
+      initialized: false,
+    }
+  }
+
+  /// Register a callback called for each state change matching `filter`
+  pub fn on<F>(&mut self, filter: WatchFilter, callback: F)
+  where
+    F: FnMut(&StateChange) + Send + 'static,
+  {
+    self.callback_vec.push((filter, Box::new(callback)));
+  }
+
+  /// Replace the token used to query HSM, eg: after it was renewed
+  pub fn set_auth_token(&mut self, auth_token: &str) {
+    self.ctx = self.ctx.with_auth_token(auth_token);
+  }
+
+  /// Last state known of a component
+  pub fn state(&self, xname: &str) -> Option<&str> {
+    self.state_map.get(xname).map(String::as_str)
+  }
+
+  /// Ask SMD to post SCNs for every state change to `url`, where the caller receives
+  /// them and forwards them to `run`. Returns the id of the subscription, recreated
+  /// by `run` if SMD loses it and deleted by `unsubscribe`
+  pub async fn subscribe(
+    &mut self,
+    subscriber: &str,
+    url: &str,
+  ) -> Result<u64, Error> {
+    let subscription = hsm::subscription::http_client::post(
+      &self.ctx,
+      &ScnSubscription::all_states(subscriber, url),
+    )
+    .await?;
+
+    let id = match subscription.id {
+      Some(id) => id,
+      None => {
+        return Err(Error::Message(
+          "SMD did not return the id of the SCN subscription".to_string(),
+        ))
+      }
+    };
+
+    self.subscription_opt = Some(subscription);
+
+    Ok(id)
+  }
+
+  /// Recreate the SCN subscription if SMD does not know it anymore, eg: after its
+  /// database was reset. Does nothing if `subscribe` was not called
+  pub async fn renew_subscription(&mut self) -> Result<(), Error> {
+    let subscription = match &self.subscription_opt {
+      Some(subscription) => subscription.clone(),
+      None => return Ok(()),
+    };
+
+    let subscription_array =
+      hsm::subscription::http_client::get_all(&self.ctx).await?;
+
+    if subscription_array
+      .subscription_list
+      .unwrap_or_default()
+      .iter()
+      .any(|existing| existing.id == subscription.id)
+    {
+      return Ok(());
+    }
+
+    log::warn!(
+      "SCN subscription {:?} not found in SMD, subscribing again",
+      subscription.id
+    );
+
+    self
+      .subscribe(&subscription.subscriber, &subscription.url)
+      .await?;
+
+    Ok(())
+  }
+
+  /// Delete the SCN subscription created by `subscribe`
+  pub async fn unsubscribe(&mut self) -> Result<(), Error> {
+    if let Some(id) = self
+      .subscription_opt
+      .as_ref()
+      .and_then(|subscription| subscription.id)
+    {
+      hsm::subscription::http_client::delete_one(&self.ctx, id).await?;
+    }
+
+    self.subscription_opt = None;
+
+    Ok(())
+  }
+
+  /// Query HSM and notify the changes since the previous poll or SCN. Only the
+  /// components the callbacks filter on are queried, all of them if one callback is
+  /// not restricted to a group or xnames. The first poll only records the current
+  /// state of the components
+  pub async fn poll(&mut self) -> Result<Vec<StateChange>, Error> {
+    self.refresh_group_members().await?;
+
+    let component_array = match self.watched_xname_set() {
+      Some(xname_set) if xname_set.is_empty() => {
+        ComponentArray { components: None }
+      }
+      Some(xname_set) => {
+        let mut xname_vec: Vec<String> = xname_set.into_iter().collect();
+        xname_vec.sort();
+
+        component::client::post_query(
+          &self.ctx,
+          ComponentArrayPostQuery {
+            component_ids: Some(xname_vec),
+            state_only: Some(true),
+            ..Default::default()
+          },
+        )
+        .await?
+      }
+      None => {
+        component::client::get(
+          &self.ctx,
+          None,
+          None,
+          None,
+          None,
+          None,
+          None,
+          None,
+          None,
+          None,
+          None,
+          None,
+          None,
+          None,
+          None,
+          None,
+          None,
+          Some("true"),
+          None,
+          None,
+          None,
+        )
+        .await?
+      }
+    };
+
+    let mut state_change_vec = Vec::new();
+
+    for component in component_array.components.unwrap_or_default() {
+      if let (Some(xname), Some(state)) = (component.id, component.state) {
+        if let Some(state_change) = self.update(xname, state) {
+          state_change_vec.push(state_change);
+        }
+      }
+    }
+
+    if !self.initialized {
+      self.initialized = true;
+      return Ok(Vec::new());
+    }
+
+    self.dispatch(&state_change_vec);
+
+    Ok(state_change_vec)
+  }
+
+  /// Apply a SCN received by the caller and notify the resulting changes. SCNs not
+  /// changing the state (eg: only the flag or role) are ignored
+  pub fn ingest_scn(&mut self, scn: &Scn) -> Vec<StateChange> {
+    let Some(state) = &scn.state else {
+      return Vec::new();
+    };
+
+    let state_change_vec: Vec<StateChange> = scn
+      .components
+      .iter()
+      .filter_map(|xname| self.update(xname.clone(), state.clone()))
+      .collect();
+
+    self.dispatch(&state_change_vec);
+
+    state_change_vec
+  }
+
+  /// Watch until the future is dropped. SCNs received on `scn_rx` are applied as they
+  /// come and HSM is polled every `reconcile_interval` to catch missed ones. Without SCN
+  /// channel, or once it is closed, HSM is polled every `poll_interval`. Polls failing
+  /// are retried with an exponential backoff up to `max_backoff`
+  pub async fn run(&mut self, mut scn_rx: Option<Receiver<Scn>>) {
+    let mut backoff_opt: Option<Duration> = None;
+
+    loop {
+      if let Err(e) = self.renew_subscription().await {
+        log::warn!("Could not renew the SCN subscription. Reason:\n{}", e);
+      }
+
+      match self.poll().await {
+        Ok(_) => backoff_opt = None,
+        Err(e) => {
+          let backoff = backoff_opt
+            .map_or(self.settings.poll_interval, |backoff| {
+              (backoff * 2).min(self.settings.max_backoff)
+            });
+          log::warn!(
+            "Could not poll HSM component states, retrying in {:?}. Reason:\n{}",
+            backoff,
+            e
+          );
+          backoff_opt = Some(backoff);
+        }
+      }
+
+      let interval = match (backoff_opt, &scn_rx) {
+        (Some(backoff), _) => backoff,
+        (None, Some(_)) => self.settings.reconcile_interval,
+        (None, None) => self.settings.poll_interval,
+      };
+
+      let sleep = tokio::time::sleep(interval);
+      tokio::pin!(sleep);
+
+      while let Some(rx) = scn_rx.as_mut() {
+        tokio::select! {
+          _ = &mut sleep => break,
+          scn_opt = rx.recv() => match scn_opt {
+            Some(scn) => {
+              self.ingest_scn(&scn);
+            }
+            None => {
+              log::warn!("SCN channel closed, falling back to polling HSM");
+              scn_rx = None;
+            }
+          }
+        }
+      }
+
+      sleep.await;
+    }
+  }
+
+  fn update(&mut self, xname: String, state: String) -> Option<StateChange> {
+    match self.state_map.get(&xname) {
+      Some(previous) if *previous == state => None,
+      _ => {
+        let previous = self.state_map.insert(xname.clone(), state.clone());
+
+        Some(StateChange {
+          xname,
+          previous,
+          current: state,
+        })
+      }
+    }
+  }
+
+  fn dispatch(&mut self, state_change_vec: &[StateChange]) {
+    for (filter, callback) in self.callback_vec.iter_mut() {
+      let group_member_set = filter
+        .group_label
+        .as_ref()
+        .and_then(|group_label| self.group_member_map.get(group_label));
+
+      for state_change in state_change_vec {
+        if filter.matches(state_change, group_member_set) {
+          callback(state_change);
+        }
+      }
+    }
+  }
+
+  /// Components matched by the callback filters, `None` if one of them, or the
+  /// lack of callbacks, means every component
+  fn watched_xname_set(&self) -> Option<HashSet<String>> {
+    if self.callback_vec.is_empty() {
+      return None;
+    }
+
+    let mut xname_set = HashSet::new();
+
+    for (filter, _) in &self.callback_vec {
+      if !filter.xname_vec.is_empty() {
+        xname_set.extend(filter.xname_vec.iter().cloned());
+      } else if let Some(group_label) = &filter.group_label {
+        if let Some(member_set) = self.group_member_map.get(group_label) {
+          xname_set.extend(member_set.iter().cloned());
+        }
+      } else {
+        return None;
+      }
+    }
+
+    Some(xname_set)
+  }
+
+  async fn refresh_group_members(&mut self) -> Result<(), Error> {
+    let group_label_set: HashSet<String> = self
+      .callback_vec
+      .iter()
+      .filter_map(|(filter, _)| filter.group_label.clone())
+      .collect();
+
+    for group_label in group_label_set {
+      let members = hsm::cache::get_members(&self.ctx, &group_label).await?;
+
+      self.group_member_map.insert(
+        group_label,
+        members.ids.unwrap_or_default().into_iter().collect(),
+      );
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,265 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  bss,
+  error::Error,
+  hsm::{
+    self,
+    group::types::Member,
+    inventory::{
+      ethernet_interfaces::{self, types::ComponentEthernetInterface},
+      types::MacAddress,
+    },
+  },
+  http::ApiContext,
+  node::types::Xname,
+};
+
+/// Outcome of a node hardware replacement
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReplacementReport {
+  pub xname: String,
+  pub old_macs: Vec<String>,
+  pub new_macs: Vec<String>,
+  /// HSM groups the node had to be added back to after discovery
+  pub restored_groups: Vec<String>,
+  /// True if the boot parameters had to be set again after discovery
+  pub restored_boot_parameters: bool,
+  /// True if the node reached the Ready state before the timeout
+  pub ready: bool,
+}
+
+/// Replace the hardware of a node keeping its xname: each ethernet interface of the node
+/// listed in `mac_replacement_vec` (old MAC address, new MAC address) is replaced by one
+/// with the new MAC address and the IP addresses of the old one, HSM discovery is
+/// triggered on the node BMC and the node is expected to reach Ready within `timeout`.
+/// HSM group memberships and boot parameters are captured before the replacement and
+/// restored if discovery dropped them
+pub async fn replace_node(
+  ctx: &ApiContext,
+  xname: &str,
+  mac_replacement_vec: &[(String, String)],
+  timeout: Duration,
+) -> Result<ReplacementReport, Error> {
+  let bmc = xname.parse::<Xname>()?.bmc().ok_or_else(|| {
+    Error::Message(format!("'{}' has no BMC, not a node xname", xname))
+  })?;
+
+  let mac_replacement_vec = mac_replacement_vec
+    .iter()
+    .map(|(old_mac, new_mac)| {
+      Ok((
+        old_mac.parse::<MacAddress>()?,
+        new_mac.parse::<MacAddress>()?,
+      ))
+    })
+    .collect::<Result<Vec<(MacAddress, MacAddress)>, Error>>()?;
+
+  if mac_replacement_vec.is_empty() {
+    return Err(Error::Message(
+      "At least one MAC address replacement is required".to_string(),
+    ));
+  }
+
+  // Capture node configuration before the replacement
+  let group_label_vec = get_group_labels(ctx, xname).await?;
+
+  let boot_parameters_opt =
+    bss::client::get(ctx, &Some(vec![xname.to_string()]))
+      .await?
+      .into_iter()
+      .next();
+
+  let old_eth_interface_vec = ethernet_interfaces::client::get(
+    ctx,
+    None,
+    None,
+    None,
+    Some(xname),
+    None,
+    None,
+    None,
+  )
+  .await?;
+
+  // Find the interface of each old MAC address before changing anything
+  let swap_vec = mac_replacement_vec
+    .iter()
+    .map(|(old_mac, new_mac)| {
+      old_eth_interface_vec
+        .iter()
+        .find(|eth_interface| {
+          eth_interface.mac_address.as_ref() == Some(old_mac)
+        })
+        .map(|eth_interface| (eth_interface, new_mac))
+        .ok_or_else(|| {
+          Error::Message(format!(
+            "'{}' has no ethernet interface with MAC address '{}'",
+            xname, old_mac
+          ))
+        })
+    })
+    .collect::<Result<Vec<(&ComponentEthernetInterface, &MacAddress)>, Error>>(
+    )?;
+
+  for (old_eth_interface, new_mac) in swap_vec {
+    swap_eth_interface(ctx, xname, old_eth_interface, new_mac).await?;
+  }
+
+  log::info!("Trigger discovery of '{}'", bmc);
+  hsm::inventory::redfish_endpoint::client::post_discover(
+    ctx,
+    &[bmc.to_string()],
+    true,
+  )
+  .await?;
+
+  let straggler_vec = hsm::component::utils::wait_for_state(
+    ctx,
+    &[xname.to_string()],
+    "Ready",
+    timeout,
+    Duration::from_secs(10),
+  )
+  .await?;
+
+  // Restore configuration dropped by discovery
+  let current_group_label_vec = get_group_labels(ctx, xname).await?;
+
+  let mut restored_group_vec = Vec::new();
+
+  for group_label in group_label_vec {
+    if !current_group_label_vec.contains(&group_label) {
+      hsm::group::client::post_member(
+        ctx,
+        &group_label,
+        Member {
+          id: Some(xname.to_string()),
+        },
+      )
+      .await?;
+      restored_group_vec.push(group_label);
+    }
+  }
+
+  let mut restored_boot_parameters = false;
+
+  if let Some(boot_parameters) = boot_parameters_opt {
+    let boot_parameters_missing =
+      bss::client::get(ctx, &Some(vec![xname.to_string()]))
+        .await?
+        .is_empty();
+
+    if boot_parameters_missing {
+      // The captured entry may be shared with other nodes, only restore the one of
+      // the replaced node
+      bss::client::post(
+        ctx,
+        bss::types::BootParameters {
+          hosts: vec![xname.to_string()],
+          macs: boot_parameters.macs.as_ref().map(|_| {
+            mac_replacement_vec
+              .iter()
+              .map(|(_, new_mac)| new_mac.to_string())
+              .collect()
+          }),
+          nids: None,
+          ..boot_parameters
+        },
+      )
+      .await?;
+      restored_boot_parameters = true;
+    }
+  }
+
+  Ok(ReplacementReport {
+    xname: xname.to_string(),
+    old_macs: mac_replacement_vec
+      .iter()
+      .map(|(old_mac, _)| old_mac.to_string())
+      .collect(),
+    new_macs: mac_replacement_vec
+      .into_iter()
+      .map(|(_, new_mac)| String::from(new_mac))
+      .collect(),
+    restored_groups: restored_group_vec,
+    restored_boot_parameters,
+    ready: straggler_vec.is_empty(),
+  })
+}
+
+/// Replace an ethernet interface by one with `new_mac` and the same IP addresses. The
+/// old interface is deleted first and posted back if the new one can't be created
+async fn swap_eth_interface(
+  ctx: &ApiContext,
+  xname: &str,
+  old_eth_interface: &ComponentEthernetInterface,
+  new_mac: &MacAddress,
+) -> Result<(), Error> {
+  if let Some(old_id) = &old_eth_interface.id {
+    log::info!("Delete ethernet interface '{}' of '{}'", old_id, xname);
+    ethernet_interfaces::client::delete_one(ctx, old_id).await?;
+  }
+
+  log::info!("Create ethernet interface '{}' for '{}'", new_mac, xname);
+  let post_rslt = ethernet_interfaces::client::post(
+    ctx,
+    ComponentEthernetInterface {
+      id: None,
+      description: None,
+      mac_address: Some(new_mac.clone()),
+      ip_addresses: old_eth_interface.ip_addresses.clone(),
+      last_update: None,
+      component_id: Some(xname.to_string()),
+      parent_hms_type: None,
+    },
+  )
+  .await;
+
+  let e = match post_rslt {
+    Ok(_) => return Ok(()),
+    Err(e) => e,
+  };
+
+  log::error!(
+    "Could not create ethernet interface '{}' for '{}'. Restore the old one",
+    new_mac,
+    xname
+  );
+
+  let restore_rslt = ethernet_interfaces::client::post(
+    ctx,
+    ComponentEthernetInterface {
+      last_update: None,
+      ..old_eth_interface.clone()
+    },
+  )
+  .await;
+
+  Err(match restore_rslt {
+    Ok(_) => Error::Message(format!(
+      "Could not create ethernet interface '{}' for '{}', the old interface was restored. Reason:\n{}",
+      new_mac, xname, e
+    )),
+    Err(restore_error) => Error::Message(format!(
+      "Could not create ethernet interface '{}' for '{}'. Reason:\n{}\nCould not restore the old interface either: {}",
+      new_mac, xname, e, restore_error
+    )),
+  })
+}
+
+async fn get_group_labels(
+  ctx: &ApiContext,
+  xname: &str,
+) -> Result<Vec<String>, Error> {
+  Ok(
+    hsm::group::client::get_all(ctx)
+      .await?
+      .into_iter()
+      .filter(|group| group.get_members().iter().any(|member| member == xname))
+      .map(|group| group.label)
+      .collect(),
+  )
+}
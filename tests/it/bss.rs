@@ -0,0 +1,50 @@
+use ochami_rs::bss::{self, types::BootParameters};
+
+use crate::harness::deployment;
+
+const XNAME: &str = "x9000c1s0b0n1";
+
+#[tokio::test]
+#[ignore = "changes a live deployment, see tests/it/harness.rs"]
+async fn test_boot_parameters_crud() {
+  let ctx = &deployment().ctx;
+
+  let boot_parameters = BootParameters {
+    hosts: vec![XNAME.to_string()],
+    params: "console=ttyS0,115200".to_string(),
+    kernel: "s3://boot-images/it/kernel".to_string(),
+    initrd: "s3://boot-images/it/initrd".to_string(),
+    ..Default::default()
+  };
+
+  bss::client::post(ctx, boot_parameters.clone())
+    .await
+    .unwrap();
+
+  let boot_parameters_vec =
+    bss::client::get(ctx, &Some(vec![XNAME.to_string()]))
+      .await
+      .unwrap();
+  assert_eq!(boot_parameters_vec.len(), 1);
+  assert_eq!(boot_parameters_vec[0].kernel, boot_parameters.kernel);
+
+  let updated = BootParameters {
+    params: "console=ttyS0,115200 quiet".to_string(),
+    ..boot_parameters.clone()
+  };
+
+  bss::client::put(ctx, &updated).await.unwrap();
+
+  let boot_parameters_vec =
+    bss::client::get(ctx, &Some(vec![XNAME.to_string()]))
+      .await
+      .unwrap();
+  assert_eq!(boot_parameters_vec[0].params, updated.params);
+
+  bss::client::delete(ctx, &boot_parameters).await.unwrap();
+
+  assert!(bss::client::get(ctx, &Some(vec![XNAME.to_string()]),)
+    .await
+    .map(|boot_parameters_vec| boot_parameters_vec.is_empty())
+    .unwrap_or(true));
+}
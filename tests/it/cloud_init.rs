@@ -0,0 +1,32 @@
+use ochami_rs::cloud_init::{self, types::GroupData};
+
+use crate::harness::{deployment, unique_name};
+
+#[tokio::test]
+#[ignore = "changes a live deployment, see tests/it/harness.rs"]
+async fn test_group_crud() {
+  let ctx = &deployment().ctx;
+
+  let group = GroupData {
+    name: unique_name("it"),
+    description: Some("ochami-rs integration tests".to_string()),
+    ..Default::default()
+  };
+
+  cloud_init::http_client::post_group(ctx, &group)
+    .await
+    .unwrap();
+
+  let fetched = cloud_init::http_client::get_group(ctx, &group.name)
+    .await
+    .unwrap();
+  assert_eq!(fetched.name, group.name);
+
+  cloud_init::http_client::delete_group(ctx, &group.name)
+    .await
+    .unwrap();
+
+  assert!(cloud_init::http_client::get_group(ctx, &group.name)
+    .await
+    .is_err());
+}
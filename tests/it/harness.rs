@@ -0,0 +1,64 @@
+// Deployment the integration tests run against. It is configured with the
+// `OCHAMI_*` environment variables read by `OchamiConfig::from_env`. If
+// `OCHAMI_IT_COMPOSE_FILE` is set, the compose stack (eg: the OpenCHAMI quickstart) is
+// started first with `docker compose up --wait` and left running. The stack is started
+// with docker compose instead of testcontainers since the OpenCHAMI quickstart is a
+// compose file.
+// Tests create and delete components, groups and boot parameters, so they are
+// `#[ignore]`d and fail unless `OCHAMI_IT_ALLOW_DESTRUCTIVE=1` is set, so the
+// `OCHAMI_*` configuration of a production system is never used by mistake.
+
+use std::{env, process::Command, sync::OnceLock};
+
+use ochami_rs::{
+  config::{OchamiConfig, ENV_BASE_URL},
+  http::ApiContext,
+};
+
+pub const ENV_COMPOSE_FILE: &str = "OCHAMI_IT_COMPOSE_FILE";
+pub const ENV_ALLOW_DESTRUCTIVE: &str = "OCHAMI_IT_ALLOW_DESTRUCTIVE";
+
+pub struct Deployment {
+  pub ctx: ApiContext,
+}
+
+static DEPLOYMENT: OnceLock<Deployment> = OnceLock::new();
+
+pub fn deployment() -> &'static Deployment {
+  DEPLOYMENT.get_or_init(start)
+}
+
+fn start() -> Deployment {
+  assert!(
+    env::var(ENV_ALLOW_DESTRUCTIVE).as_deref() == Ok("1"),
+    "Integration tests change the deployment they run against, set '{}=1' to run them",
+    ENV_ALLOW_DESTRUCTIVE
+  );
+
+  if let Ok(compose_file) = env::var(ENV_COMPOSE_FILE) {
+    let status = Command::new("docker")
+      .args(["compose", "-f", &compose_file, "up", "--detach", "--wait"])
+      .status()
+      .expect("Could not run 'docker compose'");
+
+    assert!(status.success(), "Could not start '{}'", compose_file);
+  }
+
+  assert!(
+    env::var(ENV_BASE_URL).is_ok(),
+    "'{}' is not set, no deployment to run the integration tests against",
+    ENV_BASE_URL
+  );
+
+  let config =
+    OchamiConfig::from_env().expect("Invalid OpenCHAMI configuration");
+
+  Deployment {
+    ctx: config.context().expect("No access token configured"),
+  }
+}
+
+/// Name unique to a test run, so tests can run concurrently against the same deployment
+pub fn unique_name(prefix: &str) -> String {
+  format!("{}-{}", prefix, uuid::Uuid::new_v4().simple())
+}
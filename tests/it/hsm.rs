@@ -0,0 +1,96 @@
+use ochami_rs::hsm::{
+  self,
+  component::types::{ComponentArrayPostArray, ComponentCreate},
+  group::types::{Group, Member},
+};
+
+use crate::harness::{deployment, unique_name};
+
+const XNAME: &str = "x9000c1s0b0n0";
+
+#[tokio::test]
+#[ignore = "changes a live deployment, see tests/it/harness.rs"]
+async fn test_component_crud() {
+  let ctx = &deployment().ctx;
+
+  hsm::component::client::post(
+    ctx,
+    ComponentArrayPostArray {
+      components: vec![ComponentCreate {
+        id: XNAME.to_string(),
+        state: "Off".to_string(),
+        flag: None,
+        enabled: Some(true),
+        software_status: None,
+        role: Some("Compute".to_string()),
+        sub_role: None,
+        nid: None,
+        subtype: None,
+        net_type: None,
+        arch: None,
+        class: None,
+      }],
+      force: Some(true),
+    },
+  )
+  .await
+  .unwrap();
+
+  let component = hsm::component::client::get_one(ctx, XNAME).await.unwrap();
+  assert_eq!(component.id.as_deref(), Some(XNAME));
+  assert_eq!(component.role.as_deref(), Some("Compute"));
+
+  hsm::component::client::delete_one(ctx, XNAME)
+    .await
+    .unwrap();
+
+  assert!(hsm::component::client::get_one(ctx, XNAME).await.is_err());
+}
+
+#[tokio::test]
+#[ignore = "changes a live deployment, see tests/it/harness.rs"]
+async fn test_group_crud() {
+  let ctx = &deployment().ctx;
+
+  let group_label = unique_name("it");
+
+  hsm::group::client::post(
+    ctx,
+    Group {
+      label: group_label.clone(),
+      description: Some("ochami-rs integration tests".to_string()),
+      tags: None,
+      members: None,
+      exclusive_group: None,
+    },
+  )
+  .await
+  .unwrap();
+
+  hsm::group::client::post_member(
+    ctx,
+    &group_label,
+    Member {
+      id: Some(XNAME.to_string()),
+    },
+  )
+  .await
+  .unwrap();
+
+  let members = hsm::group::client::get_members(ctx, &group_label)
+    .await
+    .unwrap();
+  assert_eq!(members.ids, Some(vec![XNAME.to_string()]));
+
+  hsm::group::client::delete_member(ctx, &group_label, XNAME)
+    .await
+    .unwrap();
+
+  hsm::group::client::delete_one(ctx, &group_label)
+    .await
+    .unwrap();
+
+  assert!(hsm::group::client::get_one(ctx, &group_label)
+    .await
+    .is_err());
+}
@@ -0,0 +1,9 @@
+// Integration tests run against a live OpenCHAMI deployment (SMD, BSS, PCS and
+// cloud-init), see `harness`. Run with:
+//
+//   OCHAMI_IT_ALLOW_DESTRUCTIVE=1 cargo test --features integration-tests --test it -- --ignored
+mod bss;
+mod cloud_init;
+mod harness;
+mod hsm;
+mod pcs;
@@ -0,0 +1,29 @@
+use ochami_rs::{health, pcs};
+
+use crate::harness::deployment;
+
+// Power transitions need real BMCs, only read-only PCS endpoints are covered
+
+#[tokio::test]
+#[ignore = "changes a live deployment, see tests/it/harness.rs"]
+async fn test_health() {
+  let ctx = &deployment().ctx;
+
+  // Each service is reached through its configured path, catches path prefix
+  // regressions
+  let health_report = health::get_report(ctx).await;
+
+  assert!(
+    health_report.is_operational(),
+    "Unhealthy services: {:?}",
+    health_report.unhealthy()
+  );
+}
+
+#[tokio::test]
+#[ignore = "changes a live deployment, see tests/it/harness.rs"]
+async fn test_transitions() {
+  let ctx = &deployment().ctx;
+
+  pcs::transitions::client::get_all(ctx).await.unwrap();
+}
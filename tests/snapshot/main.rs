@@ -0,0 +1,81 @@
+// Round trip tests: the payloads of `src/fixtures`, recorded from SMD, BSS and PCS, are
+// deserialized into their type and serialized again. The output must be the same JSON as
+// the payload, so any field the crate drops, renames or rewrites shows up as a failure
+use std::{fs, path::PathBuf};
+
+use ochami_rs::{
+  bss::types::BootParameters,
+  hsm::{
+    component::types::ComponentArray, group::types::Group,
+    inventory::ethernet_interfaces::types::ComponentEthernetInterface,
+    memberships::types::Membership,
+  },
+  pcs::{
+    power_status::types::PowerStatusAll, transitions::types::TransitionResponse,
+  },
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+/// Checks the fixture `name` deserializes into `T` and serializes back into the same
+/// JSON
+fn assert_round_trip<T: Serialize + DeserializeOwned>(name: &str) {
+  let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+    .join("src/fixtures")
+    .join(name);
+
+  let payload = fs::read_to_string(&path)
+    .unwrap_or_else(|e| panic!("could not read {}: {}", path.display(), e));
+
+  let expected: Value = serde_json::from_str(&payload)
+    .unwrap_or_else(|e| panic!("{} is not valid JSON: {}", name, e));
+
+  let value: T = serde_json::from_str(&payload)
+    .unwrap_or_else(|e| panic!("{} does not deserialize: {}", name, e));
+  let actual = serde_json::to_value(&value).unwrap();
+
+  assert!(
+    actual == expected,
+    "{} does not round trip\n--- expected\n{:#}\n--- actual\n{:#}",
+    name,
+    expected,
+    actual
+  );
+}
+
+#[test]
+fn test_smd_components() {
+  assert_round_trip::<ComponentArray>("components.json");
+}
+
+#[test]
+fn test_smd_groups() {
+  assert_round_trip::<Vec<Group>>("groups.json");
+}
+
+#[test]
+fn test_smd_memberships() {
+  assert_round_trip::<Vec<Membership>>("memberships.json");
+}
+
+#[test]
+fn test_smd_ethernet_interfaces() {
+  assert_round_trip::<Vec<ComponentEthernetInterface>>(
+    "ethernet_interfaces.json",
+  );
+}
+
+#[test]
+fn test_bss_boot_parameters() {
+  assert_round_trip::<Vec<BootParameters>>("boot_parameters.json");
+}
+
+#[test]
+fn test_pcs_transition() {
+  assert_round_trip::<TransitionResponse>("transition.json");
+}
+
+#[test]
+fn test_pcs_power_status() {
+  assert_round_trip::<PowerStatusAll>("power_status.json");
+}